@@ -0,0 +1,165 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// === רשומות הפעלה אוטומטית של תוכנות שכבר הוסרו ===
+// ~/.config/autostart/*.desktop ו-~/.config/systemd/user/*.service: אם התוכנה שהם מריצים לא
+// קיימת, הם רק מאטים את הכניסה. unit שמופעל (enabled) מדווח אבל לא נמחק - systemctl --user disable קודם.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleEntry {
+    // Name= של ה-desktop entry, או שם הקובץ של ה-unit
+    pub name: String,
+    // הפקודה שלא נמצאה, כפי שהיא כתובה בקובץ
+    pub program: String,
+    pub enabled: bool,
+}
+
+// הערך הראשון של key בקבוצה [group] של קובץ בסגנון ini (desktop entry, systemd unit)
+fn ini_value<'a>(contents: &'a str, group: &str, key: &str) -> Option<&'a str> {
+    let mut in_group = false;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_group = line == format!("[{}]", group);
+            continue;
+        }
+        if !in_group || line.starts_with('#') {
+            continue;
+        }
+        if let Some((k, v)) = line.split_once('=') {
+            if k.trim() == key {
+                return Some(v.trim());
+            }
+        }
+    }
+    None
+}
+
+// שורת פקודה -> ארגומנטים. מרכאות כפולות עוטפות ארגומנט עם רווחים, \ מבטל את התו שאחריו בתוכן
+fn split_arguments(command: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' if quoted => current.extend(chars.next()),
+            ' ' | '\t' if !quoted => {
+                if !current.is_empty() {
+                    arguments.push(std::mem::take(&mut current));
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        arguments.push(current);
+    }
+    arguments
+}
+
+// התוכנה שרצה בפועל: "env LANG=C foo --x" -> foo
+fn program_of(command: &str) -> Option<String> {
+    let arguments = split_arguments(command);
+    let mut rest = arguments.iter();
+    let mut first = rest.next()?;
+    if first == "env" {
+        first = rest.find(|a| !a.contains('=') && !a.starts_with('-'))?;
+    }
+    Some(first.clone())
+}
+
+// TryExec קיים בדיוק בשביל הבדיקה הזאת; בלעדיו - המילה הראשונה של Exec
+pub fn desktop_program(contents: &str) -> Option<String> {
+    // Hidden=true הוא הדרך לכבות רשומה של המערכת - הקובץ הוא ההגדרה, לא שארית
+    if ini_value(contents, "Desktop Entry", "Hidden") == Some("true") {
+        return None;
+    }
+    if let Some(try_exec) = ini_value(contents, "Desktop Entry", "TryExec").filter(|v| !v.is_empty()) {
+        return Some(try_exec.to_string());
+    }
+    program_of(ini_value(contents, "Desktop Entry", "Exec")?)
+}
+
+// ExecStart=-/usr/bin/foo: התחיליות @-:+!| משנות איך systemd מריץ, לא מה
+pub fn unit_program(contents: &str) -> Option<String> {
+    let exec = ini_value(contents, "Service", "ExecStart")?;
+    program_of(exec.trim_start_matches(['@', '-', ':', '+', '!', '|']))
+}
+
+// path = תיקיות החיפוש, כבר תחת root. פקודה עם משתנים או נתיב יחסי - לא יודעים, אז "קיימת"
+pub fn program_exists(program: &str, search_path: &[PathBuf], root: &Path) -> bool {
+    if program.contains('$') || program.starts_with('~') {
+        return true;
+    }
+    if let Some(absolute) = program.strip_prefix('/') {
+        return root.join(absolute).exists();
+    }
+    if program.contains('/') {
+        return true;
+    }
+    search_path.iter().any(|dir| dir.join(program).is_file())
+}
+
+// systemctl --user enable יוצר link ב-<target>.wants/ (או .requires/) ליד ה-unit
+pub fn unit_is_enabled(unit_dir: &Path, unit_name: &str) -> bool {
+    let Ok(entries) = fs::read_dir(unit_dir) else { return false };
+    entries.filter_map(|e| e.ok()).any(|entry| {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        (name.ends_with(".wants") || name.ends_with(".requires")) && fs::symlink_metadata(entry.path().join(unit_name)).is_ok()
+    })
+}
+
+// None = תקין, לא מוכר או לא קריא
+pub fn stale_entry(path: &Path, search_path: &[PathBuf], root: &Path) -> Option<StaleEntry> {
+    let contents = fs::read_to_string(path).ok()?;
+    let file_name = path.file_name()?.to_string_lossy().into_owned();
+    let (name, program, enabled) = match path.extension()?.to_str()? {
+        "desktop" => {
+            let name = ini_value(&contents, "Desktop Entry", "Name").unwrap_or(&file_name).to_string();
+            (name, desktop_program(&contents)?, false)
+        }
+        "service" => {
+            let enabled = path.parent().is_some_and(|dir| unit_is_enabled(dir, &file_name));
+            (file_name, unit_program(&contents)?, enabled)
+        }
+        _ => return None,
+    };
+    if program_exists(&program, search_path, root) {
+        return None;
+    }
+    Some(StaleEntry { name, program, enabled })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn desktop_program_skips_env_and_prefers_try_exec() {
+        let entry = "[Desktop Entry]\nName=Foo\nExec=env GDK_BACKEND=x11 foo --tray %U\n";
+        assert_eq!(desktop_program(entry).as_deref(), Some("foo"));
+        let quoted = "[Desktop Entry]\nExec=\"/opt/My App/run\" --min\n";
+        assert_eq!(desktop_program(quoted).as_deref(), Some("/opt/My App/run"));
+        let with_try = "[Desktop Entry]\nTryExec=bar\nExec=sh -c 'bar --daemon'\n";
+        assert_eq!(desktop_program(with_try).as_deref(), Some("bar"));
+        assert_eq!(desktop_program("[Desktop Entry]\nHidden=true\nExec=gone\n"), None);
+        // Exec של פעולה אחרת בקובץ לא נחשב
+        assert_eq!(desktop_program("[Desktop Action quit]\nExec=quit\n"), None);
+    }
+
+    #[test]
+    fn unit_program_strips_exec_prefixes() {
+        let unit = "[Unit]\nDescription=x\n[Service]\nExecStart=-@/usr/bin/syncd syncd --foreground\n";
+        assert_eq!(unit_program(unit).as_deref(), Some("/usr/bin/syncd"));
+        assert_eq!(unit_program("[Service]\nType=oneshot\n"), None);
+    }
+
+    #[test]
+    fn programs_with_variables_count_as_present() {
+        assert!(program_exists("$HOME/bin/x", &[], Path::new("/")));
+        assert!(!program_exists("/definitely/not/here", &[], Path::new("/")));
+    }
+}
@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// === דפדפנים ממשפחת Chromium: פרופילים ונתוני Service Workers לכל אתר ===
+
+pub struct Browser {
+    pub name: &'static str,
+    // תיקיית ה-User Data, יחסית ל-~/.config
+    pub config_dir: &'static str,
+    pub processes: &'static [&'static str],
+}
+
+pub const BROWSERS: &[Browser] = &[
+    Browser { name: "Google Chrome", config_dir: "google-chrome", processes: &["chrome"] },
+    Browser { name: "Chromium", config_dir: "chromium", processes: &["chromium", "chromium-browser"] },
+    Browser { name: "Brave", config_dir: "BraveSoftware/Brave-Browser", processes: &["brave"] },
+    Browser { name: "Microsoft Edge", config_dir: "microsoft-edge", processes: &["msedge"] },
+    Browser { name: "Vivaldi", config_dir: "vivaldi", processes: &["vivaldi-bin"] },
+];
+
+// Default, Profile 1, Profile 2... - מה שיש בו תיקיית Service Worker
+pub fn profiles(user_data: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(user_data) else { return Vec::new() };
+    let mut profiles: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.join("Service Worker").is_dir())
+        .collect();
+    profiles.sort();
+    profiles
+}
+
+// Service Worker/CacheStorage/<hash>: תיקייה לכל origin, ו-index.txt שאומר של מי היא
+pub struct SiteCache {
+    pub dir: PathBuf,
+    // None = אין index.txt או שלא הצלחנו לקרוא אותו
+    pub origin: Option<String>,
+}
+
+pub fn site_caches(profile: &Path) -> Vec<SiteCache> {
+    let Ok(entries) = fs::read_dir(profile.join("Service Worker/CacheStorage")) else { return Vec::new() };
+    let mut caches: Vec<SiteCache> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|e| {
+            let dir = e.path();
+            let origin = fs::read(dir.join("index.txt")).ok().and_then(|index| origin_from_index(&index));
+            SiteCache { dir, origin }
+        })
+        .collect();
+    caches.sort_by(|a, b| a.dir.cmp(&b.dir));
+    caches
+}
+
+// index.txt הוא protobuf (CacheStorageIndex): שדה 2 = origin, ובגרסאות חדשות שדה 3 = storage key,
+// שאחרי ה-^ שלו באים פרטי partitioning שלא מעניינים כאן
+pub fn origin_from_index(index: &[u8]) -> Option<String> {
+    let mut rest = index;
+    let mut origin = None;
+    while !rest.is_empty() {
+        let (key, after_key) = read_varint(rest)?;
+        rest = after_key;
+        match key & 7 {
+            0 => rest = read_varint(rest)?.1,
+            1 => rest = rest.get(8..)?,
+            5 => rest = rest.get(4..)?,
+            2 => {
+                let (len, after_len) = read_varint(rest)?;
+                let len = usize::try_from(len).ok()?;
+                let value = after_len.get(..len)?;
+                rest = &after_len[len..];
+                if matches!(key >> 3, 2 | 3) {
+                    let text = std::str::from_utf8(value).ok()?;
+                    let text = text.split('^').next().unwrap_or(text);
+                    if text.contains("://") {
+                        origin = Some(text.trim_end_matches('/').to_string());
+                    }
+                }
+            }
+            _ => return None,
+        }
+    }
+    origin
+}
+
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+    None
+}
+
+// "https://mail.example.com:8443" -> "mail.example.com"
+pub fn host(origin: &str) -> &str {
+    let without_scheme = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+    let authority = without_scheme.split('/').next().unwrap_or(without_scheme);
+    authority.rsplit_once(':').filter(|(_, port)| port.chars().all(|c| c.is_ascii_digit())).map_or(authority, |(host, _)| host)
+}
+
+// example.com ברשימה שומר גם את mail.example.com
+pub fn is_kept(origin: &str, keep: &[String]) -> bool {
+    let host = host(origin).to_ascii_lowercase();
+    keep.iter().map(|d| d.trim().trim_start_matches('.').to_ascii_lowercase()).filter(|d| !d.is_empty()).any(|domain| {
+        host == domain || host.strip_suffix(&domain).is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(number: u8, value: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![(number << 3) | 2, value.len() as u8];
+        bytes.extend_from_slice(value);
+        bytes
+    }
+
+    #[test]
+    fn origin_comes_from_the_index_proto() {
+        let mut index = field(1, &field(1, b"v1"));
+        index.extend(field(2, b"https://app.example.com/"));
+        assert_eq!(origin_from_index(&index).as_deref(), Some("https://app.example.com"));
+
+        let partitioned = field(3, b"https://maps.example.org/^0https://news.example.net");
+        assert_eq!(origin_from_index(&partitioned).as_deref(), Some("https://maps.example.org"));
+        assert_eq!(origin_from_index(b"\xff\xff"), None);
+    }
+
+    #[test]
+    fn keep_list_matches_subdomains_only() {
+        let keep = vec!["example.com".to_string(), " .Work.org ".to_string()];
+        assert!(is_kept("https://example.com", &keep));
+        assert!(is_kept("https://mail.example.com:8443", &keep));
+        assert!(is_kept("https://app.work.org", &keep));
+        assert!(!is_kept("https://notexample.com", &keep));
+    }
+}
@@ -0,0 +1,531 @@
+use clap::Parser;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::completions::{self, CompletionShell};
+use crate::config::Settings;
+use crate::custom;
+use crate::engine::{DeletionStrategy, RetryReport, RiskLevel, Skipped, SystemCleaner};
+use crate::events::{CleanEvent, EventSink};
+use crate::exclusions::{self, LoadError, Rule};
+use crate::history::{self, RunRecord};
+use crate::i18n::{self, tr_plain};
+use crate::largest;
+use crate::lock::{InstanceLock, LockError};
+use crate::log_tail;
+use crate::manifest::DeletionManifest;
+use crate::mounts;
+use crate::priority;
+use crate::progress::format_duration;
+use crate::quarantine::QuarantineRun;
+use crate::registry::{self, CleanerEntry};
+use crate::run_id;
+use crate::self_test;
+use crate::units;
+use crate::users::{self, UserAccount};
+
+// === מצב שורת פקודה: ריצה בלי חלון (cron, סקריפטים) ===
+// בלי --preview/--clean/--list נפתח ה-GUI כרגיל
+
+#[derive(Debug, Parser)]
+#[command(name = "sysclean", version, about = "System Cleaner Pro - clean caches, logs and package leftovers")]
+pub struct Cli {
+    /// Show what would be deleted, without deleting (comma-separated ids; none = the default selection)
+    #[arg(long, value_name = "IDS", value_delimiter = ',', num_args = 0.., conflicts_with = "clean")]
+    pub preview: Option<Vec<String>>,
+
+    /// Clean the given items (comma-separated ids; none = the default selection)
+    #[arg(long, value_name = "IDS", value_delimiter = ',', num_args = 0..)]
+    pub clean: Option<Vec<String>>,
+
+    /// List the available item ids and exit
+    #[arg(long)]
+    pub list: bool,
+
+    /// With --list: print only the ids, one per line (for scripts and shell completion)
+    #[arg(long, requires = "list")]
+    pub ids_only: bool,
+
+    /// Print a shell completion script to stdout and exit
+    #[arg(long, value_name = "SHELL")]
+    pub generate_completions: Option<CompletionShell>,
+
+    /// Print the man page (roff) to stdout and exit
+    #[arg(long)]
+    pub generate_manpage: bool,
+
+    /// Only run if the home or root disk has less than this percentage free (for cron jobs)
+    #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub if_free_below: Option<u8>,
+
+    /// Idle I/O priority and nice 19 (always on when not attached to a terminal, e.g. from cron)
+    #[arg(long)]
+    pub low_priority: bool,
+
+    /// When running as root: clean this user's home (default: every login user's home)
+    #[arg(long, value_name = "NAME")]
+    pub user: Option<String>,
+
+    /// Treat DIR as the home directory, without per-user runs (for tests against a fixture tree)
+    #[arg(long, value_name = "DIR", hide = true, conflicts_with = "user")]
+    pub home: Option<PathBuf>,
+
+    /// Mirror the log to stdout with timestamps, also when the GUI opens
+    #[arg(long, short)]
+    pub verbose: bool,
+
+    /// Print newline-delimited JSON events to stdout instead of log lines (logs go to stderr)
+    #[arg(long)]
+    pub json_progress: bool,
+
+    /// After the run, print how long each item took, slowest first
+    #[arg(long)]
+    pub timings: bool,
+
+    /// Serve org.supercleaner.Cleaner1 on the session bus until interrupted
+    #[arg(long, conflicts_with_all = ["preview", "clean", "list"])]
+    pub dbus: bool,
+
+    /// With --json-progress: emit only every Nth file_deleted event
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "json_progress", value_parser = clap::value_parser!(u64).range(1..))]
+    pub json_sample: u64,
+
+    /// With --preview: ignore cached directory sizes and walk everything again
+    #[arg(long, requires = "preview")]
+    pub rescan: bool,
+
+    /// Also exclude the paths and globs listed in FILE, one per line (# comments), for this run only; repeatable
+    #[arg(long, value_name = "FILE")]
+    pub exclude_from: Vec<PathBuf>,
+
+    /// Run every cleaner, preview and clean, against a generated sandbox tree and print a pass/fail table; touches none of your files
+    #[arg(long, conflicts_with_all = ["preview", "clean", "list", "dbus"])]
+    pub self_test: bool,
+}
+
+impl Cli {
+    pub fn is_headless(&self) -> bool {
+        self.list
+            || self.dbus
+            || self.generate_manpage
+            || self.generate_completions.is_some()
+            || self.preview.is_some()
+            || self.clean.is_some()
+            || self.self_test
+    }
+}
+
+// קוד יציאה: 0 הצליח, 1 פריט נכשל, 2 שימוש שגוי או מופע אחר כבר רץ
+pub fn run(cli: Cli) -> i32 {
+    let generated = match cli.generate_completions {
+        Some(shell) => Some(completions::write_completions(shell, &mut std::io::stdout())),
+        None if cli.generate_manpage => Some(completions::write_manpage(&mut std::io::stdout())),
+        None => None,
+    };
+    if let Some(result) = generated {
+        return match result {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        };
+    }
+    // לפני טעינת ההגדרות: הבדיקה לא תלויה במה שהמשתמש הגדיר, ולא נוגעת בקבצים שלו
+    if cli.self_test {
+        return self_test::run();
+    }
+
+    let (mut settings, recovery) = Settings::load_or_default();
+    i18n::set_language(settings.appearance.language);
+    units::set_byte_units(settings.appearance.byte_units);
+    if let Some(recovery) = recovery {
+        eprintln!("{}", recovery.message(tr_plain));
+    }
+    let custom = custom::load();
+    for error in &custom.errors {
+        eprintln!("{}", tr_plain("log.custom_invalid", &[error]));
+    }
+    let entries = registry::all_cleaners(&custom.cleaners);
+
+    if cli.list {
+        if cli.ids_only {
+            for entry in &entries {
+                println!("{}", entry.id);
+            }
+            return 0;
+        }
+        for entry in &entries {
+            let mut line = format!("{:<20} {:<10} {}", entry.id, entry.category, entry.name);
+            if !entry.is_applicable() {
+                line.push_str(&format!(" {}", tr_plain("sidebar.not_installed", &[])));
+            }
+            println!("{}", line);
+        }
+        return 0;
+    }
+
+    if cli.dbus {
+        return serve_dbus();
+    }
+
+    let (dry_run, requested) = match (cli.preview, cli.clean) {
+        (Some(ids), _) => (true, ids),
+        (None, Some(ids)) => (false, ids),
+        (None, None) => return 0,
+    };
+    let items = match select_items(&entries, &requested) {
+        Ok(items) => items,
+        Err(unknown) => {
+            eprintln!("Unknown item id(s): {} (see --list)", unknown.join(", "));
+            return 2;
+        }
+    };
+
+    // רשימה עם שורה שגויה לא רצה בכלל - החרגה שנבלעה בשקט היא קובץ שנמחק בטעות
+    let mut imported: Vec<(PathBuf, Vec<Rule>)> = Vec::new();
+    for source in &cli.exclude_from {
+        match exclusions::load_list(source) {
+            Ok(rules) => imported.push((source.clone(), rules)),
+            Err(LoadError::Io(e)) => {
+                eprintln!("{}", tr_plain("cli.exclusions_unreadable", &[&source.display().to_string(), &e.to_string()]));
+                return 2;
+            }
+            Err(LoadError::Lines(errors)) => {
+                for error in errors {
+                    let location = format!("{}:{}", source.display(), error.line);
+                    eprintln!("{}", tr_plain("cli.exclusion_line", &[&location, &tr_plain(error.reason, &[]), &error.text]));
+                }
+                return 2;
+            }
+        }
+    }
+
+    // כ-root: פריטי הבית רצים לכל משתמש. בלי root - רק הבית הנוכחי
+    let run_users: Vec<UserAccount> = match (&cli.user, users::is_root()) {
+        (Some(_), false) => {
+            eprintln!("{}", tr_plain("cli.user_needs_root", &[]));
+            return 2;
+        }
+        (Some(name), true) => match users::find(name) {
+            Some(user) => vec![user],
+            None => {
+                eprintln!("{}", tr_plain("cli.unknown_user", &[name]));
+                return 2;
+            }
+        },
+        (None, _) if cli.home.is_some() => Vec::new(),
+        (None, true) => users::human_users(),
+        (None, false) => Vec::new(),
+    };
+
+    if let Some(percent) = cli.if_free_below {
+        let disks: Vec<_> = mounts::real_mounts()
+            .into_iter()
+            .filter_map(|m| mounts::disk_usage(&m.mount_point).map(|usage| (m, usage)))
+            .collect();
+        let mut check = settings.clone();
+        check.general.low_space_percent = percent;
+        check.general.low_space_gb = 0;
+        if check.low_space_disk(&disks).is_none() {
+            println!("{}", tr_plain("cli.enough_space", &[&percent.to_string()]));
+            return 0;
+        }
+    }
+
+    let _instance_lock = match InstanceLock::acquire() {
+        Ok(lock) => lock,
+        Err(e @ LockError::Held(_)) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    // ריצה מתוזמנת (cron/systemd timer) - אין טרמינל, ואף אחד לא מחכה לה
+    let low_priority = cli.low_priority || settings.advanced.low_priority || !std::io::stdout().is_terminal();
+    let json = cli.json_progress;
+    if cli.rescan {
+        settings.advanced.force_rescan = true;
+    }
+    let run_id = run_id::new_run_id();
+    let mut cleaner = SystemCleaner::new(true, dry_run)
+        .with_run_id(run_id.clone())
+        .with_settings(&settings)
+        .with_custom(Arc::new(custom.cleaners.clone()));
+    if let Some(home) = cli.home.clone() {
+        cleaner = cleaner.with_home(home);
+    }
+    for (source, rules) in imported {
+        cleaner = cleaner.with_imported_exclusions(source, rules);
+    }
+    // stdout שייך לאירועים - הלוג הרגיל עובר ל-stderr
+    if json {
+        cleaner = cleaner
+            .with_callback(Arc::new(Mutex::new(Box::new(|line: String| eprintln!("{}", line)) as Box<dyn Fn(String) + Send + Sync>)))
+            .with_events(json_sink(cli.json_sample));
+    } else if cli.verbose {
+        // במקום println הרגיל - עם שעה וצבע
+        cleaner = cleaner.with_callback(log_tail::stdout_sink(&run_id));
+    }
+    if low_priority {
+        if cleaner.throttle_every == 0 {
+            cleaner = cleaner.with_throttle(settings.advanced.low_priority_pause_every);
+        }
+        // לפני יצירת ה-runtime, כדי שה-threads שלו יירשו את העדיפות
+        match priority::lower_current_thread() {
+            Ok(()) => say(json, &tr_plain("log.low_priority", &[])),
+            Err(e) => say(json, &tr_plain("log.low_priority_failed", &[&e.to_string()])),
+        }
+    }
+    if settings.safety.quarantine_enabled && !dry_run {
+        match QuarantineRun::create(&run_id) {
+            Ok(run) => cleaner = cleaner.with_strategy(DeletionStrategy::Quarantine(Arc::new(run))),
+            Err(e) => {
+                eprintln!("{}", tr_plain("app.quarantine_failed", &[&e.to_string()]));
+                return 1;
+            }
+        }
+    }
+    if settings.safety.deletion_manifest && !dry_run {
+        match DeletionManifest::create(&run_id) {
+            Ok(manifest) => cleaner = cleaner.with_manifest(Arc::new(manifest)),
+            Err(e) => {
+                eprintln!("{}", tr_plain("app.manifest_failed", &[&e.to_string()]));
+                return 1;
+            }
+        }
+    }
+    let item_ids: Vec<String> = items.iter().map(|e| e.id.clone()).collect();
+
+    let started = Instant::now();
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    let secure_delete = settings.safety.secure_delete_privacy;
+    let skip_in_use_files = settings.safety.skip_in_use_files;
+    let mut failed_items = 0;
+    let mut cleaned_items: Vec<String> = Vec::new();
+    let mut per_user_bytes: Vec<(String, u64)> = run_users.iter().map(|u| (u.name.clone(), 0)).collect();
+    runtime.block_on(async {
+        for entry in items {
+            let item_users: Vec<Option<UserAccount>> = if entry.per_user && !run_users.is_empty() {
+                run_users.iter().cloned().map(Some).collect()
+            } else {
+                vec![None]
+            };
+            for user in item_users {
+                let label = match &user {
+                    Some(user) => format!("{} [{}]", entry.id, user.name),
+                    None => entry.id.clone(),
+                };
+                let user_name = user.as_ref().map(|u| u.name.clone());
+                let cleaner = cleaner.clone()
+                    .with_secure_delete(secure_delete && entry.risk == RiskLevel::Privacy)
+                    .with_skip_in_use(skip_in_use_files && entry.skip_in_use)
+                    .with_keep_newest(entry.keep_newest)
+                    .with_keep_recent(settings.keep_recent(&entry.id))
+                    .with_session_protected(entry.session_protected)
+                    .with_sub_entries(entry.default_sub_entries())
+                    .with_user(user);
+                let before = cleaner.get_stats_sync().bytes_freed;
+                match cleaner.run_item(&entry.id).await {
+                    Ok(()) => cleaned_items.push(entry.id.clone()),
+                    // דילוג (דפדפן פתוח, אין תצוגה מקדימה...) אינו כישלון
+                    Err(e) if e.downcast_ref::<Skipped>().is_some() => say(json, &format!("{}: {}", label, e)),
+                    Err(e) => {
+                        eprintln!("{}: {}", label, e);
+                        failed_items += 1;
+                    }
+                }
+                if let Some(total) = user_name.and_then(|name| per_user_bytes.iter_mut().find(|(n, _)| *n == name)) {
+                    total.1 += cleaner.get_stats_sync().bytes_freed - before;
+                }
+            }
+        }
+    });
+    let retry = if dry_run { RetryReport::default() } else { runtime.block_on(cleaner.retry_failed()) };
+
+    let stats = cleaner.get_stats_sync();
+    cleaner.emit(CleanEvent::RunDone {
+        run_id: run_id.clone(),
+        dry_run,
+        files: stats.files_deleted,
+        bytes: stats.bytes_freed,
+        ram_bytes: stats.ram_bytes_freed,
+        errors: stats.errors + failed_items,
+        skipped_in_use: stats.skipped_in_use,
+        skipped_needs_root: stats.skipped_needs_root,
+        skipped_protected: stats.skipped_protected,
+        skipped_immutable: stats.skipped_immutable,
+        recovered_on_retry: retry.recovered,
+        failed_files: retry.failures.len() as u64,
+        duration_ms: started.elapsed().as_millis() as u64,
+        largest: stats.largest.clone(),
+    });
+    let mut summary = if !dry_run && stats.ram_bytes_freed > 0 {
+        let disk = SystemCleaner::format_bytes(stats.disk_bytes_freed);
+        let ram = SystemCleaner::format_bytes(stats.ram_bytes_freed);
+        tr_plain("notify.freed_split", &[&disk, &ram, &stats.files_deleted.to_string()])
+    } else {
+        let key = if dry_run { "notify.preview" } else { "notify.freed" };
+        tr_plain(key, &[&SystemCleaner::format_bytes(stats.bytes_freed), &stats.files_deleted.to_string()])
+    };
+    if stats.git_verified + stats.git_skipped > 0 {
+        summary.push_str(&tr_plain("summary.git", &[&stats.git_verified.to_string(), &stats.git_skipped.to_string()]));
+    }
+    if stats.files_organized > 0 {
+        summary.push_str(&tr_plain("summary.organized", &[&SystemCleaner::format_bytes(stats.bytes_organized), &stats.files_organized.to_string()]));
+    }
+    if retry.recovered > 0 {
+        summary.push_str(&tr_plain("summary.retried", &[&retry.recovered.to_string()]));
+    }
+    if !retry.failures.is_empty() {
+        summary.push_str(&tr_plain("summary.failed_files", &[&retry.failures.len().to_string()]));
+        eprintln!("{}", tr_plain("log.failed_files", &[&retry.failures.len().to_string()]));
+        for line in retry.failure_lines() {
+            eprintln!("{}", line);
+        }
+    }
+    for note in cleaner.summary_notes().into_iter().chain(cleaner.exclusion_notes()).chain(cleaner.snapshot_notes()) {
+        summary.push_str(&note);
+    }
+    if dry_run {
+        if settings.advanced.estimate_unreadable_as_root && !cleaner.unmeasured.denied_paths().is_empty() {
+            if let Err(e) = cleaner.unmeasured.estimate_as_root() {
+                eprintln!("{}", tr_plain("log.unmeasured_estimate_failed", &[&e.to_string()]));
+            }
+        }
+        if let Some(line) = cleaner.unmeasured.summary_line(tr_plain, SystemCleaner::format_bytes) {
+            summary.push_str(&format!(" — {}", line));
+        }
+    }
+    summary.push_str(&tr_plain("summary.duration", &[&format_duration(started.elapsed())]));
+    for (user, bytes) in &per_user_bytes {
+        summary.push_str(&tr_plain("summary.user", &[user, &SystemCleaner::format_bytes(*bytes)]));
+    }
+    if low_priority {
+        summary.push_str(&tr_plain("summary.low_priority", &[]));
+    }
+    if let Some(manifest) = &cleaner.manifest {
+        manifest.flush();
+        summary.push_str(&tr_plain("summary.manifest", &[&manifest.path().display().to_string()]));
+    }
+    summary.push_str(&tr_plain("summary.run_id", &[&run_id]));
+    say(json, &summary);
+    if !stats.largest.is_empty() {
+        let key = if dry_run { "cli.largest_preview" } else { "cli.largest" };
+        say(json, &tr_plain(key, &[&stats.largest.len().to_string()]));
+        for line in largest::table_lines(&stats.largest) {
+            say(json, &line);
+        }
+    }
+    if cli.timings {
+        print_timings(json, &stats.durations);
+    }
+
+    if !dry_run {
+        let record = RunRecord {
+            run_id,
+            finished: chrono::Local::now(),
+            items: item_ids,
+            files: stats.files_deleted,
+            bytes: stats.bytes_freed,
+            manifest: cleaner.manifest.as_ref().map(|m| m.path().to_path_buf()),
+            largest: stats.largest.clone(),
+        };
+        if let Err(e) = history::append(&record).and_then(|()| history::mark_cleaned(&cleaned_items, record.finished)) {
+            eprintln!("{}", tr_plain("log.history_failed", &[&e.to_string()]));
+        }
+    }
+
+    if failed_items > 0 { 1 } else { 0 }
+}
+
+// מצב daemon: כל ריצה לוקחת את הנעילה בעצמה, כך שאין נעילה כאן
+#[cfg(target_os = "linux")]
+fn serve_dbus() -> i32 {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    runtime.block_on(async {
+        let _connection = match crate::dbus::start(true).await {
+            Ok(connection) => connection,
+            Err(e) => {
+                eprintln!("{}", tr_plain("cli.dbus_failed", &[&e.to_string()]));
+                return 1;
+            }
+        };
+        println!("{}", tr_plain("cli.dbus_serving", &[crate::dbus::BUS_NAME]));
+        let _ = tokio::signal::ctrl_c().await;
+        0
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn serve_dbus() -> i32 {
+    eprintln!("{}", tr_plain("cli.dbus_unavailable", &[]));
+    2
+}
+
+// הטבלה של --timings: מהאיטי למהיר, כמו בלוג של ה-GUI
+fn print_timings(json: bool, durations: &HashMap<String, Duration>) {
+    let mut rows: Vec<(&String, &Duration)> = durations.iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(a.1));
+    say(json, &tr_plain("cli.timings", &[]));
+    for (id, duration) in rows {
+        say(json, &format!("  {:>8}  {}", format_duration(*duration), id));
+    }
+}
+
+// עם --json-progress כל מה שאינו אירוע הולך ל-stderr
+fn say(json: bool, line: &str) {
+    if json {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+// שורת JSON לכל אירוע. file_deleted נדגם (כל N), כדי שניקוי של מאות אלפי קבצים לא יציף את הצרכן
+fn json_sink(sample_every: u64) -> EventSink {
+    let deleted = AtomicU64::new(0);
+    Arc::new(move |event: CleanEvent| {
+        if matches!(event, CleanEvent::FileDeleted { .. }) && !deleted.fetch_add(1, Ordering::Relaxed).is_multiple_of(sample_every) {
+            return;
+        }
+        if let Ok(line) = serde_json::to_string(&event) {
+            println!("{}", line);
+        }
+    })
+}
+
+// בלי ids - מה שמסומן כברירת מחדל בסרגל הצד
+// בסדר הריצה (registry::run_order), לא בסדר שביקשו
+pub fn select_items<'a>(entries: &'a [CleanerEntry], requested: &[String]) -> Result<Vec<&'a CleanerEntry>, Vec<String>> {
+    let selected: Vec<&CleanerEntry> = if requested.is_empty() {
+        entries.iter().filter(|e| e.enabled_by_default && e.is_applicable()).collect()
+    } else {
+        let unknown: Vec<String> = requested.iter().filter(|id| !entries.iter().any(|e| &e.id == *id)).cloned().collect();
+        if !unknown.is_empty() {
+            return Err(unknown);
+        }
+        entries.iter().filter(|e| requested.contains(&e.id)).collect()
+    };
+    Ok(registry::sort_for_run(entries, selected, |e| e.id.as_str()))
+}
@@ -0,0 +1,128 @@
+use clap::builder::PossibleValuesParser;
+use clap::{CommandFactory, ValueEnum};
+use std::io::{self, Write};
+
+use crate::cli::Cli;
+use crate::custom;
+use crate::registry;
+
+// === השלמה אוטומטית ודף man, נוצרים מאותה הגדרת clap של ה-CLI ===
+// ה-ids של הפריטים נכנסים לסקריפט כרשימה קבועה (מה-registry ברגע היצירה), ובנוסף יש פונקציית
+// עזר שקוראת ל-`--list --ids-only` בזמן ההשלמה - כך גם פריטים מוגדרי-משתמש שנוספו אחר כך מושלמים.
+
+const BIN: &str = "sysclean";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+// ה-Cli שמפרסרים בפועל מקבל כל id (השגיאה שלנו ברורה יותר); רק בסקריפט הערכים סגורים
+fn command_with_ids() -> clap::Command {
+    let custom = custom::load();
+    let ids: Vec<String> = registry::all_cleaners(&custom.cleaners).into_iter().map(|e| e.id).collect();
+    Cli::command()
+        .mut_arg("clean", |arg| arg.value_parser(PossibleValuesParser::new(ids.clone())))
+        .mut_arg("preview", |arg| arg.value_parser(PossibleValuesParser::new(ids)))
+}
+
+pub fn write_completions(shell: CompletionShell, out: &mut dyn Write) -> io::Result<()> {
+    let mut command = command_with_ids();
+    let generator = match shell {
+        CompletionShell::Bash => clap_complete::Shell::Bash,
+        CompletionShell::Zsh => clap_complete::Shell::Zsh,
+        CompletionShell::Fish => clap_complete::Shell::Fish,
+    };
+    clap_complete::generate(generator, &mut command, BIN, out);
+    out.write_all(dynamic_ids_snippet(shell).as_bytes())
+}
+
+pub fn write_manpage(out: &mut dyn Write) -> io::Result<()> {
+    clap_mangen::Man::new(Cli::command()).render(out)
+}
+
+// הערכים הם רשימה מופרדת בפסיקים - משלימים רק את החלק שאחרי הפסיק האחרון
+fn dynamic_ids_snippet(shell: CompletionShell) -> &'static str {
+    match shell {
+        CompletionShell::Bash => r#"
+_sysclean_item_ids() {
+    sysclean --list --ids-only 2>/dev/null
+}
+
+_sysclean_with_ids() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "$prev" in
+        --clean|--preview)
+            local done="" last="$cur"
+            if [[ "$cur" == *,* ]]; then
+                done="${cur%,*},"
+                last="${cur##*,}"
+            fi
+            COMPREPLY=($(compgen -P "$done" -W "$(_sysclean_item_ids)" -- "$last"))
+            ;;
+        *)
+            _sysclean "$@"
+            ;;
+    esac
+}
+
+complete -F _sysclean_with_ids -o nosort -o bashdefault -o default sysclean
+"#,
+        CompletionShell::Zsh => r#"
+_sysclean_item_ids() {
+    local -a ids
+    ids=(${(f)"$(sysclean --list --ids-only 2>/dev/null)"})
+    _values -s , 'item id' $ids
+}
+
+_sysclean_with_ids() {
+    case "${words[CURRENT-1]}" in
+        --clean|--preview) _sysclean_item_ids ;;
+        *) _sysclean "$@" ;;
+    esac
+}
+
+compdef _sysclean_with_ids sysclean
+"#,
+        CompletionShell::Fish => r#"
+function __sysclean_item_ids
+    sysclean --list --ids-only 2>/dev/null
+end
+
+complete -c sysclean -l clean -f -a "(__sysclean_item_ids)"
+complete -c sysclean -l preview -f -a "(__sysclean_item_ids)"
+"#,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(shell: CompletionShell) -> String {
+        let mut out = Vec::new();
+        write_completions(shell, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn every_shell_knows_every_id_and_asks_the_binary_for_new_ones() {
+        let custom = custom::load();
+        let ids: Vec<String> = registry::all_cleaners(&custom.cleaners).into_iter().map(|e| e.id).collect();
+        for shell in [CompletionShell::Bash, CompletionShell::Zsh, CompletionShell::Fish] {
+            let script = script(shell);
+            for id in &ids {
+                assert!(script.contains(id.as_str()), "{:?} is missing {}", shell, id);
+            }
+            assert!(script.contains("sysclean --list --ids-only"), "{:?} has no dynamic ids", shell);
+        }
+    }
+
+    #[test]
+    fn ids_complete_after_the_last_comma() {
+        assert!(script(CompletionShell::Bash).contains(r#"last="${cur##*,}""#));
+        assert!(script(CompletionShell::Zsh).contains("_values -s ,"));
+    }
+}
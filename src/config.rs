@@ -0,0 +1,510 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::engine::{self, DEFAULT_COMMAND_TIMEOUT, DEFAULT_DELETE_RETRIES, DEFAULT_SCREEN_MEDIA_AGE_DAYS, DEFAULT_RETRY_BACKOFF, DEFAULT_WIPE_RESERVE_MB, DEFAULT_LARGE_FILE_AGE_DAYS, DEFAULT_LARGE_FILE_MB, DEFAULT_SHRED_MAX_BYTES};
+use crate::duplicates::{self, KeepPolicy};
+use crate::history;
+use crate::i18n::Language;
+use crate::largest;
+use crate::manifest;
+use crate::mounts::{self, DiskUsage, MountInfo};
+use crate::persist;
+use crate::priority;
+use crate::quarantine;
+use crate::units::ByteUnits;
+
+// === הגדרות גלובליות, נשמרות ב-~/.config/system-cleaner-pro/config.toml ===
+
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir().unwrap_or_else(|| PathBuf::from("/tmp")).join("system-cleaner-pro")
+}
+
+pub fn config_path() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeneralSettings {
+    // קבצים חדשים מזה לא נמחקים (0 = כבוי)
+    pub min_file_age_days: u32,
+    pub confirm_before_clean: bool,
+    // התראת שולחן עבודה כשריצה ארוכה מסתיימת והחלון לא בפוקוס
+    pub notify_on_finish: bool,
+    // באנר "הדיסק כמעט מלא" כשלדיסק של הבית או של / נשאר פחות מזה
+    pub low_space_warning: bool,
+    pub low_space_percent: u8,
+    pub low_space_gb: u64,
+    // מה מסומן בסרגל כשהאפליקציה עולה (נבחר באשף ההפעלה הראשונה)
+    pub preset: SelectionPreset,
+    // תצוגה מקדימה ברקע מיד בהפעלה, כדי שהגדלים יהיו מוכנים
+    pub scan_on_startup: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectionPreset {
+    // מה שה-registry מסמן כברירת מחדל
+    #[default]
+    Standard,
+    // רק מתוכם, אלה שברמת סיכון Safe
+    Safe,
+}
+
+impl Default for GeneralSettings {
+    fn default() -> Self {
+        GeneralSettings {
+            min_file_age_days: 0,
+            confirm_before_clean: true,
+            notify_on_finish: true,
+            low_space_warning: true,
+            low_space_percent: 10,
+            low_space_gb: 5,
+            preset: SelectionPreset::Standard,
+            scan_on_startup: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafetySettings {
+    // נתיבים שלא נוגעים בהם (כולל כל מה שמתחתם)
+    pub exclusions: Vec<String>,
+    // גודל רקורסיבי ליד כל החרגה בעורך (נמדד ברקע - DirSizer)
+    pub show_exclusion_sizes: bool,
+    pub quarantine_enabled: bool,
+    pub secure_delete_privacy: bool,
+    pub shred_max_mb: u64,
+    // פריטים שמסומנים ב-registry כרגישים (דפדפנים) בודקים קבצים פתוחים לפני מחיקה
+    pub skip_in_use_files: bool,
+    // לא יורדים ל-mount אחר (שיתוף רשת, bind mount) שנמצא בתוך תיקייה שמנקים
+    pub same_filesystem: bool,
+    // רשימת CSV של כל קובץ שנמחק בריצה (תיקיית manifests), בלי לשמור את הקבצים עצמם
+    pub deletion_manifest: bool,
+    // תיקייה לקריאה בלבד שלך: u+w לרגע כדי למחוק בתוכה (ראו SystemCleaner::fix_permissions)
+    pub fix_permissions: bool,
+    // דומיינים (כולל תת-דומיינים) שהנתונים שלהם בדפדפן נשמרים, למשל אתרים שצריכים לעבוד אופליין
+    pub keep_sites: Vec<String>,
+    // *~ / *.pyc על כל הבית: בתוך git repo נמחק רק מה ש-git מאשר שהוא ignored
+    pub git_aware: bool,
+    // פריט -> ימים: קבצים חדשים מזה נשארים בפריט הזה (0 או חסר = כבוי). רק לפריטים עם keeps_recent
+    pub keep_recent_days: BTreeMap<String, u32>,
+    // "I know what I'm doing": System Cache מרוקן גם את gdm/dconf/keyring וכו' בזמן session גרפי
+    pub ignore_session_protection: bool,
+}
+
+impl Default for SafetySettings {
+    fn default() -> Self {
+        SafetySettings {
+            exclusions: Vec::new(),
+            show_exclusion_sizes: false,
+            quarantine_enabled: false,
+            secure_delete_privacy: false,
+            shred_max_mb: DEFAULT_SHRED_MAX_BYTES / (1024 * 1024),
+            skip_in_use_files: true,
+            same_filesystem: true,
+            deletion_manifest: false,
+            fix_permissions: false,
+            keep_sites: Vec::new(),
+            git_aware: true,
+            keep_recent_days: BTreeMap::new(),
+            ignore_session_protection: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeChoice {
+    // לפי העדפת המערכת (ברירת המחדל של eframe בהפעלה הראשונה)
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppearanceSettings {
+    pub language: Language,
+    pub theme: ThemeChoice,
+    pub zoom: f32,
+    pub show_descriptions: bool,
+    // פריטים שאין להם מה לעשות במערכת הזו (APT על Fedora) - מוסתרים אלא אם מסומן
+    pub show_inapplicable: bool,
+    pub byte_units: ByteUnits,
+    // נגישות (palette.rs): רקע/טקסט בניגודיות גבוהה, וגוונים שלא נשענים על אדום מול ירוק
+    pub high_contrast: bool,
+    pub color_blind_palette: bool,
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        AppearanceSettings {
+            language: Language::English,
+            theme: ThemeChoice::System,
+            zoom: 1.0,
+            show_descriptions: true,
+            show_inapplicable: false,
+            byte_units: ByteUnits::Iec,
+            high_contrast: false,
+            color_blind_palette: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdvancedSettings {
+    pub quarantine_max_age_days: i64,
+    pub quarantine_max_size_mb: u64,
+    // "Super Cleaner data": כמה ריצות נשארות בהיסטוריה, ומאיזה גיל רשימות מחיקות נמחקות (0 = בלי הגבלה)
+    pub history_max_runs: usize,
+    pub manifest_max_age_days: u64,
+    // apt/dnf/flatpak שלא סיימו עד אז נהרגים
+    pub command_timeout_mins: u64,
+    // "Empty Folders" נכנס גם לתיקיות נסתרות בבית (~/.config וכו')
+    pub empty_dirs_include_hidden: bool,
+    // "Broken Symlinks" מוחק גם links ל-/media, /run/media, /mnt
+    pub broken_links_include_removable: bool,
+    // I/O במחלקת idle ו-nice 19; ריצות מ-cron (CLI בלי טרמינל) תמיד במצב הזה
+    pub low_priority: bool,
+    // גיבוי כש-ioprio לא משפיע: הפסקה קצרה כל N מחיקות (0 = בלי)
+    pub low_priority_pause_every: u64,
+    // ריקון תיקיות גדולות ב-threads מקבילים (0 = לפי מספר הליבות, 1 = כבוי). על HDD ובעדיפות נמוכה - תמיד אחד
+    pub walk_threads: usize,
+    // "Wipe Free Space": איזה mount (ריק = זה של תיקיית הבית) וכמה להשאיר פנוי
+    pub wipe_mount: String,
+    pub wipe_reserve_mb: u64,
+    // "Android SDK": snapshots של אמולטור ישנים מזה נמחקים (0 = לא נוגעים)
+    pub android_snapshot_max_age_days: u32,
+    // "Screenshots & Screencasts": הקלטות וצילומים ב-~/Videos, ~/Pictures ישנים מזה מוצעים למחיקה
+    pub screen_media_min_age_days: u32,
+    // org.supercleaner.Cleaner ב-session bus כל עוד החלון פתוח
+    pub dbus_service: bool,
+    // סבב חוזר בסוף הריצה על קבצים שלא נמחקו: כמה ניסיונות, וההמתנה הבסיסית ביניהם
+    pub delete_retries: u32,
+    pub delete_retry_backoff_ms: u64,
+    // תצוגה מקדימה לא סורקת שוב תיקיות שלא השתנו מאז הפעם הקודמת; force_rescan מתעלם מהמטמון (ומרענן אותו)
+    pub size_cache: bool,
+    pub force_rescan: bool,
+    // טבלת הקבצים הגדולים ביותר בסיכום הריצה: כמה שורות (0 = בלי)
+    pub largest_files: usize,
+    // בסוף תצוגה מקדימה: du דרך pkexec על התיקיות שלא נקראו, כדי לתת גודל לשורת "לא נמדד"
+    pub estimate_unreadable_as_root: bool,
+}
+
+impl Default for AdvancedSettings {
+    fn default() -> Self {
+        AdvancedSettings {
+            quarantine_max_age_days: quarantine::DEFAULT_MAX_AGE_DAYS,
+            quarantine_max_size_mb: quarantine::DEFAULT_MAX_TOTAL_BYTES / (1024 * 1024),
+            history_max_runs: history::DEFAULT_MAX_RUNS,
+            manifest_max_age_days: manifest::DEFAULT_MAX_AGE_DAYS,
+            command_timeout_mins: DEFAULT_COMMAND_TIMEOUT.as_secs() / 60,
+            empty_dirs_include_hidden: false,
+            broken_links_include_removable: false,
+            low_priority: false,
+            low_priority_pause_every: priority::DEFAULT_PAUSE_EVERY,
+            walk_threads: 0,
+            wipe_mount: String::new(),
+            wipe_reserve_mb: DEFAULT_WIPE_RESERVE_MB,
+            android_snapshot_max_age_days: 0,
+            screen_media_min_age_days: DEFAULT_SCREEN_MEDIA_AGE_DAYS,
+            dbus_service: false,
+            delete_retries: DEFAULT_DELETE_RETRIES,
+            delete_retry_backoff_ms: DEFAULT_RETRY_BACKOFF.as_millis() as u64,
+            size_cache: true,
+            force_rescan: false,
+            largest_files: largest::DEFAULT_LIMIT,
+            estimate_unreadable_as_root: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuplicateSettings {
+    pub roots: Vec<String>,
+    // קבצים גדולים מזה לא נסרקים (hash של קבצי וידאו ענקיים לוקח הרבה זמן)
+    pub max_file_mb: u64,
+    pub keep: KeepPolicy,
+}
+
+impl Default for DuplicateSettings {
+    fn default() -> Self {
+        DuplicateSettings {
+            roots: vec!["~/Downloads".to_string(), "~/Pictures".to_string(), "~/Documents".to_string()],
+            max_file_mb: duplicates::DEFAULT_MAX_FILE_MB,
+            keep: KeepPolicy::Oldest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LargeFileSettings {
+    pub roots: Vec<String>,
+    pub min_size_mb: u64,
+    // לא שונו כך וכך ימים
+    pub min_age_days: u64,
+    // להעביר ל-archive_dir/<שנה>/ במקום למחוק
+    pub archive: bool,
+    pub archive_dir: String,
+}
+
+impl Default for LargeFileSettings {
+    fn default() -> Self {
+        LargeFileSettings {
+            roots: vec!["~/Downloads".to_string()],
+            min_size_mb: DEFAULT_LARGE_FILE_MB,
+            min_age_days: DEFAULT_LARGE_FILE_AGE_DAYS,
+            archive: false,
+            archive_dir: "~/Downloads/_archive".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub general: GeneralSettings,
+    pub safety: SafetySettings,
+    pub appearance: AppearanceSettings,
+    pub advanced: AdvancedSettings,
+    pub duplicates: DuplicateSettings,
+    pub large_files: LargeFileSettings,
+}
+
+// קובץ ההגדרות היה קיים אבל לא נקרא - ממשיכים עם ברירות מחדל, ומספרים למשתמש
+#[derive(Debug, Clone)]
+pub struct ConfigRecovery {
+    pub error: String,
+    // None = גם ההזזה נכשלה; הקובץ עדיין במקומו ויידרס בשמירה הבאה
+    pub moved_to: Option<PathBuf>,
+}
+
+impl ConfigRecovery {
+    // translate = tr_args ב-GUI, tr_plain ב-CLI
+    pub fn message(&self, translate: fn(&str, &[&str]) -> String) -> String {
+        match &self.moved_to {
+            Some(moved) => translate("banner.config_recovered", &[&self.error, &moved.display().to_string()]),
+            None => translate("banner.config_unreadable", &[&self.error]),
+        }
+    }
+}
+
+impl Settings {
+    // ל-GUI ול-CLI: קובץ חסר = ברירות מחדל, קובץ שבור = ברירות מחדל והקובץ הולך הצידה
+    pub fn load_or_default() -> (Self, Option<ConfigRecovery>) {
+        Self::load_or_default_from(&config_path())
+    }
+
+    pub fn load_or_default_from(path: &Path) -> (Self, Option<ConfigRecovery>) {
+        let error = match fs::read_to_string(path) {
+            Ok(content) => match toml::from_str(&content) {
+                Ok(settings) => return (settings, None),
+                Err(e) => e.to_string(),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return (Self::default(), None),
+            // גם UTF-8 לא תקין מגיע לכאן
+            Err(e) => e.to_string(),
+        };
+        let moved_to = persist::set_aside(path).ok();
+        (Self::default(), Some(ConfigRecovery { error, moved_to }))
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(config_dir())?;
+        let content = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        persist::write_atomically(&config_path(), content.as_bytes())
+    }
+
+    pub fn exclusion_paths(&self) -> Vec<PathBuf> {
+        expand_paths(&self.safety.exclusions)
+    }
+
+    pub fn keep_recent(&self, id: &str) -> Option<Duration> {
+        match self.safety.keep_recent_days.get(id) {
+            None | Some(0) => None,
+            Some(&days) => Some(Duration::from_secs(days as u64 * 24 * 60 * 60)),
+        }
+    }
+
+    pub fn duplicate_roots(&self) -> Vec<PathBuf> {
+        expand_paths(&self.duplicates.roots)
+    }
+
+    pub fn large_file_roots(&self) -> Vec<PathBuf> {
+        expand_paths(&self.large_files.roots)
+    }
+
+    pub fn large_file_archive(&self) -> Option<PathBuf> {
+        self.large_files.archive.then(|| expand_path(&self.large_files.archive_dir))
+    }
+
+    // הדיסק הראשון (מתוך אלה שהניקוי משפיע עליהם) שעבר את הסף, אם יש
+    pub fn low_space_disk<'a>(&self, disks: &'a [(MountInfo, DiskUsage)]) -> Option<&'a (MountInfo, DiskUsage)> {
+        let mounts: Vec<MountInfo> = disks.iter().map(|(m, _)| m.clone()).collect();
+        let watched = mounts::watched_mount_points(&mounts);
+        let min_free_bytes = self.general.low_space_gb * 1024 * 1024 * 1024;
+        disks.iter().find(|(m, usage)| {
+            watched.contains(&m.mount_point)
+                && mounts::is_low_on_space(usage, self.general.low_space_percent as f32, min_free_bytes)
+        })
+    }
+
+    pub fn wipe_mount(&self) -> PathBuf {
+        match self.advanced.wipe_mount.trim() {
+            "" => engine::default_wipe_mount(),
+            mount => PathBuf::from(mount),
+        }
+    }
+}
+
+// ~/.cache, ~/.config, ~/.local/share, ~/.local/state - או לאן ש-XDG_*_HOME מפנים
+// (מקובל כשמחיצת הבית קטנה)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XdgDir {
+    Cache,
+    Config,
+    Data,
+    State,
+}
+
+impl XdgDir {
+    const ALL: [XdgDir; 4] = [XdgDir::Cache, XdgDir::Config, XdgDir::Data, XdgDir::State];
+
+    fn default_relative(self) -> &'static str {
+        match self {
+            XdgDir::Cache => ".cache",
+            XdgDir::Config => ".config",
+            XdgDir::Data => ".local/share",
+            XdgDir::State => ".local/state",
+        }
+    }
+
+    fn env_value(self) -> Option<PathBuf> {
+        match self {
+            XdgDir::Cache => dirs::cache_dir(),
+            XdgDir::Config => dirs::config_dir(),
+            XdgDir::Data => dirs::data_dir(),
+            XdgDir::State => dirs::state_dir(),
+        }
+    }
+
+    // לפי הסביבה של התהליך (נקראת פעם אחת). מי שמחזיק SystemPaths משתמש ב-paths.cache וכו'
+    pub fn under(self, home: &Path) -> PathBuf {
+        static ENV: OnceLock<XdgEnv> = OnceLock::new();
+        ENV.get_or_init(XdgEnv::from_env).resolve(self, home)
+    }
+}
+
+// XDG_*_HOME של מי שהריץ. המשתנים שייכים לסביבה שלו - רק לבית שלו (owner);
+// לבית של משתמש אחר (ריצה כ-root) ברירות המחדל. בבדיקות ממלאים ידנית (SystemPaths::with_xdg)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XdgEnv {
+    pub owner: Option<PathBuf>,
+    pub dirs: Vec<(XdgDir, PathBuf)>,
+}
+
+impl XdgEnv {
+    pub fn from_env() -> Self {
+        XdgEnv {
+            owner: dirs::home_dir(),
+            dirs: XdgDir::ALL.into_iter().filter_map(|dir| dir.env_value().map(|path| (dir, path))).collect(),
+        }
+    }
+
+    pub fn resolve(&self, dir: XdgDir, home: &Path) -> PathBuf {
+        self.dirs
+            .iter()
+            .find(|(d, _)| *d == dir && self.owner.as_deref() == Some(home))
+            .map_or_else(|| home.join(dir.default_relative()), |(_, path)| path.clone())
+    }
+}
+
+// "~/x" -> בית המשתמש. "~/.cache/x" וכו' -> תיקיית ה-XDG המתאימה
+pub fn expand_path(path: &str) -> PathBuf {
+    let path = path.trim();
+    let home = dirs::home_dir().unwrap_or_default();
+    if path == "~" {
+        return home;
+    }
+    let Some(rest) = path.strip_prefix("~/") else {
+        return PathBuf::from(path);
+    };
+    for dir in XdgDir::ALL {
+        let relative = dir.default_relative();
+        if rest == relative {
+            return dir.under(&home);
+        }
+        if let Some(inner) = rest.strip_prefix(relative).and_then(|r| r.strip_prefix('/')) {
+            return dir.under(&home).join(inner);
+        }
+    }
+    home.join(rest)
+}
+
+// שורות ריקות נזרקות
+fn expand_paths(paths: &[String]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter(|e| !e.trim().is_empty())
+        .map(|e| expand_path(e.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_written(contents: &[u8]) -> (tempfile::TempDir, Settings, Option<ConfigRecovery>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, contents).unwrap();
+        let (settings, recovery) = Settings::load_or_default_from(&path);
+        (dir, settings, recovery)
+    }
+
+    #[test]
+    fn truncated_file_falls_back_to_defaults_and_is_kept_aside() {
+        let full = toml::to_string_pretty(&Settings::default()).unwrap();
+        // נקטע אחרי "key = ", לפני הערך - כמו כתיבה שנעצרה באמצע
+        let cut = full[full.len() / 2..].find("= ").unwrap() + full.len() / 2 + 2;
+        let (dir, settings, recovery) = load_written(&full.as_bytes()[..cut]);
+        assert_eq!(settings, Settings::default());
+        let moved = recovery.unwrap().moved_to.unwrap();
+        assert!(moved.file_name().unwrap().to_string_lossy().starts_with("config.toml.broken-"));
+        assert!(!dir.path().join("config.toml").exists());
+    }
+
+    #[test]
+    fn invalid_syntax_and_types_are_recovered() {
+        for contents in [&b"[general\nmin_file_age_days = 3\n"[..], b"[general]\nmin_file_age_days = \"soon\"\n", b"\xff\xfe"] {
+            let (_dir, settings, recovery) = load_written(contents);
+            assert_eq!(settings, Settings::default());
+            assert!(recovery.is_some_and(|r| !r.error.is_empty() && r.moved_to.is_some()));
+        }
+    }
+
+    #[test]
+    fn missing_file_is_not_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let (settings, recovery) = Settings::load_or_default_from(&dir.path().join("config.toml"));
+        assert_eq!(settings, Settings::default());
+        assert!(recovery.is_none());
+    }
+
+    #[test]
+    fn xdg_homes_apply_only_to_their_owner() {
+        let env = XdgEnv { owner: Some(PathBuf::from("/home/me")), dirs: vec![(XdgDir::Cache, PathBuf::from("/scratch/cache"))] };
+        assert_eq!(env.resolve(XdgDir::Cache, Path::new("/home/me")), Path::new("/scratch/cache"));
+        assert_eq!(env.resolve(XdgDir::Data, Path::new("/home/me")), Path::new("/home/me/.local/share"));
+        assert_eq!(env.resolve(XdgDir::Cache, Path::new("/home/other")), Path::new("/home/other/.cache"));
+    }
+}
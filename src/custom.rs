@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::{self, expand_path};
+use crate::engine::{self, RiskLevel, Target};
+use crate::hooks;
+use crate::persist;
+use crate::registry;
+
+// === פריטים מוגדרים ע"י המשתמש: ~/.config/system-cleaner-pro/custom-cleaners.toml ===
+//
+// [[cleaner]]
+// id = "myapp_cache"
+// name = "MyApp cache"
+// category = "dev"
+// directories = ["~/.cache/myapp"]
+// patterns = [{ root = "~/projects", glob = "*.orig" }]
+// min_age_days = 7
+// risk = "Safe"
+
+pub const CATEGORIES: &[&str] = &["system", "browsers", "dev", "privacy", "analysis", "custom"];
+
+pub fn custom_cleaners_path() -> PathBuf {
+    config::config_dir().join("custom-cleaners.toml")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomPattern {
+    pub root: String,
+    pub glob: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomCleaner {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub category: String,
+    #[serde(default)]
+    pub directories: Vec<String>,
+    #[serde(default)]
+    pub patterns: Vec<CustomPattern>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_age_days: Option<u32>,
+    pub risk: RiskLevel,
+    // תיקייה שנגררה לחלון - רק להפעלה הזו, עד שהמשתמש שומר אותה
+    #[serde(skip)]
+    pub temporary: bool,
+    // קובץ מ-hooks.d: המנוע מריץ אותו במקום directories/patterns
+    #[serde(skip)]
+    pub hook: Option<PathBuf>,
+}
+
+impl CustomCleaner {
+    // פריט זמני מתיקייה שנגררה לחלון. id יציב מהשם, כדי שאפשר יהיה לשמור אותו כמו שהוא
+    pub fn from_dropped_dir(dir: &Path, taken: &HashSet<String>) -> Self {
+        let name = dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| dir.display().to_string());
+        let id = unique_id("folder", &name, taken);
+        CustomCleaner {
+            id,
+            name,
+            description: dir.display().to_string(),
+            category: "custom".to_string(),
+            directories: vec![dir.display().to_string()],
+            patterns: Vec::new(),
+            min_age_days: None,
+            risk: RiskLevel::Moderate,
+            temporary: true,
+            hook: None,
+        }
+    }
+
+    // פריט מ-hooks.d, בקטגוריה משלו. לא נשמר לקובץ - נמצא מחדש בכל הפעלה
+    pub fn from_hook(path: &Path, taken: &HashSet<String>) -> Self {
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let id = unique_id("hook", &name, taken);
+        CustomCleaner {
+            id,
+            name,
+            description: path.display().to_string(),
+            category: "hooks".to_string(),
+            directories: Vec::new(),
+            patterns: Vec::new(),
+            min_age_days: None,
+            risk: RiskLevel::Moderate,
+            temporary: false,
+            hook: Some(path.to_path_buf()),
+        }
+    }
+
+    pub fn targets(&self) -> Vec<Target> {
+        if let Some(hook) = &self.hook {
+            return vec![Target::Command(vec![hook.display().to_string(), "clean".to_string()])];
+        }
+        let dirs = self.directories.iter().map(|d| Target::Directory(expand_path(d)));
+        let patterns = self.patterns.iter().map(|p| Target::Pattern { root: expand_path(&p.root), pattern: p.glob.clone(), sibling: None });
+        dirs.chain(patterns).collect()
+    }
+
+    pub fn min_age(&self) -> Option<Duration> {
+        self.min_age_days.filter(|days| *days > 0).map(|days| Duration::from_secs(days as u64 * 24 * 60 * 60))
+    }
+
+    fn validate(&self, taken: &HashSet<String>) -> Result<(), String> {
+        let valid_id = !self.id.is_empty() && self.id.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+        if !valid_id {
+            return Err("id must be lowercase letters, digits and '_'".to_string());
+        }
+        if taken.contains(&self.id) {
+            return Err("id is already used".to_string());
+        }
+        if self.name.trim().is_empty() {
+            return Err("name is empty".to_string());
+        }
+        if !CATEGORIES.contains(&self.category.as_str()) {
+            return Err(format!("unknown category '{}' (one of: {})", self.category, CATEGORIES.join(", ")));
+        }
+        if self.directories.is_empty() && self.patterns.is_empty() {
+            return Err("no directories or patterns".to_string());
+        }
+        for target in self.targets() {
+            let path = match &target {
+                Target::Directory(dir) => dir,
+                Target::Pattern { root, .. } => root,
+                _ => continue,
+            };
+            if !path.is_absolute() {
+                return Err(format!("{} is not an absolute path", path.display()));
+            }
+            if engine::is_protected_path(path) {
+                return Err(format!("{} is a protected path", path.display()));
+            }
+        }
+        Ok(())
+    }
+}
+
+// id יציב מהשם: אותו קובץ/תיקייה מקבלים אותו id בכל הפעלה
+fn unique_id(prefix: &str, name: &str, taken: &HashSet<String>) -> String {
+    let slug: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    let base = format!("{}_{}", prefix, slug.trim_matches('_'));
+    (1..)
+        .map(|n| if n == 1 { base.clone() } else { format!("{}_{}", base, n) })
+        .find(|id| !taken.contains(id))
+        .unwrap_or(base)
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CustomFile {
+    #[serde(default)]
+    cleaner: Vec<CustomCleaner>,
+}
+
+// מה שנטען, ושורות שגיאה ללוג ההפעלה. קובץ שבור לא מפיל את האפליקציה
+#[derive(Debug, Default)]
+pub struct CustomLoad {
+    pub cleaners: Vec<CustomCleaner>,
+    pub errors: Vec<String>,
+}
+
+pub fn load() -> CustomLoad {
+    let mut load = load_file();
+    let discovery = hooks::discover();
+    let mut taken: HashSet<String> = registry::builtin_cleaners().into_iter().map(|e| e.id).collect();
+    taken.extend(load.cleaners.iter().map(|c| c.id.clone()));
+    for hook in discovery.hooks {
+        let cleaner = CustomCleaner::from_hook(&hook, &taken);
+        taken.insert(cleaner.id.clone());
+        load.cleaners.push(cleaner);
+    }
+    load.errors.extend(discovery.errors);
+    load
+}
+
+fn load_file() -> CustomLoad {
+    let path = custom_cleaners_path();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return CustomLoad::default(),
+        Err(e) => return CustomLoad { cleaners: Vec::new(), errors: vec![format!("{}: {}", path.display(), e)] },
+    };
+    let file: CustomFile = match toml::from_str(&content) {
+        Ok(file) => file,
+        Err(e) => return CustomLoad { cleaners: Vec::new(), errors: vec![format!("{}: {}", path.display(), e)] },
+    };
+
+    let mut taken: HashSet<String> = registry::builtin_cleaners().into_iter().map(|e| e.id).collect();
+    let mut load = CustomLoad::default();
+    for cleaner in file.cleaner {
+        match cleaner.validate(&taken) {
+            Ok(()) => {
+                taken.insert(cleaner.id.clone());
+                load.cleaners.push(cleaner);
+            }
+            Err(e) => load.errors.push(format!("{}: [{}] {}", path.display(), cleaner.id, e)),
+        }
+    }
+    load
+}
+
+// "שמור כפריט קבוע": מוסיפים בלוק [[cleaner]] לסוף הקובץ, בלי לגעת בהערות ובסדר של מה שכבר שם
+pub fn append(cleaner: &CustomCleaner) -> io::Result<()> {
+    let path = custom_cleaners_path();
+    let mut content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    let block = toml::to_string(&CustomFile { cleaner: vec![cleaner.clone()] }).map_err(io::Error::other)?;
+    if !content.is_empty() && !content.ends_with("\n\n") {
+        content.push_str(if content.ends_with('\n') { "\n" } else { "\n\n" });
+    }
+    content.push_str(&block);
+    fs::create_dir_all(config::config_dir())?;
+    persist::write_atomically(&path, content.as_bytes())
+}
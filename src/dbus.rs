@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
+use zbus::object_server::SignalContext;
+use zbus::{fdo, interface, Connection};
+
+use crate::cli::select_items;
+use crate::config::Settings;
+use crate::custom;
+use crate::engine::{DeletionStrategy, RiskLevel, SystemCleaner};
+use crate::events::{CleanEvent, EventSink};
+use crate::history::{self, RunRecord};
+use crate::i18n;
+use crate::lock::{InstanceLock, LockError};
+use crate::manifest::DeletionManifest;
+use crate::quarantine::QuarantineRun;
+use crate::registry;
+use crate::run_id;
+
+// === שירות D-Bus ב-session bus: הפעלה מתוספי GNOME ומסקריפטים ===
+// Preview/Clean רק מכניסים לתור ומחזירים run id; הריצות עצמן אחת אחרי השנייה על thread משלהן,
+// ועם אותה נעילה שה-GUI וה-CLI לוקחים - כך שגם ריצה מהחלון לא תרוץ במקביל לריצה מה-bus.
+
+pub const BUS_NAME: &str = "org.supercleaner.Cleaner";
+pub const OBJECT_PATH: &str = "/org/supercleaner/Cleaner1";
+
+// ריצה אחרת (GUI/CLI) מחזיקה בנעילה - בודקים שוב כל כמה זמן
+const LOCK_RETRY: Duration = Duration::from_secs(2);
+
+struct RunRequest {
+    run_id: String,
+    ids: Vec<String>,
+    dry_run: bool,
+    cancel: CancellationToken,
+}
+
+// מה-thread של הריצות אל ה-runtime של החיבור, ששולח את ה-signals
+enum Signal {
+    Progress { run_id: String, done: u64, total: u64 },
+    Finished { run_id: String, bytes_freed: u64, files: u64 },
+}
+
+pub struct CleanerService {
+    queue: mpsc::UnboundedSender<RunRequest>,
+    // ריצות שבתור או רצות כרגע, לפי run id - בשביל Abort
+    runs: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    // הגודל שהתצוגה המקדימה האחרונה מצאה לכל פריט
+    last_preview: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl CleanerService {
+    fn enqueue(&self, ids: Vec<String>, dry_run: bool) -> fdo::Result<String> {
+        let custom = custom::load();
+        let entries = registry::all_cleaners(&custom.cleaners);
+        if let Err(unknown) = select_items(&entries, &ids) {
+            return Err(fdo::Error::InvalidArgs(format!("Unknown item id(s): {}", unknown.join(", "))));
+        }
+        let run_id = run_id::new_run_id();
+        let cancel = CancellationToken::new();
+        self.runs.lock().unwrap().insert(run_id.clone(), cancel.clone());
+        let request = RunRequest { run_id: run_id.clone(), ids, dry_run, cancel };
+        if self.queue.send(request).is_err() {
+            self.runs.lock().unwrap().remove(&run_id);
+            return Err(fdo::Error::Failed("cleaner worker is not running".to_string()));
+        }
+        Ok(run_id)
+    }
+}
+
+#[interface(name = "org.supercleaner.Cleaner1")]
+impl CleanerService {
+    // (id, שם מתורגם, קטגוריה, בייטים בתצוגה המקדימה האחרונה - 0 אם עוד לא הייתה). רק פריטים שיש להם מה לעשות במערכת הזו
+    async fn list_items(&self) -> Vec<(String, String, String, u64)> {
+        let custom = custom::load();
+        let last_preview = self.last_preview.lock().unwrap();
+        registry::all_cleaners(&custom.cleaners)
+            .into_iter()
+            .filter(|e| e.is_applicable())
+            .map(|e| {
+                let name = i18n::tr_or(&format!("item.{}.name", e.id), &e.name);
+                let bytes = last_preview.get(&e.id).copied().unwrap_or(0);
+                (e.id, name, e.category, bytes)
+            })
+            .collect()
+    }
+
+    // רשימה ריקה = בחירת ברירת המחדל, כמו ב-CLI
+    async fn preview(&self, ids: Vec<String>) -> fdo::Result<String> {
+        self.enqueue(ids, true)
+    }
+
+    async fn clean(&self, ids: Vec<String>) -> fdo::Result<String> {
+        self.enqueue(ids, false)
+    }
+
+    // ריצה שעוד בתור לא מתחילה; ריצה פעילה נעצרת כמו ב-Abort מהחלון
+    async fn abort(&self, run_id: String) -> fdo::Result<()> {
+        match self.runs.lock().unwrap().get(&run_id) {
+            Some(cancel) => {
+                cancel.cancel();
+                Ok(())
+            }
+            None => Err(fdo::Error::InvalidArgs(format!("Unknown run id: {}", run_id))),
+        }
+    }
+
+    // done/total בפריטים
+    #[zbus(signal)]
+    async fn progress(ctxt: &SignalContext<'_>, run_id: &str, done: u64, total: u64) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn finished(ctxt: &SignalContext<'_>, run_id: &str, bytes_freed: u64, files: u64) -> zbus::Result<()>;
+}
+
+// לוקח את השם ב-session bus ומתחיל לקבל בקשות. השירות חי כל עוד החיבור שמוחזר חי,
+// ורק בתוך tokio runtime שממשיך לרוץ
+pub async fn start(verbose: bool) -> zbus::Result<Connection> {
+    let (queue, requests) = mpsc::unbounded_channel();
+    let (signals, mut pending) = mpsc::unbounded_channel();
+    let runs = Arc::new(Mutex::new(HashMap::new()));
+    let last_preview = Arc::new(Mutex::new(HashMap::new()));
+    let service = CleanerService {
+        queue,
+        runs: runs.clone(),
+        last_preview: last_preview.clone(),
+    };
+    let connection = zbus::connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, service)?
+        .build()
+        .await?;
+
+    let emitter = connection.clone();
+    tokio::spawn(async move {
+        let Ok(ctxt) = SignalContext::new(&emitter, OBJECT_PATH) else { return };
+        while let Some(signal) = pending.recv().await {
+            let _ = match signal {
+                Signal::Progress { run_id, done, total } => CleanerService::progress(&ctxt, &run_id, done, total).await,
+                Signal::Finished { run_id, bytes_freed, files } => CleanerService::finished(&ctxt, &run_id, bytes_freed, files).await,
+            };
+        }
+    });
+
+    // המטפלים של המנוע אינם Send - ריצה על thread עם runtime משלו, כמו ה-worker של ה-GUI
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                eprintln!("D-Bus: {}", e);
+                return;
+            }
+        };
+        runtime.block_on(run_queue(requests, signals, runs, last_preview, verbose));
+    });
+    Ok(connection)
+}
+
+async fn run_queue(
+    mut requests: mpsc::UnboundedReceiver<RunRequest>,
+    signals: mpsc::UnboundedSender<Signal>,
+    runs: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    last_preview: Arc<Mutex<HashMap<String, u64>>>,
+    verbose: bool,
+) {
+    while let Some(request) = requests.recv().await {
+        let (bytes_freed, files) = execute(&request, &signals, &last_preview, verbose).await;
+        runs.lock().unwrap().remove(&request.run_id);
+        let _ = signals.send(Signal::Finished { run_id: request.run_id, bytes_freed, files });
+    }
+}
+
+// מחכים לתורנו על הנעילה. Abort בזמן ההמתנה מוותר על הריצה
+async fn wait_for_lock(cancel: &CancellationToken) -> Option<InstanceLock> {
+    loop {
+        match InstanceLock::acquire() {
+            Ok(lock) => return Some(lock),
+            Err(LockError::Held(_)) => {}
+            Err(e) => {
+                eprintln!("D-Bus: {}", e);
+                return None;
+            }
+        }
+        tokio::select! {
+            _ = cancel.cancelled() => return None,
+            _ = tokio::time::sleep(LOCK_RETRY) => {}
+        }
+    }
+}
+
+// (בייטים, קבצים). אותה הכנה כמו ב-CLI: הגדרות, פריטים מוגדרי-משתמש, הסגר, רשימת מחיקות
+async fn execute(
+    request: &RunRequest,
+    signals: &mpsc::UnboundedSender<Signal>,
+    last_preview: &Arc<Mutex<HashMap<String, u64>>>,
+    verbose: bool,
+) -> (u64, u64) {
+    let Some(_lock) = wait_for_lock(&request.cancel).await else { return (0, 0) };
+    let (settings, recovery) = Settings::load_or_default();
+    if let Some(recovery) = recovery {
+        eprintln!("{}", recovery.message(i18n::tr_plain));
+    }
+    let custom = custom::load();
+    let entries = registry::all_cleaners(&custom.cleaners);
+    let Ok(items) = select_items(&entries, &request.ids) else { return (0, 0) };
+
+    let mut cleaner = SystemCleaner::new(verbose, request.dry_run)
+        .with_run_id(request.run_id.clone())
+        .with_settings(&settings)
+        .with_custom(Arc::new(custom.cleaners.clone()))
+        .with_cancel(request.cancel.clone());
+    if request.dry_run {
+        cleaner = cleaner.with_events(record_preview_sizes(last_preview.clone()));
+    } else if settings.safety.quarantine_enabled {
+        match QuarantineRun::create(&request.run_id) {
+            Ok(run) => cleaner = cleaner.with_strategy(DeletionStrategy::Quarantine(Arc::new(run))),
+            Err(e) => {
+                eprintln!("D-Bus: {}", e);
+                return (0, 0);
+            }
+        }
+    }
+    if settings.safety.deletion_manifest && !request.dry_run {
+        match DeletionManifest::create(&request.run_id) {
+            Ok(manifest) => cleaner = cleaner.with_manifest(Arc::new(manifest)),
+            Err(e) => {
+                eprintln!("D-Bus: {}", e);
+                return (0, 0);
+            }
+        }
+    }
+
+    let total = items.len() as u64;
+    let mut cleaned_items: Vec<String> = Vec::new();
+    for (done, entry) in items.iter().enumerate() {
+        if request.cancel.is_cancelled() {
+            break;
+        }
+        let item = cleaner.clone()
+            .with_secure_delete(settings.safety.secure_delete_privacy && entry.risk == RiskLevel::Privacy)
+            .with_skip_in_use(settings.safety.skip_in_use_files && entry.skip_in_use)
+            .with_keep_newest(entry.keep_newest)
+            .with_keep_recent(settings.keep_recent(&entry.id))
+            .with_session_protected(entry.session_protected)
+            .with_sub_entries(entry.default_sub_entries());
+        // כישלון של פריט לא עוצר את השאר; הפרטים בלוג
+        match item.run_item(&entry.id).await {
+            Ok(()) => cleaned_items.push(entry.id.clone()),
+            Err(e) => {
+                if verbose {
+                    eprintln!("{}: {}", entry.id, e);
+                }
+            }
+        }
+        let _ = signals.send(Signal::Progress { run_id: request.run_id.clone(), done: done as u64 + 1, total });
+    }
+    if !request.dry_run {
+        let retry = cleaner.retry_failed().await;
+        if verbose {
+            for line in retry.failure_lines() {
+                eprintln!("{}", line);
+            }
+        }
+    }
+    let stats = cleaner.get_stats_sync();
+    if !request.dry_run {
+        let record = RunRecord {
+            run_id: request.run_id.clone(),
+            finished: chrono::Local::now(),
+            items: items.iter().map(|e| e.id.clone()).collect(),
+            files: stats.files_deleted,
+            bytes: stats.bytes_freed,
+            manifest: cleaner.manifest.as_ref().map(|m| {
+                m.flush();
+                m.path().to_path_buf()
+            }),
+            largest: stats.largest.clone(),
+        };
+        if let Err(e) = history::append(&record).and_then(|()| history::mark_cleaned(&cleaned_items, record.finished)) {
+            eprintln!("D-Bus: {}", e);
+        }
+    }
+    (stats.bytes_freed, stats.files_deleted)
+}
+
+fn record_preview_sizes(sizes: Arc<Mutex<HashMap<String, u64>>>) -> EventSink {
+    Arc::new(move |event: CleanEvent| {
+        if let CleanEvent::ItemDone { id, bytes, .. } = event {
+            sizes.lock().unwrap().insert(id, bytes);
+        }
+    })
+}
+
+// ל-GUI: השירות על thread משלו, עד שה-handle נזרק (כיבוי בהגדרות או יציאה מהאפליקציה)
+pub struct ServiceHandle {
+    stop: Option<oneshot::Sender<()>>,
+}
+
+impl Drop for ServiceHandle {
+    fn drop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+    }
+}
+
+pub fn spawn() -> ServiceHandle {
+    let (stop, stopped) = oneshot::channel::<()>();
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                eprintln!("D-Bus: {}", e);
+                return;
+            }
+        };
+        runtime.block_on(async {
+            match start(false).await {
+                // השם משתחרר כשהחיבור נסגר
+                Ok(_connection) => {
+                    let _ = stopped.await;
+                }
+                Err(e) => eprintln!("D-Bus: {}", e),
+            }
+        });
+    });
+    ServiceHandle { stop: Some(stop) }
+}
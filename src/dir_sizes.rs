@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use walkdir::WalkDir;
+
+use crate::size_cache::{self, CachedSize, DirFingerprint, SizeCache};
+
+// === גודל רקורסיבי של תיקיות ברקע, לתצוגה בלבד (עורך ההחרגות) ===
+// thread אחד עובר על תור הבקשות; ה-UI רק קורא את המפה ומציג spinner עד שהתוצאה מגיעה.
+// התוצאות נשמרות במטמון הגדלים של התצוגה המקדימה (size-cache.json), תחת פריט משלהן
+
+const CACHE_ITEM: &str = "dir_sizes";
+const CACHE_PATTERN: &str = "**";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirSize {
+    Pending,
+    Ready { files: u64, bytes: u64 },
+    // לא קיים, או שאין הרשאה לתיקייה עצמה
+    Missing,
+}
+
+pub struct DirSizer {
+    results: Arc<Mutex<HashMap<PathBuf, DirSize>>>,
+    // (נתיב, לעקוף את המטמון)
+    queue: mpsc::Sender<(PathBuf, bool)>,
+}
+
+impl DirSizer {
+    // on_ready נקרא מה-thread של הסריקה אחרי כל תוצאה (ב-GUI: request_repaint)
+    pub fn spawn(on_ready: impl Fn() + Send + 'static) -> Self {
+        Self::spawn_with_cache(size_cache::cache_path(), on_ready)
+    }
+
+    pub fn spawn_with_cache(cache_path: PathBuf, on_ready: impl Fn() + Send + 'static) -> Self {
+        let results = Arc::new(Mutex::new(HashMap::new()));
+        let (queue, requests) = mpsc::channel();
+        let worker_results = results.clone();
+        thread::spawn(move || worker(requests, worker_results, cache_path, on_ready));
+        DirSizer { results, queue }
+    }
+
+    // נתיב שעוד לא נמדד נכנס לתור בקריאה הראשונה
+    pub fn get(&self, path: &Path) -> DirSize {
+        let mut results = self.results.lock().unwrap();
+        if let Some(size) = results.get(path) {
+            return *size;
+        }
+        results.insert(path.to_path_buf(), DirSize::Pending);
+        let _ = self.queue.send((path.to_path_buf(), false));
+        DirSize::Pending
+    }
+
+    // המטמון מזהה רק שינוי בילדים הישירים - כפתור הרענון מודד מחדש בכל מקרה
+    pub fn refresh(&self, path: &Path) {
+        self.results.lock().unwrap().insert(path.to_path_buf(), DirSize::Pending);
+        let _ = self.queue.send((path.to_path_buf(), true));
+    }
+}
+
+fn worker(requests: mpsc::Receiver<(PathBuf, bool)>, results: Arc<Mutex<HashMap<PathBuf, DirSize>>>, cache_path: PathBuf, on_ready: impl Fn()) {
+    // נסגר כשה-DirSizer נהרס
+    while let Ok(first) = requests.recv() {
+        // מה שהצטבר בינתיים נמדד מול אותה טעינה של המטמון, שנשמר פעם אחת בסוף (Drop)
+        let cache = SizeCache::load_from(cache_path.clone(), false);
+        for (path, force) in std::iter::once(first).chain(requests.try_iter()) {
+            let size = measure_cached(&cache, &path, force);
+            results.lock().unwrap().insert(path, size);
+            on_ready();
+        }
+    }
+}
+
+fn measure_cached(cache: &SizeCache, path: &Path, force: bool) -> DirSize {
+    let Some(fingerprint) = DirFingerprint::of(path) else {
+        // קובץ בודד - אין מה לשמור במטמון
+        return match path.symlink_metadata() {
+            Ok(metadata) if metadata.is_file() => DirSize::Ready { files: 1, bytes: metadata.len() },
+            _ => DirSize::Missing,
+        };
+    };
+    if !force {
+        if let Some(cached) = cache.get(CACHE_ITEM, CACHE_PATTERN, path, fingerprint) {
+            return DirSize::Ready { files: cached.files, bytes: cached.bytes };
+        }
+    }
+    let (files, bytes) = measure(path);
+    cache.put(CACHE_ITEM, CACHE_PATTERN, path, CachedSize { fingerprint, files, bytes });
+    DirSize::Ready { files, bytes }
+}
+
+// בלי לעקוב אחרי symlinks ובלי לחצות mounts - מה שההחרגה באמת שומרת מפני המנוע
+fn measure(dir: &Path) -> (u64, u64) {
+    WalkDir::new(dir)
+        .follow_links(false)
+        .same_file_system(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .fold((0, 0), |(files, bytes), m| (files + 1, bytes + m.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    fn wait_for(sizer: &DirSizer, path: &Path) -> DirSize {
+        let started = Instant::now();
+        loop {
+            match sizer.get(path) {
+                DirSize::Pending if started.elapsed() < Duration::from_secs(10) => thread::sleep(Duration::from_millis(10)),
+                size => return size,
+            }
+        }
+    }
+
+    #[test]
+    fn sizes_arrive_in_the_background_and_are_cached() {
+        let temp = tempfile::tempdir().unwrap();
+        let keep = temp.path().join("keep");
+        fs::create_dir_all(keep.join("nested")).unwrap();
+        fs::write(keep.join("a.bin"), [1; 1000]).unwrap();
+        fs::write(keep.join("nested/b.bin"), [2; 500]).unwrap();
+        let cache_path = temp.path().join("size-cache.json");
+
+        let sizer = DirSizer::spawn_with_cache(cache_path.clone(), || {});
+        assert_eq!(wait_for(&sizer, &keep), DirSize::Ready { files: 2, bytes: 1500 });
+        assert_eq!(wait_for(&sizer, &temp.path().join("gone")), DirSize::Missing);
+        drop(sizer);
+
+        // קובץ עמוק השתנה אבל ה-fingerprint של התיקייה לא - המטמון עונה, והרענון מודד מחדש
+        fs::write(keep.join("nested/b.bin"), [2; 700]).unwrap();
+        let started = Instant::now();
+        while !cache_path.exists() && started.elapsed() < Duration::from_secs(10) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let sizer = DirSizer::spawn_with_cache(cache_path, || {});
+        assert_eq!(wait_for(&sizer, &keep), DirSize::Ready { files: 2, bytes: 1500 });
+        sizer.refresh(&keep);
+        assert_eq!(wait_for(&sizer, &keep), DirSize::Ready { files: 2, bytes: 1700 });
+    }
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio_util::sync::CancellationToken;
+use crate::pause::PauseGate;
+use crate::progress::ScanProgress;
+use walkdir::WalkDir;
+
+// === מציאת קבצים כפולים: קיבוץ לפי גודל, ואישור לפי hash (blake3) ===
+
+const HASH_CHUNK: usize = 64 * 1024;
+pub const DEFAULT_MAX_FILE_MB: u64 = 1024;
+
+// איזה עותק משאירים בכל קבוצה
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeepPolicy {
+    #[default]
+    Oldest,
+    Newest,
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateFile {
+    pub path: PathBuf,
+    pub modified: SystemTime,
+    // מסומן = יימחק בניקוי
+    pub selected: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub size: u64,
+    pub hash: String,
+    pub files: Vec<DuplicateFile>,
+}
+
+impl DuplicateGroup {
+    // כל העותקים חוץ מאחד מסומנים למחיקה
+    pub fn apply_policy(&mut self, keep: KeepPolicy) {
+        let keep_index = match keep {
+            KeepPolicy::Oldest => self.files.iter().enumerate().min_by_key(|(_, f)| f.modified),
+            KeepPolicy::Newest => self.files.iter().enumerate().max_by_key(|(_, f)| f.modified),
+        }
+        .map(|(i, _)| i);
+        for (i, file) in self.files.iter_mut().enumerate() {
+            file.selected = Some(i) != keep_index;
+        }
+    }
+
+    pub fn reclaimable(&self) -> u64 {
+        self.size * self.files.iter().filter(|f| f.selected).count() as u64
+    }
+}
+
+// ההגדרות של סריקה + המקום שבו התוצאות נשמרות בין תצוגה מקדימה לניקוי
+#[derive(Debug, Clone)]
+pub struct DuplicateScan {
+    pub roots: Vec<PathBuf>,
+    pub max_file_bytes: u64,
+    pub keep: KeepPolicy,
+    pub groups: Arc<Mutex<Vec<DuplicateGroup>>>,
+    pub progress: Arc<ScanProgress>,
+}
+
+impl Default for DuplicateScan {
+    fn default() -> Self {
+        DuplicateScan {
+            roots: default_roots(),
+            max_file_bytes: DEFAULT_MAX_FILE_MB * 1024 * 1024,
+            keep: KeepPolicy::Oldest,
+            groups: Arc::new(Mutex::new(Vec::new())),
+            progress: Arc::new(ScanProgress::default()),
+        }
+    }
+}
+
+pub fn default_roots() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+    ["Downloads", "Pictures", "Documents"].iter().map(|d| home.join(d)).collect()
+}
+
+fn hash_file(path: &Path, progress: &ScanProgress, cancel: &CancellationToken, pause: &PauseGate) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; HASH_CHUNK];
+    loop {
+        pause.wait(cancel);
+        if cancel.is_cancelled() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled"));
+        }
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        progress.done.fetch_add(n as u64, Ordering::Relaxed);
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+// קבצים שאי אפשר לקרוא מדולגים בשקט - הסריקה ממשיכה
+// hard links לאותו קובץ הם לא כפילויות. ב-Windows אין (dev, ino) ב-std - לא מסננים
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+pub fn find_duplicates(scan: &DuplicateScan, cancel: &CancellationToken, pause: &PauseGate) -> Vec<DuplicateGroup> {
+    scan.progress.reset();
+
+    // שלב 1: קיבוץ לפי גודל. hard links (אותו dev+inode) נספרים פעם אחת - מחיקה שלהם לא משחררת כלום
+    let mut by_size: HashMap<u64, Vec<(PathBuf, SystemTime)>> = HashMap::new();
+    let mut seen_inodes = HashSet::new();
+    for root in &scan.roots {
+        for entry in WalkDir::new(root).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+            pause.wait(cancel);
+            if cancel.is_cancelled() {
+                return Vec::new();
+            }
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let metadata = match entry.metadata() {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+            let size = metadata.len();
+            if size == 0 || size > scan.max_file_bytes || file_id(&metadata).is_some_and(|id| !seen_inodes.insert(id)) {
+                continue;
+            }
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            by_size.entry(size).or_default().push((entry.into_path(), modified));
+        }
+    }
+    by_size.retain(|_, files| files.len() > 1);
+
+    let total: u64 = by_size.iter().map(|(size, files)| size * files.len() as u64).sum();
+    scan.progress.total.store(total, Ordering::Relaxed);
+
+    // שלב 2: hash רק למועמדים
+    let mut groups = Vec::new();
+    for (size, files) in by_size {
+        let mut by_hash: HashMap<String, Vec<DuplicateFile>> = HashMap::new();
+        for (path, modified) in files {
+            pause.wait(cancel);
+            if cancel.is_cancelled() {
+                return Vec::new();
+            }
+            match hash_file(&path, &scan.progress, cancel, pause) {
+                Ok(hash) => by_hash.entry(hash).or_default().push(DuplicateFile { path, modified, selected: false }),
+                // לא נקרא - מוסיפים את הגודל להתקדמות כדי שהבר לא ייתקע (fraction חסום ב-1.0)
+                Err(_) => {
+                    scan.progress.done.fetch_add(size, Ordering::Relaxed);
+                }
+            }
+        }
+        for (hash, files) in by_hash {
+            if files.len() > 1 {
+                let mut group = DuplicateGroup { size, hash, files };
+                group.apply_policy(scan.keep);
+                groups.push(group);
+            }
+        }
+    }
+    // הכי הרבה מקום לפנות - ראשון
+    groups.sort_by_key(|g| std::cmp::Reverse(g.size * (g.files.len() as u64 - 1)));
+    groups
+}
+
+// הקובץ השתנה מאז הסריקה? אז הוא כבר לא בהכרח כפול
+pub fn unchanged_since_scan(file: &DuplicateFile, size: u64) -> Option<fs::Metadata> {
+    let metadata = fs::symlink_metadata(&file.path).ok()?;
+    let modified = metadata.modified().ok()?;
+    (metadata.is_file() && metadata.len() == size && modified == file.modified).then_some(metadata)
+}
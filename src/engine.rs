@@ -0,0 +1,6054 @@
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+#[cfg(target_os = "linux")]
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command as ProcessCommand;
+use tokio_util::sync::CancellationToken;
+use futures::future::{FutureExt, LocalBoxFuture};
+use walkdir::WalkDir;
+use which::which;
+use chrono::{DateTime, Local};
+
+use crate::autostart;
+use crate::chromium;
+use crate::config::{Settings, XdgDir};
+use crate::custom::CustomCleaner;
+use crate::duplicates::{self, DuplicateScan};
+use crate::events::{CleanEvent, EventSink};
+use crate::exclusions::{Exclusions, Rule};
+use crate::file_manager;
+use crate::firefox;
+use crate::git;
+use crate::history;
+use crate::hooks;
+use crate::i18n::{tr, tr_args};
+use crate::largest::{LargeDeletion, LargestFiles};
+use crate::lock;
+use crate::manifest::{self, DeletionManifest};
+use crate::pause::PauseGate;
+use crate::persist;
+use crate::paths::SystemPaths;
+use crate::mounts;
+use crate::parallel_walk;
+use crate::preview::{PreviewEntry, PreviewStore};
+use crate::progress::{format_duration, CurrentPath, ScanProgress};
+use crate::priority;
+use crate::quarantine::{self, QuarantineRun};
+use crate::run_id;
+use crate::size_cache::{CachedSize, DirFingerprint, SizeCache};
+#[cfg(not(windows))]
+use crate::trash;
+use crate::units;
+use crate::unmeasured::{self, Unmeasured};
+use crate::users::{self, UserAccount};
+use crate::wine;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleaningStats {
+    pub files_deleted: u64,
+    pub bytes_freed: u64,
+    pub directories_cleaned: u64,
+    // פריטים שנכשלו (כולל id שאין לו מטפל במנוע)
+    #[serde(default)]
+    pub errors: u64,
+    // קבצים שדולגו כי תהליך אחר מחזיק אותם פתוחים
+    #[serde(default)]
+    pub skipped_in_use: u64,
+    // מיקומים של המערכת שדולגו כי הריצה לא כ-root
+    #[serde(default)]
+    pub skipped_needs_root: u64,
+    // ב-macOS: נתיבים ש-SIP או הגנת הפרטיות חוסמים, גם ל-root
+    #[serde(default)]
+    pub skipped_protected: u64,
+    // chattr +i / +a (על הקובץ או על התיקייה שלו) - גם root לא מוחק עד שמסירים את הדגל
+    #[serde(default)]
+    pub skipped_immutable: u64,
+    // תבניות על כל הבית (*~, *.pyc): קבצים בתוך git repo - שנמחקו אחרי ש-git אישר שהם ignored, ושנשארו
+    #[serde(default)]
+    pub git_verified: u64,
+    #[serde(default)]
+    pub git_skipped: u64,
+    // כמה זמן לקח כל פריט, בלי זמן ההשהיה (פריט שרץ לכמה משתמשים - הסכום)
+    #[serde(default)]
+    pub durations: HashMap<String, Duration>,
+    // הועברו לארכיון (DeletionStrategy::MoveTo) - לא פינו מקום, אז לא נספרים ב-bytes_freed
+    #[serde(default)]
+    pub files_organized: u64,
+    #[serde(default)]
+    pub bytes_organized: u64,
+    // bytes_freed מתחלק לשניים: מה שהתפנה בדיסק, ומה שהיה ב-tmpfs (/tmp ברוב ההפצות) ושחרר זיכרון
+    #[serde(default)]
+    pub disk_bytes_freed: u64,
+    #[serde(default)]
+    pub ram_bytes_freed: u64,
+    // עיגוני btrfs/ZFS שנמחקו בהם קבצים - שם snapshots יכולים להחזיק את המקום
+    #[serde(default)]
+    pub snapshot_fs_mounts: Vec<mounts::MountInfo>,
+    // הקבצים הגדולים ביותר שנמחקו (בתצוגה מקדימה - שהיו נמחקים), מהגדול לקטן
+    #[serde(default)]
+    pub largest: Vec<LargeDeletion>,
+    pub timestamp: DateTime<Local>,
+}
+
+impl CleaningStats {
+    pub fn new() -> Self {
+        CleaningStats {
+            files_deleted: 0,
+            bytes_freed: 0,
+            directories_cleaned: 0,
+            errors: 0,
+            skipped_in_use: 0,
+            skipped_needs_root: 0,
+            skipped_protected: 0,
+            skipped_immutable: 0,
+            git_verified: 0,
+            git_skipped: 0,
+            durations: HashMap::new(),
+            files_organized: 0,
+            bytes_organized: 0,
+            disk_bytes_freed: 0,
+            ram_bytes_freed: 0,
+            snapshot_fs_mounts: Vec::new(),
+            largest: Vec::new(),
+            timestamp: Local::now(),
+        }
+    }
+}
+
+// מונים שמתעדכנים בלולאת המחיקה - ה-GUI קורא אותם כל פריים בלי לנעול mutex,
+// וכמה workers מוסיפים אליהם במקביל בלי לחכות זה לזה
+#[derive(Debug, Default)]
+pub struct LiveCounters {
+    pub files_deleted: AtomicU64,
+    pub bytes_freed: AtomicU64,
+    // החלק של bytes_freed שהיה על tmpfs/ramfs
+    pub ram_bytes_freed: AtomicU64,
+    pub directories_cleaned: AtomicU64,
+    pub errors: AtomicU64,
+    pub skipped_in_use: AtomicU64,
+    pub skipped_needs_root: AtomicU64,
+    pub skipped_protected: AtomicU64,
+    pub skipped_immutable: AtomicU64,
+    pub git_verified: AtomicU64,
+    pub git_skipped: AtomicU64,
+    pub files_organized: AtomicU64,
+    pub bytes_organized: AtomicU64,
+}
+
+impl LiveCounters {
+    // כל מונה נקרא בנפרד - תמונה שנלקחת באמצע מחיקה יכולה לפגר בקובץ אחד, לא יותר
+    pub fn snapshot(&self) -> CleaningStats {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let bytes_freed = load(&self.bytes_freed);
+        let ram_bytes_freed = load(&self.ram_bytes_freed);
+        CleaningStats {
+            files_deleted: load(&self.files_deleted),
+            bytes_freed,
+            directories_cleaned: load(&self.directories_cleaned),
+            errors: load(&self.errors),
+            skipped_in_use: load(&self.skipped_in_use),
+            skipped_needs_root: load(&self.skipped_needs_root),
+            skipped_protected: load(&self.skipped_protected),
+            skipped_immutable: load(&self.skipped_immutable),
+            git_verified: load(&self.git_verified),
+            git_skipped: load(&self.git_skipped),
+            files_organized: load(&self.files_organized),
+            bytes_organized: load(&self.bytes_organized),
+            disk_bytes_freed: bytes_freed.saturating_sub(ram_bytes_freed),
+            ram_bytes_freed,
+            ..CleaningStats::new()
+        }
+    }
+}
+
+pub type CleanResult = Result<(), Box<dyn std::error::Error>>;
+// מטפל של פריט: מקבל את ה-cleaner ומחזיר את ה-future של הניקוי
+pub type ItemHandler = for<'a> fn(&'a SystemCleaner) -> LocalBoxFuture<'a, CleanResult>;
+// מזהה ההתקן של נתיב - מוחלף בבדיקות, שלא יכולות ליצור mounts אמיתיים
+pub type DeviceIdProvider = fn(&Path, &fs::Metadata) -> u64;
+// האם התיקייה על דיסק מסתובב - מוחלף בבדיקות כדי שהמסלול המקבילי ירוץ בכל מכונה
+pub type RotationalProbe = fn(&Path) -> bool;
+
+pub type LogCallback = Arc<Mutex<Box<dyn Fn(String) + Send + Sync>>>;
+
+// כמה מסוכן הפריט - קובע התנהגות (למשל דריסה לפני מחיקה בפריטי פרטיות)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RiskLevel {
+    Safe,
+    Moderate,
+    Privacy,
+    Dangerous,
+}
+
+const SHRED_CHUNK: usize = 1024 * 1024;
+pub const DEFAULT_SHRED_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+// התוצאה של פריט בודד בריצה - מוצג כאייקון בסרגל הצד
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ItemOutcome {
+    Ok { files: u64, bytes: u64 },
+    Skipped(String),
+    Failed(String),
+}
+
+// פריט שלא רלוונטי כרגע ("not installed", "needs root") - לא נחשב כשגיאה
+#[derive(Debug)]
+pub struct Skipped(pub String);
+
+impl std::fmt::Display for Skipped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "skipped: {}", self.0)
+    }
+}
+
+impl std::error::Error for Skipped {}
+
+fn skip(reason: &str) -> Box<dyn std::error::Error> {
+    Box::new(Skipped(reason.to_string()))
+}
+
+// שגיאה שחוזרת מתוך spawn_blocking חייבת לעבור בין threads
+type BlockingError = Box<dyn std::error::Error + Send + Sync>;
+
+fn skip_blocking(reason: &str) -> BlockingError {
+    Box::new(Skipped(reason.to_string()))
+}
+
+// מריץ ניקוי חוסם על thread נפרד ומחזיר את השגיאה בטיפוס של שאר הפריטים (downcast ל-Skipped עדיין עובד)
+async fn run_blocking(f: impl FnOnce() -> Result<(), BlockingError> + Send + 'static) -> Result<(), Box<dyn std::error::Error>> {
+    tokio::task::spawn_blocking(f).await?.map_err(|e| e as Box<dyn std::error::Error>)
+}
+
+// בודק לפי /proc/<pid>/comm אם אחד התהליכים רץ
+#[cfg(not(target_os = "macos"))]
+pub fn is_process_running(names: &[&str]) -> bool {
+    let entries = match fs::read_dir("/proc") {
+        Ok(e) => e,
+        Err(_) => return false,
+    };
+    entries.filter_map(|e| e.ok()).any(|entry| {
+        fs::read_to_string(entry.path().join("comm"))
+        .map(|comm| names.contains(&comm.trim()))
+        .unwrap_or(false)
+    })
+}
+
+// ב-macOS אין /proc: pgrep -x משווה לשם התהליך, כמו comm בלינוקס.
+// pgrep שלא רץ = לא יודעים, ואז עדיף להניח שהתהליך פתוח
+#[cfg(target_os = "macos")]
+pub fn is_process_running(names: &[&str]) -> bool {
+    names.iter().any(|name| {
+        std::process::Command::new("pgrep")
+            .arg("-x")
+            .arg(name)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_or(true, |status| status.success())
+    })
+}
+
+// XDG_SESSION_TYPE=x11/wayland (לא tty) - יש שולחן עבודה חי שמחזיק קבצים ב-~/.cache
+pub fn is_graphical_session() -> bool {
+    std::env::var("XDG_SESSION_TYPE").is_ok_and(|kind| !kind.is_empty() && kind != "tty")
+}
+
+fn is_root() -> bool {
+    users::is_root()
+}
+
+// מה פריט נוגע בו בפועל. המנוע מריץ את הרשימה הזו, וה-GUI מציג אותה - כך היא לא יכולה לשקר.
+#[derive(Debug, Clone)]
+pub enum Target {
+    // מרוקנים את התוכן
+    Directory(PathBuf),
+    // קבצים שמתאימים לתבנית, רקורסיבית. עם sibling: רק בתיקיות שיש בהן גם קובץ שמתאים לו
+    Pattern { root: PathBuf, pattern: String, sibling: Option<String> },
+    // כל תיקייה בשם dir_name מתחת ל-root (למשל cache2 של Firefox)
+    Search { root: PathBuf, dir_name: String },
+    // פקודה חיצונית
+    Command(Vec<String>),
+    // רק נסרק, והתוצאות נבחרות בעץ התצוגה המקדימה - אף פעם לא מרוקן
+    ScanOnly(PathBuf),
+    // המקום הפנוי ב-mount נדרס באפסים; שום קובץ קיים לא נמחק
+    FreeSpace(PathBuf),
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Directory(dir) => write!(f, "{}", dir.display()),
+            Target::Pattern { root, pattern, sibling: None } => write!(f, "{}/**/{}", root.display(), pattern),
+            Target::Pattern { root, pattern, sibling: Some(sibling) } => write!(f, "{}/**/{} (next to {})", root.display(), pattern, sibling),
+            Target::Search { root, dir_name } => write!(f, "{}/**/{}/", root.display(), dir_name),
+            Target::Command(argv) => write!(f, "runs: {}", argv.join(" ")),
+            Target::ScanOnly(root) => write!(f, "{} (scan, choose in preview)", root.display()),
+            Target::FreeSpace(mount) => write!(f, "free space on {} (overwritten with zeros)", mount.display()),
+        }
+    }
+}
+
+fn dir_target<P: Into<PathBuf>>(path: P) -> Target {
+    Target::Directory(path.into())
+}
+
+fn pattern_target<P: Into<PathBuf>>(root: P, pattern: &str) -> Target {
+    Target::Pattern { root: root.into(), pattern: pattern.to_string(), sibling: None }
+}
+
+// "*.log" ליד "*.tex": קובץ log בתיקייה אחרת לא שייך לפריט
+fn sibling_pattern_target<P: Into<PathBuf>>(root: P, pattern: &str, sibling: &str) -> Target {
+    Target::Pattern { root: root.into(), pattern: pattern.to_string(), sibling: Some(sibling.to_string()) }
+}
+
+fn command_target(argv: &[&str]) -> Target {
+    Target::Command(argv.iter().map(|a| a.to_string()).collect())
+}
+
+// הכלי עצמו אם הוא מותקן (אינדקס: הוא גם עוצר את ה-daemon; composer: יודע מה בטוח למחוק),
+// אחרת מחיקת התיקיות. ScanOnly = רק למדידת הגודל סביב הפקודה.
+fn tool_or_dirs_targets(tools: &[&[&str]], dirs: Vec<PathBuf>) -> Vec<Target> {
+    match tools.iter().find(|argv| which(argv[0]).is_ok()) {
+        Some(argv) => std::iter::once(command_target(argv)).chain(dirs.into_iter().map(Target::ScanOnly)).collect(),
+        None => dirs.into_iter().map(Target::Directory).collect(),
+    }
+}
+
+// ב-macOS כמעט הכול תחת ~/Library/Caches; "System Cache" הוא ה-cache של האפליקציות שם
+#[cfg(target_os = "macos")]
+fn macos_targets(id: &str, paths: &SystemPaths) -> Option<Vec<Target>> {
+    let home = &paths.home;
+    let caches = home.join("Library/Caches");
+    let targets = match id {
+        "var_cache" => vec![dir_target(&caches)],
+        "trash" => vec![dir_target(home.join(".Trash"))],
+        "chrome_cache" => vec![dir_target(caches.join("Google/Chrome"))],
+        "brave_cache" => vec![dir_target(caches.join("BraveSoftware/Brave-Browser"))],
+        "firefox_cache" => vec![Target::Search { root: caches.join("Firefox/Profiles"), dir_name: "cache2".to_string() }],
+        "firefox_leftovers" => vec![Target::ScanOnly(home.join("Library/Application Support/Firefox"))],
+        _ => return None,
+    };
+    Some(targets)
+}
+
+// EPERM (ולא EACCES) ב-macOS = SIP או הגנת פרטיות (Full Disk Access), ששום הרשאה בקובץ לא עוקפת
+#[cfg(target_os = "macos")]
+fn is_protected_by_os(e: &io::Error) -> bool {
+    e.raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn is_protected_by_os(_e: &io::Error) -> bool {
+    false
+}
+
+// ב-ecryptfs השם המוצפן ארוך בהרבה מהמקורי, ושם של יותר מ-~143 תווים כבר לא נכנס ב-255 של ה-lower
+fn is_name_too_long(e: &io::Error) -> bool {
+    // ENAMETOOLONG ב-unix, ERROR_FILENAME_EXCED_RANGE ב-Windows
+    e.kind() == io::ErrorKind::InvalidFilename
+}
+
+#[cfg(target_os = "linux")]
+nix::ioctl_read!(fs_ioc_getflags, b'f', 1, libc::c_long);
+
+// FS_IMMUTABLE_FL / FS_APPEND_FL מ-linux/fs.h
+#[cfg(target_os = "linux")]
+const IMMUTABLE_FLAGS: libc::c_long = 0x10 | 0x20;
+
+// EPERM ב-Linux על קובץ שהבעלות עליו בסדר: בודקים את הדגלים של הקובץ ושל התיקייה שמכילה אותו.
+// רק קבצים רגילים ותיקיות נפתחים - FIFO או התקן אסור לפתוח סתם
+#[cfg(target_os = "linux")]
+fn is_immutable(path: &Path, e: &io::Error) -> bool {
+    use std::os::unix::fs::OpenOptionsExt;
+    if e.raw_os_error() != Some(libc::EPERM) {
+        return false;
+    }
+    let flagged = |path: &Path| {
+        let Ok(metadata) = fs::symlink_metadata(path) else { return false };
+        if !metadata.is_file() && !metadata.is_dir() {
+            return false;
+        }
+        let Ok(file) = OpenOptions::new().read(true).custom_flags(libc::O_NONBLOCK | libc::O_NOFOLLOW).open(path) else { return false };
+        let mut flags: libc::c_long = 0;
+        // SAFETY: ה-fd פתוח עד סוף הבלוק, והמצביע הוא למשתנה מקומי בגודל שה-ioctl מצפה לו
+        let result = unsafe { fs_ioc_getflags(std::os::fd::AsRawFd::as_raw_fd(&file), &mut flags) };
+        result.is_ok() && flags & IMMUTABLE_FLAGS != 0
+    };
+    flagged(path) || path.parent().is_some_and(flagged)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_immutable(_path: &Path, _e: &io::Error) -> bool {
+    false
+}
+
+// "Would remove: <path> (12 files, 3.4MB)" או "(3.4MB)" לקובץ בודד
+fn parse_brew_would_remove(line: &str) -> Option<(PathBuf, u64, u64)> {
+    let rest = line.trim().strip_prefix("Would remove: ")?;
+    let open = rest.rfind(" (")?;
+    let usage = rest[open + 2..].strip_suffix(')')?;
+    let (files, size) = match usage.split_once(", ") {
+        Some((files, size)) => (files.split_whitespace().next()?.parse().ok()?, size),
+        None => (1, usage),
+    };
+    Some((PathBuf::from(&rest[..open]), files, parse_brew_size(size)?))
+}
+
+// brew מציג גדלים בבסיס 1024, למשל "512B", "3.4MB"
+fn parse_brew_size(size: &str) -> Option<u64> {
+    let unit_start = size.find(|c: char| c.is_ascii_alphabetic())?;
+    let value: f64 = size[..unit_start].parse().ok()?;
+    let multiplier: u64 = match &size[unit_start..] {
+        "B" => 1,
+        "KB" => 1 << 10,
+        "MB" => 1 << 20,
+        "GB" => 1 << 30,
+        "TB" => 1 << 40,
+        _ => return None,
+    };
+    Some((value * multiplier as f64) as u64)
+}
+
+// ב-Windows הנתיבים יושבים ב-%LOCALAPPDATA% ולא ב-XDG. סל המיחזור עובר דרך ה-Shell API
+// (ראו clean_trash), ולכן אין לו נתיבים כאן.
+#[cfg(windows)]
+fn windows_targets(id: &str, paths: &SystemPaths) -> Option<Vec<Target>> {
+    let local = dirs::data_local_dir().unwrap_or_else(|| paths.home.join("AppData/Local"));
+    let system_root = std::env::var_os("SystemRoot").map(PathBuf::from).unwrap_or_else(|| PathBuf::from(r"C:\Windows"));
+    let targets = match id {
+        "tmp" => vec![dir_target(&paths.tmp), dir_target(system_root.join("Temp"))],
+        "trash" => Vec::new(),
+        "thumbnails" => vec![pattern_target(local.join(r"Microsoft\Windows\Explorer"), "thumbcache_*.db")],
+        "chrome_cache" => vec![dir_target(local.join(r"Google\Chrome\User Data\Default\Cache"))],
+        "brave_cache" => vec![dir_target(local.join(r"BraveSoftware\Brave-Browser\User Data\Default\Cache"))],
+        // הפרופילים ב-%APPDATA%, אבל cache2 שלהם נשמר בעותק המקביל ב-%LOCALAPPDATA%
+        "firefox_cache" => vec![Target::Search { root: local.join(r"Mozilla\Firefox\Profiles"), dir_name: "cache2".to_string() }],
+        // דוחות הקריסה והטלמטריה בפרופיל עצמו, ב-%APPDATA%
+        "firefox_leftovers" => vec![Target::ScanOnly(dirs::data_dir().unwrap_or_else(|| paths.home.join("AppData/Roaming")).join(r"Mozilla\Firefox"))],
+        _ => return None,
+    };
+    Some(targets)
+}
+
+fn lang_cache_sections(paths: &SystemPaths) -> Vec<(&'static str, Vec<Target>)> {
+    let (home, cache) = (&paths.home, &paths.cache);
+    let mut gems = vec![Target::Search { root: home.join(".gem"), dir_name: "cache".to_string() }];
+    if which("gem").is_ok() {
+        gems.push(command_target(&["gem", "cleanup"]));
+    }
+    vec![
+        ("RubyGems", gems),
+        ("Bundler", vec![dir_target(home.join(".bundle/cache"))]),
+        ("Composer", tool_or_dirs_targets(&[&["composer", "clear-cache"]], vec![cache.join("composer")])),
+        // ScanOnly: ההתקנות הישנות של GHC מטופלות בנפרד (remove_old_ghc)
+        ("Stack", vec![dir_target(home.join(".stack/pantry")), Target::ScanOnly(home.join(".stack/programs"))]),
+    ]
+}
+
+// תוצרי בנייה של LaTeX - רק ליד קובץ .tex, כך שכל .log אחר בבית נשאר
+const TEX_ARTIFACTS: &[&str] = &["*.aux", "*.log", "*.toc", "*.synctex.gz"];
+
+// "emacs lock" - symlink בשם .#file שהיעד שלו הוא "user@host.PID:boot", לא קובץ אמיתי
+const EMACS_LOCK_PATTERN: &str = ".#*";
+
+fn doc_temp_sections(paths: &SystemPaths) -> Vec<(&'static str, Vec<Target>)> {
+    let home = &paths.home;
+    vec![
+        ("LibreOffice", vec![dir_target(paths.config.join("libreoffice/4/user/backup"))]),
+        ("TeX", TEX_ARTIFACTS.iter().map(|p| sibling_pattern_target(home, p, "*.tex")).collect()),
+        ("Editor autosaves", vec![pattern_target(home, "#*#"), pattern_target(home, EMACS_LOCK_PATTERN)]),
+    ]
+}
+
+// חלק לכל תת-פריט ב-registry ("cache" / "media")
+fn screen_leftover_sections(paths: &SystemPaths) -> Vec<(&'static str, Vec<Target>)> {
+    let (home, cache) = (&paths.home, &paths.cache);
+    vec![
+        ("cache", vec![dir_target(cache.join("gnome-screenshot")), dir_target(cache.join("spectacle"))]),
+        // ScanOnly: רק קבצים ישנים, לבחירה בתצוגה המקדימה (find_old_screen_media)
+        ("media", vec![Target::ScanOnly(home.join("Videos/Screencasts")), Target::ScanOnly(home.join("Pictures/Screenshots"))]),
+    ]
+}
+
+// "user@host.PID:boot" -> (host, PID). ה-host יכול להכיל נקודות - ה-PID אחרי האחרונה
+fn emacs_lock_owner(target: &str) -> Option<(&str, u32)> {
+    let owner = target.split(':').next()?;
+    let (user_host, pid) = owner.rsplit_once('.')?;
+    let (_, host) = user_host.split_once('@')?;
+    Some((host, pid.parse().ok()?))
+}
+
+fn local_hostname() -> Option<String> {
+    fs::read_to_string("/proc/sys/kernel/hostname").ok().map(|h| h.trim().to_string())
+}
+
+// יש בתיקייה קובץ שמתאים ל-pattern (בלי להיכנס לתתי-תיקיות)
+fn dir_has_match(dir: &Path, pattern: &str) -> bool {
+    fs::read_dir(dir).into_iter().flatten().filter_map(|e| e.ok())
+        .any(|e| e.file_type().is_ok_and(|t| t.is_file()) && name_matches(&e.file_name(), pattern))
+}
+
+fn is_available(target: &Target) -> bool {
+    match target {
+        Target::Command(argv) => which(&argv[0]).is_ok(),
+        Target::Directory(dir) | Target::ScanOnly(dir) | Target::Search { root: dir, .. } | Target::Pattern { root: dir, .. } => dir.exists(),
+        Target::FreeSpace(_) => false,
+    }
+}
+
+// "ghc-9.4.7", "ghc-tinfo6-9.4.7" -> [9, 4, 7]
+fn ghc_version(dir: &Path) -> Option<Vec<u32>> {
+    let name = dir.file_name()?.to_str()?;
+    if !name.starts_with("ghc-") {
+        return None;
+    }
+    parse_version(name.rsplit('-').next()?)
+}
+
+fn parse_version(text: &str) -> Option<Vec<u32>> {
+    text.split('.').map(|part| part.parse().ok()).collect()
+}
+
+// גרסאות GHC שה-global project של stack מפנה אליהן. resolver כמו lts-22.0 לא אומר את הגרסה
+// בעצמו - אז לוקחים אותה מ-.stack-work/install/<platform>/<hash>/<ghc-version>.
+// None = לא ברור, ואז לא מוחקים כלום.
+fn stack_global_ghc(home: &Path) -> Option<HashSet<Vec<u32>>> {
+    let project = home.join(".stack/global-project");
+    let Ok(yaml) = fs::read_to_string(project.join("stack.yaml")) else {
+        return Some(HashSet::new());
+    };
+    let mut versions = HashSet::new();
+    let mut snapshot_without_version = false;
+    for line in yaml.lines() {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.split('#').next().unwrap_or("").trim().trim_matches('"');
+        match key.trim() {
+            "compiler" | "resolver" | "snapshot" => match value.strip_prefix("ghc-").and_then(parse_version) {
+                Some(version) => {
+                    versions.insert(version);
+                }
+                None if key.trim() != "compiler" => snapshot_without_version = true,
+                None => {}
+            },
+            _ => {}
+        }
+    }
+    let installed = WalkDir::new(project.join(".stack-work/install"))
+        .min_depth(3)
+        .max_depth(3)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+        .filter_map(|e| e.file_name().to_str().and_then(parse_version));
+    versions.extend(installed);
+    if snapshot_without_version && versions.is_empty() {
+        return None;
+    }
+    Some(versions)
+}
+
+// sso/cache: {"expiresAt": "..."}; cli/cache: {"Credentials": {"Expiration": "..."}}.
+// ה-CLI הישן כותב "2024-01-01T00:00:00UTC" במקום Z
+fn aws_token_expiry(content: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let expiry = json.get("expiresAt")
+        .or_else(|| json.get("Credentials").and_then(|c| c.get("Expiration")))?
+        .as_str()?;
+    let normalized = match expiry.strip_suffix("UTC") {
+        Some(stripped) => format!("{}Z", stripped),
+        None => expiry.to_string(),
+    };
+    chrono::DateTime::parse_from_rfc3339(&normalized).ok().map(|t| t.with_timezone(&chrono::Utc))
+}
+
+#[cfg(unix)]
+fn is_writable_dir(dir: &Path) -> bool {
+    nix::unistd::access(dir, nix::unistd::AccessFlags::W_OK).is_ok()
+}
+
+#[cfg(not(unix))]
+fn is_writable_dir(dir: &Path) -> bool {
+    fs::metadata(dir).map(|m| m.is_dir() && !m.permissions().readonly()).unwrap_or(false)
+}
+
+// המקום שהקובץ תופס בדיסק; בלי blocks (Windows) - האורך שלו
+#[cfg(unix)]
+fn allocated_bytes(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.blocks() * 512
+}
+
+#[cfg(not(unix))]
+fn allocated_bytes(metadata: &fs::Metadata) -> u64 {
+    metadata.len()
+}
+
+// (קבצים, בייטים בדיסק) - לפני ואחרי פקודה שמנקה בעצמה
+fn dir_usage(dirs: &[PathBuf]) -> (u64, u64) {
+    dirs.iter()
+        .flat_map(|dir| WalkDir::new(dir).into_iter().filter_map(|e| e.ok()))
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .fold((0, 0), |(files, bytes), m| (files + 1, bytes + allocated_bytes(&m)))
+}
+
+// "Large old downloads": קבצים גדולים שלא נגעו בהם הרבה זמן
+#[derive(Debug, Clone)]
+pub struct LargeFileScan {
+    pub roots: Vec<PathBuf>,
+    pub min_bytes: u64,
+    pub min_age: Duration,
+    // במקום מחיקה: <archive>/<שנה>/ לפי זמן השינוי
+    pub archive: Option<PathBuf>,
+}
+
+pub const DEFAULT_LARGE_FILE_MB: u64 = 100;
+pub const DEFAULT_LARGE_FILE_AGE_DAYS: u64 = 180;
+
+impl Default for LargeFileScan {
+    fn default() -> Self {
+        LargeFileScan {
+            roots: vec![dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp")).join("Downloads")],
+            min_bytes: DEFAULT_LARGE_FILE_MB * 1024 * 1024,
+            min_age: Duration::from_secs(DEFAULT_LARGE_FILE_AGE_DAYS * 24 * 60 * 60),
+            archive: None,
+        }
+    }
+}
+
+// "Wipe free space": קובץ אפסים עד שהדיסק כמעט מלא, ואז מוחקים אותו -
+// כך בלוקים של קבצים שנמחקו בעבר נדרסים
+#[derive(Debug, Clone)]
+pub struct FreeSpaceWipe {
+    pub mount: PathBuf,
+    // כמה מקום משאירים פנוי, כדי שהמערכת לא תיתקע בזמן הכתיבה
+    pub reserve_bytes: u64,
+    pub progress: Arc<ScanProgress>,
+}
+
+pub const DEFAULT_WIPE_RESERVE_MB: u64 = 1024;
+const WIPE_CHUNK: usize = 4 * 1024 * 1024;
+// מישהו אחר אולי כותב לדיסק במקביל - בודקים את המקום הפנוי בפועל כל 256 MB
+const WIPE_RECHECK_EVERY: u64 = 64;
+
+impl Default for FreeSpaceWipe {
+    fn default() -> Self {
+        FreeSpaceWipe {
+            mount: default_wipe_mount(),
+            reserve_bytes: DEFAULT_WIPE_RESERVE_MB * 1024 * 1024,
+            progress: Arc::new(ScanProgress::default()),
+        }
+    }
+}
+
+// "Super Cleaner data": כמה מהנתונים של התוכנה עצמה נשמרים (0 = בלי הגבלה, חוץ מההסגר)
+#[derive(Debug, Clone)]
+pub struct OwnDataPolicy {
+    pub history_max_runs: usize,
+    pub manifest_max_age_days: u64,
+    pub quarantine_max_age_days: i64,
+    pub quarantine_max_bytes: u64,
+}
+
+impl Default for OwnDataPolicy {
+    fn default() -> Self {
+        OwnDataPolicy {
+            history_max_runs: history::DEFAULT_MAX_RUNS,
+            manifest_max_age_days: manifest::DEFAULT_MAX_AGE_DAYS,
+            quarantine_max_age_days: quarantine::DEFAULT_MAX_AGE_DAYS,
+            quarantine_max_bytes: quarantine::DEFAULT_MAX_TOTAL_BYTES,
+        }
+    }
+}
+
+// ה-mount של תיקיית הבית
+pub fn default_wipe_mount() -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+    mounts::mount_for(&home, &mounts::read_mounts())
+        .map(|m| m.mount_point.clone())
+        .unwrap_or(home)
+}
+
+// מה עושים עם קובץ שנבחר למחיקה
+#[derive(Clone)]
+pub enum DeletionStrategy {
+    Delete,
+    Quarantine(Arc<QuarantineRun>),
+    // לא מוחקים: מעבירים ל-<dir>/<שנה>/ עם אותו שם (ו-" (2)" וכו' אם תפוס)
+    MoveTo(PathBuf),
+}
+
+// התיקייה של השנה שבה הקובץ שונה לאחרונה
+pub fn archive_dir(root: &Path, metadata: &fs::Metadata) -> PathBuf {
+    let modified: DateTime<Local> = metadata.modified().map(DateTime::from).unwrap_or_else(|_| Local::now());
+    root.join(modified.format("%Y").to_string())
+}
+
+// "report.pdf" -> "report (2).pdf", "report (3).pdf"... עד שם פנוי בתיקייה
+pub fn unique_destination(dir: &Path, name: &OsStr) -> PathBuf {
+    let candidate = dir.join(name);
+    if fs::symlink_metadata(&candidate).is_err() {
+        return candidate;
+    }
+    let original = Path::new(name);
+    let stem = original.file_stem().unwrap_or(name).to_string_lossy();
+    let extension = original.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (2..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, extension)))
+        .find(|path| fs::symlink_metadata(path).is_err())
+        .unwrap()
+}
+
+pub const APP_DIR_NAME: &str = "system-cleaner-pro";
+
+// ~/.local/share/system-cleaner-pro
+pub fn app_data_dir() -> PathBuf {
+    dirs::data_dir().unwrap_or_else(|| PathBuf::from("/tmp")).join(APP_DIR_NAME)
+}
+
+// walk_threads = 0: יותר מזה כבר לא מאיץ - הדיסק הוא צוואר הבקבוק
+const MAX_WALK_THREADS: usize = 8;
+
+// מה שריקון תיקייה אחת מקבל מהקורא (SystemCleaner::clean_options), במקום פרמטרים נפרדים
+#[derive(Clone)]
+pub struct CleanOptions {
+    // קבצים שנגעו בהם לאחרונה מזה נשארים: הגיל הגלובלי, או keep_recent של הפריט אם הוא ארוך יותר
+    pub min_age: Option<Duration>,
+    // N הקבצים החדשים ביותר נשארים
+    pub keep_newest: Option<usize>,
+    pub exclusions: Arc<Exclusions>,
+    // תתי-עצים שמדלגים עליהם בריקון הזה בלבד (הגנת session ב-~/.cache)
+    pub skip: Vec<PathBuf>,
+}
+
+impl CleanOptions {
+    fn skips(&self, path: &Path) -> bool {
+        self.skip.iter().any(|dir| path.starts_with(dir))
+    }
+
+    fn is_too_recent(&self, metadata: &fs::Metadata) -> bool {
+        is_younger_than(metadata, self.min_age)
+    }
+}
+
+// mtime בעתיד (שעון של שרת NFS שרץ קדימה) = נוצר עכשיו. אחרת הקובץ הכי חדש נראה כמו הכי ישן ונמחק
+fn is_younger_than(metadata: &fs::Metadata, min_age: Option<Duration>) -> bool {
+    let Some(min_age) = min_age else { return false };
+    metadata.modified()
+    .ok()
+    .map(|mtime| SystemTime::now().duration_since(mtime).unwrap_or(Duration::ZERO))
+    .is_some_and(|age| age < min_age)
+}
+
+// ~/.cache שהוא symlink ל-scratch מקומי, בית ב-NFS או bind mount: הנתיב האמיתי קובע את ה-mount
+// (same_filesystem, אימות המקום הפנוי בסוף, tmpfs). נפתר פעם אחת לכל שורש, לא לכל קובץ
+fn canonical_root(dir: &Path) -> PathBuf {
+    fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf())
+}
+
+// cache שנבנה מקבצי מקור (mime.cache מ-packages, icon-theme.cache מהערכה, fontconfig מתיקיות הגופנים)
+// ישן כשקובץ או תיקייה במקורות השתנו אחריו. תיקייה שמכילה את ה-cache עצמו לא נחשבת -
+// כתיבת ה-cache (rename לתוכה) מקדמת את ה-mtime שלה. בלי cache או בלי מקורות - לא ישן
+pub fn is_stale_relative_to(cache: &Path, sources: &[PathBuf]) -> bool {
+    let Some(cache_time) = fs::metadata(cache).and_then(|m| m.modified()).ok() else { return false };
+    let container = cache.parent();
+    sources
+        .iter()
+        .flat_map(|source| WalkDir::new(source).into_iter().filter_map(|e| e.ok()))
+        .filter(|entry| entry.path() != cache && Some(entry.path()) != container)
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .any(|mtime| mtime > cache_time)
+}
+
+// ריקון תיקייה בסריקה מקבילית: אותם סינונים כמו ב-WalkDir של delete_directory_contents_blocking
+struct ContentsVisitor<'a> {
+    cleaner: &'a SystemCleaner,
+    options: &'a CleanOptions,
+    own_data: &'a Path,
+    root_device: Option<u64>,
+    entries: AtomicU64,
+    files: Mutex<Vec<(PathBuf, fs::Metadata)>>,
+}
+
+impl parallel_walk::Visitor for ContentsVisitor<'_> {
+    fn enter(&self, path: &Path, metadata: &fs::Metadata) -> bool {
+        let cleaner = self.cleaner;
+        let kept = metadata.is_dir() && cleaner.is_kept(path);
+        let enter = !path.starts_with(self.own_data)
+            && !cleaner.is_excluded_by(&self.options.exclusions, path)
+            && !self.options.skips(path)
+            && !cleaner.crosses_device_at(path, metadata, self.root_device)
+            && !kept;
+        if enter {
+            self.entries.fetch_add(1, Ordering::Relaxed);
+        }
+        enter
+    }
+
+    fn visit(&self, path: PathBuf, metadata: fs::Metadata) {
+        self.cleaner.current_path.set(&path);
+        if !(metadata.is_file() || metadata.is_symlink()) || path.file_name() == Some(OsStr::new(CACHEDIR_TAG)) || self.options.is_too_recent(&metadata) {
+            return;
+        }
+        if let Ok(mut files) = self.files.lock() {
+            files.push((path, metadata));
+        }
+    }
+
+    fn error(&self, path: &Path, error: &io::Error) {
+        self.cleaner.report_walk_error(path, error);
+    }
+
+    fn stop(&self) -> bool {
+        self.cleaner.checkpoint()
+    }
+}
+
+// סריקת תיקייה ארוכה מזה נרשמת בלוג (רק ב-verbose) - לאתר מה מאט את התצוגה המקדימה
+const SLOW_WALK: Duration = Duration::from_secs(2);
+
+// מחיקות שנכשלו (קובץ תפוס, תקלת NFS) מנוסות שוב בסוף הריצה
+pub const DEFAULT_DELETE_RETRIES: u32 = 2;
+
+// "Screenshots & Screencasts": הקלטה שלא נפתחה חודש כנראה נשכחה (או נקטעה באמצע)
+pub const DEFAULT_SCREEN_MEDIA_AGE_DAYS: u32 = 30;
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+// קובץ שלא נמחק - לסבב החוזר, ואם גם שם נכשל - לרשימה בסיכום
+#[derive(Debug, Clone)]
+pub struct FailedDeletion {
+    pub path: PathBuf,
+    pub item: String,
+    pub kind: io::ErrorKind,
+}
+
+#[derive(Debug, Default)]
+pub struct RetryReport {
+    // נמחקו בניסיון חוזר
+    pub recovered: u64,
+    pub failures: Vec<FailedDeletion>,
+}
+
+impl RetryReport {
+    // שורה לכל כישלון: סוג השגיאה והנתיב
+    pub fn failure_lines(&self) -> Vec<String> {
+        self.failures.iter().map(|f| format!("  {:?}  {}  [{}]", f.kind, f.path.display(), f.item)).collect()
+    }
+}
+
+// apt שמחכה ל-lock יכול לחכות לנצח - אחרי הזמן הזה התהליך נהרג
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+// הודעות של apt/dpkg/dnf/rpm כשמנהל חבילות אחר מחזיק בנעילה
+fn is_package_lock_message(line: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "Could not get lock",
+        "Waiting for cache lock",
+        "Unable to acquire the dpkg frontend lock",
+        "Unable to lock the administration directory",
+        "Waiting for process with pid",
+        "Failed to obtain the transaction lock",
+        "can't create transaction lock",
+    ];
+    MARKERS.iter().any(|m| line.contains(m))
+}
+
+#[derive(Clone)]
+pub struct SystemCleaner {
+    pub live: Arc<LiveCounters>,
+    // משך כל פריט - מתעדכן פעם אחת לפריט, לא בלולאה
+    pub durations: Arc<Mutex<HashMap<String, Duration>>>,
+    // /proc/mounts נקרא פעם אחת לריצה (בפעם הראשונה שצריך), לא לכל קובץ
+    mounts: Arc<OnceLock<Vec<mounts::MountInfo>>>,
+    // עיגוני btrfs/ZFS שפינינו בהם משהו (CleaningStats::snapshot_fs_mounts)
+    snapshot_fs_mounts: Arc<Mutex<Vec<mounts::MountInfo>>>,
+    // (dev, ino) של קבצים עם כמה hard links שכבר נספרו
+    #[cfg(unix)]
+    seen_inodes: Arc<Mutex<HashSet<(u64, u64)>>>,
+    // התיקיות שהריצה נגעה בהן - לאימות המקום הפנוי בסוף
+    touched_roots: Arc<Mutex<Vec<PathBuf>>>,
+    // תוספות לשורת הסיכום מפריטים (למשל אם רענון מסד הנתונים של התפריט הצליח)
+    summary_notes: Arc<Mutex<Vec<String>>>,
+    // N המחיקות הגדולות ביותר של הריצה, לטבלה בסיכום
+    pub largest: Arc<LargestFiles>,
+    // מה שתצוגה מקדימה לא הצליחה למדוד (הרשאות, I/O) - לשורה מתחת לסך
+    pub unmeasured: Arc<Unmeasured>,
+    pub verbose: bool,
+    pub dry_run: bool,
+    pub strategy: DeletionStrategy,
+    // דריסה באפסים לפני unlink
+    pub secure_delete: bool,
+    pub shred_max_bytes: u64,
+    // קבצים שנגעו בהם לאחרונה מזה לא נמחקים
+    pub min_age: Option<Duration>,
+    // משותף בין העותקים של הריצה, כדי שספירת הפגיעות של כל כלל תצטבר למקום אחד
+    pub exclusions: Arc<Exclusions>,
+    // אתרים שנתוני הדפדפן שלהם לא מסומנים מראש למחיקה (SafetySettings::keep_sites)
+    pub keep_sites: Vec<String>,
+    // כל מנוי מקבל כל שורה (ה-GUI, ו-stdout עם --verbose)
+    pub log_sinks: Vec<LogCallback>,
+    // אירועים מובנים (פריט התחיל/הסתיים, קובץ נמחק) - לצד הלוג הטקסטואלי, לא במקומו
+    pub events: Option<EventSink>,
+    // run_id::new_run_id - משותף להסגר, לרשימת המחיקות, להיסטוריה ולאירועים של הריצה
+    pub run_id: String,
+    // רשימת המחיקות של הריצה (CSV). רק במחיקה אמיתית
+    pub manifest: Option<Arc<DeletionManifest>>,
+    // גדלים מסריקות קודמות של תבניות. נקרא רק בתצוגה מקדימה
+    pub size_cache: Option<Arc<SizeCache>>,
+    // הפריט שרץ כרגע - לעמודת item ברשימה. נקבע ב-run_item על השיבוט שמריץ את המטפל
+    item_id: String,
+    // עצירה מה-UI: הורגת פקודות חיצוניות ומפסיקה מחיקות באמצע
+    pub cancel: CancellationToken,
+    pub pause: PauseGate,
+    pub command_timeout: Duration,
+    // לא מוחקים קבצים פתוחים (sqlite WAL, cache ממופה) - עולה סריקה של /proc
+    pub skip_in_use: bool,
+    // ריקון תיקייה משאיר את N הקבצים החדשים ביותר (cache של pip, לוגים מסובבים)
+    pub keep_newest: Option<usize>,
+    // "keep files newer than" של הפריט (cache של דפדפן לפני טיסה) - בנוסף ל-min_age הגלובלי, לא במקומו
+    pub keep_recent: Option<Duration>,
+    // שמות (globs) בתוך ~/.cache שלא מרוקנים בזמן session גרפי - מה-registry של הפריט
+    pub session_protected: &'static [&'static str],
+    // כבוי כשאין session גרפי, או כש-"I know what I'm doing" מסומן בהגדרות
+    pub session_protection: bool,
+    // סריקת תיקייה נשארת על ההתקן של השורש שלה
+    pub same_filesystem: bool,
+    // תיקייה לקריאה בלבד של המשתמש עצמו: u+w לרגע כדי למחוק בתוכה, ואז ההרשאות חוזרות
+    pub fix_permissions: bool,
+    // תבנית על כל הבית: קובץ בתוך git work tree נמחק רק אם git check-ignore מאשר (ראו git.rs)
+    pub git_aware: bool,
+    pub device_id: DeviceIdProvider,
+    pub duplicates: DuplicateScan,
+    pub large_files: LargeFileScan,
+    pub wipe: FreeSpaceWipe,
+    pub own_data: OwnDataPolicy,
+    // פריטים מ-custom-cleaners.toml (נטענים בהפעלה)
+    pub custom: Arc<Vec<CustomCleaner>>,
+    // בחירה לכל קובץ מהתצוגה המקדימה, לפריטים שמוחקים נתוני משתמש
+    pub preview: PreviewStore,
+    // תיקיות ריקות: גם בתוך ~/.config וכו' (כבוי כברירת מחדל)
+    pub empty_dirs_include_hidden: bool,
+    // symlinks שבורים שמצביעים ל-/media, /mnt וכו' - אולי רק הדיסק לא מחובר כרגע
+    pub broken_links_include_removable: bool,
+    // ריצה כ-root: הבית של המשתמש הזה במקום /root (None = המשתמש הנוכחי)
+    pub user: Option<UserAccount>,
+    // הבית ושורשי המערכת - כל הנתיבים של הפריטים נגזרים מכאן
+    pub paths: SystemPaths,
+    // snapshots של אמולטור Android ישנים מזה נמחקים (None = לא נוגעים, ברירת המחדל)
+    pub android_snapshot_age: Option<Duration>,
+    // "Screenshots & Screencasts": הקלטות וצילומים ישנים מזה מוצעים למחיקה
+    pub screen_media_age: Duration,
+    // תתי-הפריטים המסומנים של הפריט הנוכחי (registry::SubEntry::id)
+    pub sub_entries: Vec<String>,
+    // מצב עדיפות נמוכה: הפסקה קצרה כל N מחיקות (0 = כבוי). המונה משותף לכל השיבוטים של הריצה
+    pub throttle_every: u64,
+    deletions: Arc<AtomicU64>,
+    // משותף לכל השיבוטים של הריצה; מתרוקן ב-retry_failed
+    failed_deletions: Arc<Mutex<Vec<FailedDeletion>>>,
+    pub delete_retries: u32,
+    // ההמתנה לפני ניסיון n היא n * retry_backoff
+    pub retry_backoff: Duration,
+    // ריקון תיקייה: כמה threads סורקים ומוחקים במקביל (0 = לפי מספר הליבות, 1 = כמו קודם)
+    pub walk_threads: usize,
+    pub is_rotational: RotationalProbe,
+    // הקובץ שבטיפול עכשיו, לסרגל המצב (משותף עם ה-GUI, כמו wipe.progress)
+    pub current_path: Arc<CurrentPath>,
+}
+
+// === נתיבים מוגנים: אף פריט (גם לא מוגדר-משתמש) לא מרוקן אותם ===
+
+// אסור לרוקן את אלה או כל תיקייה מעליהם (למשל "/" או "/home")
+const PROTECTED_ROOTS: &[&str] = &["/", "/home", "/root", "/etc", "/usr", "/bin", "/sbin", "/lib", "/lib64", "/boot", "/opt", "/srv", "/var", "/var/lib"];
+// ואת אלה גם לא שום דבר מתחתם
+const PROTECTED_TREES: &[&str] = &["/etc", "/usr", "/bin", "/sbin", "/lib", "/lib64", "/boot", "/proc", "/sys", "/dev", "/var/lib"];
+const PROTECTED_HOME_TREES: &[&str] = &[".ssh", ".gnupg", ".password-store", ".local/share/keyrings", "Documents", "Pictures", "Desktop", ".Private", ".ecryptfs"];
+// הצד המוצפן של בית ב-ecryptfs (ה-lower): שמות מוצפנים שמחיקה בהם הורסת קבצים אמיתיים ב-upper
+const ENCRYPTED_HOME_DIRS: &[&str] = &[".Private", ".ecryptfs"];
+
+// tokens של AWS CLI/SSO: רק שפג תוקפם נמחקים (remove_expired_aws_tokens), לא כל ה-*.json
+const AWS_TOKEN_CACHES: &[&str] = &[".aws/cli/cache", ".aws/sso/cache"];
+
+pub fn is_protected_path(path: &Path) -> bool {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/root"));
+    // אחרי נרמול של "..", כדי ש-~/.cache/../ לא יעקוף את הבדיקה
+    let path: PathBuf = path.components().fold(PathBuf::new(), |mut acc, c| {
+        match c {
+            std::path::Component::ParentDir => {
+                acc.pop();
+            }
+            std::path::Component::CurDir => {}
+            c => acc.push(c),
+        }
+        acc
+    });
+    let is_root_or_above = |root: &Path| root.starts_with(&path);
+    // כשרצים כ-root, גם הבתים של שאר המשתמשים
+    let homes = || std::iter::once(home.as_path()).chain(users::human_homes().iter().map(PathBuf::as_path));
+    PROTECTED_ROOTS.iter().map(Path::new).any(is_root_or_above)
+        || homes().any(is_root_or_above)
+        || PROTECTED_TREES.iter().any(|t| path.starts_with(t))
+        || homes().any(|home| {
+            PROTECTED_HOME_TREES.iter().any(|t| path.starts_with(home.join(t)))
+                || path.starts_with(XdgDir::Data.under(home).join("keyrings"))
+        })
+}
+
+// קובץ הכתיבה חייב להיות על ה-mount עצמו: שורש ה-mount אם מותר לכתוב בו, אחרת הבית או /tmp אם הם עליו
+fn create_wipe_file(mount: &Path, home: &Path) -> io::Result<(PathBuf, fs::File)> {
+    let all_mounts = mounts::read_mounts();
+    let mut candidates = vec![mount.to_path_buf(), home.to_path_buf()];
+    candidates.push(std::env::temp_dir());
+    let mut last_error = io::Error::new(io::ErrorKind::PermissionDenied, format!("no writable directory on {}", mount.display()));
+    for dir in candidates {
+        if mounts::mount_for(&dir, &all_mounts).map(|m| m.mount_point.as_path()) != Some(mount) {
+            continue;
+        }
+        let path = dir.join(format!(".sysclean-wipe-{}.tmp", std::process::id()));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+// === שמות קבצים: לא כל שם הוא UTF-8 תקין ===
+
+// glob פשוט ('*' בכל מקום) על הבייטים של השם - בלי to_string_lossy,
+// כדי ש-U+FFFD שנוצר מבייטים שבורים לא "יתאים" לתבנית בטעות
+pub fn name_matches(name: &OsStr, pattern: &str) -> bool {
+    let name = name.as_encoded_bytes();
+    let pattern = pattern.as_bytes();
+    let (mut n, mut p) = (0, 0);
+    // המיקום האחרון של '*' והמקום בשם שממנו הוא מתחיל לבלוע
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            star = Some((p, n));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == name[n] {
+            n += 1;
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&b| b == b'*')
+}
+
+// לתצוגה בלוג בלבד: תווי בקרה, תווי כיווניות ו-U+FFFD מוצגים כ-escape,
+// בייטים שאינם UTF-8 כ-\xNN. המחיקה עצמה תמיד על ה-PathBuf המקורי.
+pub fn escape_name(name: &OsStr) -> String {
+    let mut out = String::with_capacity(name.len());
+    for chunk in name.as_encoded_bytes().utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() || c == '\u{FFFD}' || matches!(c, '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}') => {
+                    out.push_str(&format!("\\u{{{:x}}}", c as u32));
+                }
+                c => out.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            out.push_str(&format!("\\x{:02x}", byte));
+        }
+    }
+    out
+}
+
+// קבצים מתחת ל-dir שתהליך כלשהו מחזיק פתוחים (fd) או ממופים (mmap).
+// סריקה אחת של /proc לכל תיקייה, ואז בדיקה ב-HashSet לכל קובץ.
+#[cfg(target_os = "linux")]
+pub fn open_files_under(dir: &Path) -> HashSet<PathBuf> {
+    let mut open = HashSet::new();
+    let procs = match fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return open,
+    };
+    for proc_entry in procs.filter_map(|e| e.ok()) {
+        if !proc_entry.file_name().as_encoded_bytes().iter().all(u8::is_ascii_digit) {
+            continue;
+        }
+        let proc_dir = proc_entry.path();
+        // של תהליכים של משתמשים אחרים אין הרשאה - פשוט מדלגים
+        if let Ok(fds) = fs::read_dir(proc_dir.join("fd")) {
+            for fd in fds.filter_map(|e| e.ok()) {
+                if let Ok(target) = fs::read_link(fd.path()) {
+                    if target.starts_with(dir) {
+                        open.insert(target);
+                    }
+                }
+            }
+        }
+        if let Ok(maps) = fs::read(proc_dir.join("maps")) {
+            for line in maps.split(|&b| b == b'\n') {
+                // "addr perms offset dev inode /path" - רק בשדה האחרון יש '/'
+                if let Some(start) = line.iter().position(|&b| b == b'/') {
+                    let path = Path::new(OsStr::from_bytes(&line[start..]));
+                    if path.starts_with(dir) {
+                        open.insert(path.to_path_buf());
+                    }
+                }
+            }
+        }
+    }
+    open
+}
+
+// בלי /proc אין דרך זולה לדעת - שום קובץ לא נחשב תפוס
+#[cfg(not(target_os = "linux"))]
+pub fn open_files_under(_dir: &Path) -> HashSet<PathBuf> {
+    HashSet::new()
+}
+
+// שם הקובץ, או הנתיב כולו כשאין שם (למשל "/")
+fn display_name(path: &Path) -> String {
+    escape_name(path.file_name().unwrap_or(path.as_os_str()))
+}
+
+// https://bford.info/cachedir/ - cargo, pip, ccache וחברים מסמנים כך תיקיות cache.
+// הקובץ עצמו אף פעם לא נמחק, אחרת הכלי מאבד את הסימון
+const CACHEDIR_TAG: &str = "CACHEDIR.TAG";
+const CACHEDIR_SIGNATURE: &[u8] = b"Signature: 8a477f597d28d172789f06886806bc55";
+// תיקייה שיש בה את הקובץ הזה לא מרוקנת, וגם שום דבר מתחתיה
+pub const KEEP_MARKER: &str = ".supercleaner-keep";
+
+// רק עם החתימה - קובץ בשם הזה בלי החתימה לא מסמן כלום
+fn is_tagged_cache_dir(dir: &Path) -> bool {
+    let mut signature = [0u8; CACHEDIR_SIGNATURE.len()];
+    fs::File::open(dir.join(CACHEDIR_TAG)).and_then(|mut file| file.read_exact(&mut signature)).is_ok() && signature == CACHEDIR_SIGNATURE
+}
+
+// מעבר אחד של אפסים, בחתיכות, ואז fsync
+fn overwrite_with_zeros(path: &Path, len: u64) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let zeros = vec![0u8; SHRED_CHUNK];
+    let mut remaining = len;
+    while remaining > 0 {
+        let n = remaining.min(SHRED_CHUNK as u64) as usize;
+        file.write_all(&zeros[..n])?;
+        remaining -= n as u64;
+    }
+    file.sync_all()
+}
+
+// עוד שם (hard link) מצביע על אותו תוכן
+#[cfg(unix)]
+fn has_other_links(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink() > 1
+}
+
+#[cfg(not(unix))]
+fn has_other_links(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn is_copy_on_write(path: &Path) -> bool {
+    use nix::sys::statfs::{statfs, BTRFS_SUPER_MAGIC};
+    statfs(path).map(|s| s.filesystem_type() == BTRFS_SUPER_MAGIC).unwrap_or(false)
+}
+
+#[cfg(target_os = "linux")]
+fn is_network_fs(path: &Path) -> bool {
+    use nix::sys::statfs::{statfs, NFS_SUPER_MAGIC, SMB_SUPER_MAGIC};
+    statfs(path).map(|s| [NFS_SUPER_MAGIC, SMB_SUPER_MAGIC].contains(&s.filesystem_type())).unwrap_or(false)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_network_fs(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_copy_on_write(_path: &Path) -> bool {
+    false
+}
+
+impl SystemCleaner {
+    pub fn new(verbose: bool, dry_run: bool) -> Self {
+        SystemCleaner {
+            live: Arc::new(LiveCounters::default()),
+            durations: Arc::new(Mutex::new(HashMap::new())),
+            mounts: Arc::new(OnceLock::new()),
+            snapshot_fs_mounts: Arc::new(Mutex::new(Vec::new())),
+            #[cfg(unix)]
+            seen_inodes: Arc::new(Mutex::new(HashSet::new())),
+            touched_roots: Arc::new(Mutex::new(Vec::new())),
+            summary_notes: Arc::new(Mutex::new(Vec::new())),
+            largest: Arc::new(LargestFiles::default()),
+            unmeasured: Arc::new(Unmeasured::new()),
+            verbose,
+            dry_run,
+            strategy: DeletionStrategy::Delete,
+            secure_delete: false,
+            shred_max_bytes: DEFAULT_SHRED_MAX_BYTES,
+            min_age: None,
+            exclusions: Arc::new(Exclusions::default()),
+            keep_sites: Vec::new(),
+            log_sinks: Vec::new(),
+            events: None,
+            run_id: run_id::new_run_id(),
+            manifest: None,
+            size_cache: None,
+            item_id: String::new(),
+            cancel: CancellationToken::new(),
+            pause: PauseGate::default(),
+            command_timeout: DEFAULT_COMMAND_TIMEOUT,
+            skip_in_use: false,
+            keep_newest: None,
+            keep_recent: None,
+            session_protected: &[],
+            session_protection: is_graphical_session(),
+            same_filesystem: true,
+            fix_permissions: false,
+            git_aware: true,
+            device_id: |_, metadata| mounts::device_id(metadata),
+            duplicates: DuplicateScan::default(),
+            large_files: LargeFileScan::default(),
+            wipe: FreeSpaceWipe::default(),
+            own_data: OwnDataPolicy::default(),
+            custom: Arc::new(Vec::new()),
+            preview: PreviewStore::default(),
+            empty_dirs_include_hidden: false,
+            broken_links_include_removable: false,
+            android_snapshot_age: None,
+            screen_media_age: Duration::from_secs(DEFAULT_SCREEN_MEDIA_AGE_DAYS as u64 * 24 * 60 * 60),
+            sub_entries: Vec::new(),
+            user: None,
+            paths: SystemPaths::default(),
+            throttle_every: 0,
+            deletions: Arc::new(AtomicU64::new(0)),
+            failed_deletions: Arc::new(Mutex::new(Vec::new())),
+            // ההגדרות מדליקות את זה (with_settings); בלי הגדרות - הסדר הצפוי של thread אחד
+            walk_threads: 1,
+            is_rotational: mounts::is_rotational,
+            current_path: Arc::new(CurrentPath::default()),
+            delete_retries: DEFAULT_DELETE_RETRIES,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: DeletionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn with_secure_delete(mut self, secure_delete: bool) -> Self {
+        self.secure_delete = secure_delete;
+        self
+    }
+
+    pub fn with_shred_limit(mut self, max_bytes: u64) -> Self {
+        self.shred_max_bytes = max_bytes;
+        self
+    }
+
+    pub fn with_min_age(mut self, min_age: Option<Duration>) -> Self {
+        self.min_age = min_age;
+        self
+    }
+
+    pub fn with_exclusions(mut self, exclusions: Vec<PathBuf>) -> Self {
+        self.exclusions = Arc::new(Exclusions::from_paths(exclusions));
+        self
+    }
+
+    // כללים מקובץ (--exclude-from / ייבוא ב-GUI), בנוסף להחרגות מההגדרות - לריצה הזו בלבד
+    pub fn with_imported_exclusions(mut self, source: PathBuf, rules: Vec<Rule>) -> Self {
+        let mut exclusions = (*self.exclusions).clone();
+        exclusions.import(source, rules);
+        self.exclusions = Arc::new(exclusions);
+        self
+    }
+
+    pub fn with_keep_sites(mut self, sites: Vec<String>) -> Self {
+        self.keep_sites = sites;
+        self
+    }
+
+    pub fn with_cancel(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn with_command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
+    pub fn with_skip_in_use(mut self, skip_in_use: bool) -> Self {
+        self.skip_in_use = skip_in_use;
+        self
+    }
+
+    pub fn with_keep_newest(mut self, keep_newest: Option<usize>) -> Self {
+        self.keep_newest = keep_newest;
+        self
+    }
+
+    pub fn with_keep_recent(mut self, keep_recent: Option<Duration>) -> Self {
+        self.keep_recent = keep_recent;
+        self
+    }
+
+    pub fn with_session_protected(mut self, names: &'static [&'static str]) -> Self {
+        self.session_protected = names;
+        self
+    }
+
+    pub fn with_session_protection(mut self, enabled: bool) -> Self {
+        self.session_protection = enabled && is_graphical_session();
+        self
+    }
+
+    pub fn with_same_filesystem(mut self, same_filesystem: bool) -> Self {
+        self.same_filesystem = same_filesystem;
+        self
+    }
+
+    pub fn with_fix_permissions(mut self, fix_permissions: bool) -> Self {
+        self.fix_permissions = fix_permissions;
+        self
+    }
+
+    pub fn with_git_aware(mut self, git_aware: bool) -> Self {
+        self.git_aware = git_aware;
+        self
+    }
+
+    pub fn with_duplicates(mut self, duplicates: DuplicateScan) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    pub fn with_large_files(mut self, large_files: LargeFileScan) -> Self {
+        self.large_files = large_files;
+        self
+    }
+
+    pub fn with_preview(mut self, preview: PreviewStore) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    pub fn with_empty_dirs_include_hidden(mut self, include_hidden: bool) -> Self {
+        self.empty_dirs_include_hidden = include_hidden;
+        self
+    }
+
+    pub fn with_broken_links_include_removable(mut self, include_removable: bool) -> Self {
+        self.broken_links_include_removable = include_removable;
+        self
+    }
+
+    // אחרי with_settings: שורשי הכפולים/הקבצים הגדולים עוברים גם הם לבית החדש
+    pub fn with_paths(mut self, paths: SystemPaths) -> Self {
+        self.rebase_roots(&paths.home);
+        self.paths = paths;
+        self
+    }
+
+    pub fn with_home(self, home: PathBuf) -> Self {
+        let paths = self.paths.clone().with_home(home);
+        self.with_paths(paths)
+    }
+
+    pub fn with_user(mut self, user: Option<UserAccount>) -> Self {
+        if let Some(user) = &user {
+            self = self.with_home(user.home.clone());
+        }
+        self.user = user;
+        self
+    }
+
+    // שורשי הסריקה מההגדרות (~/Downloads וכו') הורחבו לבית הנוכחי - מעבירים אותם לבית החדש
+    fn rebase_roots(&mut self, new_home: &Path) {
+        let old_home = self.paths.home.clone();
+        let rebase = |roots: &mut [PathBuf]| {
+            for root in roots.iter_mut() {
+                if let Ok(rest) = root.strip_prefix(&old_home) {
+                    *root = new_home.join(rest);
+                }
+            }
+        };
+        rebase(&mut self.duplicates.roots);
+        rebase(&mut self.large_files.roots);
+        rebase(self.large_files.archive.as_mut_slice());
+    }
+
+    pub fn with_android_snapshot_age(mut self, age: Option<Duration>) -> Self {
+        self.android_snapshot_age = age;
+        self
+    }
+
+    pub fn with_screen_media_age(mut self, age: Duration) -> Self {
+        self.screen_media_age = age;
+        self
+    }
+
+    pub fn with_sub_entries(mut self, sub_entries: Vec<String>) -> Self {
+        self.sub_entries = sub_entries;
+        self
+    }
+
+    pub fn with_pause(mut self, pause: PauseGate) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    pub fn with_custom(mut self, custom: Arc<Vec<CustomCleaner>>) -> Self {
+        self.custom = custom;
+        self
+    }
+
+    pub fn with_wipe(mut self, wipe: FreeSpaceWipe) -> Self {
+        self.wipe = wipe;
+        self
+    }
+
+    pub fn with_own_data(mut self, policy: OwnDataPolicy) -> Self {
+        self.own_data = policy;
+        self
+    }
+
+    pub fn with_throttle(mut self, every: u64) -> Self {
+        self.throttle_every = every;
+        self
+    }
+
+    pub fn with_walk_threads(mut self, threads: usize) -> Self {
+        self.walk_threads = threads;
+        self
+    }
+
+    pub fn with_largest_limit(mut self, limit: usize) -> Self {
+        self.largest = Arc::new(LargestFiles::new(limit));
+        self
+    }
+
+    pub fn with_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.delete_retries = retries;
+        self.retry_backoff = backoff;
+        self
+    }
+
+    // כל מה שבא מ-config.toml - משותף ל-GUI ול-CLI
+    pub fn with_settings(self, settings: &Settings) -> Self {
+        let min_age = match settings.general.min_file_age_days {
+            0 => None,
+            days => Some(Duration::from_secs(days as u64 * 24 * 60 * 60)),
+        };
+        let android_snapshot_age = match settings.advanced.android_snapshot_max_age_days {
+            0 => None,
+            days => Some(Duration::from_secs(days as u64 * 24 * 60 * 60)),
+        };
+        let throttle = if settings.advanced.low_priority { settings.advanced.low_priority_pause_every } else { 0 };
+        // ניקוי לא משתמש במטמון - אין טעם לטעון אותו
+        let size_cache = (settings.advanced.size_cache && self.dry_run)
+            .then(|| Arc::new(SizeCache::load(settings.advanced.force_rescan)));
+        self.with_size_cache(size_cache)
+            .with_command_timeout(Duration::from_secs(settings.advanced.command_timeout_mins * 60))
+            .with_duplicates(DuplicateScan {
+                roots: settings.duplicate_roots(),
+                max_file_bytes: settings.duplicates.max_file_mb * 1024 * 1024,
+                keep: settings.duplicates.keep,
+                ..DuplicateScan::default()
+            })
+            .with_large_files(LargeFileScan {
+                roots: settings.large_file_roots(),
+                min_bytes: settings.large_files.min_size_mb * 1024 * 1024,
+                min_age: Duration::from_secs(settings.large_files.min_age_days * 24 * 60 * 60),
+                archive: settings.large_file_archive(),
+            })
+            .with_wipe(FreeSpaceWipe {
+                mount: settings.wipe_mount(),
+                reserve_bytes: settings.advanced.wipe_reserve_mb * 1024 * 1024,
+                ..FreeSpaceWipe::default()
+            })
+            .with_own_data(OwnDataPolicy {
+                history_max_runs: settings.advanced.history_max_runs,
+                manifest_max_age_days: settings.advanced.manifest_max_age_days,
+                quarantine_max_age_days: settings.advanced.quarantine_max_age_days,
+                quarantine_max_bytes: settings.advanced.quarantine_max_size_mb * 1024 * 1024,
+            })
+            .with_empty_dirs_include_hidden(settings.advanced.empty_dirs_include_hidden)
+            .with_broken_links_include_removable(settings.advanced.broken_links_include_removable)
+            .with_android_snapshot_age(android_snapshot_age)
+            .with_screen_media_age(Duration::from_secs(settings.advanced.screen_media_min_age_days as u64 * 24 * 60 * 60))
+            .with_min_age(min_age)
+            .with_exclusions(settings.exclusion_paths())
+            .with_keep_sites(settings.safety.keep_sites.clone())
+            .with_shred_limit(settings.safety.shred_max_mb * 1024 * 1024)
+            .with_throttle(throttle)
+            .with_walk_threads(if settings.advanced.low_priority { 1 } else { settings.advanced.walk_threads })
+            .with_same_filesystem(settings.safety.same_filesystem)
+            .with_fix_permissions(settings.safety.fix_permissions)
+            .with_git_aware(settings.safety.git_aware)
+            .with_session_protection(!settings.safety.ignore_session_protection)
+            .with_retries(settings.advanced.delete_retries, Duration::from_millis(settings.advanced.delete_retry_backoff_ms))
+            .with_largest_limit(settings.advanced.largest_files)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    // נקודת עצירה בין קבצים: ממתין כאן בזמן השהיה, ומחזיר true אם הריצה בוטלה
+    pub fn checkpoint(&self) -> bool {
+        self.pause.wait(&self.cancel);
+        self.is_cancelled()
+    }
+
+    pub async fn checkpoint_async(&self) -> bool {
+        self.pause.wait_async(&self.cancel).await;
+        self.is_cancelled()
+    }
+
+    // גם הצד המוצפן של ecryptfs: אף סורק של הבית לא נכנס אליו
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.is_excluded_by(&self.exclusions, path)
+    }
+
+    fn is_excluded_by(&self, exclusions: &Exclusions, path: &Path) -> bool {
+        exclusions.matches(path)
+            || ENCRYPTED_HOME_DIRS.iter().any(|d| path.starts_with(self.paths.home.join(d)))
+    }
+
+    fn is_too_recent(&self, metadata: &fs::Metadata) -> bool {
+        is_younger_than(metadata, self.min_age)
+    }
+
+    // ברירת המחדל לריקון תיקייה של הפריט הנוכחי
+    pub fn clean_options(&self) -> CleanOptions {
+        CleanOptions {
+            min_age: self.min_age.max(self.keep_recent),
+            keep_newest: self.keep_newest,
+            exclusions: self.exclusions.clone(),
+            skip: Vec::new(),
+        }
+    }
+
+    // פריטים שמרוקנים תיקיות משלהם (לא Target::Directory) לא משאירים N אחרונים
+    fn clean_options_all(&self) -> CleanOptions {
+        CleanOptions { keep_newest: None, ..self.clean_options() }
+    }
+
+    // מוסיף מנוי; לא מחליף את הקודמים
+    pub fn with_callback(mut self, callback: LogCallback) -> Self {
+        self.log_sinks.push(callback);
+        self
+    }
+
+    pub fn with_events(mut self, events: EventSink) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    pub fn with_run_id(mut self, run_id: String) -> Self {
+        self.run_id = run_id;
+        self
+    }
+
+    pub fn with_manifest(mut self, manifest: Arc<DeletionManifest>) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    pub fn with_size_cache(mut self, cache: Option<Arc<SizeCache>>) -> Self {
+        self.size_cache = cache;
+        self
+    }
+
+    pub fn emit(&self, event: CleanEvent) {
+        if let Some(events) = &self.events {
+            events(event);
+        }
+    }
+
+    // שליחת לוג למסך השחור
+    async fn log(&self, message: &str) {
+        self.log_sync(message);
+    }
+
+    // גרסה סינכרונית ללולאות שרצות בתוך spawn_blocking
+    pub fn log_sync(&self, message: &str) {
+        if self.log_sinks.is_empty() {
+            if self.verbose {
+                println!("{}", message);
+            }
+            return;
+        }
+        for sink in &self.log_sinks {
+            if let Ok(cb) = sink.lock() {
+                cb(message.to_string());
+            }
+        }
+    }
+
+    // כשרצים כ-root עבור משתמש אחר - הבית שלו, לא /root (with_user מעדכן את paths)
+    pub fn get_home_dir(&self) -> PathBuf {
+        self.paths.home.clone()
+    }
+
+    // קבצים שהריצה יוצרת בבית של משתמש אחר שייכים לו, לא ל-root
+    #[cfg(unix)]
+    fn own_created(&self, path: &Path) {
+        if let Some(user) = &self.user {
+            let owner = (Some(nix::unistd::Uid::from_raw(user.uid)), Some(nix::unistd::Gid::from_raw(user.gid)));
+            if let Err(e) = nix::unistd::chown(path, owner.0, owner.1) {
+                self.log_sync(&format!("chown {}: {}", display_name(path), e));
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn own_created(&self, _path: &Path) {}
+
+    // לפי יחידות התצוגה שנבחרו בהגדרות (units::set_byte_units)
+    pub fn format_bytes(bytes: u64) -> String {
+        units::format_bytes(bytes)
+    }
+
+    pub fn get_stats_sync(&self) -> CleaningStats {
+        let mut stats = self.live.snapshot();
+        stats.durations = self.durations.lock().unwrap().clone();
+        stats.snapshot_fs_mounts = self.snapshot_fs_mounts.lock().unwrap().clone();
+        stats.largest = self.largest.top();
+        stats
+    }
+
+    pub fn record_error(&self) {
+        self.live.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // שורשים שכדאי למדוד עליהם מקום פנוי לפני הריצה
+    pub fn candidate_roots(&self) -> Vec<PathBuf> {
+        let paths = &self.paths;
+        vec![
+            paths.home.clone(),
+            paths.root.clone(),
+            paths.tmp.clone(),
+            paths.var_tmp.clone(),
+            paths.system_cache.clone(),
+            paths.system_log.clone(),
+        ]
+    }
+
+    pub fn touched_roots(&self) -> Vec<PathBuf> {
+        self.touched_roots.lock().map(|t| t.clone()).unwrap_or_default()
+    }
+
+    pub fn summary_notes(&self) -> Vec<String> {
+        self.summary_notes.lock().map(|notes| notes.clone()).unwrap_or_default()
+    }
+
+    // כמה כללים יובאו, וכמה מחיקות כל כלל מנע (רק מה שפגע)
+    pub fn exclusion_notes(&self) -> Vec<String> {
+        let imported = self.exclusions.imported().iter().map(|(source, count)| {
+            tr_args("summary.exclusions_loaded", &[&count.to_string(), &source.display().to_string()])
+        });
+        let hits = self.exclusions.rules().iter().filter(|rule| rule.hits() > 0).map(|rule| {
+            tr_args("summary.exclusion_hits", &[&rule.text, &rule.hits().to_string()])
+        });
+        imported.chain(hits).collect()
+    }
+
+    fn add_summary_note(&self, note: String) {
+        if let Ok(mut notes) = self.summary_notes.lock() {
+            notes.push(note);
+        }
+    }
+
+    fn touch(&self, root: &Path) {
+        if let Ok(mut touched) = self.touched_roots.lock() {
+            if !touched.iter().any(|t| t == root) {
+                touched.push(root.to_path_buf());
+            }
+        }
+    }
+
+    fn record_file(&self, size: u64) {
+        self.record_freed(1, size);
+    }
+
+    // מה שפקודה חיצונית פינתה, לפי מדידה לפני ואחרי
+    fn record_freed(&self, files: u64, bytes: u64) {
+        self.live.files_deleted.fetch_add(files, Ordering::Relaxed);
+        self.live.bytes_freed.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    // המקום שהקובץ תופס בפועל בדיסק (blocks), לא האורך שלו.
+    // None = hard link שכבר נספר, מחיקה שלו לא משחררת כלום.
+    #[cfg(unix)]
+    fn disk_usage(&self, metadata: &fs::Metadata) -> Option<u64> {
+        use std::os::unix::fs::MetadataExt;
+        if metadata.nlink() > 1 {
+            if let Ok(mut seen) = self.seen_inodes.lock() {
+                if !seen.insert((metadata.dev(), metadata.ino())) {
+                    return None;
+                }
+            }
+        }
+        Some(allocated_bytes(metadata))
+    }
+
+    #[cfg(not(unix))]
+    fn disk_usage(&self, metadata: &fs::Metadata) -> Option<u64> {
+        Some(allocated_bytes(metadata))
+    }
+
+    // מחיקה או העברה להסגר לפי ה-strategy. ב-dry run לא נוגעים בכלום.
+    fn remove_file(&self, path: &Path, metadata: &fs::Metadata, shred: bool) -> bool {
+        if self.dry_run {
+            return true;
+        }
+        if shred {
+            self.shred_file(path, metadata);
+        }
+        let result = match self.delete_or_stash(path, metadata) {
+            Err(e) if self.fix_permissions && e.kind() == io::ErrorKind::PermissionDenied => {
+                self.delete_with_writable_parent(path, metadata).map_err(|_| e)
+            }
+            result => result,
+        };
+        match result {
+            Ok(()) => {
+                self.throttle();
+                true
+            }
+            Err(e) => {
+                self.report_failed(path, &e);
+                false
+            }
+        }
+    }
+
+    // רק כשהמשתמש הוא הבעלים של התיקייה וחסר בה רק u+w - כל מקרה אחר נשאר EACCES רגיל
+    #[cfg(unix)]
+    fn delete_with_writable_parent(&self, path: &Path, metadata: &fs::Metadata) -> io::Result<()> {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        let denied = || io::Error::from(io::ErrorKind::PermissionDenied);
+        let parent = path.parent().ok_or_else(denied)?;
+        let parent_metadata = fs::symlink_metadata(parent)?;
+        if !parent_metadata.is_dir() || parent_metadata.uid() != nix::unistd::geteuid().as_raw() || parent_metadata.mode() & 0o200 != 0 {
+            return Err(denied());
+        }
+        let original = parent_metadata.permissions();
+        fs::set_permissions(parent, fs::Permissions::from_mode(original.mode() | 0o200))?;
+        let result = self.delete_or_stash(path, metadata);
+        match fs::set_permissions(parent, original) {
+            Ok(()) if result.is_ok() => self.log_sync(&tr_args("log.fixed_permissions", &[&display_name(path), &parent.display().to_string()])),
+            Ok(()) => {}
+            Err(e) => self.log_sync(&tr_args("log.restore_permissions_failed", &[&parent.display().to_string(), &e.to_string()])),
+        }
+        result
+    }
+
+    #[cfg(not(unix))]
+    fn delete_with_writable_parent(&self, _path: &Path, _metadata: &fs::Metadata) -> io::Result<()> {
+        Err(io::Error::from(io::ErrorKind::PermissionDenied))
+    }
+
+    fn delete_or_stash(&self, path: &Path, metadata: &fs::Metadata) -> io::Result<()> {
+        match &self.strategy {
+            DeletionStrategy::Delete => fs::remove_file(path),
+            DeletionStrategy::Quarantine(run) => run.stash(path, metadata.len()),
+            DeletionStrategy::MoveTo(root) => {
+                let name = path.file_name().ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+                let destination = unique_destination(&archive_dir(root, metadata), name);
+                // move_file: rename, או copy+remove כשהארכיון על דיסק אחר
+                quarantine::move_file(path, &destination)?;
+                self.log_sync(&tr_args("log.archived", &[&display_name(path), &destination.display().to_string()]));
+                Ok(())
+            }
+        }
+    }
+
+    // הרשאות לא משתנות תוך שנייה - EACCES הולך ישר לדילוגים שדורשים root, בלי ניסיון חוזר
+    fn report_failed(&self, path: &Path, e: &io::Error) {
+        // הסבב החוזר מוחק - קובץ שלא הועבר לארכיון נשאר במקומו
+        if matches!(self.strategy, DeletionStrategy::MoveTo(_)) {
+            self.log_sync(&tr_args("log.archive_failed", &[&display_name(path), &e.to_string()]));
+            self.record_error();
+            return;
+        }
+        if is_protected_by_os(e) {
+            self.report_protected(path);
+        } else if is_name_too_long(e) {
+            self.report_name_too_long(path);
+        } else if is_immutable(path, e) {
+            self.report_immutable(path);
+        } else if e.kind() == io::ErrorKind::PermissionDenied {
+            self.report_needs_root(path);
+        } else if e.kind() != io::ErrorKind::NotFound {
+            self.failed_deletions.lock().unwrap().push(FailedDeletion {
+                path: path.to_path_buf(),
+                item: self.item_id.clone(),
+                kind: e.kind(),
+            });
+        }
+    }
+
+    // סבב אחד אחרי כל הפריטים על מה שלא נמחק. עצירה באמצע משאירה את השאר ככישלונות
+    pub async fn retry_failed(&self) -> RetryReport {
+        let mut pending = std::mem::take(&mut *self.failed_deletions.lock().unwrap());
+        let mut report = RetryReport::default();
+        for attempt in 1..=self.delete_retries {
+            if pending.is_empty() || self.checkpoint_async().await {
+                break;
+            }
+            self.log(&tr_args("log.retrying", &[&pending.len().to_string(), &attempt.to_string()])).await;
+            tokio::select! {
+                _ = self.cancel.cancelled() => break,
+                _ = tokio::time::sleep(self.retry_backoff * attempt) => {}
+            }
+            let mut still_failing = Vec::new();
+            for failed in pending {
+                if self.is_cancelled() {
+                    still_failing.push(failed);
+                    continue;
+                }
+                // נעלם בינתיים (התהליך שהחזיק אותו ניקה בעצמו) - אין מה לדווח
+                let Ok(metadata) = fs::symlink_metadata(&failed.path) else { continue };
+                let mut item = self.clone();
+                item.item_id = failed.item.clone();
+                match item.delete_or_stash(&failed.path, &metadata) {
+                    Ok(()) => {
+                        item.report_deleted(&failed.path, &metadata);
+                        report.recovered += 1;
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                    Err(e) if e.kind() == io::ErrorKind::PermissionDenied => item.report_failed(&failed.path, &e),
+                    Err(e) => still_failing.push(FailedDeletion { kind: e.kind(), ..failed }),
+                }
+            }
+            pending = still_failing;
+        }
+        self.finish_batch();
+        report.failures = pending;
+        report
+    }
+
+    // רק מתוך spawn_blocking - זה sleep חוסם
+    fn throttle(&self) {
+        if self.throttle_every == 0 {
+            return;
+        }
+        if (self.deletions.fetch_add(1, Ordering::Relaxed) + 1).is_multiple_of(self.throttle_every) {
+            std::thread::sleep(priority::THROTTLE_PAUSE);
+        }
+    }
+
+    // האם לדרוס קבצים בתיקייה הזו לפני מחיקה
+    fn shred_enabled_for(&self, dir: &Path) -> bool {
+        if !self.secure_delete || self.dry_run || !matches!(self.strategy, DeletionStrategy::Delete) {
+            return false;
+        }
+        // ב-btrfs (copy-on-write) הכתיבה הולכת לבלוקים חדשים, הדריסה לא מועילה
+        if is_copy_on_write(dir) {
+            self.log_sync(&format!("🔒 Secure delete skipped on copy-on-write filesystem (btrfs): {}", dir.display()));
+            return false;
+        }
+        true
+    }
+
+    fn shred_file(&self, path: &Path, metadata: &fs::Metadata) {
+        // דריסה דרך השם הזה הייתה מאפסת גם את מה שהשמות האחרים עדיין מצביעים עליו - רק ה-unlink
+        if has_other_links(metadata) {
+            self.log_sync(&tr_args("log.shred_hard_link", &[&display_name(path)]));
+            return;
+        }
+        let len = metadata.len();
+        if len > self.shred_max_bytes {
+            self.log_sync(&format!("🔒 Not overwriting {} (larger than {})", path.display(), Self::format_bytes(self.shred_max_bytes)));
+            return;
+        }
+        if let Err(e) = overwrite_with_zeros(path, len) {
+            self.log_sync(&format!("{}: overwrite failed for {}: {}", tr("log.error"), path.display(), e));
+        }
+    }
+
+    fn finish_batch(&self) {
+        if let DeletionStrategy::Quarantine(run) = &self.strategy {
+            run.flush();
+        }
+        if let Some(manifest) = &self.manifest {
+            manifest.flush();
+        }
+    }
+
+    fn in_use_under(&self, dir: &Path) -> HashSet<PathBuf> {
+        if !self.skip_in_use {
+            return HashSet::new();
+        }
+        // /proc מראה רק את המכונה הזו - קובץ ב-NFS פתוח בתחנה אחרת לא יופיע שם, אז הסריקה רק מבזבזת זמן
+        if is_network_fs(dir) {
+            self.log_sync(&tr_args("log.in_use_network_fs", &[&dir.display().to_string()]));
+            return HashSet::new();
+        }
+        open_files_under(dir)
+    }
+
+    fn report_in_use(&self, path: &Path) {
+        self.live.skipped_in_use.fetch_add(1, Ordering::Relaxed);
+        self.log_sync(&tr_args("log.in_use", &[&display_name(path)]));
+    }
+
+    fn report_needs_root(&self, path: &Path) {
+        self.live.skipped_needs_root.fetch_add(1, Ordering::Relaxed);
+        self.log_sync(&tr_args("log.needs_root", &[&display_name(path)]));
+    }
+
+    fn report_protected(&self, path: &Path) {
+        self.live.skipped_protected.fetch_add(1, Ordering::Relaxed);
+        self.log_sync(&tr_args("log.protected", &[&display_name(path)]));
+    }
+
+    fn report_immutable(&self, path: &Path) {
+        self.live.skipped_immutable.fetch_add(1, Ordering::Relaxed);
+        self.log_sync(&tr_args("log.immutable", &[&display_name(path)]));
+    }
+
+    // ניסיון חוזר לא יעזור - רק שם קצר יותר
+    fn report_name_too_long(&self, path: &Path) {
+        self.record_error();
+        self.log_sync(&tr_args("log.name_too_long", &[&display_name(path)]));
+    }
+
+    // המשתמש סימן את התיקייה ב-.supercleaner-keep
+    fn is_kept(&self, dir: &Path) -> bool {
+        if !dir.join(KEEP_MARKER).exists() {
+            return false;
+        }
+        self.log_sync(&tr_args("log.keep_marker", &[&dir.display().to_string()]));
+        true
+    }
+
+    // נקודת עיגון בתוך התיקייה: מדלגים עליה ועל כל מה שמתחתיה
+    fn crosses_device(&self, entry: &walkdir::DirEntry, root_device: Option<u64>) -> bool {
+        entry.metadata().is_ok_and(|metadata| self.crosses_device_at(entry.path(), &metadata, root_device))
+    }
+
+    fn crosses_device_at(&self, path: &Path, metadata: &fs::Metadata, root_device: Option<u64>) -> bool {
+        let Some(root_device) = root_device else { return false };
+        if (self.device_id)(path, metadata) == root_device {
+            return false;
+        }
+        self.log_sync(&tr_args("log.other_filesystem", &[&display_name(path)]));
+        true
+    }
+
+    // שגיאות סריקה נבלעות כמו קודם, חוץ מנתיב שמערכת ההפעלה חוסמת - עליו מדווחים
+    fn walk_entry(&self, entry: walkdir::Result<walkdir::DirEntry>) -> Option<walkdir::DirEntry> {
+        match entry {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                if let (Some(path), Some(err)) = (e.path(), e.io_error()) {
+                    self.report_walk_error(path, err);
+                }
+                None
+            }
+        }
+    }
+
+    fn report_walk_error(&self, path: &Path, e: &io::Error) {
+        if self.dry_run {
+            self.unmeasured.record(unmeasured::classify(e, is_protected_by_os(e)), path);
+        }
+        if is_protected_by_os(e) {
+            self.report_protected(path);
+        } else if is_name_too_long(e) {
+            self.report_name_too_long(path);
+        }
+    }
+
+    // 0 = לפי מספר הליבות. על HDD תמיד thread אחד
+    fn walk_threads_for(&self, dir: &Path) -> usize {
+        let threads = match self.walk_threads {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get().min(MAX_WALK_THREADS)),
+            n => n,
+        };
+        if threads > 1 && (self.is_rotational)(dir) {
+            return 1;
+        }
+        threads
+    }
+
+    // הועבר לארכיון: לא פינה מקום, נספר בנפרד. בריצה אמיתית היעד המדויק כבר נרשם ב-delete_or_stash
+    fn report_organized(&self, path: &Path, metadata: &fs::Metadata, root: &Path) {
+        if self.dry_run {
+            self.log_sync(&tr_args("log.would_archive", &[&display_name(path), &archive_dir(root, metadata).display().to_string()]));
+        }
+        self.live.files_organized.fetch_add(1, Ordering::Relaxed);
+        self.live.bytes_organized.fetch_add(metadata.len(), Ordering::Relaxed);
+    }
+
+    // ה-mount העמוק ביותר קובע: דיסק שמעוגן מתחת ל-/run (tmpfs) הוא עדיין דיסק
+    fn mount_of(&self, path: &Path) -> Option<&mounts::MountInfo> {
+        mounts::mount_for(path, self.mounts.get_or_init(mounts::read_mounts))
+    }
+
+    fn note_snapshot_fs(&self, mount: &mounts::MountInfo) {
+        let mut seen = self.snapshot_fs_mounts.lock().unwrap();
+        if !seen.iter().any(|m| m.mount_point == mount.mount_point) {
+            seen.push(mount.clone());
+        }
+    }
+
+    // בסוף הריצה: על עיגון btrfs/ZFS עם snapshots, המקום חוזר רק כשהם מתחלפים. מריץ btrfs/zfs - לא מה-GUI thread
+    pub fn snapshot_notes(&self) -> Vec<String> {
+        let mounts = self.snapshot_fs_mounts.lock().unwrap().clone();
+        mounts
+            .iter()
+            .filter_map(|mount| {
+                let count = mounts::snapshot_count(mount).filter(|&n| n > 0)?;
+                let fs_name = if mount.fs_type == "zfs" { "ZFS" } else { "btrfs" };
+                Some(tr_args("summary.snapshots", &[&mount.mount_point.display().to_string(), fs_name, &count.to_string()]))
+            })
+            .collect()
+    }
+
+    // מחזיר כמה נספר כמפונה (0 לקישור קשיח שכבר נספר או להעברה לארכיון)
+    fn report_deleted(&self, path: &Path, metadata: &fs::Metadata) -> u64 {
+        if let DeletionStrategy::MoveTo(root) = &self.strategy {
+            self.report_organized(path, metadata, root);
+            return 0;
+        }
+        let name = display_name(path);
+        let size = match self.disk_usage(metadata) {
+            Some(size) => {
+                self.log_sync(&tr_args("log.deleted", &[&name, &Self::format_bytes(size)]));
+                size
+            }
+            None => {
+                self.log_sync(&tr_args("log.deleted_hardlink", &[&name]));
+                0
+            }
+        };
+        self.record_file(size);
+        self.largest.record(size, path, &self.item_id);
+        match self.mount_of(path) {
+            Some(mount) if mount.is_ram_backed() => {
+                self.live.ram_bytes_freed.fetch_add(size, Ordering::Relaxed);
+            }
+            Some(mount) if mount.has_snapshots_support() && size > 0 => self.note_snapshot_fs(mount),
+            _ => {}
+        }
+        if let (Some(manifest), false) = (&self.manifest, self.dry_run) {
+            manifest.record(&self.item_id, path, metadata.len(), metadata.modified().ok());
+        }
+        self.emit(CleanEvent::FileDeleted { path: path.to_path_buf(), bytes: size });
+        size
+    }
+
+    // === Helper Methods ===
+
+    async fn clean_directory_contents<P: AsRef<Path>>(&self, dir: P, _category: &str, options: CleanOptions) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = canonical_root(dir.as_ref());
+        if !dir.exists() { return Ok(()); }
+        self.touch(&dir);
+
+        // WalkDir ו-remove_file חוסמים - מריצים אותם על thread נפרד כדי לא לתקוע את ה-executor
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || this.delete_directory_contents_blocking(&dir, &options)).await?;
+        Ok(())
+    }
+
+    fn delete_directory_contents_blocking(&self, dir: &Path, options: &CleanOptions) {
+        let mut files_to_delete = Vec::new();
+        // לא נוגעים בנתונים של האפליקציה עצמה (הסגר וכו'). בצורה הקנונית, כמו השורש
+        let own_data = canonical_root(&app_data_dir());
+        let walk_started = Instant::now();
+        let paused_before = self.pause.paused_for();
+        let mut entries = 0u64;
+        let root_device = fs::metadata(dir).ok().filter(|_| self.same_filesystem).map(|m| (self.device_id)(dir, &m));
+        if self.is_kept(dir) {
+            return;
+        }
+        let threads = self.walk_threads_for(dir);
+
+        if threads > 1 {
+            let visitor = ContentsVisitor { cleaner: self, options, own_data: &own_data, root_device, entries: AtomicU64::new(0), files: Mutex::new(Vec::new()) };
+            parallel_walk::walk(dir, threads, &visitor);
+            entries = visitor.entries.into_inner();
+            files_to_delete = visitor.files.into_inner().unwrap_or_default();
+        } else {
+            // הורה לפני ילדים: כך filter_entry גוזם תיקייה (למשל עם .supercleaner-keep) לפני שהקבצים
+            // שלה נאספו, ולא מדלג בטעות על שאר האחים שלה כמו עם contents_first
+            for entry in WalkDir::new(dir).min_depth(1).into_iter()
+                .filter_entry(|e| {
+                    let kept = e.file_type().is_dir() && self.is_kept(e.path());
+                    !e.path().starts_with(&own_data)
+                        && !self.is_excluded_by(&options.exclusions, e.path())
+                        && !options.skips(e.path())
+                        && !self.crosses_device(e, root_device)
+                        && !kept
+                })
+                .filter_map(|e| self.walk_entry(e)) {
+                if self.checkpoint() {
+                    break;
+                }
+                entries += 1;
+                let path = entry.path();
+                self.current_path.set(path);
+                // בלי לעקוב אחרי symlinks: נמחק הקישור עצמו ונספר הגודל שלו, לא של היעד
+                if entry.file_name() == CACHEDIR_TAG {
+                    continue;
+                }
+                match entry.metadata() {
+                    Ok(metadata) => {
+                        if (metadata.is_file() || metadata.is_symlink()) && !options.is_too_recent(&metadata) {
+                            files_to_delete.push((path.to_path_buf(), metadata));
+                        }
+                    }
+                    Err(e) => {
+                        if let Some(err) = e.io_error() {
+                            self.report_walk_error(path, err);
+                        }
+                    }
+                }
+            }
+        }
+
+        let walk_time = walk_started.elapsed().saturating_sub(self.pause.paused_for().saturating_sub(paused_before));
+        if self.verbose && walk_time > SLOW_WALK {
+            self.log_sync(&tr_args("log.slow_walk", &[&dir.display().to_string(), &format_duration(walk_time), &entries.to_string()]));
+        }
+
+        if let Some(keep) = options.keep_newest {
+            // החדשים ביותר קודם; mtime זהה - לפי נתיב, כדי שכל תצוגה מקדימה תשאיר את אותם קבצים
+            let mtime = |metadata: &fs::Metadata| metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            files_to_delete.sort_by(|(a_path, a), (b_path, b)| mtime(b).cmp(&mtime(a)).then_with(|| a_path.cmp(b_path)));
+            let kept = files_to_delete.drain(..keep.min(files_to_delete.len())).count();
+            if kept > 0 {
+                self.log_sync(&tr_args("log.kept_newest", &[&dir.display().to_string(), &kept.to_string()]));
+            }
+        }
+
+        let shred = self.shred_enabled_for(dir);
+        let in_use = self.in_use_under(dir);
+        let removed = Mutex::new(HashSet::new());
+        let delete = |(path, metadata): &(PathBuf, fs::Metadata)| {
+            self.current_path.set(path);
+            if in_use.contains(path) {
+                self.report_in_use(path);
+                return;
+            }
+            // דריסה דרך symlink הייתה דורסת את היעד
+            if self.remove_file(path, metadata, shred && metadata.is_file()) {
+                self.report_deleted(path, metadata);
+                removed.lock().unwrap().insert(path.clone());
+            }
+        };
+        // מחיקה מקבילית: כל thread לוקח את הקובץ הבא ברשימה, עד שנגמרת או שמבטלים
+        let next = AtomicUsize::new(0);
+        let worker = || {
+            while !self.checkpoint() {
+                let Some(file) = files_to_delete.get(next.fetch_add(1, Ordering::Relaxed)) else { break };
+                delete(file);
+            }
+        };
+        if threads > 1 {
+            std::thread::scope(|scope| {
+                for _ in 0..threads.min(files_to_delete.len()) {
+                    scope.spawn(worker);
+                }
+            });
+        } else {
+            worker();
+        }
+        if !self.checkpoint() {
+            self.remove_emptied_dirs(dir, &removed.into_inner().unwrap_or_default());
+        }
+        self.current_path.clear();
+        self.finish_batch();
+    }
+
+    // תיקיות מתחת ל-root שכל מה שהיה בהן נמחק עכשיו. מהעמוקה לרדודה, כדי שהורה שנשאר רק עם
+    // תיקיות כאלה יתרוקן גם הוא. בתצוגה מקדימה הקבצים עדיין שם - נחשבים לפי removed,
+    // כך שהספירה זהה לזו של ניקוי אמיתי. תיקייה שהייתה ריקה מלכתחילה לא נספרת (זה empty_dirs)
+    fn remove_emptied_dirs(&self, root: &Path, removed: &HashSet<PathBuf>) {
+        let mut candidates: Vec<&Path> = removed
+            .iter()
+            .flat_map(|path| path.ancestors().skip(1).take_while(|dir| *dir != root && dir.starts_with(root)))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+        candidates.sort_by(|a, b| b.components().count().cmp(&a.components().count()).then_with(|| a.cmp(b)));
+        let structural = self.structural_dirs();
+        let mut emptied: HashSet<&Path> = HashSet::new();
+        for dir in candidates {
+            if self.checkpoint() {
+                return;
+            }
+            if structural.contains(dir) || self.is_kept(dir) {
+                continue;
+            }
+            // תיקייה לקריאה בלבד נפתחה רק לרגע (fix_permissions) - את התוכן מחקנו, את התיקייה עצמה משאירים
+            if fs::symlink_metadata(dir).is_ok_and(|metadata| metadata.permissions().readonly()) {
+                continue;
+            }
+            // שגיאת קריאה = לא יודעים מה יש שם, אז לא נוגעים
+            let Ok(children) = fs::read_dir(dir) else { continue };
+            let mut children = children.map(|child| child.map(|c| c.path()));
+            if !children.all(|child| child.is_ok_and(|path| removed.contains(&path) || emptied.contains(path.as_path()))) {
+                continue;
+            }
+            if self.dry_run {
+                self.log_sync(&tr_args("log.dir_empty_after", &[&escape_name(dir.as_os_str())]));
+            } else if fs::remove_dir(dir).is_ok() {
+                self.log_sync(&tr_args("log.empty_dir_removed", &[&escape_name(dir.as_os_str())]));
+            } else {
+                continue;
+            }
+            self.live.directories_cleaned.fetch_add(1, Ordering::Relaxed);
+            emptied.insert(dir);
+        }
+    }
+
+    async fn clean_files_by_pattern<P: AsRef<Path>>(&self, dir: P, pattern: &str, sibling: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = canonical_root(dir.as_ref());
+        if !dir.exists() { return Ok(()); }
+        self.touch(&dir);
+
+        let this = self.clone();
+        let pattern = pattern.to_string();
+        tokio::task::spawn_blocking(move || this.delete_files_by_pattern_blocking(&dir, &pattern, sibling.as_deref())).await?;
+        Ok(())
+    }
+
+    fn delete_files_by_pattern_blocking(&self, dir: &Path, pattern: &str, sibling: Option<&str>) {
+        // הערה: glob פשוט. לשיפור אפשר להשתמש ב-glob crate
+        let own_data = canonical_root(&app_data_dir());
+        let shred = self.shred_enabled_for(dir);
+        let in_use = self.in_use_under(dir);
+        // בתצוגה מקדימה בלבד: תיקייה ברמה העליונה שלא השתנתה לא נסרקת שוב
+        let cache = self.size_cache.as_ref().filter(|_| self.dry_run);
+        // התיקייה העליונה שנסרקת עכשיו ומה נמצא בה עד כה - נשמרת כשהסריקה שלה מסתיימת
+        let mut scanning: Option<(PathBuf, CachedSize)> = None;
+        let mut cancelled = false;
+        // רק תבנית על כל הבית נכנסת ל-repos; בתוך ~/.cache וכו' אין מה לבדוק
+        let git_aware = self.git_aware && dir == self.paths.home;
+        let mut work_trees: HashMap<PathBuf, Option<PathBuf>> = HashMap::new();
+        let mut in_repos: HashMap<PathBuf, Vec<(PathBuf, fs::Metadata)>> = HashMap::new();
+        // תשובה אחת לכל תיקייה, לא read_dir לכל קובץ שמתאים
+        let mut has_sibling: HashMap<PathBuf, bool> = HashMap::new();
+        // אותה תבנית עם ובלי sibling - תוצאות שונות, לא אותה רשומה ב-cache
+        let cache_key = match sibling {
+            Some(sibling) => format!("{}@{}", pattern, sibling),
+            None => pattern.to_string(),
+        };
+        let pattern_key = cache_key.as_str();
+        let mut walker = WalkDir::new(dir).into_iter()
+            .filter_entry(|e| !e.path().starts_with(&own_data) && !self.is_excluded(e.path()));
+        while let Some(entry) = walker.next() {
+            let Some(entry) = self.walk_entry(entry) else { continue };
+            if self.checkpoint() {
+                cancelled = true;
+                break;
+            }
+            self.current_path.set(entry.path());
+            if let (Some(cache), 1) = (cache, entry.depth()) {
+                if let Some((top, size)) = scanning.take() {
+                    cache.put(&self.item_id, pattern_key, &top, size);
+                }
+                if entry.file_type().is_dir() {
+                    if let Some(fingerprint) = DirFingerprint::of(entry.path()) {
+                        // החרגה חדשה בפנים משנה את התוצאה גם כשהתיקייה עצמה לא השתנתה
+                        let has_exclusions = self.exclusions.may_match_under(entry.path());
+                        match cache.get(&self.item_id, pattern_key, entry.path(), fingerprint).filter(|_| !has_exclusions) {
+                            Some(cached) => {
+                                self.record_freed(cached.files, cached.bytes);
+                                self.log_sync(&tr_args("log.cached_size", &[&display_name(entry.path()), &Self::format_bytes(cached.bytes), &cached.files.to_string()]));
+                                walker.skip_current_dir();
+                            }
+                            None => scanning = Some((entry.path().to_path_buf(), CachedSize { fingerprint, files: 0, bytes: 0 })),
+                        }
+                    }
+                    continue;
+                }
+            }
+            if entry.file_type().is_file() {
+                let near_sibling = |has_sibling: &mut HashMap<PathBuf, bool>| sibling.is_none_or(|sibling| {
+                    let parent = entry.path().parent().unwrap_or(dir);
+                    *has_sibling.entry(parent.to_path_buf()).or_insert_with(|| dir_has_match(parent, sibling))
+                });
+                if name_matches(entry.file_name(), pattern) && near_sibling(&mut has_sibling) {
+                    let metadata = match entry.metadata() {
+                        Ok(m) => m,
+                        Err(_) => continue,
+                    };
+                    if self.is_too_recent(&metadata) {
+                        continue;
+                    }
+                    if in_use.contains(entry.path()) {
+                        self.report_in_use(entry.path());
+                        continue;
+                    }
+                    if let Some(repo) = git_aware.then(|| git::work_tree_of(entry.path(), dir, &mut work_trees)).flatten() {
+                        // נבדק בסוף מול git - התיקייה העליונה כבר לא נשמרת ב-cache, הסכום שלה חלקי
+                        if scanning.as_ref().is_some_and(|(top, _)| entry.path().starts_with(top)) {
+                            scanning = None;
+                        }
+                        in_repos.entry(repo).or_default().push((entry.into_path(), metadata));
+                        continue;
+                    }
+                    if self.remove_file(entry.path(), &metadata, shred) {
+                        let size = self.report_deleted(entry.path(), &metadata);
+                        if let Some((_, found)) = scanning.as_mut().filter(|(top, _)| entry.path().starts_with(top)) {
+                            found.files += 1;
+                            found.bytes += size;
+                        }
+                    }
+                }
+            }
+        }
+        // סריקה שנעצרה באמצע לא נשמרת - התוצאה חלקית
+        if let (Some(cache), Some((top, size)), false) = (cache, scanning, cancelled) {
+            cache.put(&self.item_id, pattern_key, &top, size);
+        }
+        if !cancelled {
+            self.delete_ignored_in_repos(in_repos, shred);
+        }
+        self.current_path.clear();
+        self.finish_batch();
+    }
+
+    // batch אחד של check-ignore לכל repo. מה שלא אושר (במעקב, או פשוט לא ב-.gitignore) נשאר
+    fn delete_ignored_in_repos(&self, in_repos: HashMap<PathBuf, Vec<(PathBuf, fs::Metadata)>>, shred: bool) {
+        for (repo, files) in in_repos {
+            if self.checkpoint() {
+                return;
+            }
+            let paths: Vec<PathBuf> = files.iter().map(|(path, _)| path.clone()).collect();
+            let Some(ignored) = git::ignored(&repo, &paths) else {
+                self.live.git_skipped.fetch_add(files.len() as u64, Ordering::Relaxed);
+                self.log_sync(&tr_args("log.git_unavailable", &[&files.len().to_string(), &display_name(&repo)]));
+                continue;
+            };
+            for (path, metadata) in files {
+                if !ignored.contains(&path) {
+                    self.live.git_skipped.fetch_add(1, Ordering::Relaxed);
+                    self.log_sync(&tr_args("log.git_kept", &[&display_name(&path)]));
+                    continue;
+                }
+                self.live.git_verified.fetch_add(1, Ordering::Relaxed);
+                if self.remove_file(&path, &metadata, shred) {
+                    self.report_deleted(&path, &metadata);
+                }
+            }
+        }
+    }
+
+    // === Targets ===
+
+    // הנתיבים/פקודות של כל פריט, עבור המשתמש הנוכחי
+    pub fn targets(&self, id: &str) -> Vec<Target> {
+        let paths = &self.paths;
+        let (home, cache, config, data, state) = (&paths.home, &paths.cache, &paths.config, &paths.data, &paths.state);
+        #[cfg(windows)]
+        if let Some(targets) = windows_targets(id, paths) {
+            return targets;
+        }
+        #[cfg(target_os = "macos")]
+        if let Some(targets) = macos_targets(id, paths) {
+            return targets;
+        }
+        match id {
+            "tmp" => vec![dir_target(&paths.tmp), dir_target(&paths.var_tmp)],
+            "var_cache" => vec![dir_target(&paths.system_cache), dir_target(cache)],
+            "trash" => vec![dir_target(data.join("Trash"))],
+            "logs" => vec![
+                dir_target(&paths.system_log),
+                pattern_target(data, "*.log"),
+                pattern_target(config, "*.log"),
+                pattern_target(state, "*.log"),
+            ],
+            "thumbnails" => vec![
+                dir_target(home.join(".thumbnails")),
+                dir_target(cache.join("thumbnails")),
+                dir_target(data.join("thumbnails")),
+            ],
+            "empty_dirs" => vec![Target::ScanOnly(home.to_path_buf())],
+            "wipe_free_space" => vec![Target::FreeSpace(self.wipe.mount.clone())],
+            "broken_symlinks" => vec![Target::ScanOnly(home.to_path_buf())],
+            // כל תיקייה עם CACHEDIR.TAG תקין - נמצאות רק בזמן הריצה
+            "cachedir_sweep" => vec![Target::ScanOnly(home.to_path_buf())],
+            "clipboard" => vec![command_target(&["xclip", "-selection", "clipboard", "/dev/null"])],
+            "recent_docs" => vec![pattern_target(data, "recently-used.xbel")],
+            "fm_leftovers" => vec![
+                Target::ScanOnly(config.join("gtk-3.0/bookmarks")),
+                pattern_target(data.join("gvfs-metadata"), "*.log"),
+                Target::ScanOnly(data.join("dolphin/view_properties/local")),
+            ],
+            "broken_desktop" => vec![pattern_target(data.join("applications"), "*.desktop")],
+            "app_data" => vec![Target::ScanOnly(data.join(APP_DIR_NAME))],
+            "stale_autostart" => vec![Target::ScanOnly(config.join("autostart")), Target::ScanOnly(config.join("systemd/user"))],
+            "fonts" => vec![
+                dir_target(cache.join("fontconfig")),
+                dir_target(paths.system_cache.join("fontconfig")),
+                Target::ScanOnly(data.join("mime")),
+                Target::ScanOnly(data.join("icons")),
+                Target::ScanOnly(home.join(".icons")),
+                Target::ScanOnly(paths.system_share.join("icons")),
+                command_target(&["fc-cache", "-f"]),
+            ],
+            "android" => vec![
+                dir_target(home.join(".android/cache")),
+                dir_target(home.join(".android/build-cache")),
+                pattern_target(home.join(".gradle/daemon"), "*.log"),
+                pattern_target(&paths.tmp, "adb.*.log"),
+                // snapshots ו-locks ישנים, לכל AVD בנפרד
+                Target::ScanOnly(home.join(".android/avd")),
+            ],
+            "devops" => vec![
+                dir_target(cache.join("helm")),
+                dir_target(home.join(".kube/cache")),
+                dir_target(home.join(".kube/http-cache")),
+                // רק tokens שפג תוקפם - מחיקת tokens בתוקף מכריחה להתחבר מחדש
+                pattern_target(home.join(".aws/cli/cache"), "*.json"),
+                pattern_target(home.join(".aws/sso/cache"), "*.json"),
+                dir_target(home.join(".terraform.d/plugin-cache")),
+                dir_target(cache.join("pre-commit")),
+            ],
+            "lang_caches" => lang_cache_sections(paths).into_iter().flat_map(|(_, targets)| targets).collect(),
+            "doc_temp" => doc_temp_sections(paths).into_iter().flat_map(|(_, targets)| targets).collect(),
+            "screen_leftovers" => screen_leftover_sections(paths).into_iter().flat_map(|(_, targets)| targets).collect(),
+            "wine" => wine::prefixes(home, &paths.data).iter().flat_map(|p| wine::cache_dirs(p)).map(Target::Directory).collect(),
+            "tracker" => tool_or_dirs_targets(
+                &[&["tracker3", "reset", "--filesystem"]],
+                vec![cache.join("tracker3"), data.join("tracker")],
+            ),
+            "baloo" => tool_or_dirs_targets(
+                &[&["balooctl6", "purge"], &["balooctl", "purge"]],
+                vec![data.join("baloo")],
+            ),
+
+            "chrome_cache" => vec![dir_target(config.join("google-chrome/Default/Cache"))],
+            "firefox_cache" => vec![Target::Search { root: home.join(".mozilla/firefox"), dir_name: "cache2".to_string() }],
+            // ScanOnly: התיקייה של profiles.ini - הפרופילים נקראים ממנו
+            "site_storage" => chromium::BROWSERS.iter().map(|b| Target::ScanOnly(config.join(b.config_dir))).collect(),
+            "firefox_leftovers" => vec![
+                Target::ScanOnly(home.join(".mozilla/firefox")),
+                Target::ScanOnly(home.join("snap/firefox/common/.mozilla/firefox")),
+                Target::ScanOnly(home.join(".var/app/org.mozilla.firefox/.mozilla/firefox")),
+            ],
+            "brave_cache" => vec![dir_target(config.join("BraveSoftware/Brave-Browser/Default/Cache"))],
+
+            "pycache" => vec![pattern_target(home, "*.pyc"), pattern_target(home, "__pycache__")],
+            // http-v2 מ-pip 23.3; החבילות שהורדו לאחרונה יותקנו שוב בקרוב
+            "pip_cache" => vec![dir_target(cache.join("pip/http")), dir_target(cache.join("pip/http-v2"))],
+            "vim" => vec![
+                pattern_target(home, "*.swp"),
+                pattern_target(home, "*.swo"),
+                pattern_target(home.join(".vim"), "*.swp"),
+            ],
+            "backup_files" => vec![pattern_target(home, "*~"), pattern_target(home, "*.bak")],
+            "xcode" => vec![dir_target(home.join("Library/Developer/Xcode/DerivedData"))],
+
+            "apt" => vec![command_target(&["apt-get", "autoremove", "-y"]), command_target(&["apt-get", "clean"])],
+            "dnf" => vec![command_target(&["dnf", "autoremove", "-y"]), command_target(&["dnf", "clean", "all"])],
+            "flatpak" => vec![
+                command_target(&["flatpak", "uninstall", "--unused", "-y"]),
+                dir_target(home.join(".var/app")),
+            ],
+            "brew" => vec![command_target(&["brew", "cleanup", "--prune=all"]), Target::ScanOnly(cache.join("Homebrew"))],
+
+            "duplicates" => self.duplicates.roots.iter().cloned().map(Target::ScanOnly).collect(),
+            "large_downloads" => self.large_files.roots.iter().cloned().map(Target::ScanOnly).collect(),
+            _ => self.custom.iter().find(|c| c.id == id).map(|c| c.targets()).unwrap_or_default(),
+        }
+    }
+
+    async fn clean_targets(&self, id: &str, category: &str) -> Result<(), Box<dyn std::error::Error>> {
+        for target in self.targets(id) {
+            self.clean_target(target, category).await?;
+        }
+        Ok(())
+    }
+
+    async fn clean_target(&self, target: Target, category: &str) -> Result<(), Box<dyn std::error::Error>> {
+        match target {
+            Target::Directory(dir) => self.clean_directory_contents(dir, category, self.clean_options()).await?,
+            Target::Pattern { root, pattern, sibling } => self.clean_files_by_pattern(root, &pattern, sibling).await?,
+            Target::Search { root, dir_name } => {
+                if !root.exists() { return Ok(()); }
+                let found: Vec<PathBuf> = tokio::task::spawn_blocking(move || {
+                    WalkDir::new(root).into_iter().filter_map(|e| e.ok())
+                    .filter(|e| e.file_type().is_dir() && e.file_name() == OsStr::new(&dir_name))
+                    .map(|e| e.into_path())
+                    .collect()
+                }).await?;
+                for dir in found {
+                    self.clean_directory_contents(dir, category, self.clean_options()).await?;
+                }
+            }
+            Target::Command(argv) => {
+                if !self.dry_run {
+                    self.run_command(&argv).await?;
+                }
+            }
+            Target::ScanOnly(_) | Target::FreeSpace(_) => {}
+        }
+        Ok(())
+    }
+
+    // מריץ פקודה חיצונית בלי לחסום: הפלט נשלח ללוג שורה-שורה, עם timeout וביטול
+    async fn run_command(&self, argv: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        self.run_command_output(argv, false).await.map(|_| ())
+    }
+
+    // capture: stdout חוזר למי שקרא (hooks מדווחים בו), במקום ללוג. stderr תמיד ללוג
+    async fn run_command_output(&self, argv: &[String], capture: bool) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        // פקודה שכבר רצה לא נעצרת, אבל חדשה לא מתחילה בזמן השהיה
+        if self.checkpoint_async().await {
+            return Err(skip("aborted"));
+        }
+        let program = &argv[0];
+        let mut child = ProcessCommand::new(program)
+            .args(&argv[1..])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        let mut stdout = child.stdout.take().map(|s| BufReader::new(s).lines());
+        let mut stderr = child.stderr.take().map(|s| BufReader::new(s).lines());
+        let mut lock_held = false;
+        let mut captured = Vec::new();
+
+        let deadline = tokio::time::sleep(self.command_timeout);
+        tokio::pin!(deadline);
+
+        loop {
+            tokio::select! {
+                line = async { stdout.as_mut().unwrap().next_line().await }, if stdout.is_some() => match line {
+                    Ok(Some(line)) if capture => captured.push(line),
+                    Ok(Some(line)) => {
+                        lock_held |= is_package_lock_message(&line);
+                        self.log(&format!("  {}", line)).await;
+                    }
+                    _ => stdout = None,
+                },
+                line = async { stderr.as_mut().unwrap().next_line().await }, if stderr.is_some() => match line {
+                    Ok(Some(line)) => {
+                        lock_held |= is_package_lock_message(&line);
+                        self.log(&format!("  {}", line)).await;
+                    }
+                    _ => stderr = None,
+                },
+                status = child.wait(), if stdout.is_none() && stderr.is_none() => {
+                    let status = status?;
+                    if status.success() {
+                        return Ok(captured);
+                    }
+                    if lock_held {
+                        return Err(format!("{}: package database is locked by another process (is another package manager running?)", program).into());
+                    }
+                    return Err(format!("{} failed ({})", program, status).into());
+                }
+                _ = &mut deadline => {
+                    let _ = child.kill().await;
+                    if lock_held {
+                        return Err(format!("{}: gave up waiting for the package database lock held by another process", program).into());
+                    }
+                    return Err(format!("{} timed out after {} min and was killed", program, self.command_timeout.as_secs() / 60).into());
+                }
+                _ = self.cancel.cancelled() => {
+                    let _ = child.kill().await;
+                    return Err(skip("aborted"));
+                }
+            }
+        }
+    }
+
+    // === Dispatch ===
+
+    // המקור היחיד למיפוי id -> פונקציה. id בלי מטפל = None, ולא "הצלחה" שקטה
+    pub fn handler(id: &str) -> Option<ItemHandler> {
+        let handler: ItemHandler = match id {
+            "tmp" => |c| Box::pin(c.clean_temp_files()),
+            "var_cache" => |c| Box::pin(c.clean_system_cache()),
+            "trash" => |c| Box::pin(c.clean_trash()),
+            "logs" => |c| Box::pin(c.clean_logs()),
+            "thumbnails" => |c| Box::pin(c.clean_thumbnails()),
+            "clipboard" => |c| Box::pin(c.clean_clipboard()),
+            "recent_docs" => |c| Box::pin(c.clean_recent_docs()),
+            "fm_leftovers" => |c| Box::pin(c.clean_file_manager_leftovers()),
+            "broken_desktop" => |c| Box::pin(c.clean_broken_desktop_files()),
+            "app_data" => |c| Box::pin(c.clean_own_data()),
+            "stale_autostart" => |c| Box::pin(c.clean_stale_autostart()),
+            "fonts" => |c| Box::pin(c.clean_font_caches()),
+            "wine" => |c| Box::pin(c.clean_wine()),
+            "android" => |c| Box::pin(c.clean_android()),
+            "devops" => |c| Box::pin(c.clean_devops()),
+            "lang_caches" => |c| Box::pin(c.clean_lang_caches()),
+            "tracker" => |c| Box::pin(c.clean_search_index("tracker", "GNOME Tracker")),
+            "baloo" => |c| Box::pin(c.clean_search_index("baloo", "KDE Baloo")),
+
+            "chrome_cache" => |c| Box::pin(c.clean_chrome_cache()),
+            "firefox_cache" => |c| Box::pin(c.clean_firefox_cache()),
+            "firefox_leftovers" => |c| Box::pin(c.clean_firefox_leftovers()),
+            "site_storage" => |c| Box::pin(c.clean_site_storage()),
+            "brave_cache" => |c| Box::pin(c.clean_brave_cache()),
+
+            "pycache" => |c| Box::pin(c.clean_python_cache()),
+            "pip_cache" => |c| Box::pin(c.clean_pip_cache()),
+            "vim" => |c| Box::pin(c.clean_vim()),
+            "backup_files" => |c| Box::pin(c.clean_backup_files()),
+            "doc_temp" => |c| Box::pin(c.clean_doc_temp()),
+            "screen_leftovers" => |c| Box::pin(c.clean_screen_leftovers()),
+            "xcode" => |c| Box::pin(c.clean_xcode()),
+
+            "apt" => |c| Box::pin(c.clean_apt()),
+            "dnf" => |c| Box::pin(c.clean_dnf()),
+            "flatpak" => |c| Box::pin(c.clean_flatpak()),
+            "brew" => |c| Box::pin(c.clean_homebrew()),
+
+            "duplicates" => |c| Box::pin(c.clean_duplicates()),
+            "large_downloads" => |c| Box::pin(c.clean_large_downloads()),
+            "empty_dirs" => |c| Box::pin(c.clean_empty_dirs()),
+            "wipe_free_space" => |c| Box::pin(c.clean_wipe_free_space()),
+            "broken_symlinks" => |c| Box::pin(c.clean_broken_symlinks()),
+            "cachedir_sweep" => |c| Box::pin(c.clean_tagged_caches()),
+            _ => return None,
+        };
+        Some(handler)
+    }
+
+    pub fn has_handler(id: &str) -> bool {
+        Self::handler(id).is_some()
+    }
+
+    // panic בתוך מטפל (unwrap על נתיב מוזר) נהיה כישלון של הפריט הזה בלבד, עם שורה אדומה בלוג;
+    // שאר הפריטים ממשיכים, וה-worker מגיע לסוף הריצה כרגיל
+    async fn catch_item_panic(&self, item: LocalBoxFuture<'_, CleanResult>) -> CleanResult {
+        match std::panic::AssertUnwindSafe(item).catch_unwind().await {
+            Ok(result) => result,
+            Err(payload) => {
+                let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                self.log_sync(&tr_args("log.item_panicked", &[&self.item_id, &message]));
+                Err(format!("{} crashed: {}", self.item_id, message).into())
+            }
+        }
+    }
+
+    pub async fn run_item(&self, id: &str) -> CleanResult {
+        let user = self.user.as_ref().map(|u| u.name.clone());
+        self.emit(CleanEvent::ItemStart { id: id.to_string(), user: user.clone() });
+        let before = self.get_stats_sync();
+        let started = Instant::now();
+        let paused_before = self.pause.paused_for();
+        let mut this = self.clone();
+        this.item_id = id.to_string();
+        let result = match Self::handler(id) {
+            Some(handler) => this.catch_item_panic(handler(&this)).await,
+            None if self.custom.iter().any(|c| c.id == id) => this.catch_item_panic(Box::pin(this.clean_custom(id))).await,
+            None => {
+                self.log(&tr_args("log.unknown_item", &[id])).await;
+                Err(format!("no cleaner for item '{}'", id).into())
+            }
+        };
+        let duration = started.elapsed().saturating_sub(self.pause.paused_for().saturating_sub(paused_before));
+        *self.durations.lock().unwrap().entry(id.to_string()).or_default() += duration;
+        let after = self.get_stats_sync();
+        let skipped = result.as_ref().err().and_then(|e| e.downcast_ref::<Skipped>()).map(|s| s.0.clone());
+        let failed = result.is_err() && skipped.is_none();
+        self.emit(CleanEvent::ItemDone {
+            id: id.to_string(),
+            user,
+            files: after.files_deleted - before.files_deleted,
+            bytes: after.bytes_freed - before.bytes_freed,
+            errors: after.errors - before.errors + failed as u64,
+            skipped,
+            duration_ms: duration.as_millis() as u64,
+        });
+        result
+    }
+
+    // פריט מוגדר-משתמש: אותם Directory/Pattern כמו בפריטים המובנים, עם גיל מינימלי משלו
+    async fn clean_custom(&self, id: &str) -> CleanResult {
+        let custom = match self.custom.iter().find(|c| c.id == id) {
+            Some(custom) => custom.clone(),
+            None => return Err(format!("no cleaner for item '{}'", id).into()),
+        };
+        if let Some(hook) = &custom.hook {
+            return self.run_hook(&custom.name, hook).await;
+        }
+        self.log(&tr_args("log.custom", &[&custom.name])).await;
+        // נבדק כבר בטעינה; בודקים שוב כי ~ יכול להצביע למקום אחר כשרצים כ-root
+        if let Some(path) = custom.targets().iter().find_map(|t| match t {
+            Target::Directory(dir) | Target::Pattern { root: dir, .. } if is_protected_path(dir) => Some(dir.clone()),
+            _ => None,
+        }) {
+            return Err(format!("{} is a protected path", path.display()).into());
+        }
+        let cleaner = match custom.min_age() {
+            Some(min_age) => self.clone().with_min_age(self.min_age.max(Some(min_age))),
+            None => self.clone(),
+        };
+        cleaner.clean_targets(id, "Custom").await
+    }
+
+    // hook מ-hooks.d: בתצוגה מקדימה `scan` ומה שהוא מדווח נספר; בניקוי `clean` מוחק בעצמו
+    // ומדפיס כמה פינה. יציאה לא-אפס = הפריט נכשל
+    async fn run_hook(&self, name: &str, hook: &Path) -> CleanResult {
+        hooks::check(hook).map_err(|e| format!("{}: {}", hook.display(), e))?;
+        self.log(&tr_args("log.hook", &[name])).await;
+        let mode = if self.dry_run { "scan" } else { "clean" };
+        let lines = self.run_command_output(&[hook.display().to_string(), mode.to_string()], true).await?;
+        if self.dry_run {
+            let (entries, bad) = hooks::parse_scan(&lines);
+            for line in &bad {
+                self.log(&format!("  {}", line)).await;
+            }
+            if !bad.is_empty() {
+                self.log(&tr_args("log.hook_bad_lines", &[name, &bad.len().to_string()])).await;
+            }
+            let bytes: u64 = entries.iter().map(|(size, _)| size).sum();
+            for (size, path) in &entries {
+                self.log(&format!("  {}  {}", Self::format_bytes(*size), path.display())).await;
+            }
+            self.record_freed(entries.len() as u64, bytes);
+            self.log(&tr_args("log.hook_scan", &[name, &Self::format_bytes(bytes), &entries.len().to_string()])).await;
+            return Ok(());
+        }
+        for line in &lines {
+            self.log(&format!("  {}", line)).await;
+        }
+        match hooks::parse_clean(&lines) {
+            Some(bytes) => {
+                self.record_freed(0, bytes);
+                self.log(&tr_args("log.hook_freed", &[name, &Self::format_bytes(bytes)])).await;
+            }
+            None => self.log(&tr_args("log.hook_no_total", &[name])).await,
+        }
+        Ok(())
+    }
+
+    // === System Cleaning ===
+
+    pub async fn clean_temp_files(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.clean_targets("tmp", "System").await
+    }
+
+    // בזמן session גרפי, תיקיות שהשולחן עבודה מחזיק (gdm, dconf, keyring, ה-compositor) לא מרוקנות
+    #[cfg(not(target_os = "macos"))]
+    pub async fn clean_system_cache(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let protected = self.session_protected_dirs();
+        if !protected.is_empty() {
+            for dir in &protected {
+                self.log(&tr_args("log.session_protected", &[&dir.display().to_string()])).await;
+            }
+            let measured = protected.clone();
+            let (_, bytes) = tokio::task::spawn_blocking(move || dir_usage(&measured)).await?;
+            self.add_summary_note(tr_args("summary.session_protected", &[&Self::format_bytes(bytes)]));
+        }
+        for target in self.targets("var_cache") {
+            match target {
+                Target::Directory(dir) => {
+                    let options = CleanOptions { skip: protected.clone(), ..self.clean_options() };
+                    self.clean_directory_contents(dir, "System", options).await?;
+                }
+                other => self.clean_target(other, "System").await?,
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn session_protected_dirs(&self) -> Vec<PathBuf> {
+        if !self.session_protection || self.session_protected.is_empty() {
+            return Vec::new();
+        }
+        // clean_directory_contents עובר על השורש הקנוני - גם הדילוגים צריכים להיות תחתיו
+        let cache = canonical_root(&self.paths.cache);
+        let Ok(entries) = fs::read_dir(&cache) else { return Vec::new() };
+        let mut dirs: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| self.session_protected.iter().any(|pattern| name_matches(&e.file_name(), pattern)))
+            .map(|e| cache.join(e.file_name()))
+            .collect();
+        dirs.sort();
+        dirs
+    }
+
+    // ~/Library/Caches: תיקייה לכל אפליקציה (com.apple.Safari, Google...) - כל אחת בשורה משלה עם הגודל
+    #[cfg(target_os = "macos")]
+    pub async fn clean_system_cache(&self) -> Result<(), Box<dyn std::error::Error>> {
+        for target in self.targets("var_cache") {
+            let Target::Directory(root) = target else { continue };
+            let apps = match fs::read_dir(&root) {
+                Ok(apps) => apps,
+                Err(e) => {
+                    if is_protected_by_os(&e) {
+                        self.report_protected(&root);
+                    }
+                    continue;
+                }
+            };
+            let mut apps: Vec<PathBuf> = apps.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()).collect();
+            apps.sort();
+            for app in apps {
+                if self.checkpoint_async().await {
+                    break;
+                }
+                let before = self.get_stats_sync();
+                self.clean_directory_contents(&app, "System", self.clean_options_all()).await?;
+                let after = self.get_stats_sync();
+                if after.files_deleted > before.files_deleted {
+                    self.log(&tr_args("log.tool_target", &[
+                        &display_name(&app),
+                        &Self::format_bytes(after.bytes_freed - before.bytes_freed),
+                        &(after.files_deleted - before.files_deleted).to_string(),
+                    ])).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // תצוגה מקדימה ממלאת גם את העץ: מיקום מקורי ותאריך מחיקה מה-.trashinfo, לפי הדיסק.
+    // ניקוי אחרי תצוגה מקדימה מוחק רק את מה שנשאר מסומן; בלעדיה - את כל הפח, כמו קודם
+    #[cfg(not(windows))]
+    pub async fn clean_trash(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.trash")).await;
+        if self.dry_run {
+            self.clean_targets("trash", "Trash").await?;
+            let this = self.clone();
+            let entries = tokio::task::spawn_blocking(move || this.find_trashed_items()).await?;
+            // ב-macOS אין files/ ו-info/ - בלי רשימה, והניקוי מרוקן הכול
+            if !entries.is_empty() {
+                self.preview.set("trash", entries);
+            }
+            return Ok(());
+        }
+        if self.preview.selected("trash").is_none() {
+            return self.clean_targets("trash", "Trash").await;
+        }
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let selected = this.current_selection("trash", || this.find_trashed_items()).unwrap_or_default();
+            this.remove_trashed_items(selected)
+        })
+        .await?;
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    fn find_trashed_items(&self) -> Vec<PreviewEntry> {
+        let mounts = self.mounts.get_or_init(mounts::read_mounts);
+        let mut found = Vec::new();
+        for target in self.targets("trash") {
+            let Target::Directory(dir) = target else { continue };
+            let volume = mounts::mount_for(&dir, mounts).map_or_else(|| dir.clone(), |m| m.mount_point.clone());
+            let group = tr_args("preview.trash_volume", &[&volume.display().to_string()]);
+            for item in trash::list(&dir, &volume) {
+                if self.is_excluded(&item.path) {
+                    continue;
+                }
+                let Ok(metadata) = fs::symlink_metadata(&item.path) else { continue };
+                let size = if metadata.is_dir() {
+                    WalkDir::new(&item.path).into_iter().filter_map(|e| e.ok()).filter_map(|e| e.metadata().ok()).map(|m| allocated_bytes(&m)).sum()
+                } else {
+                    allocated_bytes(&metadata)
+                };
+                let entry = PreviewEntry::new(item.path, size, metadata.modified().ok()).with_group(group.clone());
+                found.push(match item.info {
+                    Some(info) => {
+                        let original = escape_name(info.original.as_os_str());
+                        let title = match info.deleted {
+                            Some(deleted) => tr_args("preview.trashed", &[&original, &deleted.format("%Y-%m-%d").to_string(), &Self::format_bytes(size)]),
+                            None => tr_args("preview.trashed_undated", &[&original, &Self::format_bytes(size)]),
+                        };
+                        entry.with_title(title)
+                    }
+                    None => entry.with_warning(tr("preview.trash_no_info")),
+                });
+            }
+        }
+        found
+    }
+
+    // קובץ או symlink נמחק כמו שהוא (בלי לעקוב ליעד); תיקייה - כולה. ה-.trashinfo הולך איתם
+    #[cfg(not(windows))]
+    fn remove_trashed_items(&self, selected: Vec<PreviewEntry>) {
+        let mut removed = HashSet::new();
+        for entry in selected {
+            if self.checkpoint() {
+                break;
+            }
+            if self.is_excluded(&entry.path) {
+                continue;
+            }
+            let Ok(metadata) = fs::symlink_metadata(&entry.path) else { continue };
+            if metadata.is_dir() {
+                self.remove_tree(&entry.path);
+            } else if self.remove_file(&entry.path, &metadata, false) {
+                self.report_deleted(&entry.path, &metadata);
+            }
+            if fs::symlink_metadata(&entry.path).is_ok() {
+                continue;
+            }
+            if let Some(info_path) = trash::info_path_for(&entry.path) {
+                if let Ok(info_metadata) = fs::symlink_metadata(&info_path) {
+                    if self.remove_file(&info_path, &info_metadata, false) {
+                        self.report_deleted(&info_path, &info_metadata);
+                    }
+                }
+            }
+            removed.insert(entry.path);
+        }
+        self.preview.remove_paths("trash", &removed);
+        self.finish_batch();
+    }
+
+    // SHEmptyRecycleBinW מרוקן את הסל של כל הכוננים; הגודל נמדד לפני, כי אחרי אין מה לספור
+    #[cfg(windows)]
+    pub async fn clean_trash(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.trash")).await;
+        let (files, bytes) = crate::win::recycle_bin_usage()?;
+        if files == 0 {
+            return Ok(());
+        }
+        if !self.dry_run {
+            crate::win::empty_recycle_bin()?;
+        }
+        self.record_freed(files, bytes);
+        Ok(())
+    }
+
+    pub async fn clean_logs(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // Rotated logs and application logs
+        self.log(&tr("log.logs")).await;
+        self.clean_targets("logs", "Logs").await
+    }
+
+    pub async fn clean_thumbnails(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.thumbnails")).await;
+        self.clean_targets("thumbnails", "Thumbnails").await
+    }
+
+    // כל cache כאן נמחק רק כשהוא ישן ממקורותיו (is_stale_relative_to) - מחיקה של cache עדכני
+    // רק גורמת לבנייה מחדש איטית בכניסה הבאה. מה שנמחק נבנה מחדש מיד אחר כך
+    pub async fn clean_font_caches(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.fonts")).await;
+        let mut rebuild = Vec::new();
+        let mut icon_dirs = Vec::new();
+        let mut mime_dirs = Vec::new();
+        let mut fonts_cleaned = false;
+        let font_sources = self.font_source_dirs();
+        for target in self.targets("fonts") {
+            match target {
+                Target::Directory(dir) if !dir.exists() => {}
+                Target::Directory(dir) if !is_stale_relative_to(&dir, &font_sources) => self.report_up_to_date(&dir),
+                Target::Directory(dir) if dir.starts_with(&self.paths.system_cache) && !is_root() => self.report_needs_root(&dir),
+                Target::Directory(dir) => {
+                    self.clean_directory_contents(dir, "Fonts", self.clean_options_all()).await?;
+                    fonts_cleaned = true;
+                }
+                Target::ScanOnly(root) if root == self.paths.data.join("mime") => mime_dirs.push(root),
+                Target::ScanOnly(root) => icon_dirs.push(root),
+                Target::Command(argv) => rebuild.push(argv),
+                _ => {}
+            }
+        }
+
+        let this = self.clone();
+        let themes = tokio::task::spawn_blocking(move || this.remove_stale_icon_caches(&icon_dirs)).await?;
+        let this = self.clone();
+        let mime_dirs = tokio::task::spawn_blocking(move || this.remove_stale_mime_caches(&mime_dirs)).await?;
+
+        // בתצוגה מקדימה רק מודדים - בלי לבנות מחדש
+        if self.dry_run {
+            return Ok(());
+        }
+        // fc-cache -f על cache עדכני הוא בדיוק הבנייה מחדש שחוסכים
+        if fonts_cleaned {
+            for argv in rebuild {
+                if which(&argv[0]).is_ok() {
+                    self.run_command(&argv).await?;
+                }
+            }
+        }
+        let rebuilds = [("gtk-update-icon-cache", &["-f", "-t"][..], themes), ("update-mime-database", &[][..], mime_dirs)];
+        for (program, args, dirs) in rebuilds {
+            if which(program).is_err() {
+                continue;
+            }
+            for dir in dirs {
+                let argv = std::iter::once(program).chain(args.iter().copied()).map(str::to_string)
+                    .chain(std::iter::once(dir.display().to_string()))
+                    .collect::<Vec<_>>();
+                self.run_command(&argv).await?;
+            }
+        }
+        Ok(())
+    }
+
+    // התיקיות שמהן fontconfig בונה את ה-cache של המשתמש
+    fn font_source_dirs(&self) -> Vec<PathBuf> {
+        vec![self.paths.data.join("fonts"), self.paths.home.join(".fonts"), self.paths.system_share.join("fonts")]
+    }
+
+    fn report_up_to_date(&self, cache: &Path) {
+        if cache.exists() {
+            self.log_sync(&tr_args("log.cache_up_to_date", &[&cache.display().to_string()]));
+        }
+    }
+
+    // מחזיר את תיקיות הערכות שה-cache שלהן נמחק (לבנייה מחדש)
+    fn remove_stale_icon_caches(&self, roots: &[PathBuf]) -> Vec<PathBuf> {
+        let mut themes = Vec::new();
+        for root in roots {
+            let Ok(entries) = fs::read_dir(root) else { continue };
+            self.touch(root);
+            for theme in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+                if self.checkpoint() {
+                    return themes;
+                }
+                let cache = theme.join("icon-theme.cache");
+                if self.remove_stale_cache(&cache, std::slice::from_ref(&theme)) {
+                    themes.push(theme);
+                }
+            }
+        }
+        self.finish_batch();
+        themes
+    }
+
+    // mime.cache מול packages/*.xml. מחזיר את תיקיות ה-mime שצריך לבנות מחדש
+    fn remove_stale_mime_caches(&self, roots: &[PathBuf]) -> Vec<PathBuf> {
+        let mut rebuilt = Vec::new();
+        for root in roots {
+            if self.checkpoint() {
+                break;
+            }
+            self.touch(root);
+            if self.remove_stale_cache(&root.join("mime.cache"), &[root.join("packages")]) {
+                rebuilt.push(root.clone());
+            }
+        }
+        self.finish_batch();
+        rebuilt
+    }
+
+    // true = ה-cache נמחק (או היה נמחק בתצוגה מקדימה)
+    fn remove_stale_cache(&self, cache: &Path, sources: &[PathBuf]) -> bool {
+        let Ok(metadata) = fs::symlink_metadata(cache) else { return false };
+        if !is_stale_relative_to(cache, sources) {
+            self.report_up_to_date(cache);
+            return false;
+        }
+        if !cache.parent().is_some_and(is_writable_dir) {
+            self.report_needs_root(cache);
+            return false;
+        }
+        if !self.remove_file(cache, &metadata, false) {
+            return false;
+        }
+        self.report_deleted(cache, &metadata);
+        true
+    }
+    pub async fn clean_clipboard(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if which("xclip").is_err() {
+            return Err(skip("not installed"));
+        }
+        if !self.dry_run {
+            self.log(&tr("log.clipboard")).await;
+        }
+        self.clean_targets("clipboard", "Privacy").await
+    }
+
+    pub async fn clean_recent_docs(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.clean_targets("recent_docs", "Privacy").await
+    }
+
+    // סימניות ותצוגות של תיקיות שנמחקו. יומני gvfs-metadata גדלים בלי גבול ונוצרים מחדש
+    pub async fn clean_file_manager_leftovers(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.fm_leftovers")).await;
+        for target in self.targets("fm_leftovers") {
+            match target {
+                Target::ScanOnly(path) if path.file_name() == Some(OsStr::new("bookmarks")) => {
+                    let this = self.clone();
+                    tokio::task::spawn_blocking(move || this.prune_gtk_bookmarks(&path)).await?;
+                }
+                Target::ScanOnly(local) => {
+                    let this = self.clone();
+                    tokio::task::spawn_blocking(move || this.prune_view_properties(&local)).await?;
+                }
+                target => self.clean_target(target, "Privacy").await?,
+            }
+        }
+        Ok(())
+    }
+
+    fn prune_gtk_bookmarks(&self, path: &Path) {
+        if self.is_excluded(path) {
+            return;
+        }
+        let Ok(contents) = fs::read(path) else { return };
+        self.touch(path.parent().unwrap_or(path));
+        // הנתיבים בסימניות אמיתיים; ב-chroot בודקים אותם תחת השורש שלו
+        let (kept, removed) = file_manager::prune_bookmarks(&contents, |p| self.under_root(p).exists());
+        if removed.is_empty() {
+            return;
+        }
+        if !self.dry_run {
+            if let Err(e) = persist::write_atomically(path, &kept) {
+                self.log_sync(&tr_args("log.bookmarks_failed", &[&path.display().to_string(), &e.to_string()]));
+                self.record_error();
+                return;
+            }
+        }
+        for dead in &removed {
+            self.log_sync(&tr_args("log.dead_bookmark", &[&dead.display().to_string()]));
+        }
+        // שורות ולא קבצים - רק הבייטים נספרים
+        self.record_freed(0, (contents.len() - kept.len()) as u64);
+    }
+
+    fn prune_view_properties(&self, local: &Path) {
+        if !local.exists() {
+            return;
+        }
+        self.touch(local);
+        for dead in file_manager::dead_view_properties(local, &self.paths.root) {
+            if self.checkpoint() {
+                break;
+            }
+            if self.is_excluded(&dead) {
+                continue;
+            }
+            let shown = dead.strip_prefix(local).map(|p| Path::new("/").join(p)).unwrap_or_else(|_| dead.clone());
+            self.log_sync(&tr_args("log.dead_view_properties", &[&shown.display().to_string()]));
+            for entry in WalkDir::new(&dead).contents_first(true).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+                let Ok(metadata) = entry.metadata() else { continue };
+                if metadata.is_dir() {
+                    if !self.dry_run {
+                        let _ = fs::remove_dir(entry.path());
+                    }
+                } else if self.remove_file(entry.path(), &metadata, false) {
+                    self.report_deleted(entry.path(), &metadata);
+                }
+            }
+        }
+        self.finish_batch();
+    }
+
+    // נתיב מוחלט של המערכת האמיתית -> אותו נתיב תחת SystemPaths::root
+    fn under_root(&self, absolute: &Path) -> PathBuf {
+        self.paths.root.join(absolute.strip_prefix("/").unwrap_or(absolute))
+    }
+
+    // קיצורי דרך שהתוכנה שלהם (TryExec, או המילה הראשונה של Exec) כבר לא מותקנת.
+    // אחרי מחיקה - מרעננים את מסד הנתונים של התפריט ואת ה-MIME, אחרת הם מפנים לקבצים שנמחקו עד הכניסה הבאה
+    pub async fn clean_broken_desktop_files(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.broken_desktop")).await;
+        let this = self.clone();
+        let removed = tokio::task::spawn_blocking(move || this.remove_broken_desktop_files()).await?;
+        if removed > 0 {
+            self.refresh_desktop_databases().await;
+        }
+        Ok(())
+    }
+
+    fn remove_broken_desktop_files(&self) -> u64 {
+        let search_path = self.program_search_path();
+        let mut removed = 0;
+        for target in self.targets("broken_desktop") {
+            let Target::Pattern { root, .. } = target else { continue };
+            if !root.exists() {
+                continue;
+            }
+            self.touch(&root);
+            for entry in WalkDir::new(&root).into_iter().filter_entry(|e| !self.is_excluded(e.path())).filter_map(|e| e.ok()) {
+                if self.checkpoint() {
+                    break;
+                }
+                let path = entry.path();
+                if !entry.file_type().is_file() || path.extension() != Some(OsStr::new("desktop")) {
+                    continue;
+                }
+                let Some(stale) = autostart::stale_entry(path, &search_path, &self.paths.root) else { continue };
+                let Ok(metadata) = entry.metadata() else { continue };
+                if self.remove_file(path, &metadata, false) {
+                    self.log_sync(&tr_args("log.broken_desktop_removed", &[&stale.name, &stale.program]));
+                    self.report_deleted(path, &metadata);
+                    removed += 1;
+                }
+            }
+        }
+        self.finish_batch();
+        removed
+    }
+
+    // כלי שלא מותקן - אין מה לרענן. כישלון נרשם בלוג ובסיכום, אבל הפריט עצמו הצליח: הקבצים כבר נמחקו
+    async fn refresh_desktop_databases(&self) {
+        let data = &self.paths.data;
+        for (program, dir) in [("update-desktop-database", data.join("applications")), ("update-mime-database", data.join("mime"))] {
+            if which(program).is_err() || !dir.is_dir() {
+                continue;
+            }
+            if self.dry_run {
+                self.log(&tr_args("log.refresh_would_run", &[program, &dir.display().to_string()])).await;
+                self.add_summary_note(tr_args("summary.refresh_would_run", &[program]));
+                continue;
+            }
+            let argv = vec![program.to_string(), dir.display().to_string()];
+            match self.run_command(&argv).await {
+                Ok(()) => self.add_summary_note(tr_args("summary.refresh_done", &[program])),
+                Err(e) => {
+                    self.log(&format!("{}: {}", tr("log.error"), e)).await;
+                    self.add_summary_note(tr_args("summary.refresh_failed", &[program]));
+                }
+            }
+        }
+    }
+
+    // === Super Cleaner data ===
+
+    // ההיסטוריה, רשימות המחיקות וההסגר של התוכנה עצמה. config.toml נמצא ב-config_dir ולא כאן,
+    // ורשימת המחיקות וההסגר של הריצה הנוכחית לא נמחקים
+    pub async fn clean_own_data(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = self.paths.data.join(APP_DIR_NAME);
+        if !dir.is_dir() {
+            return Err(skip("no app data yet"));
+        }
+        let size: u64 = WalkDir::new(&dir).into_iter().filter_map(|e| e.ok()).filter_map(|e| e.metadata().ok()).filter(|m| m.is_file()).map(|m| allocated_bytes(&m)).sum();
+        self.log(&tr_args("log.own_data", &[&dir.display().to_string(), &Self::format_bytes(size)])).await;
+        self.touch(&dir);
+        let active_quarantine = match &self.strategy {
+            DeletionStrategy::Quarantine(run) => Some(run.run_id.clone()),
+            _ => None,
+        };
+        // מה שנמחק מההסגר לא עובר להסגר של הריצה הזו
+        let this = self.clone().with_strategy(DeletionStrategy::Delete);
+        tokio::task::spawn_blocking(move || {
+            this.trim_history(&dir.join(history::HISTORY_FILE));
+            this.remove_old_manifests(&dir.join(manifest::MANIFESTS_DIR));
+            this.purge_quarantine(&dir.join(quarantine::QUARANTINE_DIR), active_quarantine.as_deref());
+            this.finish_batch();
+        })
+        .await?;
+        Ok(())
+    }
+
+    // נכתב מחדש בשלמותו - לא נספר כקובץ שנמחק, רק המקום שהתפנה
+    fn trim_history(&self, path: &Path) {
+        let Ok(content) = fs::read_to_string(path) else { return };
+        let Some(kept) = history::trim_to_last(&content, self.own_data.history_max_runs) else { return };
+        let dropped = content.lines().filter(|line| !line.trim().is_empty()).count() - self.own_data.history_max_runs;
+        let freed = (content.len() - kept.len()) as u64;
+        if !self.dry_run {
+            if let Err(e) = persist::write_atomically(path, kept.as_bytes()) {
+                self.log_sync(&format!("{}: {}", tr("log.error"), e));
+                self.record_error();
+                return;
+            }
+        }
+        self.log_sync(&tr_args("log.history_trimmed", &[&dropped.to_string(), &Self::format_bytes(freed)]));
+        self.record_freed(0, freed);
+    }
+
+    fn remove_old_manifests(&self, dir: &Path) {
+        let days = self.own_data.manifest_max_age_days;
+        let Ok(entries) = fs::read_dir(dir) else { return };
+        if days == 0 {
+            return;
+        }
+        let cutoff = SystemTime::now().checked_sub(Duration::from_secs(days * 24 * 60 * 60)).unwrap_or(SystemTime::UNIX_EPOCH);
+        let active = self.manifest.as_ref().and_then(|m| m.path().file_name().map(OsStr::to_os_string));
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.extension() != Some(OsStr::new("csv")) || active.as_deref() == path.file_name() {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else { continue };
+            if metadata.is_file() && metadata.modified().is_ok_and(|m| m < cutoff) && self.remove_file(&path, &metadata, false) {
+                self.report_deleted(&path, &metadata);
+            }
+        }
+    }
+
+    // אותם כללים כמו הניקוי האוטומטי בהפעלה (quarantine::purge_expired)
+    fn purge_quarantine(&self, root: &Path, active: Option<&str>) {
+        let runs = quarantine::list_runs_in(root).into_iter().filter(|run| Some(run.run_id.as_str()) != active).collect();
+        let policy = &self.own_data;
+        for run in quarantine::expired_runs(runs, policy.quarantine_max_age_days, policy.quarantine_max_bytes, Local::now()) {
+            if self.checkpoint() {
+                return;
+            }
+            self.log_sync(&tr_args("log.quarantine_expired", &[&run.run_id, &Self::format_bytes(run.bytes)]));
+            self.remove_tree(&run.dir);
+        }
+    }
+
+    // תיקיות מבניות שגם כשהן ריקות - צריכות להישאר (XDG, סל המחזור)
+    fn structural_dirs(&self) -> HashSet<PathBuf> {
+        let home = self.get_home_dir();
+        let mut dirs: HashSet<PathBuf> = [
+            dirs::desktop_dir(),
+            dirs::document_dir(),
+            dirs::download_dir(),
+            dirs::audio_dir(),
+            dirs::picture_dir(),
+            dirs::public_dir(),
+            dirs::template_dir(),
+            dirs::video_dir(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        for sub in ["", "/files", "/info", "/expunged"] {
+            dirs.insert(PathBuf::from(format!("{}/Trash{}", self.paths.data.display(), sub)));
+        }
+        dirs.insert(home);
+        dirs
+    }
+
+    // סריקה רגילה (הורה לפני ילדים), כדי ש-filter_entry באמת יגזום: עם contents_first הילדים כבר
+    // יצאו כשההורה נדחה, והדחייה מדלגת על שאר האחים שלו. אחר כך מלמטה למעלה - תיקייה שיש בה
+    // רק תיקיות ריקות ריקה גם היא. התוצאה מסודרת מלמטה למעלה.
+    fn find_empty_dirs(&self, root: &Path) -> Vec<PathBuf> {
+        let structural = self.structural_dirs();
+        let own_data = app_data_dir();
+        let non_empty: RefCell<HashSet<PathBuf>> = RefCell::new(HashSet::new());
+        let mark_parent = |path: &Path| {
+            if let Some(parent) = path.parent() {
+                non_empty.borrow_mut().insert(parent.to_path_buf());
+            }
+        };
+
+        let mut dirs = Vec::new();
+        let walker = WalkDir::new(root)
+            .min_depth(1)
+            .follow_links(false)
+            .same_file_system(true)
+            .into_iter()
+            .filter_entry(|e| {
+                let hidden_root = e.depth() == 1 && e.file_name().as_encoded_bytes().starts_with(b".");
+                let skip = (hidden_root && !self.empty_dirs_include_hidden)
+                    || e.path().starts_with(&own_data)
+                    || self.is_excluded(e.path());
+                // מה שלא נסרק נחשב "תוכן" - ההורה שלו לא ריק
+                if skip {
+                    mark_parent(e.path());
+                }
+                !skip
+            });
+        for entry in walker {
+            if self.checkpoint() {
+                return Vec::new();
+            }
+            let entry = match entry {
+                Ok(entry) => entry,
+                // אין הרשאה וכו' - לא יודעים מה יש שם, אז לא נוגעים
+                Err(e) => {
+                    if let Some(path) = e.path() {
+                        non_empty.borrow_mut().insert(path.to_path_buf());
+                        mark_parent(path);
+                    }
+                    continue;
+                }
+            };
+            let path = entry.path();
+            // symlink (גם לתיקייה) נחשב תוכן - לא עוקבים אחריו ולא מוחקים את ההורה
+            if !entry.file_type().is_dir() || structural.contains(path) {
+                mark_parent(path);
+                continue;
+            }
+            dirs.push(entry.into_path());
+        }
+
+        // בסדר ההפוך כל תיקייה מגיעה אחרי כל מה שמתחתיה
+        let mut non_empty = non_empty.into_inner();
+        let mut empty = Vec::new();
+        for dir in dirs.into_iter().rev() {
+            if non_empty.contains(&dir) {
+                if let Some(parent) = dir.parent() {
+                    non_empty.insert(parent.to_path_buf());
+                }
+                continue;
+            }
+            empty.push(dir);
+        }
+        empty
+    }
+
+    pub async fn clean_empty_dirs(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.empty_dirs")).await;
+        let home = self.get_home_dir();
+        self.touch(&home);
+        let this = self.clone();
+        let empty = tokio::task::spawn_blocking(move || this.find_empty_dirs(&home)).await?;
+
+        if self.dry_run {
+            self.log(&tr_args("log.empty_dirs_found", &[&empty.len().to_string()])).await;
+            for dir in empty.iter().take(10) {
+                self.log(&format!("  {}", escape_name(dir.as_os_str()))).await;
+            }
+            if empty.len() > 10 {
+                self.log(&tr_args("log.and_more", &[&(empty.len() - 10).to_string()])).await;
+            }
+            self.live.directories_cleaned.fetch_add(empty.len() as u64, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        for dir in empty {
+            if self.checkpoint_async().await {
+                break;
+            }
+            // remove_dir נכשל אם משהו נוצר שם בינתיים - בדיוק מה שרוצים
+            if fs::remove_dir(&dir).is_ok() {
+                self.live.directories_cleaned.fetch_add(1, Ordering::Relaxed);
+                self.log(&tr_args("log.empty_dir_removed", &[&escape_name(dir.as_os_str())])).await;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn clean_broken_symlinks(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.broken_symlinks")).await;
+        let home = self.get_home_dir();
+        self.touch(&home);
+        let this = self.clone();
+        run_blocking(move || {
+            this.clean_from_preview("broken_symlinks", || this.find_broken_symlinks(&home), |entry| this.remove_broken_symlink(entry))
+        })
+        .await
+    }
+
+    // follow_links(false): לולאות של links לא נסרקות בכלל, ו-metadata על לולאה מחזיר ELOOP ולא NotFound
+    fn find_broken_symlinks(&self, root: &Path) -> Vec<PreviewEntry> {
+        let own_data = app_data_dir();
+        // עיגון אחר מתחת לבית (למשל ה-lower של ecryptfs) - לא שלנו לנקות
+        let root_device = fs::metadata(root).ok().filter(|_| self.same_filesystem).map(|m| (self.device_id)(root, &m));
+        let mut found = Vec::new();
+        for entry in WalkDir::new(root).follow_links(false).into_iter()
+            .filter_entry(|e| !e.path().starts_with(&own_data) && !self.is_excluded(e.path()) && !self.crosses_device(e, root_device))
+            .filter_map(|e| e.ok()) {
+            if self.checkpoint() {
+                return Vec::new();
+            }
+            if !entry.path_is_symlink() {
+                continue;
+            }
+            if let Some(target) = self.broken_link_target(entry.path()) {
+                let modified = fs::symlink_metadata(entry.path()).and_then(|m| m.modified()).ok();
+                let detail = escape_name(target.as_os_str());
+                found.push(PreviewEntry::new(entry.into_path(), 0, modified).with_detail(detail));
+            }
+        }
+        found
+    }
+
+    // היעד המלא של link שבור, או None אם הוא תקין / לא link / מדולג
+    fn broken_link_target(&self, link: &Path) -> Option<PathBuf> {
+        if !fs::symlink_metadata(link).ok()?.file_type().is_symlink() {
+            return None;
+        }
+        match fs::metadata(link) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            _ => return None,
+        }
+        let target = fs::read_link(link).ok()?;
+        // יעד יחסי - יחסית לתיקייה של ה-link, לא ל-cwd
+        let resolved = match link.parent() {
+            Some(parent) if target.is_relative() => parent.join(&target),
+            _ => target,
+        };
+        let removable = ["/media", "/run/media", "/mnt"].iter().any(|m| resolved.starts_with(m));
+        if removable && !self.broken_links_include_removable {
+            return None;
+        }
+        Some(resolved)
+    }
+
+    fn remove_broken_symlink(&self, entry: &PreviewEntry) -> bool {
+        // אולי היעד חזר בינתיים (דיסק חובר, קובץ שוחזר)
+        if self.broken_link_target(&entry.path).is_none() {
+            self.log_sync(&tr_args("log.changed_since_scan", &[&display_name(&entry.path)]));
+            return false;
+        }
+        let metadata = match fs::symlink_metadata(&entry.path) {
+            Ok(m) => m,
+            Err(_) => return false,
+        };
+        if self.remove_file(&entry.path, &metadata, false) {
+            self.log_sync(&tr_args("log.broken_symlink_removed", &[&display_name(&entry.path), &entry.detail]));
+            self.record_file(0);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub async fn clean_tagged_caches(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.cachedir_sweep")).await;
+        let home = self.get_home_dir();
+        let this = self.clone();
+        let found = tokio::task::spawn_blocking(move || this.find_tagged_cache_dirs(&home)).await?;
+        for dir in found {
+            if self.checkpoint_async().await {
+                break;
+            }
+            self.log(&tr_args("log.cachedir_found", &[&dir.display().to_string()])).await;
+            self.clean_directory_contents(dir, "Cache", self.clean_options_all()).await?;
+        }
+        Ok(())
+    }
+
+    // לא יורדים לתוך תיקייה מסומנת - מה שמסומן בתוכה ממילא מתרוקן איתה
+    fn find_tagged_cache_dirs(&self, root: &Path) -> Vec<PathBuf> {
+        let own_data = app_data_dir();
+        let mut found = Vec::new();
+        let mut walker = WalkDir::new(root).follow_links(false).same_file_system(true).into_iter();
+        while let Some(entry) = walker.next() {
+            if self.checkpoint() {
+                return Vec::new();
+            }
+            let Some(entry) = self.walk_entry(entry) else { continue };
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+            if entry.path().starts_with(&own_data) || self.is_excluded(entry.path()) {
+                walker.skip_current_dir();
+                continue;
+            }
+            if is_tagged_cache_dir(entry.path()) {
+                walker.skip_current_dir();
+                found.push(entry.into_path());
+            }
+        }
+        found
+    }
+
+    pub async fn clean_stale_autostart(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.stale_autostart")).await;
+        self.touch(&self.paths.config);
+        let this = self.clone();
+        run_blocking(move || {
+            this.clean_from_preview("stale_autostart", || this.find_stale_autostart(), |entry| this.remove_stale_autostart(entry))
+        })
+        .await
+    }
+
+    // איפה מחפשים פקודה בלי נתיב: ה-PATH של התהליך ועוד תיקיות שלא תמיד בו (cron, root), הכל תחת root
+    fn program_search_path(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = std::env::var_os("PATH").map(|path| std::env::split_paths(&path).collect()).unwrap_or_default();
+        dirs.extend(
+            ["/usr/local/sbin", "/usr/local/bin", "/usr/sbin", "/usr/bin", "/sbin", "/bin", "/usr/games", "/snap/bin", "/var/lib/flatpak/exports/bin"]
+                .iter()
+                .map(PathBuf::from),
+        );
+        let mut dirs: Vec<PathBuf> = dirs.iter().filter(|d| d.is_absolute()).map(|d| self.under_root(d)).collect();
+        dirs.push(self.paths.home.join(".local/bin"));
+        dirs.push(self.paths.data.join("flatpak/exports/bin"));
+        dirs
+    }
+
+    fn find_stale_autostart(&self) -> Vec<PreviewEntry> {
+        let search_path = self.program_search_path();
+        let mut found = Vec::new();
+        for target in self.targets("stale_autostart") {
+            let Target::ScanOnly(dir) = target else { continue };
+            let Ok(entries) = fs::read_dir(&dir) else { continue };
+            let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_file()).collect();
+            paths.sort();
+            for path in paths {
+                if self.is_excluded(&path) {
+                    continue;
+                }
+                let Some(stale) = autostart::stale_entry(&path, &search_path, &self.paths.root) else { continue };
+                let metadata = fs::symlink_metadata(&path).ok();
+                let mut entry = PreviewEntry::new(path, metadata.as_ref().map_or(0, |m| m.len()), metadata.and_then(|m| m.modified().ok()));
+                entry.detail = tr_args(if stale.enabled { "preview.stale_unit_enabled" } else { "preview.stale_autostart" }, &[&stale.name, &stale.program]);
+                // unit שמופעל מוצג, אבל לא מסומן - וגם אם יסמנו אותו, remove_stale_autostart לא ימחק
+                entry.selected = !stale.enabled;
+                found.push(entry);
+            }
+        }
+        found
+    }
+
+    fn remove_stale_autostart(&self, entry: &PreviewEntry) -> bool {
+        // אולי התוכנה הותקנה מחדש בינתיים, או שה-unit הופעל
+        let Some(stale) = autostart::stale_entry(&entry.path, &self.program_search_path(), &self.paths.root) else {
+            self.log_sync(&tr_args("log.changed_since_scan", &[&display_name(&entry.path)]));
+            return false;
+        };
+        if stale.enabled {
+            self.log_sync(&tr_args("log.stale_unit_enabled", &[&stale.name, &stale.program]));
+            return false;
+        }
+        let metadata = match fs::symlink_metadata(&entry.path) {
+            Ok(m) => m,
+            Err(_) => return false,
+        };
+        if self.remove_file(&entry.path, &metadata, false) {
+            self.log_sync(&tr_args("log.stale_autostart_removed", &[&stale.name, &stale.program]));
+            self.report_deleted(&entry.path, &metadata);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub async fn clean_wipe_free_space(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.wipe_free_space")).await;
+        let this = self.clone();
+        let mount = self.wipe.mount.clone();
+        run_blocking(move || this.wipe_free_space(&mount)).await
+    }
+
+    pub fn wipe_free_space(&self, mount: &Path) -> Result<(), BlockingError> {
+        let reserve = self.wipe.reserve_bytes;
+        let free = mounts::free_space(mount).ok_or_else(|| format!("cannot read free space on {}", mount.display()))?;
+        // כבר עכשיו פחות מהרזרבה - לא נוגעים בכלל
+        if free <= reserve {
+            return Err(skip_blocking(&format!("less than {} free on {}", Self::format_bytes(reserve), mount.display())));
+        }
+        let to_write = free - reserve;
+        if self.dry_run {
+            self.log_sync(&tr_args("log.wipe_would_write", &[&Self::format_bytes(to_write), &mount.display().to_string()]));
+            return Ok(());
+        }
+
+        let progress = &self.wipe.progress;
+        progress.reset();
+        progress.total.store(to_write, Ordering::Relaxed);
+        let (path, file) = create_wipe_file(mount, &self.get_home_dir())?;
+        self.own_created(&path);
+        let result = self.write_zeros(file, mount, to_write);
+        // הקובץ נמחק תמיד - גם בביטול ובשגיאה
+        let removed = fs::remove_file(&path);
+        let written = result?;
+        removed?;
+        self.log_sync(&tr_args("log.wipe_done", &[&Self::format_bytes(written), &mount.display().to_string()]));
+        Ok(())
+    }
+
+    // עד limit, ENOSPC, או שהמקום הפנוי בפועל ירד לרזרבה. מחזיר כמה נכתב
+    fn write_zeros(&self, mut file: fs::File, mount: &Path, limit: u64) -> io::Result<u64> {
+        let chunk = vec![0u8; WIPE_CHUNK];
+        let mut written = 0u64;
+        let mut chunks = 0u64;
+        while written < limit {
+            if self.checkpoint() {
+                // ביטול = עוצרים מיד, בלי fsync שעלול לקחת דקות
+                return Ok(written);
+            }
+            chunks += 1;
+            if chunks.is_multiple_of(WIPE_RECHECK_EVERY) && mounts::free_space(mount).is_some_and(|free| free <= self.wipe.reserve_bytes) {
+                break;
+            }
+            let n = chunk.len().min((limit - written) as usize);
+            match file.write(&chunk[..n]) {
+                Ok(0) => break,
+                Ok(n) => {
+                    written += n as u64;
+                    self.wipe.progress.done.fetch_add(n as u64, Ordering::Relaxed);
+                }
+                Err(e) if e.kind() == io::ErrorKind::StorageFull => break,
+                Err(e) => return Err(e),
+            }
+        }
+        file.sync_all()?;
+        Ok(written)
+    }
+
+    // כל יעד מופיע בלוג עם הגודל שלו. בזמן שאמולטור או adb רצים לא נוגעים ב-locks ובלוגים שלהם
+    pub async fn clean_android(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let home = self.get_home_dir();
+        if !home.join(".android").is_dir() {
+            return Err(skip("not installed"));
+        }
+        self.log(&tr("log.android")).await;
+        let emulator_running = is_process_running(&["emulator", "qemu-system-x86_64", "qemu-system-aarch64", "qemu-system-x86_64-headless"]);
+        let adb_running = is_process_running(&["adb"]);
+        for target in self.targets("android") {
+            if self.checkpoint_async().await {
+                break;
+            }
+            let before = self.get_stats_sync();
+            let label = target.to_string();
+            match target {
+                Target::Directory(dir) => self.clean_directory_contents(dir, "Android", self.clean_options_all()).await?,
+                Target::Pattern { root, pattern, sibling } => {
+                    if pattern.starts_with("adb.") && adb_running {
+                        self.log(&tr_args("log.android_running", &[&label])).await;
+                        continue;
+                    }
+                    self.clean_files_by_pattern(root, &pattern, sibling).await?
+                }
+                Target::ScanOnly(avd_root) => {
+                    let this = self.clone();
+                    tokio::task::spawn_blocking(move || this.clean_avd_leftovers(&avd_root, emulator_running)).await?;
+                }
+                _ => continue,
+            }
+            let after = self.get_stats_sync();
+            if after.files_deleted > before.files_deleted {
+                self.log(&tr_args("log.tool_target", &[
+                    &label,
+                    &Self::format_bytes(after.bytes_freed - before.bytes_freed),
+                    &(after.files_deleted - before.files_deleted).to_string(),
+                ])).await;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn clean_devops(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let targets: Vec<Target> = self.targets("devops").into_iter()
+            .filter(|t| match t {
+                Target::Directory(dir) | Target::Pattern { root: dir, .. } => dir.is_dir(),
+                _ => false,
+            })
+            .collect();
+        if targets.is_empty() {
+            return Err(skip("not installed"));
+        }
+        self.log(&tr("log.devops")).await;
+        let home = self.get_home_dir();
+        for target in targets {
+            if self.checkpoint_async().await {
+                break;
+            }
+            let before = self.get_stats_sync();
+            let label = target.to_string();
+            match target {
+                Target::Directory(dir) => self.clean_directory_contents(dir, "DevOps", self.clean_options_all()).await?,
+                Target::Pattern { root, .. } if AWS_TOKEN_CACHES.iter().any(|cache| root == home.join(cache)) => {
+                    let this = self.clone();
+                    tokio::task::spawn_blocking(move || this.remove_expired_aws_tokens(&root)).await?;
+                }
+                Target::Pattern { root, pattern, sibling } => self.clean_files_by_pattern(root, &pattern, sibling).await?,
+                _ => continue,
+            }
+            let after = self.get_stats_sync();
+            self.log(&tr_args("log.tool_target", &[
+                &label,
+                &Self::format_bytes(after.bytes_freed - before.bytes_freed),
+                &(after.files_deleted - before.files_deleted).to_string(),
+            ])).await;
+        }
+        Ok(())
+    }
+
+    // כל כלי בקטע משלו בלוג, עם הגודל שלו
+    pub async fn clean_lang_caches(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let sections: Vec<(&str, Vec<Target>)> = lang_cache_sections(&self.paths).into_iter()
+            .filter(|(_, targets)| targets.iter().any(is_available))
+            .collect();
+        if sections.is_empty() {
+            return Err(skip("not installed"));
+        }
+        for (tool, targets) in sections {
+            if self.checkpoint_async().await {
+                break;
+            }
+            self.log(&tr_args("log.lang_section", &[tool])).await;
+            let before = self.get_stats_sync();
+            match tool {
+                // gem cleanup לא מודד כלום - בתצוגה מקדימה מראים מה הוא היה מסיר
+                "RubyGems" if self.dry_run && which("gem").is_ok() => self.preview_gem_cleanup().await,
+                "Stack" => {
+                    let this = self.clone();
+                    let home = self.get_home_dir();
+                    tokio::task::spawn_blocking(move || this.remove_old_ghc(&home)).await?;
+                }
+                _ => {}
+            }
+            self.clean_tool_targets(targets, "Languages").await?;
+            let after = self.get_stats_sync();
+            self.log(&tr_args("log.tool_target", &[
+                tool,
+                &Self::format_bytes(after.bytes_freed - before.bytes_freed),
+                &(after.files_deleted - before.files_deleted).to_string(),
+            ])).await;
+        }
+        Ok(())
+    }
+
+    async fn preview_gem_cleanup(&self) {
+        let output = ProcessCommand::new("gem")
+            .args(["cleanup", "--dryrun"])
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output();
+        let output = match tokio::time::timeout(self.command_timeout, output).await {
+            Ok(Ok(output)) => output,
+            _ => return,
+        };
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let removals: Vec<&str> = stdout.lines().filter(|l| l.contains("Would uninstall")).collect();
+        for line in &removals {
+            self.log(&format!("  {}", line.trim())).await;
+        }
+        self.log(&tr_args("log.gem_preview", &[&removals.len().to_string()])).await;
+    }
+
+    // ~/.stack/programs/<platform>/ghc-<version>: משאירים את החדשה ביותר ואת זו שה-global project
+    // משתמש בה. אם אי אפשר לדעת מה ה-global project צריך - לא מוחקים אף GHC
+    fn remove_old_ghc(&self, home: &Path) {
+        let Some(referenced) = stack_global_ghc(home) else {
+            self.log_sync(&tr("log.stack_ghc_unknown"));
+            return;
+        };
+        let Ok(platforms) = fs::read_dir(home.join(".stack/programs")) else { return };
+        for platform in platforms.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()) {
+            let installs: Vec<(Vec<u32>, PathBuf)> = fs::read_dir(&platform).into_iter().flatten()
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .filter_map(|p| ghc_version(&p).map(|v| (v, p)))
+                .collect();
+            let Some(newest) = installs.iter().map(|(v, _)| v.clone()).max() else { continue };
+            for (version, dir) in installs {
+                if self.checkpoint() {
+                    return;
+                }
+                if version == newest || referenced.contains(&version) || self.is_excluded(&dir) {
+                    continue;
+                }
+                self.remove_tree(&dir);
+                let marker = PathBuf::from(format!("{}.installed", dir.display()));
+                if let Ok(metadata) = fs::symlink_metadata(&marker) {
+                    if self.remove_file(&marker, &metadata, false) {
+                        self.report_deleted(&marker, &metadata);
+                    }
+                }
+            }
+        }
+        self.finish_batch();
+    }
+
+    // ~/.aws/{cli,sso}/cache: קובץ JSON לכל token. נמחק רק כשהתוקף עבר; קובץ שלא מצליחים
+    // לקרוא את התוקף שלו נשאר במקומו
+    fn remove_expired_aws_tokens(&self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else { return };
+        self.touch(dir);
+        let now = chrono::Utc::now();
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if self.checkpoint() {
+                break;
+            }
+            if path.extension() != Some(OsStr::new("json")) || self.is_excluded(&path) {
+                continue;
+            }
+            let Ok(metadata) = fs::symlink_metadata(&path) else { continue };
+            if !metadata.is_file() || self.is_too_recent(&metadata) {
+                continue;
+            }
+            let expired = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| aws_token_expiry(&content))
+                .is_some_and(|expiry| expiry < now);
+            if expired && self.remove_file(&path, &metadata, false) {
+                self.report_deleted(&path, &metadata);
+            }
+        }
+        self.finish_batch();
+    }
+
+    // בכל <name>.avd: קבצי *.lock שנשארו מאמולטור שקרס, ו-snapshots ישנים מהסף (אם הוגדר)
+    fn clean_avd_leftovers(&self, avd_root: &Path, emulator_running: bool) {
+        let Ok(avds) = fs::read_dir(avd_root) else { return };
+        self.touch(avd_root);
+        let now = SystemTime::now();
+        for avd in avds.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.extension() == Some(OsStr::new("avd"))) {
+            if self.checkpoint() {
+                break;
+            }
+            let locks: Vec<PathBuf> = fs::read_dir(&avd).into_iter().flatten().filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && p.extension() == Some(OsStr::new("lock")))
+                .collect();
+            // יש lock ואמולטור רץ = ה-AVD הזה כנראה פתוח עכשיו
+            if emulator_running && !locks.is_empty() {
+                self.log_sync(&tr_args("log.android_running", &[&display_name(&avd)]));
+                continue;
+            }
+            for lock in locks {
+                if let Ok(metadata) = fs::symlink_metadata(&lock) {
+                    if self.remove_file(&lock, &metadata, false) {
+                        self.report_deleted(&lock, &metadata);
+                    }
+                }
+            }
+
+            let Some(max_age) = self.android_snapshot_age else { continue };
+            let Ok(snapshots) = fs::read_dir(avd.join("snapshots")) else { continue };
+            for snapshot in snapshots.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()) {
+                let old = fs::metadata(&snapshot)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .is_some_and(|age| age >= max_age);
+                if !old || self.is_excluded(&snapshot) {
+                    continue;
+                }
+                self.remove_tree(&snapshot);
+            }
+        }
+        self.finish_batch();
+    }
+
+    // תיקייה שלמה כולל התיקייה עצמה (snapshot, התקנת GHC) - קובץ-קובץ, כדי שייספר וייכנס להסגר
+    fn remove_tree(&self, root: &Path) {
+        for entry in WalkDir::new(root).contents_first(true).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_dir() {
+                if !self.dry_run {
+                    let _ = fs::remove_dir(entry.path());
+                }
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else { continue };
+            if self.remove_file(entry.path(), &metadata, false) {
+                self.report_deleted(entry.path(), &metadata);
+            }
+        }
+    }
+
+    // כל prefix בנפרד, עם שורת סיכום משלו. prefix שה-wineserver שלו רץ - משחק פתוח - לא נוגעים בו
+    pub async fn clean_wine(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let home = self.get_home_dir();
+        let prefixes = wine::prefixes(&home, &self.paths.data);
+        if prefixes.is_empty() {
+            return Err(skip("no Wine prefixes found"));
+        }
+        self.log(&tr("log.wine")).await;
+        let active = wine::active_prefixes(&home);
+        for prefix in prefixes {
+            if self.checkpoint_async().await {
+                break;
+            }
+            let name = display_name(&prefix);
+            if active.contains(&fs::canonicalize(&prefix).unwrap_or_else(|_| prefix.clone())) {
+                self.log(&tr_args("log.wine_in_use", &[&name])).await;
+                continue;
+            }
+            let before = self.get_stats_sync();
+            for dir in wine::cache_dirs(&prefix) {
+                self.clean_directory_contents(dir, "Wine", self.clean_options_all()).await?;
+            }
+            let after = self.get_stats_sync();
+            self.log(&tr_args("log.wine_prefix", &[
+                &name,
+                &Self::format_bytes(after.bytes_freed - before.bytes_freed),
+                &(after.files_deleted - before.files_deleted).to_string(),
+            ])).await;
+        }
+        Ok(())
+    }
+
+    // === Desktop Search Indexes ===
+
+    async fn clean_search_index(&self, id: &str, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let targets = self.targets(id);
+        let has_tool = targets.iter().any(|t| matches!(t, Target::Command(_)));
+        let dirs: Vec<PathBuf> = targets.iter()
+            .filter_map(|t| match t {
+                Target::Directory(dir) | Target::ScanOnly(dir) => Some(dir.clone()),
+                _ => None,
+            })
+            .filter(|dir| dir.exists())
+            .collect();
+        if !has_tool && dirs.is_empty() {
+            return Err(skip("not installed"));
+        }
+        self.log(&tr_args("log.search_index", &[name])).await;
+        self.clean_tool_targets(targets, "Search index").await
+    }
+
+    // יעדים מ-tool_or_dirs_targets: בלי הכלי - מחיקה רגילה; עם הכלי - הוא מנקה בעצמו ומודדים לפני ואחרי
+    async fn clean_tool_targets(&self, targets: Vec<Target>, category: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let has_tool = targets.iter().any(|t| matches!(t, Target::Command(_)));
+        if !has_tool {
+            for target in targets {
+                self.clean_target(target, category).await?;
+            }
+            return Ok(());
+        }
+        let dirs: Vec<PathBuf> = targets.iter()
+            .filter_map(|t| match t {
+                Target::ScanOnly(dir) if dir.exists() => Some(dir.clone()),
+                _ => None,
+            })
+            .collect();
+        let measure = dirs.clone();
+        let (files, bytes) = tokio::task::spawn_blocking(move || dir_usage(&measure)).await?;
+        for dir in &dirs {
+            self.touch(dir);
+        }
+        if self.dry_run {
+            self.record_freed(files, bytes);
+            return Ok(());
+        }
+        for target in targets {
+            self.clean_target(target, category).await?;
+        }
+        let (files_after, bytes_after) = tokio::task::spawn_blocking(move || dir_usage(&dirs)).await?;
+        self.record_freed(files.saturating_sub(files_after), bytes.saturating_sub(bytes_after));
+        Ok(())
+    }
+
+    // === Dev Tools ===
+
+    pub async fn clean_python_cache(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.pycache")).await;
+        // Note: "__pycache__" needs dir logic, simplified here
+        self.clean_targets("pycache", "Developer").await
+    }
+
+    pub async fn clean_pip_cache(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.pip_cache")).await;
+        self.clean_targets("pip_cache", "Developer").await
+    }
+
+    pub async fn clean_vim(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.vim")).await;
+        self.clean_targets("vim", "Developer").await
+    }
+
+    pub async fn clean_backup_files(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.backup_files")).await;
+        self.clean_targets("backup_files", "Developer").await
+    }
+
+    // כל מקור בקטע משלו בלוג, עם הגודל שלו
+    pub async fn clean_doc_temp(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.doc_temp")).await;
+        for (source, targets) in doc_temp_sections(&self.paths) {
+            if self.checkpoint_async().await {
+                break;
+            }
+            let before = self.get_stats_sync();
+            for target in targets.into_iter().filter(is_available) {
+                match target {
+                    Target::Pattern { root, pattern, .. } if pattern == EMACS_LOCK_PATTERN => {
+                        let this = self.clone();
+                        tokio::task::spawn_blocking(move || this.remove_stale_emacs_locks(&root)).await?;
+                    }
+                    target => self.clean_target(target, "Documents").await?,
+                }
+            }
+            let after = self.get_stats_sync();
+            self.log(&tr_args("log.tool_target", &[
+                source,
+                &Self::format_bytes(after.bytes_freed - before.bytes_freed),
+                &(after.files_deleted - before.files_deleted).to_string(),
+            ])).await;
+        }
+        Ok(())
+    }
+
+    // רק החלקים שסומנו. המדיה עוברת דרך התצוגה המקדימה כמו Large Old Downloads
+    pub async fn clean_screen_leftovers(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.screen_leftovers")).await;
+        for (part, targets) in screen_leftover_sections(&self.paths) {
+            if !self.sub_entries.iter().any(|s| s == part) || self.checkpoint_async().await {
+                continue;
+            }
+            if part == "media" {
+                let roots: Vec<PathBuf> = targets.into_iter().filter_map(|t| match t {
+                    Target::ScanOnly(root) if root.exists() => Some(root),
+                    _ => None,
+                }).collect();
+                for root in &roots {
+                    self.touch(root);
+                }
+                let this = self.clone();
+                run_blocking(move || {
+                    this.clean_from_preview("screen_leftovers", || this.find_old_screen_media(&roots), |entry| this.remove_unchanged_file(entry))
+                })
+                .await?;
+                continue;
+            }
+            for target in targets.into_iter().filter(is_available) {
+                self.clean_target(target, "Screenshots").await?;
+            }
+        }
+        Ok(())
+    }
+
+    // קבצים שלא נגעו בהם screen_media_age, הישנים ראשונים, בקבוצה לפי התיקייה
+    fn find_old_screen_media(&self, roots: &[PathBuf]) -> Vec<PreviewEntry> {
+        let cutoff = SystemTime::now().checked_sub(self.screen_media_age).unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut found = Vec::new();
+        for root in roots {
+            let group = display_name(root);
+            for entry in WalkDir::new(root).into_iter().filter_entry(|e| !self.is_excluded(e.path())) {
+                let Some(entry) = self.walk_entry(entry) else { continue };
+                if self.checkpoint() {
+                    return Vec::new();
+                }
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let Ok(metadata) = entry.metadata() else { continue };
+                let modified = metadata.modified().ok();
+                if modified.is_some_and(|m| m < cutoff) {
+                    found.push(PreviewEntry::new(entry.into_path(), metadata.len(), modified).with_group(group.clone()));
+                }
+            }
+        }
+        found.sort_by_key(|e| e.modified);
+        found
+    }
+
+    // lock של emacs שהתהליך שלו כבר לא חי. lock ממחשב אחר (בית משותף ב-NFS) - אי אפשר לדעת, נשאר
+    fn remove_stale_emacs_locks(&self, root: &Path) {
+        let Some(hostname) = local_hostname() else { return };
+        let own_data = app_data_dir();
+        let walker = WalkDir::new(root).into_iter()
+            .filter_entry(|e| !e.path().starts_with(&own_data) && !self.is_excluded(e.path()));
+        for entry in walker {
+            let Some(entry) = self.walk_entry(entry) else { continue };
+            if self.checkpoint() {
+                break;
+            }
+            if !entry.path_is_symlink() || !name_matches(entry.file_name(), EMACS_LOCK_PATTERN) {
+                continue;
+            }
+            let Ok(target) = fs::read_link(entry.path()) else { continue };
+            let stale = target.to_str()
+                .and_then(emacs_lock_owner)
+                .is_some_and(|(host, pid)| host == hostname && !lock::pid_alive(pid));
+            if !stale {
+                continue;
+            }
+            let Ok(metadata) = fs::symlink_metadata(entry.path()) else { continue };
+            if self.remove_file(entry.path(), &metadata, false) {
+                self.report_deleted(entry.path(), &metadata);
+            }
+        }
+        self.finish_batch();
+    }
+
+    // Xcode בונה את DerivedData מחדש בבנייה הבאה
+    pub async fn clean_xcode(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.xcode")).await;
+        self.clean_targets("xcode", "Xcode").await
+    }
+
+    // === Package Managers ===
+
+    pub async fn clean_apt(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if which("apt-get").is_err() {
+            return Err(skip("not installed"));
+        }
+        if !self.dry_run && !is_root() {
+            return Err(skip("needs root"));
+        }
+        self.log(&tr("log.apt")).await;
+        self.touch(&self.paths.system_cache);
+        self.clean_targets("apt", "Packages").await
+    }
+
+    pub async fn clean_dnf(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if which("dnf").is_err() {
+            return Err(skip("not installed"));
+        }
+        if !self.dry_run && !is_root() {
+            return Err(skip("needs root"));
+        }
+        self.log(&tr("log.dnf")).await;
+        self.touch(&self.paths.system_cache);
+        self.clean_targets("dnf", "Packages").await
+    }
+
+    pub async fn clean_flatpak(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if which("flatpak").is_err() {
+            return Err(skip("not installed"));
+        }
+        self.log(&tr("log.flatpak")).await;
+        self.touch(&self.paths.root.join("var/lib/flatpak"));
+        // Add logic from clean.rs to clean ~/.var/app cache
+        self.clean_targets("flatpak", "Flatpak").await
+    }
+
+    // brew cleanup לא מדווח כמה שחרר: בתצוגה מקדימה קוראים את --dry-run, בניקוי מודדים את ה-cache לפני ואחרי
+    pub async fn clean_homebrew(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if which("brew").is_err() {
+            return Err(skip("not installed"));
+        }
+        if is_root() {
+            return Err(skip("brew refuses to run as root"));
+        }
+        self.log(&tr("log.brew")).await;
+        if self.dry_run {
+            self.preview_brew_cleanup().await;
+            return Ok(());
+        }
+        self.clean_tool_targets(self.targets("brew"), "Packages").await
+    }
+
+    async fn preview_brew_cleanup(&self) {
+        let output = ProcessCommand::new("brew")
+            .args(["cleanup", "--prune=all", "--dry-run"])
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output();
+        let output = match tokio::time::timeout(self.command_timeout, output).await {
+            Ok(Ok(output)) => output,
+            _ => return,
+        };
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (mut files, mut bytes) = (0, 0);
+        for (path, count, size) in stdout.lines().filter_map(parse_brew_would_remove) {
+            self.log(&tr_args("log.tool_target", &[&display_name(&path), &Self::format_bytes(size), &count.to_string()])).await;
+            files += count;
+            bytes += size;
+        }
+        self.record_freed(files, bytes);
+        self.log(&tr_args("log.brew_preview", &[&Self::format_bytes(bytes)])).await;
+    }
+
+    // === Analysis ===
+
+    // תצוגה מקדימה סורקת מחדש ומסמנת הכל-חוץ-מאחד; ניקוי מוחק רק את מה שמסומן.
+    // ניקוי בלי סריקה קודמת סורק קודם, עם אותה מדיניות.
+    pub async fn clean_duplicates(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.duplicates")).await;
+        for root in &self.duplicates.roots {
+            self.touch(root);
+        }
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || this.clean_duplicates_blocking()).await?;
+        Ok(())
+    }
+
+    fn clean_duplicates_blocking(&self) {
+        let scan = &self.duplicates;
+        let needs_scan = self.dry_run || scan.groups.lock().unwrap().is_empty();
+        if needs_scan {
+            // הסריקה ארוכה - לא מחזיקים את ה-mutex בזמנה, ה-UI קורא ממנו כל פריים
+            let groups = duplicates::find_duplicates(scan, &self.cancel, &self.pause);
+            *scan.groups.lock().unwrap() = groups;
+        }
+
+        let mut groups = scan.groups.lock().unwrap().clone();
+        for group in &mut groups {
+            // תמיד נשאר עותק שלם אחד לפחות - גם אם המשתמש סימן הכל, או שהעותק שנשמר נמחק בינתיים
+            let has_kept_copy = group.files.iter().any(|f| !f.selected && duplicates::unchanged_since_scan(f, group.size).is_some());
+            if !has_kept_copy {
+                continue;
+            }
+            let mut removed = HashSet::new();
+            for file in group.files.iter().filter(|f| f.selected) {
+                if self.checkpoint() {
+                    break;
+                }
+                let metadata = match duplicates::unchanged_since_scan(file, group.size) {
+                    Some(m) => m,
+                    None => {
+                        self.log_sync(&tr_args("log.changed_since_scan", &[&display_name(&file.path)]));
+                        continue;
+                    }
+                };
+                if self.remove_file(&file.path, &metadata, false) {
+                    self.report_deleted(&file.path, &metadata);
+                    removed.insert(file.path.clone());
+                }
+            }
+            if !self.dry_run {
+                // מה שנמחק כבר לא מוצג בעץ
+                group.files.retain(|f| !removed.contains(&f.path));
+            }
+        }
+        if !self.dry_run {
+            groups.retain(|g| g.files.len() > 1);
+            *scan.groups.lock().unwrap() = groups;
+        }
+        self.finish_batch();
+    }
+
+    // הבחירה בעץ. רשימה שנטענה מהפעלה קודמת היא רק המלצה: סורקים עכשיו ומוחקים רק
+    // מה שגם סומן וגם עדיין נמצא, עם הגודל והתאריך של עכשיו
+    fn current_selection(&self, id: &str, scan: impl FnOnce() -> Vec<PreviewEntry>) -> Option<Vec<PreviewEntry>> {
+        let selected = self.preview.selected(id)?;
+        if !self.preview.is_stale(id) {
+            return Some(selected);
+        }
+        let mut fresh: HashMap<PathBuf, PreviewEntry> = scan().into_iter().map(|e| (e.path.clone(), e)).collect();
+        Some(selected.into_iter().filter_map(|e| fresh.remove(&e.path)).collect())
+    }
+
+    // תצוגה מקדימה: `scan` ממלא את העץ (והכל מדווח כאילו נמחק, כדי לראות כמה אפשר לפנות).
+    // ניקוי: רק מה שהמשתמש השאיר מסומן. בלי תצוגה מקדימה קודמת - לא נוגעים בכלום.
+    fn clean_from_preview(
+        &self,
+        id: &str,
+        scan: impl FnOnce() -> Vec<PreviewEntry>,
+        remove: impl Fn(&PreviewEntry) -> bool,
+    ) -> Result<(), BlockingError> {
+        if self.dry_run {
+            let entries = scan();
+            // מה שלא מסומן מראש לא נספר בסיכום - רק אם המשתמש יסמן אותו בעץ
+            for entry in entries.iter().filter(|e| e.selected) {
+                if self.checkpoint() {
+                    break;
+                }
+                remove(entry);
+            }
+            self.preview.set(id, entries);
+            return Ok(());
+        }
+
+        let selected = self.current_selection(id, scan).ok_or_else(|| skip_blocking("preview first to choose files"))?;
+        let mut removed = HashSet::new();
+        for entry in selected {
+            if self.checkpoint() {
+                break;
+            }
+            // הוחרג אחרי התצוגה המקדימה (תפריט בעץ או הגדרות)
+            if self.is_excluded(&entry.path) {
+                continue;
+            }
+            if remove(&entry) {
+                removed.insert(entry.path);
+            }
+        }
+        self.preview.remove_paths(id, &removed);
+        self.finish_batch();
+        Ok(())
+    }
+
+    pub async fn clean_large_downloads(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.large_downloads")).await;
+        for root in &self.large_files.roots {
+            self.touch(root);
+        }
+        let this = match &self.large_files.archive {
+            Some(archive) => self.clone().with_strategy(DeletionStrategy::MoveTo(archive.clone())),
+            None => self.clone(),
+        };
+        run_blocking(move || {
+            this.clean_from_preview("large_downloads", || this.find_large_old_files(), |entry| this.remove_unchanged_file(entry))
+        })
+        .await
+    }
+
+    // הגדולים ראשונים
+    fn find_large_old_files(&self) -> Vec<PreviewEntry> {
+        let scan = &self.large_files;
+        let cutoff = SystemTime::now().checked_sub(scan.min_age).unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut found = Vec::new();
+        for root in &scan.roots {
+            // מה שכבר בארכיון (~/Downloads/_archive) לא מועבר שוב
+            for entry in WalkDir::new(root).follow_links(false).into_iter()
+                .filter_entry(|e| !self.is_excluded(e.path()) && scan.archive.as_ref().is_none_or(|a| !e.path().starts_with(a)))
+                .filter_map(|e| e.ok()) {
+                if self.checkpoint() {
+                    return Vec::new();
+                }
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let metadata = match entry.metadata() {
+                    Ok(m) => m,
+                    Err(_) => continue,
+                };
+                let modified = metadata.modified().ok();
+                if metadata.len() >= scan.min_bytes && modified.is_some_and(|m| m < cutoff) {
+                    let mut found_entry = PreviewEntry::new(entry.into_path(), metadata.len(), modified);
+                    // בעץ: מקור -> יעד
+                    if let Some(archive) = &scan.archive {
+                        found_entry = found_entry.with_detail(format!("→ {}", archive_dir(archive, &metadata).display()));
+                    }
+                    found.push(found_entry);
+                }
+            }
+        }
+        found.sort_by_key(|e| std::cmp::Reverse(e.size));
+        found
+    }
+
+    // קובץ שהשתנה מאז התצוגה המקדימה לא נמחק - המשתמש אישר את הגרסה הקודמת
+    fn remove_unchanged_file(&self, entry: &PreviewEntry) -> bool {
+        let metadata = match fs::symlink_metadata(&entry.path) {
+            Ok(m) if m.is_file() && m.len() == entry.size && m.modified().ok() == entry.modified => m,
+            _ => {
+                self.log_sync(&tr_args("log.changed_since_scan", &[&display_name(&entry.path)]));
+                return false;
+            }
+        };
+        if self.remove_file(&entry.path, &metadata, false) {
+            self.report_deleted(&entry.path, &metadata);
+            true
+        } else {
+            false
+        }
+    }
+
+    // === Browsers (Simplified for Async) ===
+
+    // מחיקת cache של דפדפן פתוח עלולה לשבור אותו
+    fn ensure_browser_closed(&self, processes: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.dry_run && is_process_running(processes) {
+            return Err(skip("browser running"));
+        }
+        Ok(())
+    }
+
+    pub async fn clean_firefox_cache(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.ensure_browser_closed(&["firefox", "firefox-bin"])?;
+        self.log(&tr("log.firefox_cache")).await;
+        // Deep search for cache2 folders inside the profiles
+        self.clean_targets("firefox_cache", "Firefox").await
+    }
+
+    // דוחות קריסה, טלמטריה וגיבויי session ישנים - כל סוג בקבוצה משלו בתצוגה המקדימה.
+    // Firefox פתוח לא עוצר את הניקוי, רק את קבצי ה-session שהוא כותב אליהם עכשיו
+    pub async fn clean_firefox_leftovers(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.firefox_leftovers")).await;
+        let running = is_process_running(&["firefox", "firefox-bin"]);
+        let this = self.clone();
+        run_blocking(move || {
+            if this.dry_run || this.preview.selected("firefox_leftovers").is_some() {
+                return this.clean_from_preview("firefox_leftovers", || this.find_firefox_leftovers(running), |entry| this.remove_unchanged_file(entry));
+            }
+            // בלי תצוגה מקדימה (CLI, ריצה מתוזמנת): רק מה שמסומן מראש
+            for entry in this.find_firefox_leftovers(running).iter().filter(|e| e.selected) {
+                if this.checkpoint() {
+                    break;
+                }
+                this.remove_unchanged_file(entry);
+            }
+            this.finish_batch();
+            Ok(())
+        })
+        .await
+    }
+
+    fn find_firefox_leftovers(&self, running: bool) -> Vec<PreviewEntry> {
+        let mut found = Vec::new();
+        let mut skipped_session = false;
+        for target in self.targets("firefox_leftovers") {
+            let Target::ScanOnly(root) = target else { continue };
+            for profile in firefox::profiles(&root) {
+                let name = display_name(&profile);
+                for leftover in firefox::LEFTOVERS {
+                    let dir = profile.join(leftover.dir);
+                    if !dir.is_dir() {
+                        continue;
+                    }
+                    self.touch(&dir);
+                    let group = tr_args(leftover.group, &[&name]);
+                    for entry in WalkDir::new(&dir).into_iter().filter_entry(|e| !self.is_excluded(e.path())).filter_map(|e| self.walk_entry(e)) {
+                        if self.checkpoint() {
+                            return Vec::new();
+                        }
+                        if !entry.file_type().is_file() {
+                            continue;
+                        }
+                        if running && firefox::is_live_session(entry.path()) {
+                            skipped_session = true;
+                            continue;
+                        }
+                        let Ok(metadata) = entry.metadata() else { continue };
+                        found.push(
+                            PreviewEntry::new(entry.into_path(), metadata.len(), metadata.modified().ok())
+                                .with_group(group.clone())
+                                .with_selected(leftover.selected),
+                        );
+                    }
+                }
+            }
+        }
+        if skipped_session {
+            self.log_sync(&tr("log.firefox_session_in_use"));
+        }
+        found
+    }
+
+    pub async fn clean_chrome_cache(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // "Google Chrome" / "Brave Browser" - שמות התהליכים ב-macOS
+        self.ensure_browser_closed(&["chrome", "Google Chrome"])?;
+        self.log(&tr("log.chrome_cache")).await;
+        self.clean_targets("chrome_cache", "Chrome").await
+    }
+
+    pub async fn clean_brave_cache(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.ensure_browser_closed(&["brave", "Brave Browser"])?;
+        self.log(&tr("log.brave_cache")).await;
+        self.clean_targets("brave_cache", "Brave").await
+    }
+
+    // Service Worker/CacheStorage: הנתונים האופליין של אתרים ו-PWA, תיקייה לכל origin.
+    // אתרים מ-keep_sites לא מסומנים מראש. ScriptCache משותף לכל האתרים - מחיקה שלו הייתה פוגעת גם באלה שנשמרים
+    pub async fn clean_site_storage(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.log(&tr("log.site_storage")).await;
+        // דפדפן פתוח מחזיק את הקבצים ועלול לכתוב מעל המחיקה - הפרופילים שלו נשארים
+        let running: Vec<PathBuf> = if self.dry_run { Vec::new() } else { self.running_chromium_roots() };
+        let this = self.clone();
+        run_blocking(move || {
+            let remove = |entry: &PreviewEntry| {
+                if running.iter().any(|root| entry.path.starts_with(root)) {
+                    return false;
+                }
+                this.remove_tree(&entry.path);
+                !entry.path.exists()
+            };
+            if this.dry_run || this.preview.selected("site_storage").is_some() {
+                return this.clean_from_preview("site_storage", || this.find_site_storage(), remove);
+            }
+            // בלי תצוגה מקדימה (CLI, ריצה מתוזמנת): כל מה שלא ברשימת השמירה
+            for entry in this.find_site_storage().iter().filter(|e| e.selected) {
+                if this.checkpoint() {
+                    break;
+                }
+                remove(entry);
+            }
+            this.finish_batch();
+            Ok(())
+        })
+        .await
+    }
+
+    fn running_chromium_roots(&self) -> Vec<PathBuf> {
+        let mut running = Vec::new();
+        for browser in chromium::BROWSERS {
+            let root = self.paths.config.join(browser.config_dir);
+            if root.is_dir() && is_process_running(browser.processes) {
+                self.log_sync(&tr_args("log.site_storage_browser_running", &[browser.name]));
+                running.push(root);
+            }
+        }
+        running
+    }
+
+    // הגדולים ראשונים בכל פרופיל
+    fn find_site_storage(&self) -> Vec<PreviewEntry> {
+        let mut found = Vec::new();
+        for browser in chromium::BROWSERS {
+            let root = self.paths.config.join(browser.config_dir);
+            for profile in chromium::profiles(&root) {
+                let group = tr_args("preview.site_profile", &[browser.name, &display_name(&profile)]);
+                let mut sites = Vec::new();
+                for site in chromium::site_caches(&profile) {
+                    if self.checkpoint() {
+                        return Vec::new();
+                    }
+                    if self.is_excluded(&site.dir) {
+                        continue;
+                    }
+                    self.touch(&site.dir);
+                    let size: u64 = WalkDir::new(&site.dir).into_iter().filter_map(|e| e.ok()).filter_map(|e| e.metadata().ok()).map(|m| allocated_bytes(&m)).sum();
+                    let modified = fs::metadata(&site.dir).and_then(|m| m.modified()).ok();
+                    let kept = site.origin.as_deref().is_some_and(|origin| chromium::is_kept(origin, &self.keep_sites));
+                    let origin = site.origin.unwrap_or_else(|| tr_args("preview.site_unknown", &[&display_name(&site.dir)]));
+                    let mut entry = PreviewEntry::new(site.dir, size, modified)
+                        .with_title(format!("{}  {}", Self::format_bytes(size), origin))
+                        .with_group(group.clone())
+                        .with_selected(!kept);
+                    if kept {
+                        entry = entry.with_detail(tr("preview.site_kept"));
+                    }
+                    sites.push(entry);
+                }
+                sites.sort_by_key(|e| std::cmp::Reverse(e.size));
+                found.extend(sites);
+            }
+        }
+        found
+    }
+}
+
+// הפריטים שנבדקים כאן (פח, cache של Firefox) בפריסת freedesktop - ב-macOS וב-Windows יש להם targets אחרים
+#[cfg(all(test, unix, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, Fixture};
+
+    fn cleaner(fixture: &Fixture, dry_run: bool) -> SystemCleaner {
+        SystemCleaner::new(false, dry_run).with_home(fixture.home())
+    }
+
+    // הסטטיסטיקה של המנוע מול מה שבאמת נעלם מהדיסק
+    fn assert_stats_match(cleaner: &SystemCleaner, removed: &std::collections::BTreeMap<PathBuf, u64>) {
+        let stats = cleaner.get_stats_sync();
+        assert_eq!(stats.files_deleted, removed.len() as u64);
+        assert_eq!(stats.bytes_freed, removed.values().sum::<u64>());
+    }
+
+    #[test]
+    fn name_matches_globs() {
+        assert!(name_matches(OsStr::new("app.log"), "*.log"));
+        assert!(!name_matches(OsStr::new("app.log.1"), "*.log"));
+        assert!(name_matches(OsStr::new("adb.5037.log"), "adb.*.log"));
+        assert!(name_matches(OsStr::new("recently-used.xbel"), "recently-used.xbel"));
+        assert!(!name_matches(OsStr::new("main.py"), "*.pyc"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn weird_names_match_on_bytes_and_are_escaped_for_the_log() {
+        use std::os::unix::ffi::OsStrExt;
+        let invalid = OsStr::from_bytes(b"caf\xe9.bak");
+        assert!(name_matches(invalid, "*.bak"));
+        // U+FFFD בתבנית לא תואם בייט פגום - בלי to_string_lossy אין "התאמה בטעות"
+        assert!(!name_matches(OsStr::from_bytes(b"\xff"), "\u{FFFD}"));
+        assert!(name_matches(OsStr::new("line\nbreak.bak"), "*.bak"));
+
+        assert_eq!(escape_name(invalid), "caf\\xe9.bak");
+        assert_eq!(escape_name(OsStr::new("line\nbreak\t.bak")), "line\\nbreak\\t.bak");
+        assert_eq!(escape_name(OsStr::new("evil\u{202E}gpj.exe")), "evil\\u{202e}gpj.exe");
+        assert_eq!(display_name(Path::new("/tmp/dir/line\nbreak")), "line\\nbreak");
+        assert_eq!(display_name(Path::new("/")), "/");
+        let long = "x".repeat(251) + ".bak";
+        assert_eq!(display_name(&Path::new("/tmp").join(&long)), long);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn weird_names_are_deleted_by_their_original_path() {
+        use std::os::unix::ffi::OsStrExt;
+        let fixture = Fixture::new();
+        // 255 בתים - המקסימום ב-ext4
+        let long = "y".repeat(251) + ".bak";
+        let names = [OsStr::new("line\nbreak.bak"), OsStr::from_bytes(b"caf\xe9.bak"), OsStr::new(&long)];
+        let dir = fixture.home().join("docs");
+        fs::create_dir_all(&dir).unwrap();
+        for name in names {
+            fs::write(dir.join(name), b"old").unwrap();
+        }
+        let kept = fixture.file("docs/keep.txt", b"keep");
+
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let sink = logs.clone();
+        let cleaner = cleaner(&fixture, false)
+            .with_callback(Arc::new(Mutex::new(Box::new(move |line: String| sink.lock().unwrap().push(line)) as Box<dyn Fn(String) + Send + Sync>)));
+        cleaner.run_item("backup_files").await.unwrap();
+
+        for name in names {
+            assert!(fs::symlink_metadata(dir.join(name)).is_err(), "{:?}", name);
+        }
+        assert!(kept.exists());
+        assert_eq!(cleaner.get_stats_sync().files_deleted, 3);
+        let logs = logs.lock().unwrap();
+        // שורת לוג אחת לכל קובץ, בלי שבירת שורה ובלי בייטים גולמיים
+        assert!(logs.iter().all(|line| !line.contains('\n') && !line.contains('\u{FFFD}')), "{:?}", logs);
+        for escaped in ["line\\nbreak.bak", "caf\\xe9.bak"] {
+            assert!(logs.iter().any(|line| line.contains(escaped)), "{} not in {:?}", escaped, logs);
+        }
+    }
+
+    #[test]
+    fn with_home_redirects_targets() {
+        let fixture = Fixture::new();
+        let cleaner = cleaner(&fixture, true);
+        assert_eq!(cleaner.get_home_dir(), fixture.home());
+        let trash = fixture.home().join(".local/share/Trash");
+        assert!(matches!(cleaner.targets("trash").as_slice(), [Target::Directory(dir)] if *dir == trash));
+    }
+
+    #[tokio::test]
+    async fn dry_run_counts_without_removing() {
+        let fixture = Fixture::new();
+        fixture.trash_item("report.pdf", &[7; 20_000]);
+        fixture.trash_item("notes.txt", b"hello");
+        fixture.python_project("proj");
+        fixture.firefox_profile("abcd1234");
+        let before = fixture.snapshot();
+
+        let cleaner = cleaner(&fixture, true);
+        for id in ["trash", "pycache", "firefox_cache"] {
+            cleaner.run_item(id).await.unwrap();
+        }
+
+        assert_eq!(fixture.snapshot(), before);
+        // מה שריצה אמיתית הייתה מוחקת: 2 זוגות בפח, 3 pyc, 4 קבצים ב-cache2
+        let would_remove: std::collections::BTreeMap<PathBuf, u64> = before
+            .into_iter()
+            .filter(|(path, _)| {
+                path.starts_with(fixture.home().join(".local/share/Trash"))
+                    || path.extension().is_some_and(|e| e == "pyc")
+                    || path.components().any(|c| c.as_os_str() == "cache2")
+            })
+            .collect();
+        assert_eq!(would_remove.len(), 4 + 3 + 4);
+        assert_stats_match(&cleaner, &would_remove);
+    }
+
+    #[tokio::test]
+    async fn real_run_removes_only_targets() {
+        let fixture = Fixture::new();
+        let kept_in_trash = fixture.trash_item("photo.jpg", &[1; 50_000]);
+        let project = fixture.python_project("proj");
+        let logs = fixture.rotated_logs("myapp");
+        let before = fixture.snapshot();
+
+        let cleaner = cleaner(&fixture, false);
+        cleaner.run_item("trash").await.unwrap();
+        cleaner.run_item("pycache").await.unwrap();
+        let after = fixture.snapshot();
+        let removed = fixtures::removed(&before, &after);
+
+        assert!(!kept_in_trash.exists());
+        assert!(!fixture.home().join(".local/share/Trash/info/photo.jpg.trashinfo").exists());
+        assert!(removed.keys().all(|p| p.starts_with(fixture.home().join(".local/share/Trash")) || p.extension().is_some_and(|e| e == "pyc")));
+        assert_eq!(removed.len(), 2 + 3);
+        // קוד המקור והלוגים לא בתחום של הפריטים האלה
+        assert!(project.join("app/app.py").exists());
+        assert!(project.join("app/core/utils/utils.py").exists());
+        assert!(logs.join("myapp.log").exists());
+        assert_stats_match(&cleaner, &removed);
+    }
+
+    #[tokio::test]
+    async fn log_pattern_leaves_rotated_copies() {
+        let fixture = Fixture::new();
+        let logs = fixture.rotated_logs("myapp");
+        let before = fixture.snapshot();
+
+        let cleaner = cleaner(&fixture, false);
+        cleaner.clean_target(pattern_target(fixture.home().join(".local/state"), "*.log"), "Logs").await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert_eq!(removed.keys().collect::<Vec<_>>(), vec![&logs.join("myapp.log")]);
+        assert!(logs.join("myapp.log.1").exists());
+        assert!(logs.join("myapp.log.2.gz").exists());
+        assert_stats_match(&cleaner, &removed);
+    }
+
+    #[tokio::test]
+    async fn symlinks_are_removed_not_followed() {
+        let fixture = Fixture::new();
+        fixture.trash_item("old.txt", b"old");
+        let (link, target) = fixture.symlink_outside(".local/share/Trash/files/link-to-outside", "precious.bin");
+        let before = fixture.snapshot();
+
+        // דריסה דלוקה: אם הייתה עוברת דרך הקישור, היעד היה מתאפס
+        let cleaner = cleaner(&fixture, false).with_secure_delete(true);
+        cleaner.run_item("trash").await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert_eq!(fs::read(&target).unwrap(), vec![0x55; 10_000]);
+        assert!(removed.keys().all(|p| p.starts_with(fixture.home())));
+        assert_stats_match(&cleaner, &removed);
+    }
+
+    #[tokio::test]
+    async fn firefox_cache_keeps_profile_data() {
+        let fixture = Fixture::new();
+        let profile = fixture.firefox_profile("abcd1234");
+        let before = fixture.snapshot();
+
+        let cleaner = cleaner(&fixture, false);
+        // Firefox פתוח במכונה שמריצה את הבדיקות - אין מה לבדוק
+        if let Err(e) = cleaner.run_item("firefox_cache").await {
+            assert!(e.downcast_ref::<Skipped>().is_some(), "{}", e);
+            return;
+        }
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert_eq!(removed.len(), 4);
+        assert!(removed.keys().all(|p| p.starts_with(profile.join("cache2"))));
+        assert!(profile.join("places.sqlite").exists());
+        assert!(profile.join("prefs.js").exists());
+        assert!(fixture.home().join(".mozilla/firefox/profiles.ini").exists());
+        assert_stats_match(&cleaner, &removed);
+    }
+
+    #[tokio::test]
+    async fn excluded_paths_survive() {
+        let fixture = Fixture::new();
+        let kept = fixture.trash_item("keep.me", &[3; 4096]);
+        fixture.trash_item("drop.me", &[4; 4096]);
+
+        let cleaner = cleaner(&fixture, false).with_exclusions(vec![kept.clone()]);
+        cleaner.run_item("trash").await.unwrap();
+
+        assert!(kept.exists());
+        assert!(!fixture.home().join(".local/share/Trash/files/drop.me").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn sparse_file_counts_its_blocks_not_its_length() {
+        let fixture = Fixture::new();
+        let sparse = fixture.file(".cache/thumbnails/normal/sparse.png", b"");
+        let file = OpenOptions::new().write(true).open(&sparse).unwrap();
+        file.set_len(64 * 1024 * 1024).unwrap();
+        (&file).write_all(&[1; 4096]).unwrap();
+        drop(file);
+        let metadata = fs::metadata(&sparse).unwrap();
+        let expected = fixtures::allocated(&metadata);
+        assert!(expected < metadata.len() / 2, "the temp file system does not keep holes");
+
+        let cleaner = cleaner(&fixture, false);
+        cleaner.run_item("thumbnails").await.unwrap();
+
+        assert!(!sparse.exists());
+        let stats = cleaner.get_stats_sync();
+        assert_eq!((stats.files_deleted, stats.bytes_freed), (1, expected));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn hard_linked_pair_is_counted_once() {
+        let fixture = Fixture::new();
+        let first = fixture.file(".cache/thumbnails/normal/a.png", &[3; 20_000]);
+        let second = first.with_file_name("b.png");
+        fs::hard_link(&first, &second).unwrap();
+        let expected = fixtures::allocated(&fs::metadata(&first).unwrap());
+
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let sink = logs.clone();
+        let cleaner = cleaner(&fixture, false)
+            .with_callback(Arc::new(Mutex::new(Box::new(move |line: String| sink.lock().unwrap().push(line)) as Box<dyn Fn(String) + Send + Sync>)));
+        cleaner.run_item("thumbnails").await.unwrap();
+
+        assert!(!first.exists() && !second.exists());
+        let stats = cleaner.get_stats_sync();
+        assert_eq!((stats.files_deleted, stats.bytes_freed), (2, expected));
+        // איזה מהשניים נספר תלוי בסדר הסריקה - השני תמיד 0 B
+        let hard_links = logs.lock().unwrap().iter().filter(|line| line.contains("(hard link)")).count();
+        assert_eq!(hard_links, 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn secure_delete_leaves_hard_linked_data_alone() {
+        let fixture = Fixture::new();
+        let kept = fixture.outside().join("kept.bin");
+        fs::write(&kept, [0x55; 10_000]).unwrap();
+        let trashed = fixture.trash_item("linked.bin", b"");
+        fs::remove_file(&trashed).unwrap();
+        fs::hard_link(&kept, &trashed).unwrap();
+
+        let cleaner = cleaner(&fixture, false).with_secure_delete(true);
+        cleaner.run_item("trash").await.unwrap();
+
+        assert!(!trashed.exists());
+        assert_eq!(fs::read(&kept).unwrap(), vec![0x55; 10_000]);
+    }
+
+    #[tokio::test]
+    async fn empty_folders_skip_hidden_roots_and_still_see_later_siblings() {
+        let fixture = Fixture::new();
+        let home = fixture.home();
+        for dir in [".config/app/empty", "zz/empty", "aa/nested/deeper", "mm/full"] {
+            fs::create_dir_all(home.join(dir)).unwrap();
+        }
+        fixture.file("mm/full/file.txt", b"x");
+
+        let cleaner = cleaner(&fixture, false);
+        let mut found = cleaner.find_empty_dirs(&home);
+        // מלמטה למעלה: תיקייה לפני ההורה שלה
+        for (i, dir) in found.iter().enumerate() {
+            assert!(found[..i].iter().all(|earlier| !dir.starts_with(earlier)), "{:?}", found);
+        }
+        found.sort();
+        let expected: Vec<PathBuf> = ["aa", "aa/nested", "aa/nested/deeper", "zz", "zz/empty"].iter().map(|d| home.join(d)).collect();
+        assert_eq!(found, expected);
+
+        cleaner.run_item("empty_dirs").await.unwrap();
+        assert!(home.join(".config/app/empty").is_dir());
+        assert!(!home.join("zz").exists() && !home.join("aa").exists());
+        assert!(home.join("mm/full/file.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn devops_removes_only_expired_aws_tokens() {
+        let fixture = Fixture::new();
+        let expired = fixture.file(".aws/sso/cache/old.json", br#"{"expiresAt": "2001-01-01T00:00:00UTC"}"#);
+        let valid = fixture.file(".aws/sso/cache/new.json", br#"{"expiresAt": "2999-01-01T00:00:00UTC"}"#);
+        let config = fixture.file(".aws/cli/cache/notes.json", b"{}");
+        let helm = fixture.file(".cache/helm/repository/index.yaml", b"x");
+
+        let cleaner = cleaner(&fixture, false);
+        cleaner.run_item("devops").await.unwrap();
+
+        assert!(!expired.exists() && !helm.exists());
+        // json בלי תאריך תפוגה הוא לא token שפג - נשאר, גם אם התבנית תואמת
+        assert!(valid.exists() && config.exists());
+    }
+
+    #[tokio::test]
+    async fn imported_globs_survive_and_are_counted() {
+        let fixture = Fixture::new();
+        let kept = fixture.trash_item("keep.iso", &[3; 4096]);
+        fixture.trash_item("drop.me", &[4; 4096]);
+
+        let rules = crate::exclusions::parse_list("# team list\n*.iso\n/nowhere/**\n").ok().unwrap();
+        let cleaner = cleaner(&fixture, false).with_imported_exclusions(PathBuf::from("team.txt"), rules);
+        cleaner.run_item("trash").await.unwrap();
+
+        assert!(kept.exists());
+        assert!(!fixture.home().join(".local/share/Trash/files/drop.me").exists());
+        let notes = cleaner.exclusion_notes();
+        assert_eq!(notes.len(), 2);
+        assert!(notes[1].contains("*.iso"));
+    }
+
+    fn fake_root(fixture: &Fixture, dry_run: bool) -> SystemCleaner {
+        SystemCleaner::new(false, dry_run).with_paths(SystemPaths::new(fixture.root(), fixture.home()))
+    }
+
+    #[test]
+    fn fake_root_targets_stay_inside() {
+        let fixture = Fixture::new();
+        let cleaner = fake_root(&fixture, true);
+        for entry in crate::registry::builtin_cleaners() {
+            // Wipe Free Space כותב ל-mount שנבחר בהגדרות, לא לנתיב של פריט
+            if entry.id == "wipe_free_space" {
+                continue;
+            }
+            for target in cleaner.targets(&entry.id) {
+                let path = match &target {
+                    Target::Directory(dir) | Target::ScanOnly(dir) => dir,
+                    Target::Pattern { root, .. } | Target::Search { root, .. } => root,
+                    Target::Command(_) | Target::FreeSpace(_) => continue,
+                };
+                assert!(path.starts_with(fixture.root()), "{}: {} is outside the fake root", entry.id, path.display());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn fake_root_never_touches_real_system_dirs() {
+        let fixture = Fixture::new();
+        fixture.system_file("tmp/session-1/lock", b"x");
+        fixture.system_file("var/tmp/build.o", &[0; 9000]);
+        fixture.system_file("var/cache/app/blob", &[1; 20_000]);
+        fixture.system_file("var/log/syslog.1", &[2; 6000]);
+        fixture.file(".cache/thumbnails/normal/abc.png", &[3; 3000]);
+        // קובץ אמיתי ב-/tmp של המכונה: ריצה של "tmp" שלא הייתה מכבדת את השורש המזויף הייתה מוחקת אותו
+        let sentinel = tempfile::NamedTempFile::new().unwrap();
+        let before = fixture.snapshot();
+
+        let cleaner = fake_root(&fixture, false);
+        for id in ["tmp", "var_cache", "logs", "thumbnails"] {
+            cleaner.run_item(id).await.unwrap();
+        }
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert!(sentinel.path().exists());
+        assert_eq!(removed.len(), 5);
+        assert!(removed.keys().all(|p| p.starts_with(fixture.root())));
+        assert_stats_match(&cleaner, &removed);
+    }
+
+    #[test]
+    fn user_home_rebases_scan_roots() {
+        let fixture = Fixture::new();
+        let mut cleaner = fake_root(&fixture, true);
+        cleaner.duplicates.roots = vec![fixture.home().join("Music")];
+        let other = fixture.root().join("other");
+        let cleaner = cleaner.with_home(other.clone());
+        assert_eq!(cleaner.duplicates.roots, vec![other.join("Music")]);
+        assert_eq!(cleaner.paths.cache, other.join(".cache"));
+        assert_eq!(cleaner.paths.system_log, fixture.root().join("var/log"));
+    }
+
+    // XDG_CACHE_HOME/XDG_STATE_HOME על מחיצה אחרת: הניקוי שם, ומה שב-~/.cache וב-~/.local/state לא שלהם
+    #[tokio::test]
+    async fn xdg_homes_move_where_cleaners_look() {
+        let fixture = Fixture::new();
+        let xdg = fixture.root().join("xdg");
+        let moved_thumb = fixture.system_file("xdg/cache/thumbnails/normal/abc.png", &[3; 3000]);
+        let moved_log = fixture.system_file("xdg/state/app/run.log", &[4; 2000]);
+        let default_thumb = fixture.file(".cache/thumbnails/normal/def.png", &[3; 3000]);
+        let default_log = fixture.file(".local/state/app/run.log", &[4; 2000]);
+
+        let paths = SystemPaths::new(fixture.root(), fixture.home())
+            .with_xdg(XdgDir::Cache, xdg.join("cache"))
+            .with_xdg(XdgDir::State, xdg.join("state"));
+        assert_eq!((paths.cache.clone(), paths.config.clone()), (xdg.join("cache"), fixture.home().join(".config")));
+        let cleaner = SystemCleaner::new(false, false).with_paths(paths);
+        for id in ["thumbnails", "logs"] {
+            cleaner.run_item(id).await.unwrap();
+        }
+
+        assert!(!moved_thumb.exists() && !moved_log.exists());
+        assert!(default_thumb.exists() && default_log.exists());
+    }
+
+    // "share" מתחת ל-~/.cache מתנהג כמו שיתוף רשת מעוגן: התקן אחר
+    fn mock_device(path: &Path, _metadata: &fs::Metadata) -> u64 {
+        if path.components().any(|c| c.as_os_str() == "share") { 2 } else { 1 }
+    }
+
+    #[tokio::test]
+    async fn walks_stay_on_the_root_filesystem() {
+        let fixture = Fixture::new();
+        let local = fixture.file(".cache/app/blob", &[1; 4096]);
+        let remote = fixture.file(".cache/share/nested/remote.bin", &[2; 4096]);
+
+        let mut cleaner = fake_root(&fixture, false);
+        cleaner.device_id = mock_device;
+        cleaner.run_item("var_cache").await.unwrap();
+        assert!(!local.exists());
+        assert!(remote.exists());
+
+        let mut cleaner = fake_root(&fixture, false).with_same_filesystem(false);
+        cleaner.device_id = mock_device;
+        cleaner.run_item("var_cache").await.unwrap();
+        assert!(!remote.exists());
+    }
+
+    #[tokio::test]
+    async fn file_manager_leftovers_of_missing_folders() {
+        let fixture = Fixture::new();
+        fs::create_dir_all(fixture.home().join("My Projects")).unwrap();
+        // בשורש מזויף הנתיבים בסימניות יחסיים לשורש - /home הוא <tmp>/home
+        let live = "file:///home/My%20Projects Projects\nsftp://server/srv\n";
+        let bookmarks = fixture.file(".config/gtk-3.0/bookmarks", format!("file:///home/gone%20away\n{}", live).as_bytes());
+        let log = fixture.file(".local/share/gvfs-metadata/home-1a2b.log", &[0; 32_768]);
+        let index = fixture.file(".local/share/gvfs-metadata/home", &[0; 4096]);
+        let kept_view = fixture.file(".local/share/dolphin/view_properties/local/home/My Projects/.directory", b"[Dolphin]\n");
+        let dead_view = fixture.file(".local/share/dolphin/view_properties/local/home/gone/sub/.directory", b"[Dolphin]\n");
+
+        fake_root(&fixture, true).run_item("fm_leftovers").await.unwrap();
+        assert!(fs::read(&bookmarks).unwrap().starts_with(b"file:///home/gone"));
+        assert!(log.exists() && dead_view.exists());
+
+        fake_root(&fixture, false).run_item("fm_leftovers").await.unwrap();
+        assert_eq!(fs::read(&bookmarks).unwrap(), live.as_bytes());
+        assert!(!log.exists());
+        assert!(index.exists());
+        assert!(kept_view.exists());
+        assert!(!fixture.home().join(".local/share/dolphin/view_properties/local/home/gone").exists());
+    }
+
+    #[tokio::test]
+    async fn stale_downloads_move_to_yearly_archive() {
+        let fixture = Fixture::new();
+        let downloads = fixture.home().join("Downloads");
+        let archive = downloads.join("_archive");
+        let old = fixture.file("Downloads/setup.iso", &[7; 50_000]);
+        let fresh = fixture.file("Downloads/today.iso", &[8; 50_000]);
+        let two_years = SystemTime::now() - Duration::from_secs(2 * 365 * 24 * 60 * 60);
+        fs::File::options().write(true).open(&old).unwrap().set_modified(two_years).unwrap();
+        let year = DateTime::<Local>::from(two_years).format("%Y").to_string();
+        // שם תפוס בארכיון - הקובץ החדש מקבל סיומת
+        let taken = fixture.file(&format!("Downloads/_archive/{}/setup.iso", year), b"older copy");
+
+        let preview = PreviewStore::default();
+        let scan = LargeFileScan { roots: vec![downloads], min_bytes: 1, min_age: Duration::from_secs(24 * 60 * 60), archive: Some(archive.clone()) };
+        let dry = cleaner(&fixture, true).with_large_files(scan.clone()).with_preview(preview.clone());
+        dry.run_item("large_downloads").await.unwrap();
+        let listed = preview.selected("large_downloads").unwrap();
+        assert_eq!(listed.len(), 1, "the archive itself and fresh files are not listed");
+        assert_eq!(listed[0].detail, format!("→ {}", archive.join(&year).display()));
+        assert!(old.exists());
+
+        let real = cleaner(&fixture, false).with_large_files(scan).with_preview(preview);
+        real.run_item("large_downloads").await.unwrap();
+        let moved = archive.join(&year).join("setup (2).iso");
+        assert!(!old.exists() && fresh.exists());
+        assert_eq!(fs::read(&moved).unwrap(), vec![7; 50_000]);
+        assert_eq!(fs::read(&taken).unwrap(), b"older copy");
+        let stats = real.get_stats_sync();
+        assert_eq!((stats.files_deleted, stats.bytes_freed), (0, 0));
+        assert_eq!((stats.files_organized, stats.bytes_organized), (1, 50_000));
+    }
+
+    #[test]
+    fn unique_destination_adds_suffix_before_extension() {
+        let fixture = Fixture::new();
+        let dir = fixture.home();
+        assert_eq!(unique_destination(&dir, OsStr::new("a.tar.gz")), dir.join("a.tar.gz"));
+        fixture.file("a.tar.gz", b"x");
+        fixture.file("a.tar (2).gz", b"x");
+        assert_eq!(unique_destination(&dir, OsStr::new("a.tar.gz")), dir.join("a.tar (3).gz"));
+        fixture.file("README", b"x");
+        assert_eq!(unique_destination(&dir, OsStr::new("README")), dir.join("README (2)"));
+    }
+
+    #[tokio::test]
+    async fn tmpfs_bytes_are_counted_as_ram() {
+        let fixture = Fixture::new();
+        let tmp_file = fixture.system_file("tmp/session/blob", &[1; 40_000]);
+        let disk_file = fixture.file(".cache/thumbnails/normal/a.png", &[2; 12_000]);
+        let expected_ram = fixtures::allocated(&fs::metadata(&tmp_file).unwrap());
+        let expected_disk = fixtures::allocated(&fs::metadata(&disk_file).unwrap());
+
+        // בלי mounts אמיתיים: <root>/tmp הוא tmpfs, כל השאר ext4
+        let mount = |point: PathBuf, fs_type: &str| mounts::MountInfo { device: fs_type.to_string(), mount_point: point, fs_type: fs_type.to_string() };
+        let mut cleaner = fake_root(&fixture, false);
+        cleaner.mounts = Arc::new(OnceLock::from(vec![
+            mount(PathBuf::from("/"), "ext4"),
+            mount(fixture.root().join("tmp"), "tmpfs"),
+        ]));
+        cleaner.run_item("tmp").await.unwrap();
+        cleaner.run_item("thumbnails").await.unwrap();
+
+        let stats = cleaner.get_stats_sync();
+        assert_eq!(stats.ram_bytes_freed, expected_ram);
+        assert_eq!(stats.disk_bytes_freed, expected_disk);
+        assert_eq!(stats.bytes_freed, expected_ram + expected_disk);
+    }
+
+    #[tokio::test]
+    async fn freeing_on_btrfs_is_noted_in_the_stats() {
+        let fixture = Fixture::new();
+        fixture.file(".cache/thumbnails/normal/a.png", &[2; 12_000]);
+        fixture.system_file("tmp/session/blob", &[1; 4_000]);
+
+        let mount = |point: PathBuf, fs_type: &str| mounts::MountInfo { device: fs_type.to_string(), mount_point: point, fs_type: fs_type.to_string() };
+        let mut cleaner = fake_root(&fixture, false);
+        cleaner.mounts = Arc::new(OnceLock::from(vec![
+            mount(PathBuf::from("/"), "ext4"),
+            mount(fixture.root().join("tmp"), "tmpfs"),
+            mount(fixture.home(), "btrfs"),
+        ]));
+        cleaner.run_item("tmp").await.unwrap();
+        cleaner.run_item("thumbnails").await.unwrap();
+        cleaner.run_item("thumbnails").await.unwrap();
+
+        let noted: Vec<PathBuf> = cleaner.get_stats_sync().snapshot_fs_mounts.into_iter().map(|m| m.mount_point).collect();
+        assert_eq!(noted, vec![fixture.home()]);
+    }
+
+    #[tokio::test]
+    async fn repeat_preview_reuses_unchanged_directory_sizes() {
+        let fixture = Fixture::new();
+        let project = fixture.python_project("proj");
+        let cache = Arc::new(SizeCache::load_from(fixture.outside().join("size-cache.json"), false));
+        let preview = || cleaner(&fixture, true).with_size_cache(Some(cache.clone()));
+
+        let first = preview();
+        first.run_item("pycache").await.unwrap();
+        let scanned = first.get_stats_sync();
+        assert_eq!(scanned.files_deleted, 3);
+
+        // קובץ שגדל עמוק בפנים לא משנה את ה-mtime של proj - הגודל הישן חוזר מהמטמון
+        let grown = project.join("app/core/__pycache__/core.cpython-311.pyc");
+        fs::write(&grown, [0x42; 90_000]).unwrap();
+        let second = preview();
+        second.run_item("pycache").await.unwrap();
+        assert_eq!(second.get_stats_sync().bytes_freed, scanned.bytes_freed);
+
+        // ילד חדש ישירות ב-proj - התיקייה נסרקת מחדש
+        fixture.file("proj/top.cpython-311.pyc", &[0x42; 3000]);
+        let third = preview();
+        third.run_item("pycache").await.unwrap();
+        assert_eq!(third.get_stats_sync().files_deleted, 4);
+        assert!(third.get_stats_sync().bytes_freed >= scanned.bytes_freed + 90_000);
+
+        // ניקוי אמיתי לא סומך על המטמון גם כשהוא זמין
+        let before = fixture.snapshot();
+        let real = cleaner(&fixture, false).with_size_cache(Some(cache.clone()));
+        real.run_item("pycache").await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+        assert_eq!(removed.len(), 4);
+        assert!(!grown.exists());
+        assert_stats_match(&real, &removed);
+    }
+
+    #[tokio::test]
+    async fn keep_newest_spares_newest_files_with_path_tiebreak() {
+        let fixture = Fixture::new();
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let aged = |name: &str, age: Duration| {
+            let path = fixture.file(&format!(".cache/pip/http/{}", name), &[9; 5000]);
+            fs::File::options().write(true).open(&path).unwrap().set_modified(now - age).unwrap();
+            path
+        };
+        let oldest = aged("0/old.whl", day * 10);
+        let tied: Vec<PathBuf> = ["c/x.whl", "a/x.whl", "b/x.whl"].iter().map(|name| aged(name, day)).collect();
+        let before = fixture.snapshot();
+
+        // שתי תצוגות מקדימות ואז ניקוי - כולם מסכימים מה נשאר
+        for _ in 0..2 {
+            let dry = cleaner(&fixture, true).with_keep_newest(Some(2));
+            dry.run_item("pip_cache").await.unwrap();
+            assert_eq!(dry.get_stats_sync().files_deleted, 2);
+        }
+        let real = cleaner(&fixture, false).with_keep_newest(Some(2));
+        real.run_item("pip_cache").await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert_eq!(removed.keys().collect::<Vec<_>>(), vec![&oldest, &tied[0]]);
+        assert!(tied[1].exists() && tied[2].exists());
+        assert_stats_match(&real, &removed);
+    }
+
+    #[tokio::test]
+    async fn keep_recent_spares_fresh_browser_cache_on_top_of_min_age() {
+        let fixture = Fixture::new();
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let aged = |name: &str, age: Duration| {
+            let path = fixture.file(&format!(".config/google-chrome/Default/Cache/{}", name), &[7; 4000]);
+            fs::File::options().write(true).open(&path).unwrap().set_modified(now - age).unwrap();
+            path
+        };
+        let today = aged("f_today", Duration::from_secs(60));
+        let two_days = aged("f_two_days", day * 2);
+        let old = aged("f_old", day * 30);
+        let before = fixture.snapshot();
+
+        // הגיל הגלובלי (שעה) קצר מזה של הפריט (שבוע) - הארוך מנצח
+        let real = cleaner(&fixture, false)
+            .with_min_age(Some(Duration::from_secs(60 * 60)))
+            .with_keep_recent(Some(day * 7));
+        assert_eq!(real.clean_options().min_age, Some(day * 7));
+        real.run_item("chrome_cache").await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert_eq!(removed.keys().collect::<Vec<_>>(), vec![&old]);
+        assert!(today.exists() && two_days.exists());
+        assert_stats_match(&real, &removed);
+    }
+
+    #[tokio::test]
+    async fn clean_options_exclusions_apply_per_call() {
+        let fixture = Fixture::new();
+        let kept = fixture.file(".cache/app/keep/a.bin", &[1; 100]);
+        let gone = fixture.file(".cache/app/b.bin", &[2; 200]);
+        let real = cleaner(&fixture, false);
+        let options = CleanOptions {
+            exclusions: Arc::new(Exclusions::from_paths(vec![kept.parent().unwrap().to_path_buf()])),
+            ..real.clean_options()
+        };
+
+        real.clean_directory_contents(fixture.home().join(".cache/app"), "Test", options).await.unwrap();
+
+        assert!(kept.exists());
+        assert!(!gone.exists());
+        assert_eq!(real.get_stats_sync().files_deleted, 1);
+    }
+
+    #[test]
+    fn cache_is_stale_only_when_a_source_changed_after_it() {
+        let fixture = Fixture::new();
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(60 * 60);
+        let at = |path: &Path, age: Duration| fs::File::options().write(true).open(path).unwrap().set_modified(now - age).unwrap();
+        let cache = fixture.file(".local/share/mime/mime.cache", b"cache");
+        let package = fixture.file(".local/share/mime/packages/app.xml", b"<mime-info/>");
+        let packages = vec![package.parent().unwrap().to_path_buf()];
+        let set_dir_mtime = |age: Duration| fs::File::open(&packages[0]).unwrap().set_modified(now - age).unwrap();
+
+        at(&cache, hour);
+        at(&package, hour * 2);
+        set_dir_mtime(hour * 2);
+        assert!(!is_stale_relative_to(&cache, &packages));
+
+        at(&package, Duration::ZERO);
+        assert!(is_stale_relative_to(&cache, &packages));
+
+        // בלי cache או בלי מקורות אין מה לבנות מחדש
+        assert!(!is_stale_relative_to(&fixture.home().join("missing.cache"), &packages));
+        assert!(!is_stale_relative_to(&cache, &[fixture.home().join("no-such-dir")]));
+    }
+
+    #[tokio::test]
+    async fn fonts_item_removes_stale_caches_and_skips_fresh_ones() {
+        let fixture = Fixture::new();
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(60 * 60);
+        let at = |path: &Path, age: Duration| fs::File::options().write(true).open(path).unwrap().set_modified(now - age).unwrap();
+        let at_dir = |path: &Path, age: Duration| fs::File::open(path).unwrap().set_modified(now - age).unwrap();
+
+        // MIME: package חדש מה-cache - ישן
+        let mime_cache = fixture.file(".local/share/mime/mime.cache", &[1; 3000]);
+        let package = fixture.file(".local/share/mime/packages/app.xml", b"<mime-info/>");
+        at(&mime_cache, hour * 2);
+        at(&package, hour);
+        // ערכת סמלים: ה-cache נכתב אחרי כל הקבצים (וה-rename לתוכה קידם את התיקייה) - עדכני
+        let index = fixture.file(".local/share/icons/Mine/index.theme", b"[Icon Theme]\n");
+        let icon = fixture.file(".local/share/icons/Mine/16x16/app.png", &[2; 500]);
+        let icon_cache = fixture.file(".local/share/icons/Mine/icon-theme.cache", &[3; 4000]);
+        for path in [&index, &icon] {
+            at(path, hour * 3);
+        }
+        at_dir(icon.parent().unwrap(), hour * 3);
+        at(&icon_cache, hour * 2);
+        at_dir(icon_cache.parent().unwrap(), hour);
+        // fontconfig: אין גופנים חדשים ממנו - עדכני
+        let font = fixture.file(".local/share/fonts/Font.ttf", &[4; 800]);
+        let font_cache = fixture.file(".cache/fontconfig/abc-le64.cache-9", &[5; 2000]);
+        at(&font, hour * 3);
+        at_dir(font.parent().unwrap(), hour * 3);
+        at_dir(font_cache.parent().unwrap(), hour);
+
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let sink = logs.clone();
+        let dry = SystemCleaner::new(false, true)
+            .with_paths(SystemPaths::new(fixture.root(), fixture.home()))
+            .with_callback(Arc::new(Mutex::new(Box::new(move |line: String| sink.lock().unwrap().push(line)) as Box<dyn Fn(String) + Send + Sync>)));
+        dry.run_item("fonts").await.unwrap();
+
+        let stats = dry.get_stats_sync();
+        assert_eq!(stats.files_deleted, 1);
+        assert_eq!(stats.bytes_freed, fixtures::allocated(&fs::metadata(&mime_cache).unwrap()));
+        let logs = logs.lock().unwrap();
+        for fresh in [icon_cache.as_path(), font_cache.parent().unwrap()] {
+            let line = tr_args("log.cache_up_to_date", &[&fresh.display().to_string()]);
+            assert!(logs.contains(&line), "{:?}", logs);
+        }
+    }
+
+    #[tokio::test]
+    async fn session_protection_skips_desktop_caches_in_system_cache() {
+        let fixture = Fixture::new();
+        let dconf = fixture.file(".cache/dconf/user", &[1; 2000]);
+        let keyring = fixture.file(".cache/keyring-AB12/control", &[2; 300]);
+        let app = fixture.file(".cache/someapp/blob", &[3; 5000]);
+        // מה שנסרק אחרי תיקייה מוגנת (readdir לא ממוין) חייב להתרוקן גם הוא - דילוג על המוגנת לא מדלג על האחים שלה
+        let others = [".cache/aaa/x", ".cache/dconf-other/y", ".cache/zzz/deep/z", ".cache/loose.bin"].map(|p| fixture.file(p, &[4; 100]));
+        let protected = || {
+            let mut cleaner = SystemCleaner::new(false, false)
+                .with_paths(SystemPaths::new(fixture.root(), fixture.home()))
+                .with_session_protected(&["dconf", "keyring-*"]);
+            cleaner.session_protection = true;
+            cleaner
+        };
+
+        let real = protected();
+        real.run_item("var_cache").await.unwrap();
+        assert!(dconf.exists() && keyring.exists());
+        assert!(!app.exists());
+        assert!(others.iter().all(|p| !p.exists()), "{:?}", others);
+        let protected_bytes = dir_usage(&[fixture.home().join(".cache/dconf"), fixture.home().join(".cache/keyring-AB12")]).1;
+        let note = tr_args("summary.session_protected", &[&SystemCleaner::format_bytes(protected_bytes)]);
+        assert_eq!(real.summary_notes(), vec![note]);
+
+        // "I know what I'm doing" - הכל מתרוקן
+        let mut unprotected = protected();
+        unprotected.session_protection = false;
+        unprotected.run_item("var_cache").await.unwrap();
+        assert!(!dconf.exists() && !keyring.exists());
+        assert!(unprotected.summary_notes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn screen_leftovers_cleans_caches_and_reviews_old_media_only_when_ticked() {
+        let fixture = Fixture::new();
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let aged = |relative: &str, age: Duration| {
+            let path = fixture.file(relative, &[8; 6000]);
+            fs::File::options().write(true).open(&path).unwrap().set_modified(now - age).unwrap();
+            path
+        };
+        let cached = aged(".cache/gnome-screenshot/tmp.png", Duration::ZERO);
+        let old_cast = aged("Videos/Screencasts/Screencast from 2026-01-02.webm", day * 90);
+        let new_cast = aged("Videos/Screencasts/Screencast from today.webm", day);
+        let old_shot = aged("Pictures/Screenshots/Screenshot 1.png", day * 40);
+        let preview = PreviewStore::default();
+        let make = |dry_run, parts: &[&str]| {
+            cleaner(&fixture, dry_run)
+                .with_preview(preview.clone())
+                .with_sub_entries(parts.iter().map(|p| p.to_string()).collect())
+        };
+
+        // ברירת המחדל: רק ה-cache
+        let real = make(false, &["cache"]);
+        real.run_item("screen_leftovers").await.unwrap();
+        assert!(!cached.exists());
+        assert!(old_cast.exists() && new_cast.exists() && old_shot.exists());
+
+        // המדיה: תצוגה מקדימה מפרטת את הישנים, הניקוי מוחק רק את מה שנשאר מסומן
+        let dry = make(true, &["cache", "media"]);
+        dry.run_item("screen_leftovers").await.unwrap();
+        let listed: Vec<PathBuf> = preview.lock()["screen_leftovers"].iter().map(|e| e.path.clone()).collect();
+        assert_eq!(listed, vec![old_cast.clone(), old_shot.clone()]);
+        for entry in preview.lock().get_mut("screen_leftovers").unwrap().iter_mut() {
+            entry.selected = entry.path == old_cast;
+        }
+        make(false, &["cache", "media"]).run_item("screen_leftovers").await.unwrap();
+        assert!(!old_cast.exists());
+        assert!(new_cast.exists() && old_shot.exists());
+    }
+
+    #[tokio::test]
+    async fn panicking_item_fails_without_taking_down_the_run() {
+        let fixture = Fixture::new();
+        fixture.trash_item("old.txt", b"old");
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let sink = logs.clone();
+        let mut cleaner = cleaner(&fixture, false)
+            .with_callback(Arc::new(Mutex::new(Box::new(move |line: String| sink.lock().unwrap().push(line)) as Box<dyn Fn(String) + Send + Sync>)));
+        cleaner.item_id = "mock".to_string();
+
+        let result = cleaner.catch_item_panic(Box::pin(async { panic!("unwrap on a weird path") })).await;
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("mock") && error.contains("unwrap on a weird path"), "{}", error);
+        assert!(logs.lock().unwrap().iter().any(|line| line.contains("unwrap on a weird path")));
+
+        // הפריט הבא רץ כרגיל
+        cleaner.run_item("trash").await.unwrap();
+        assert_eq!(cleaner.get_stats_sync().files_deleted, 2);
+    }
+
+    #[test]
+    fn parallel_counting_loses_nothing() {
+        let cleaner = SystemCleaner::new(false, true);
+        let workers: Vec<_> = (0..8).map(|_| {
+            let cleaner = cleaner.clone();
+            std::thread::spawn(move || {
+                for _ in 0..10_000 {
+                    cleaner.record_file(3);
+                    cleaner.record_error();
+                }
+                cleaner.live.directories_cleaned.fetch_add(1, Ordering::Relaxed);
+            })
+        }).collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        let stats = cleaner.get_stats_sync();
+        assert_eq!(stats.files_deleted, 80_000);
+        assert_eq!(stats.bytes_freed, 240_000);
+        assert_eq!(stats.disk_bytes_freed, 240_000);
+        assert_eq!(stats.errors, 80_000);
+        assert_eq!(stats.directories_cleaned, 8);
+    }
+
+    // cargo test --release -- --ignored --nocapture counters_over_100k_files
+    // מחיקה של 100k קבצים, ואז רק הספירה: atomics מול ה-Mutex<CleaningStats> שהיה קודם,
+    // 8 threads שסופרים ו-thread שלוקח תמונה בלי הפסקה (ה-GUI בכל frame).
+    // רק מדפיס - על מכונה עם ליבה אחת אין תחרות על ה-lock ושני המספרים כמעט שווים
+    #[tokio::test]
+    #[ignore]
+    async fn counters_over_100k_files() {
+        const FILES: usize = 100_000;
+        let fixture = Fixture::new();
+        for i in 0..FILES {
+            fixture.file(&format!(".cache/bench/{}/{}", i % 100, i), b"x");
+        }
+        let real = cleaner(&fixture, false);
+        let started = Instant::now();
+        real.clean_directory_contents(fixture.home().join(".cache/bench"), "Test", real.clean_options()).await.unwrap();
+        let deleting = started.elapsed();
+        assert_eq!(real.get_stats_sync().files_deleted, FILES as u64);
+
+        fn timed(record: impl Fn() + Sync, snapshot: impl Fn() + Sync) -> Duration {
+            let done = std::sync::atomic::AtomicBool::new(false);
+            let started = Instant::now();
+            std::thread::scope(|scope| {
+                scope.spawn(|| {
+                    while !done.load(Ordering::Relaxed) {
+                        snapshot();
+                    }
+                });
+                let workers: Vec<_> = (0..8).map(|_| scope.spawn(|| (0..FILES / 8).for_each(|_| record()))).collect();
+                for worker in workers {
+                    worker.join().unwrap();
+                }
+                done.store(true, Ordering::Relaxed);
+            });
+            started.elapsed()
+        }
+        let locked = Mutex::new(CleaningStats::new());
+        let with_lock = timed(
+            || {
+                let mut stats = locked.lock().unwrap();
+                stats.files_deleted += 1;
+                stats.bytes_freed += 4096;
+            },
+            || drop(locked.lock().unwrap().clone()),
+        );
+        let counting = SystemCleaner::new(false, true);
+        let with_atomics = timed(|| counting.record_file(4096), || drop(counting.live.snapshot()));
+        assert_eq!(counting.get_stats_sync().files_deleted, FILES as u64);
+
+        let per_file = |total: Duration| format!("{:.1}ns", total.as_nanos() as f64 / FILES as f64);
+        println!("delete: {} per file", per_file(deleting));
+        println!("count:  mutex {} / atomics {} per file", per_file(with_lock), per_file(with_atomics));
+    }
+
+    #[tokio::test]
+    async fn stale_autostart_keeps_enabled_units() {
+        let fixture = Fixture::new();
+        fixture.system_file("usr/bin/present", b"#!/bin/sh\n");
+        let gone = fixture.file(".config/autostart/gone.desktop", b"[Desktop Entry]\nName=Gone App\nExec=/opt/gone/bin/app --minimized\n");
+        let alive = fixture.file(".config/autostart/alive.desktop", b"[Desktop Entry]\nName=Alive\nExec=env A=1 present %U\n");
+        let dead = fixture.file(".config/systemd/user/dead.service", b"[Service]\nExecStart=-/usr/bin/vanished --serve\n");
+        let enabled = fixture.file(".config/systemd/user/enabled.service", b"[Service]\nExecStart=vanished\n");
+        let wants = fixture.home().join(".config/systemd/user/default.target.wants");
+        fs::create_dir_all(&wants).unwrap();
+        std::os::unix::fs::symlink(&enabled, wants.join("enabled.service")).unwrap();
+
+        let preview = PreviewStore::default();
+        let dry = fake_root(&fixture, true).with_preview(preview.clone());
+        dry.run_item("stale_autostart").await.unwrap();
+        let listed = preview.lock().get("stale_autostart").cloned().unwrap();
+        let listed: Vec<(&Path, bool, &str)> = listed.iter().map(|e| (e.path.as_path(), e.selected, e.detail.as_str())).collect();
+        assert_eq!(listed, vec![
+            (gone.as_path(), true, "Gone App: /opt/gone/bin/app not found"),
+            (dead.as_path(), true, "dead.service: /usr/bin/vanished not found"),
+            (enabled.as_path(), false, "enabled.service: vanished not found (enabled - kept)"),
+        ]);
+        assert_eq!(dry.get_stats_sync().files_deleted, 2);
+
+        // גם אם המשתמש סימן את ה-unit המופעל - הוא לא נמחק
+        for entry in preview.lock().get_mut("stale_autostart").unwrap() {
+            entry.selected = true;
+        }
+        let real = fake_root(&fixture, false).with_preview(preview.clone());
+        real.run_item("stale_autostart").await.unwrap();
+        assert!(!gone.exists() && !dead.exists());
+        assert!(alive.exists() && enabled.exists());
+        assert_eq!(real.get_stats_sync().files_deleted, 2);
+    }
+
+    const TAG: &[u8] = b"Signature: 8a477f597d28d172789f06886806bc55\n# This file is a cache directory tag.\n";
+
+    #[tokio::test]
+    async fn keep_markers_and_cache_tags_survive_emptying() {
+        let fixture = Fixture::new();
+        let cache = fixture.home().join(".cache/app");
+        let junk = fixture.file(".cache/app/blob", &[1; 4000]);
+        let tag = fixture.file(".cache/app/CACHEDIR.TAG", TAG);
+        let kept = fixture.file(".cache/app/models/.supercleaner-keep", b"");
+        let kept_deep = fixture.file(".cache/app/models/v2/weights.bin", &[2; 4000]);
+        let nested_keep = fixture.file(".cache/app/a/b/.supercleaner-keep", b"");
+        let nested_kept = fixture.file(".cache/app/a/b/data", &[3; 4000]);
+        let beside_keep = fixture.file(".cache/app/a/loose", &[4; 4000]);
+        let before = fixture.snapshot();
+
+        let first = cleaner(&fixture, false);
+        first.clean_target(dir_target(&cache), "Cache").await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert_eq!(removed.keys().collect::<Vec<_>>(), vec![&beside_keep, &junk]);
+        assert!(tag.exists() && kept.exists() && kept_deep.exists() && nested_keep.exists() && nested_kept.exists());
+        assert_stats_match(&first, &removed);
+
+        // סימון על התיקייה עצמה - לא נוגעים בכלום
+        fixture.file(".cache/app/.supercleaner-keep", b"");
+        fixture.file(".cache/app/new", b"x");
+        let untouched = cleaner(&fixture, false);
+        untouched.clean_target(dir_target(&cache), "Cache").await.unwrap();
+        assert_eq!(untouched.get_stats_sync().files_deleted, 0);
+    }
+
+    #[tokio::test]
+    async fn cache_sweep_empties_only_signed_tagged_dirs() {
+        let fixture = Fixture::new();
+        fixture.file("code/proj/target/CACHEDIR.TAG", TAG);
+        let build = fixture.file("code/proj/target/debug/app", &[5; 8000]);
+        // תג מקונן בתוך תיקייה שכבר נמצאה - לא נספר פעמיים, וגם הוא נשאר
+        let inner_tag = fixture.file("code/proj/target/debug/incremental/CACHEDIR.TAG", TAG);
+        let inner = fixture.file("code/proj/target/debug/incremental/state", &[6; 3000]);
+        let source = fixture.file("code/proj/src/main.rs", b"fn main() {}\n");
+        let unsigned = fixture.file("notes/CACHEDIR.TAG", b"just a note\n");
+        let note = fixture.file("notes/todo.txt", b"buy milk\n");
+        let before = fixture.snapshot();
+
+        let dry = cleaner(&fixture, true);
+        dry.run_item("cachedir_sweep").await.unwrap();
+        let real = cleaner(&fixture, false);
+        real.run_item("cachedir_sweep").await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert_eq!(removed.keys().collect::<Vec<_>>(), vec![&build, &inner]);
+        assert!(inner_tag.exists() && source.exists() && unsigned.exists() && note.exists());
+        assert_eq!(dry.get_stats_sync().files_deleted, 2);
+        assert_stats_match(&real, &removed);
+    }
+
+    #[tokio::test]
+    async fn trash_preview_shows_origins_and_honors_selection() {
+        let fixture = Fixture::new();
+        let kept = fixture.trash_item("keep.pdf", &[1; 4096]);
+        let dropped = fixture.trash_item("drop.pdf", &[2; 4096]);
+        let orphan = fixture.file(".local/share/Trash/files/orphan.bin", &[3; 100]);
+        let info = |name: &str| fixture.home().join(format!(".local/share/Trash/info/{}.trashinfo", name));
+
+        let dry = cleaner(&fixture, true);
+        dry.run_item("trash").await.unwrap();
+        let entries = dry.preview.lock().get("trash").cloned().unwrap();
+        assert_eq!(entries.iter().map(|e| &e.path).collect::<Vec<_>>(), [&dropped, &kept, &orphan]);
+        assert!(entries[1].title.contains("/home/user/keep.pdf") && entries[1].title.contains("2024-01-01"));
+        assert!(entries[1].warning.is_empty());
+        // בלי .trashinfo: אין מיקום מקורי להציג, רק אזהרה
+        assert!(entries[2].title.is_empty() && !entries[2].warning.is_empty());
+        assert!(entries.iter().all(|e| e.group == entries[0].group));
+
+        for entry in dry.preview.lock().get_mut("trash").unwrap() {
+            entry.selected = entry.path != kept;
+        }
+        let before = fixture.snapshot();
+        let real = cleaner(&fixture, false).with_preview(dry.preview.clone());
+        real.run_item("trash").await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert!(kept.exists() && info("keep.pdf").exists());
+        assert!(!dropped.exists() && !info("drop.pdf").exists() && !orphan.exists());
+        assert_eq!(removed.len(), 3);
+        assert_stats_match(&real, &removed);
+        assert_eq!(dry.preview.lock().get("trash").map(|e| e.len()), Some(1));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn read_only_parent_is_opened_only_when_allowed() {
+        use std::os::unix::fs::PermissionsExt;
+        let fixture = Fixture::new();
+        let locked = fixture.file(".cache/app/locked/blob.bin", &[5; 4096]);
+        let dir = locked.parent().unwrap().to_path_buf();
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o555)).unwrap();
+
+        // root עוקף הרשאות תיקייה, אז בלי התיקון רק משתמש רגיל נתקע
+        if !is_root() {
+            let plain = cleaner(&fixture, false);
+            plain.clean_directory_contents(fixture.home().join(".cache/app"), "Test", plain.clean_options()).await.unwrap();
+            assert!(locked.exists());
+            assert_eq!(plain.get_stats_sync().skipped_needs_root, 1);
+        }
+
+        let fixing = cleaner(&fixture, false).with_fix_permissions(true);
+        fixing.clean_directory_contents(fixture.home().join(".cache/app"), "Test", fixing.clean_options()).await.unwrap();
+        assert!(!locked.exists());
+        assert_eq!(fixing.get_stats_sync().files_deleted, 1);
+        assert_eq!(fs::metadata(&dir).unwrap().permissions().mode() & 0o777, 0o555);
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlinked_cache_root_is_walked_at_its_real_location() {
+        let fixture = Fixture::new();
+        let now = SystemTime::now();
+        let scratch = fixture.outside().join("scratch-cache");
+        let aged = |relative: &str, mtime: SystemTime| {
+            let path = scratch.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, [3; 2000]).unwrap();
+            fs::File::options().write(true).open(&path).unwrap().set_modified(mtime).unwrap();
+            path
+        };
+        let month_ago = now - Duration::from_secs(30 * 24 * 60 * 60);
+        let old = aged("app/blob.bin", month_ago);
+        let kept = aged("keep/data.bin", month_ago);
+        // שעון של שרת NFS שרץ קדימה - נחשב "עכשיו", לא "ישן מאוד"
+        let skewed = aged("app/skewed.bin", now + Duration::from_secs(24 * 60 * 60));
+        std::os::unix::fs::symlink(&scratch, fixture.home().join(".cache")).unwrap();
+        let before = fixture.snapshot();
+
+        // ההחרגה בנתיב שהמשתמש רואה, השורש נפתר ל-scratch
+        let real = cleaner(&fixture, false)
+            .with_min_age(Some(Duration::from_secs(60 * 60)))
+            .with_exclusions(vec![fixture.home().join(".cache/keep")]);
+        real.clean_directory_contents(fixture.home().join(".cache"), "Test", real.clean_options()).await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+
+        assert!(!old.exists() && kept.exists() && skewed.exists());
+        assert!(fixture.home().join(".cache").is_symlink());
+        assert_eq!(removed.len(), 1);
+        assert_stats_match(&real, &removed);
+        // אימות המקום הפנוי מסתכל על ה-mount של היעד, לא של הבית
+        assert_eq!(real.touched_roots(), vec![fs::canonicalize(&scratch).unwrap()]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn preview_counts_what_it_could_not_read() {
+        use std::os::unix::fs::PermissionsExt;
+        // root קורא הכל - אין מה לספור
+        if is_root() {
+            return;
+        }
+        let fixture = Fixture::new();
+        fixture.file(".cache/app/open/a.bin", &[1; 1000]);
+        let hidden = fixture.file(".cache/app/private/b.bin", &[2; 1000]);
+        let private = hidden.parent().unwrap().to_path_buf();
+        fs::set_permissions(&private, fs::Permissions::from_mode(0o000)).unwrap();
+
+        let dry = cleaner(&fixture, true);
+        dry.clean_directory_contents(fixture.home().join(".cache/app"), "Test", dry.clean_options()).await.unwrap();
+        fs::set_permissions(&private, fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(dry.get_stats_sync().bytes_freed, 1000);
+        assert_eq!(dry.unmeasured.count(unmeasured::Reason::Permission), 1);
+        assert_eq!(dry.unmeasured.denied_paths(), vec![private]);
+        let line = dry.unmeasured.summary_line(tr_args, SystemCleaner::format_bytes).unwrap();
+        assert!(line.contains('?'), "{}", line);
+
+        // ריצה אמיתית לא מוסיפה לשורה של התצוגה המקדימה
+        let real = cleaner(&fixture, false);
+        real.clean_directory_contents(fixture.home().join(".cache/app"), "Test", real.clean_options()).await.unwrap();
+        assert_eq!(real.unmeasured.total(), 0);
+    }
+
+    #[tokio::test]
+    async fn broken_shortcuts_are_removed_and_working_ones_kept() {
+        let fixture = Fixture::new();
+        let broken = fixture.file(".local/share/applications/gone.desktop", b"[Desktop Entry]\nName=Gone\nExec=/opt/definitely-gone/bin/gone %U\n");
+        let working = fixture.file(".local/share/applications/shell.desktop", b"[Desktop Entry]\nName=Shell\nExec=sh -c true\n");
+        let hidden = fixture.file(".local/share/applications/off.desktop", b"[Desktop Entry]\nHidden=true\nExec=/opt/definitely-gone/bin/x\n");
+
+        let dry = cleaner(&fixture, true);
+        dry.run_item("broken_desktop").await.unwrap();
+        assert!(broken.exists());
+        assert_eq!(dry.get_stats_sync().files_deleted, 1);
+
+        let real = cleaner(&fixture, false);
+        real.run_item("broken_desktop").await.unwrap();
+        assert!(!broken.exists());
+        assert!(working.exists() && hidden.exists());
+        // כלי הרענון אולי לא מותקן כאן - אבל גם כשהוא נכשל, הפריט עצמו הצליח
+        assert!(real.summary_notes().len() <= 2);
+    }
+
+    #[tokio::test]
+    async fn own_data_is_trimmed_without_touching_the_active_run() {
+        let fixture = Fixture::new();
+        let data = format!(".local/share/{}", APP_DIR_NAME);
+        let history: String = (0..5).map(|i| format!("{{\"run\":{}}}\n", i)).collect();
+        let history_path = fixture.file(&format!("{}/{}", data, history::HISTORY_FILE), history.as_bytes());
+        let old_manifest = fixture.file(&format!("{}/manifests/20240101-000000.csv", data), b"timestamp,item,path,size,mtime\n");
+        let new_manifest = fixture.file(&format!("{}/manifests/20240301-000000.csv", data), b"timestamp,item,path,size,mtime\n");
+        let long_ago = SystemTime::now() - Duration::from_secs(90 * 24 * 60 * 60);
+        fs::File::options().write(true).open(&old_manifest).unwrap().set_modified(long_ago).unwrap();
+        for run in ["20240101-000000", "20240301-000000"] {
+            fixture.file(&format!("{}/quarantine/{}/manifest.jsonl", data, run), b"{\"original\":\"/x\",\"stored\":\"/y\",\"size\":800}\n");
+            fixture.file(&format!("{}/quarantine/{}/files/x", data, run), &[1; 800]);
+        }
+        let quarantine = fixture.home().join(&data).join("quarantine");
+        // שתי ריצות של 800 מעל תקרה של 1000 - הישנה הולכת, אלא אם היא ההסגר של הריצה הנוכחית
+        let policy = OwnDataPolicy { history_max_runs: 2, manifest_max_age_days: 30, quarantine_max_age_days: 7, quarantine_max_bytes: 1000 };
+
+        let active = cleaner(&fixture, false).with_own_data(policy.clone());
+        active.purge_quarantine(&quarantine, Some("20240101-000000"));
+        assert!(quarantine.join("20240101-000000/files/x").exists());
+
+        let dry = cleaner(&fixture, true).with_own_data(policy.clone());
+        dry.run_item("app_data").await.unwrap();
+        assert_eq!(fs::read_to_string(&history_path).unwrap(), history);
+        assert!(old_manifest.exists());
+        assert_eq!(dry.get_stats_sync().files_deleted, 3);
+
+        let real = cleaner(&fixture, false).with_own_data(policy);
+        real.run_item("app_data").await.unwrap();
+        assert_eq!(fs::read_to_string(&history_path).unwrap(), "{\"run\":3}\n{\"run\":4}\n");
+        assert!(!old_manifest.exists() && new_manifest.exists());
+        assert!(!quarantine.join("20240101-000000").exists());
+        assert!(quarantine.join("20240301-000000/files/x").exists());
+    }
+
+    #[tokio::test]
+    async fn parallel_emptying_matches_what_left_the_disk() {
+        let fixture = Fixture::new();
+        for dir in 0..20 {
+            for file in 0..25 {
+                fixture.file(&format!(".cache/big/{}/{}/{}.bin", dir % 4, dir, file), &vec![7; 100 + file * 37]);
+            }
+        }
+        fixture.file(".cache/big/CACHEDIR.TAG", CACHEDIR_SIGNATURE);
+        fixture.file(".cache/big/kept/.supercleaner-keep", b"");
+        let kept = fixture.file(".cache/big/kept/precious.bin", &[1; 500]);
+        let before = fixture.snapshot();
+
+        let mut dry = cleaner(&fixture, true).with_walk_threads(4);
+        dry.is_rotational = |_| false;
+        dry.clean_directory_contents(fixture.home().join(".cache/big"), "Test", dry.clean_options()).await.unwrap();
+        assert_eq!(dry.get_stats_sync().files_deleted, 500);
+
+        let mut real = cleaner(&fixture, false).with_walk_threads(4);
+        real.is_rotational = |_| false;
+        real.clean_directory_contents(fixture.home().join(".cache/big"), "Test", real.clean_options()).await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+        assert_eq!(removed.len(), 500);
+        assert!(kept.exists() && fixture.home().join(".cache/big/CACHEDIR.TAG").exists());
+        assert_stats_match(&real, &removed);
+        assert_eq!((dry.get_stats_sync().directories_cleaned, real.get_stats_sync().directories_cleaned), (24, 24));
+    }
+
+    #[tokio::test]
+    async fn firefox_leftovers_keep_session_backups_unless_chosen() {
+        let fixture = Fixture::new();
+        let profile = fixture.firefox_profile("abcd1234");
+        let profile_file = |relative: &str, size: usize| fixture.file(&format!(".mozilla/firefox/abcd1234.default-release/{}", relative), &vec![9; size]);
+        let crash = profile_file("crashes/events/1f2e3d", 300);
+        let dump = profile_file("minidumps/1f2e3d.dmp", 4000);
+        let ping = profile_file("datareporting/archived/2024-01/ping.jsonlz4", 700);
+        let backup = profile_file("sessionstore-backups/previous.jsonlz4", 2000);
+
+        let dry = cleaner(&fixture, true);
+        dry.run_item("firefox_leftovers").await.unwrap();
+        let entries = dry.preview.lock().get("firefox_leftovers").cloned().unwrap();
+        assert_eq!(entries.len(), 4);
+        let groups: HashSet<&str> = entries.iter().map(|e| e.group.as_str()).collect();
+        assert_eq!(groups.len(), 3);
+        assert!(entries.iter().all(|e| e.selected == (e.path != backup)));
+        assert_eq!(dry.get_stats_sync().files_deleted, 3);
+
+        // בלי תצוגה מקדימה - רק מה שמסומן מראש
+        let real = cleaner(&fixture, false);
+        real.run_item("firefox_leftovers").await.unwrap();
+        assert!(!crash.exists() && !dump.exists() && !ping.exists());
+        assert!(backup.exists());
+        assert!(profile.join("places.sqlite").exists() && profile.join("prefs.js").exists());
+
+        // המשתמש סימן את הגיבוי בעץ
+        let chosen = cleaner(&fixture, false);
+        chosen.preview.set("firefox_leftovers", vec![PreviewEntry::new(backup.clone(), 2000, fs::metadata(&backup).unwrap().modified().ok())]);
+        chosen.run_item("firefox_leftovers").await.unwrap();
+        assert!(!backup.exists());
+    }
+
+    #[tokio::test]
+    async fn site_storage_lists_origins_and_spares_kept_sites() {
+        let fixture = Fixture::new();
+        let storage = ".config/chromium/Default/Service Worker/CacheStorage";
+        let site = |hash: &str, origin: &str, size: usize| {
+            let mut index = vec![(2 << 3) | 2, origin.len() as u8];
+            index.extend_from_slice(origin.as_bytes());
+            fixture.file(&format!("{}/{}/index.txt", storage, hash), &index);
+            fixture.file(&format!("{}/{}/c0ffee/0123_0", storage, hash), &vec![3; size]);
+            fixture.home().join(storage).join(hash)
+        };
+        let pwa = site("aa11", "https://app.keep.example/", 5000);
+        let news = site("bb22", "https://news.example.org/", 9000);
+        let script_cache = fixture.file(".config/chromium/Default/Service Worker/ScriptCache/index", &[1; 100]);
+
+        let dry = cleaner(&fixture, true).with_keep_sites(vec!["keep.example".to_string()]);
+        dry.run_item("site_storage").await.unwrap();
+        let entries = dry.preview.lock().get("site_storage").cloned().unwrap();
+        assert_eq!(entries.iter().map(|e| (e.path.clone(), e.selected)).collect::<Vec<_>>(), vec![(news.clone(), true), (pwa.clone(), false)]);
+        assert!(entries[0].title.contains("https://news.example.org"));
+
+        // Chromium אולי פתוח במכונה שמריצה את הבדיקות - אז לא נמחק כלום
+        let real = cleaner(&fixture, false).with_keep_sites(vec!["keep.example".to_string()]);
+        real.run_item("site_storage").await.unwrap();
+        if !is_process_running(chromium::BROWSERS[1].processes) {
+            assert!(!news.exists());
+        }
+        assert!(pwa.join("index.txt").exists());
+        assert!(script_cache.exists());
+    }
+
+    #[test]
+    fn encrypted_home_internals_are_protected_and_never_walked() {
+        let home = dirs::home_dir().unwrap();
+        assert!(is_protected_path(&home.join(".Private")));
+        assert!(is_protected_path(&home.join(".ecryptfs/wrapped-passphrase")));
+
+        let fixture = Fixture::new();
+        fixture.file(".Private/ECRYPTFS_FNEK_ENCRYPTED.FWYg", b"x");
+        fs::create_dir_all(fixture.home().join(".ecryptfs")).unwrap();
+        std::os::unix::fs::symlink("/nonexistent/.Private", fixture.home().join(".ecryptfs/Private.mnt")).unwrap();
+        std::os::unix::fs::symlink("/nonexistent/target", fixture.home().join("stale-link")).unwrap();
+        let found = cleaner(&fixture, true).find_broken_symlinks(&fixture.home());
+        let paths: Vec<&Path> = found.iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(paths, vec![fixture.home().join("stale-link").as_path()]);
+    }
+
+    #[test]
+    fn name_too_long_is_told_apart() {
+        assert!(is_name_too_long(&io::Error::from_raw_os_error(libc::ENAMETOOLONG)));
+        assert!(!is_name_too_long(&io::Error::from_raw_os_error(libc::EACCES)));
+        assert!(!is_protected_by_os(&io::Error::from_raw_os_error(libc::ENAMETOOLONG)));
+    }
+
+    #[tokio::test]
+    async fn backup_files_in_git_repos_go_only_when_ignored() {
+        let fixture = Fixture::new();
+        let repo = fixture.home().join("src/app");
+        fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| std::process::Command::new("git").arg("-C").arg(&repo).args(args).output().is_ok_and(|o| o.status.success());
+        fixture.file("src/app/.gitignore", b"*.bak\n");
+        let ignored = fixture.file("src/app/old.bak", b"old");
+        let tracked = fixture.file("src/app/fixture.bak", b"kept on purpose");
+        let outside = fixture.file("notes/todo.bak", b"todo");
+        // בלי git: רק .git, וכל מה שבתוך ה-repo נשאר
+        let has_git = git(&["init", "-q"]) && git(&["add", "-f", "fixture.bak"]);
+        if !has_git {
+            fs::create_dir_all(repo.join(".git")).unwrap();
+        }
+
+        let cleaner = cleaner(&fixture, false);
+        cleaner.run_item("backup_files").await.unwrap();
+
+        assert!(tracked.exists());
+        assert!(!outside.exists());
+        assert_eq!(ignored.exists(), !has_git);
+        let stats = cleaner.get_stats_sync();
+        assert_eq!((stats.git_verified, stats.git_skipped), if has_git { (1, 1) } else { (0, 2) });
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn hooks_report_through_stats_and_fail_on_nonzero_exit() {
+        use std::os::unix::fs::PermissionsExt;
+        let fixture = Fixture::new();
+        let junk = fixture.file("cache/junk", b"12345");
+        let hook = |name: &str, script: String| {
+            let path = fixture.file(&format!("hooks.d/{}", name), script.as_bytes());
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+            CustomCleaner::from_hook(&path, &HashSet::new())
+        };
+        let good = hook(
+            "junk",
+            format!("#!/bin/sh\nif [ \"$1\" = scan ]; then printf '5\\t{0}\\n100\\t/elsewhere\\n'; else rm {0}; echo removed; echo 5; fi\n", junk.display()),
+        );
+        let broken = hook("broken", "#!/bin/sh\necho oops >&2\nexit 3\n".to_string());
+        let custom = Arc::new(vec![good.clone(), broken.clone()]);
+
+        let dry = cleaner(&fixture, true).with_custom(custom.clone());
+        dry.run_item(&good.id).await.unwrap();
+        let stats = dry.get_stats_sync();
+        assert_eq!((stats.files_deleted, stats.bytes_freed), (2, 105));
+        assert!(junk.exists());
+
+        let real = cleaner(&fixture, false).with_custom(custom);
+        real.run_item(&good.id).await.unwrap();
+        assert!(!junk.exists());
+        assert_eq!(real.get_stats_sync().bytes_freed, 5);
+        assert!(real.run_item(&broken.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn preview_counts_the_folders_a_clean_will_remove() {
+        let fixture = Fixture::new();
+        let cache = fixture.home().join(".cache/app");
+        fixture.file(".cache/app/a/x", b"x");
+        fixture.file(".cache/app/a/b/y", b"y");
+        let tag = fixture.file(".cache/app/c/CACHEDIR.TAG", TAG);
+        fixture.file(".cache/app/c/d/z", b"z");
+        fs::create_dir_all(cache.join("e")).unwrap();
+
+        let dry = cleaner(&fixture, true);
+        dry.clean_directory_contents(&cache, "Test", dry.clean_options()).await.unwrap();
+        let real = cleaner(&fixture, false);
+        real.clean_directory_contents(&cache, "Test", real.clean_options()).await.unwrap();
+
+        // a, a/b, c/d. c נשאר עם התג, e היה ריק מלכתחילה, ו-app עצמה נשארת
+        assert_eq!(dry.get_stats_sync().directories_cleaned, 3);
+        assert_eq!(real.get_stats_sync().directories_cleaned, 3);
+        assert!(!cache.join("a").exists() && !cache.join("c/d").exists());
+        assert!(tag.exists() && cache.join("e").is_dir() && cache.is_dir());
+    }
+
+    #[tokio::test]
+    async fn loaded_preview_is_checked_again_before_deleting() {
+        let fixture = Fixture::new();
+        let downloads = fixture.home().join("Downloads");
+        let year_ago = SystemTime::now() - Duration::from_secs(365 * 24 * 60 * 60);
+        let old = |name: &str, size: usize| {
+            let path = fixture.file(&format!("Downloads/{}", name), &vec![7; size]);
+            fs::File::options().write(true).open(&path).unwrap().set_modified(year_ago).unwrap();
+            path
+        };
+        let still_large = old("disk.iso", 50_000);
+        let below_limit_now = old("notes.pdf", 20_000);
+        let not_in_saved_list = old("new.iso", 60_000);
+        // מהפעלה קודמת, עם סף נמוך יותר: שניהם נמצאו וסומנו
+        let store = PreviewStore::default();
+        let saved: Vec<PreviewEntry> = [&still_large, &below_limit_now]
+            .iter()
+            .map(|p| {
+                let metadata = fs::metadata(p).unwrap();
+                PreviewEntry::new(p.to_path_buf(), metadata.len(), metadata.modified().ok()).with_selected(true)
+            })
+            .collect();
+        store.load_stale(HashMap::from([("large_downloads".to_string(), saved)]));
+
+        let scan = LargeFileScan { roots: vec![downloads], min_bytes: 30_000, min_age: Duration::from_secs(24 * 60 * 60), archive: None };
+        let real = cleaner(&fixture, false).with_large_files(scan).with_preview(store);
+        real.run_item("large_downloads").await.unwrap();
+
+        assert!(!still_large.exists());
+        assert!(below_limit_now.exists() && not_in_saved_list.exists());
+    }
+
+    #[test]
+    fn emacs_lock_targets_name_host_and_pid() {
+        assert_eq!(emacs_lock_owner("dana@laptop.12345:1712000000"), Some(("laptop", 12345)));
+        assert_eq!(emacs_lock_owner("dana@box.example.org.77"), Some(("box.example.org", 77)));
+        assert_eq!(emacs_lock_owner("dana@laptop"), None);
+        assert_eq!(emacs_lock_owner("laptop.12345:1"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn doc_temp_takes_tex_files_only_next_to_sources_and_dead_emacs_locks() {
+        let fixture = Fixture::new();
+        let aux = fixture.file("Papers/thesis/thesis.aux", &[1; 3000]);
+        let log = fixture.file("Papers/thesis/thesis.log", &[2; 5000]);
+        let synctex = fixture.file("Papers/thesis/thesis.synctex.gz", &[3; 4000]);
+        let recovery = fixture.file(".config/libreoffice/4/user/backup/report_0.odt", &[4; 6000]);
+        let autosave = fixture.file("notes/#todo.txt#", b"half written");
+        let kept = [
+            fixture.file("Papers/thesis/thesis.tex", b"\\documentclass{article}"),
+            // אין .tex באותה תיקייה
+            fixture.file("Papers/thesis/chapters/intro.aux", &[5; 3000]),
+            fixture.file("server/access.log", &[6; 3000]),
+        ];
+        let hostname = local_hostname().unwrap();
+        let lock = |name: &str, target: String| {
+            let path = fixture.home().join("notes").join(name);
+            std::os::unix::fs::symlink(target, &path).unwrap();
+            path
+        };
+        let dead = lock(".#todo.txt", format!("dana@{}.4000000000:1", hostname));
+        let alive = lock(".#plan.txt", format!("dana@{}.{}:1", hostname, std::process::id()));
+        let remote = lock(".#shared.txt", "dana@elsewhere.4000000000:1".to_string());
+
+        let before = fixture.snapshot();
+        let dry = cleaner(&fixture, true);
+        dry.run_item("doc_temp").await.unwrap();
+        assert_eq!(fixture.snapshot(), before);
+
+        let real = cleaner(&fixture, false);
+        real.run_item("doc_temp").await.unwrap();
+        let removed = fixtures::removed(&before, &fixture.snapshot());
+        let expected: HashSet<&PathBuf> = [&aux, &log, &synctex, &recovery, &autosave, &dead].into_iter().collect();
+        assert_eq!(removed.keys().collect::<HashSet<_>>(), expected);
+        assert!(kept.iter().all(|p| p.exists()));
+        assert!(fs::symlink_metadata(&alive).is_ok() && fs::symlink_metadata(&remote).is_ok());
+        assert_stats_match(&real, &removed);
+        assert_eq!(dry.get_stats_sync().files_deleted, real.get_stats_sync().files_deleted);
+    }
+
+    #[tokio::test]
+    async fn preview_and_clean_list_the_same_largest_files() {
+        let fixture = Fixture::new();
+        let huge = fixture.file(".cache/thumbnails/large/huge.png", &[1; 90_000]);
+        let big = fixture.file(".cache/thumbnails/normal/big.png", &[2; 40_000]);
+        for n in 0..10 {
+            fixture.file(&format!(".cache/thumbnails/normal/{}.png", n), &[3; 500]);
+        }
+
+        let top = |cleaner: &SystemCleaner| cleaner.get_stats_sync().largest.into_iter().map(|d| (d.path, d.item)).collect::<Vec<_>>();
+        let expected = vec![(huge.clone(), "thumbnails".to_string()), (big.clone(), "thumbnails".to_string())];
+        let dry = cleaner(&fixture, true).with_largest_limit(2);
+        dry.run_item("thumbnails").await.unwrap();
+        assert_eq!(top(&dry), expected);
+        assert!(huge.exists());
+
+        let real = cleaner(&fixture, false).with_largest_limit(2);
+        real.run_item("thumbnails").await.unwrap();
+        assert_eq!(top(&real), expected);
+        let stats = real.get_stats_sync();
+        assert!(stats.largest[0].bytes >= stats.largest[1].bytes);
+    }
+}
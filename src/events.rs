@@ -0,0 +1,66 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::largest::LargeDeletion;
+
+// === אירועים מובנים מהמנוע, לתוכנות שמפעילות אותו (--json-progress) ===
+// הסכמה היא ה-enum הזה: שורת JSON אחת לכל אירוע, השדה "event" קובע את הסוג.
+// שינוי שם של שדה שובר סקריפטים קיימים - מוסיפים שדות, לא משנים.
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum CleanEvent {
+    // {"event":"item_start","id":"firefox_cache"}
+    ItemStart {
+        id: String,
+        // רק כשרצים כ-root עבור כמה משתמשים
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user: Option<String>,
+    },
+    // {"event":"file_deleted","path":"/home/u/.cache/x","bytes":1234}
+    // בתצוגה מקדימה: קובץ שהיה נמחק. bytes = המקום בדיסק (0 ל-hard link שכבר נספר)
+    FileDeleted {
+        path: PathBuf,
+        bytes: u64,
+    },
+    // {"event":"item_done","id":"firefox_cache","files":12,"bytes":40960,"errors":0,"duration_ms":812}
+    ItemDone {
+        id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user: Option<String>,
+        files: u64,
+        bytes: u64,
+        errors: u64,
+        // הסיבה, כשהפריט דולג (דפדפן פתוח, לא מותקן...)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        skipped: Option<String>,
+        // בלי זמן השהיה
+        duration_ms: u64,
+    },
+    // תמיד האירוע האחרון
+    RunDone {
+        // run_id::new_run_id - אותו מזהה כמו בהיסטוריה, ברשימת המחיקות ובתיקיית ההסגר
+        run_id: String,
+        dry_run: bool,
+        files: u64,
+        bytes: u64,
+        // החלק של bytes שהיה על tmpfs - זיכרון, לא דיסק
+        ram_bytes: u64,
+        errors: u64,
+        skipped_in_use: u64,
+        skipped_needs_root: u64,
+        skipped_protected: u64,
+        skipped_immutable: u64,
+        // מחיקות שהצליחו רק בסבב החוזר, ומה שלא נמחק גם אחריו
+        recovered_on_retry: u64,
+        failed_files: u64,
+        duration_ms: u64,
+        // [{"bytes":2400000000,"path":"/home/u/.cache/docker.img","item":"var_cache"}, ...] מהגדול לקטן
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        largest: Vec<LargeDeletion>,
+    },
+}
+
+// נקרא מה-thread שמוחק (גם מתוך spawn_blocking) - חייב להיות מהיר ולא לחסום
+pub type EventSink = Arc<dyn Fn(CleanEvent) + Send + Sync>;
@@ -0,0 +1,333 @@
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::config::expand_path;
+
+// === החרגות: נתיבים מההגדרות, ו-globs מקובץ משותף (--exclude-from, "ייבוא החרגות") ===
+// כל כלל סופר כמה פעמים הוא עצר מחיקה, לסיכום בסוף הריצה
+
+pub struct Rule {
+    // כפי שנכתב בקובץ / בהגדרות, לסיכום
+    pub text: String,
+    matcher: Matcher,
+    hits: AtomicU64,
+}
+
+enum Matcher {
+    // הנתיב וכל מה שמתחתיו. canonical: לאן הוא מוביל כשיש symlink בדרך (~/.cache -> /scratch) -
+    // המנוע עובר על השורשים בצורה הקנונית, אז שתי הצורות נחשבות
+    Path { root: PathBuf, canonical: Option<PathBuf> },
+    // רכיבי נתיב; מספיק שהתבנית תתאים להתחלה של הנתיב, כמו ב-Path
+    Glob(Vec<Segment>),
+}
+
+impl Matcher {
+    // נתיב שעוד לא קיים נשאר כמו שהוא
+    fn path(root: PathBuf) -> Self {
+        let canonical = fs::canonicalize(&root).ok().filter(|c| *c != root);
+        Matcher::Path { root, canonical }
+    }
+}
+
+#[derive(Clone)]
+enum Segment {
+    // ** - אפס רכיבים או יותר
+    AnyDepth,
+    Name(Vec<Token>),
+}
+
+#[derive(Clone)]
+enum Token {
+    Char(char),
+    AnyChar,
+    Star,
+    Class { negated: bool, ranges: Vec<(char, char)> },
+}
+
+// הספירה לא עוברת לעותק - עותקים נוצרים רק לפני ריצה
+impl Clone for Rule {
+    fn clone(&self) -> Self {
+        let matcher = match &self.matcher {
+            Matcher::Path { root, canonical } => Matcher::Path { root: root.clone(), canonical: canonical.clone() },
+            Matcher::Glob(segments) => Matcher::Glob(segments.clone()),
+        };
+        Rule { text: self.text.clone(), matcher, hits: AtomicU64::new(0) }
+    }
+}
+
+impl Rule {
+    pub fn path(path: PathBuf) -> Self {
+        Rule { text: path.display().to_string(), matcher: Matcher::path(path), hits: AtomicU64::new(0) }
+    }
+
+    // שורה מקובץ: נתיב מלא (~ מותר) או glob עם * ? ** [a-z] [!x].
+    // בלי '/' - תבנית לשם של קובץ או תיקייה בכל עומק. השגיאה היא מפתח תרגום
+    pub fn parse(line: &str) -> Result<Self, &'static str> {
+        let text = line.trim();
+        let expanded = expand_path(text);
+        let is_glob = text.contains(['*', '?', '[']);
+        if !text.contains('/') && text != "~" {
+            let name = parse_name(text)?;
+            let matcher = Matcher::Glob(vec![Segment::AnyDepth, Segment::Name(name)]);
+            return Ok(Rule { text: text.to_string(), matcher, hits: AtomicU64::new(0) });
+        }
+        if !expanded.is_absolute() {
+            return Err("exclusions.relative");
+        }
+        if !is_glob {
+            return Ok(Rule { text: text.to_string(), matcher: Matcher::path(expanded), hits: AtomicU64::new(0) });
+        }
+        let mut segments = Vec::new();
+        for part in expanded.to_string_lossy().split('/').filter(|p| !p.is_empty()) {
+            segments.push(if part == "**" { Segment::AnyDepth } else { Segment::Name(parse_name(part)?) });
+        }
+        Ok(Rule { text: text.to_string(), matcher: Matcher::Glob(segments), hits: AtomicU64::new(0) })
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        match &self.matcher {
+            Matcher::Path { root, canonical } => path.starts_with(root) || canonical.as_ref().is_some_and(|c| path.starts_with(c)),
+            Matcher::Glob(segments) => {
+                // to_string_lossy: U+FFFD יכול להתאים ל-* או ל-? - בהחרגה, טעות לכיוון הבטוח
+                let names: Vec<String> = path
+                    .components()
+                    .filter_map(|c| match c {
+                        Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                        _ => None,
+                    })
+                    .collect();
+                matches_prefix(segments, &names)
+            }
+        }
+    }
+
+    // תיקייה שאולי יש בתוכה משהו מוחרג
+    fn may_match_under(&self, dir: &Path) -> bool {
+        match &self.matcher {
+            Matcher::Path { root, canonical } => root.starts_with(dir) || canonical.as_ref().is_some_and(|c| c.starts_with(dir)),
+            Matcher::Glob(_) => true,
+        }
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+}
+
+// רכיב אחד של glob. ** בתוך רכיב (a**b) הוא כנראה טעות, ולא * רגיל
+fn parse_name(part: &str) -> Result<Vec<Token>, &'static str> {
+    if part.is_empty() {
+        return Err("exclusions.empty");
+    }
+    if part.contains("**") {
+        return Err("exclusions.double_star");
+    }
+    let mut tokens = Vec::new();
+    let mut chars = part.chars().peekable();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '*' => Token::Star,
+            '?' => Token::AnyChar,
+            '[' => {
+                let negated = chars.next_if(|&c| c == '!' || c == '^').is_some();
+                let mut ranges = Vec::new();
+                loop {
+                    match chars.next() {
+                        None => return Err("exclusions.unclosed_class"),
+                        Some(']') if !ranges.is_empty() => break,
+                        Some(']') => return Err("exclusions.empty_class"),
+                        Some(from) => match chars.next_if_eq(&'-') {
+                            Some(_) => match chars.next() {
+                                Some(to) if to != ']' && from <= to => ranges.push((from, to)),
+                                _ => return Err("exclusions.bad_range"),
+                            },
+                            None => ranges.push((from, from)),
+                        },
+                    }
+                }
+                Token::Class { negated, ranges }
+            }
+            c => Token::Char(c),
+        });
+    }
+    Ok(tokens)
+}
+
+fn matches_prefix(segments: &[Segment], names: &[String]) -> bool {
+    match segments.split_first() {
+        None => true,
+        Some((Segment::AnyDepth, rest)) => (0..=names.len()).any(|skip| matches_prefix(rest, &names[skip..])),
+        Some((Segment::Name(tokens), rest)) => {
+            names.first().is_some_and(|name| name_matches(tokens, &name.chars().collect::<Vec<_>>())) && matches_prefix(rest, &names[1..])
+        }
+    }
+}
+
+fn name_matches(tokens: &[Token], name: &[char]) -> bool {
+    match tokens.split_first() {
+        None => name.is_empty(),
+        Some((Token::Star, rest)) => (0..=name.len()).any(|skip| name_matches(rest, &name[skip..])),
+        Some((token, rest)) => {
+            let Some((&c, name_rest)) = name.split_first() else { return false };
+            let ok = match token {
+                Token::Char(expected) => c == *expected,
+                Token::AnyChar => true,
+                Token::Class { negated, ranges } => ranges.iter().any(|&(from, to)| (from..=to).contains(&c)) != *negated,
+                Token::Star => unreachable!(),
+            };
+            ok && name_matches(rest, name_rest)
+        }
+    }
+}
+
+pub struct LineError {
+    // מ-1, כמו בעורך
+    pub line: usize,
+    pub text: String,
+    pub reason: &'static str,
+}
+
+// קובץ החרגות: שורה לכל כלל, # להערות, שורות ריקות מדולגות. שורה שגויה לא נבלעת בשקט
+pub fn parse_list(contents: &str) -> Result<Vec<Rule>, Vec<LineError>> {
+    let mut rules = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match Rule::parse(line) {
+            Ok(rule) => rules.push(rule),
+            Err(reason) => errors.push(LineError { line: index + 1, text: line.to_string(), reason }),
+        }
+    }
+    if errors.is_empty() { Ok(rules) } else { Err(errors) }
+}
+
+pub enum LoadError {
+    Io(io::Error),
+    Lines(Vec<LineError>),
+}
+
+pub fn load_list(path: &Path) -> Result<Vec<Rule>, LoadError> {
+    let contents = fs::read_to_string(path).map_err(LoadError::Io)?;
+    parse_list(&contents).map_err(LoadError::Lines)
+}
+
+#[derive(Clone, Default)]
+pub struct Exclusions {
+    rules: Vec<Rule>,
+    // קבצים שיובאו לריצה הזו, ומספר הכללים מכל אחד
+    imported: Vec<(PathBuf, usize)>,
+}
+
+impl Exclusions {
+    pub fn from_paths(paths: Vec<PathBuf>) -> Self {
+        Exclusions { rules: paths.into_iter().map(Rule::path).collect(), imported: Vec::new() }
+    }
+
+    pub fn import(&mut self, source: PathBuf, rules: Vec<Rule>) {
+        self.imported.push((source, rules.len()));
+        self.rules.extend(rules);
+    }
+
+    // הכלל הראשון שמתאים נספר
+    pub fn matches(&self, path: &Path) -> bool {
+        match self.rules.iter().find(|rule| rule.matches(path)) {
+            Some(rule) => {
+                rule.hits.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn may_match_under(&self, dir: &Path) -> bool {
+        self.rules.iter().any(|rule| rule.may_match_under(dir))
+    }
+
+    // לעורך ההחרגות: כמה פעמים נתיב מההגדרות עצר מחיקה. None = אין כלל כזה בריצה
+    pub fn hits_for(&self, path: &Path) -> Option<u64> {
+        self.rules.iter().find(|rule| matches!(&rule.matcher, Matcher::Path { root, .. } if root == path)).map(Rule::hits)
+    }
+
+    pub fn imported(&self) -> &[(PathBuf, usize)] {
+        &self.imported
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(line: &str) -> Rule {
+        Rule::parse(line).unwrap_or_else(|e| panic!("{}: {}", line, e))
+    }
+
+    #[test]
+    fn globs_match_a_prefix_of_the_path() {
+        let target = rule("/home/u/Projects/*/target");
+        assert!(target.matches(Path::new("/home/u/Projects/app/target/debug/app")));
+        assert!(!target.matches(Path::new("/home/u/Projects/app/src/target.rs")));
+
+        let deep = rule("/home/u/**/keep-[0-9]?");
+        assert!(deep.matches(Path::new("/home/u/keep-1a")));
+        assert!(deep.matches(Path::new("/home/u/.cache/x/keep-2b/file")));
+        assert!(!deep.matches(Path::new("/home/u/.cache/keep-xb")));
+
+        let name = rule("*.iso");
+        assert!(name.matches(Path::new("/home/u/Downloads/debian.iso")));
+        assert!(!rule("[!.]*").matches(Path::new("/.cache")));
+    }
+
+    #[test]
+    fn list_reports_bad_lines_by_number() {
+        let list = "# shared list\n\n~/Projects/keep\n/tmp/[abc\nrelative/path\n/tmp/a**b\n*.iso\n";
+        let errors = match parse_list(list) {
+            Err(errors) => errors,
+            Ok(_) => panic!("bad lines were accepted"),
+        };
+        let found: Vec<(usize, &str)> = errors.iter().map(|e| (e.line, e.reason)).collect();
+        assert_eq!(found, vec![(4, "exclusions.unclosed_class"), (5, "exclusions.relative"), (6, "exclusions.double_star")]);
+        assert_eq!(parse_list("# only\n~/a\n*.iso\n").map(|r| r.len()).ok(), Some(2));
+    }
+
+    #[test]
+    fn first_matching_rule_is_counted() {
+        let mut exclusions = Exclusions::from_paths(vec![PathBuf::from("/data/keep")]);
+        exclusions.import(PathBuf::from("team.txt"), vec![rule("*.iso"), rule("/data/**")]);
+        assert!(exclusions.matches(Path::new("/data/keep/a.iso")));
+        assert!(exclusions.matches(Path::new("/srv/b.iso")));
+        assert!(exclusions.matches(Path::new("/data/c")));
+        assert!(!exclusions.matches(Path::new("/srv/c")));
+        let hits: Vec<u64> = exclusions.rules().iter().map(Rule::hits).collect();
+        assert_eq!(hits, vec![1, 1, 1]);
+        assert_eq!(exclusions.hits_for(Path::new("/data/keep")), Some(1));
+        assert_eq!(exclusions.hits_for(Path::new("/data")), None);
+        assert_eq!(exclusions.imported(), &[(PathBuf::from("team.txt"), 2)]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_rules_also_match_where_a_symlink_leads() {
+        let temp = tempfile::tempdir().unwrap();
+        let scratch = temp.path().join("scratch");
+        fs::create_dir_all(scratch.join("keep")).unwrap();
+        let link = temp.path().join("cache");
+        std::os::unix::fs::symlink(&scratch, &link).unwrap();
+
+        let exclusions = Exclusions::from_paths(vec![link.join("keep")]);
+        let real = fs::canonicalize(&scratch).unwrap();
+        assert!(exclusions.matches(&real.join("keep/a.bin")));
+        assert!(exclusions.matches(&link.join("keep/b.bin")));
+        assert!(!exclusions.matches(&real.join("other/c.bin")));
+        assert!(exclusions.may_match_under(&real));
+        assert_eq!(exclusions.hits_for(&link.join("keep")), Some(2));
+    }
+}
@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+
+// === שאריות של מנהלי קבצים: סימניות GTK ותצוגות של Dolphin לתיקיות שכבר לא קיימות ===
+// קובץ הסימניות נכתב מחדש בלי השורות המתות; כל שורה אחרת נשמרת בדיוק כפי שהייתה, בייט בבייט.
+
+// file:///path/with%20escapes -> /path/with escapes. URI עם host, או שאינו file://, מחזיר None
+pub fn decode_file_uri(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    if !encoded.starts_with('/') {
+        return None;
+    }
+    Some(percent_decode(encoded))
+}
+
+// %xx -> בייט. גם ב-Path= של קבצי .trashinfo
+pub fn percent_decode(encoded: &str) -> PathBuf {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            // % בלי שתי ספרות הקסה - נשאר כמו שהוא
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    bytes_to_path(decoded)
+}
+
+#[cfg(unix)]
+fn bytes_to_path(bytes: Vec<u8>) -> PathBuf {
+    use std::os::unix::ffi::OsStringExt;
+    PathBuf::from(std::ffi::OsString::from_vec(bytes))
+}
+
+#[cfg(not(unix))]
+fn bytes_to_path(bytes: Vec<u8>) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// שורה בקובץ הסימניות: "URI [label]"
+fn bookmark_uri(line: &[u8]) -> Option<&str> {
+    let line = std::str::from_utf8(line).ok()?;
+    line.trim_end_matches('\r').split(' ').next().filter(|uri| !uri.is_empty())
+}
+
+// התוכן בלי הסימניות המתות, והנתיבים שהוסרו. exists מקבל את הנתיב המפוענח
+pub fn prune_bookmarks(contents: &[u8], exists: impl Fn(&Path) -> bool) -> (Vec<u8>, Vec<PathBuf>) {
+    let mut kept = Vec::with_capacity(contents.len());
+    let mut removed = Vec::new();
+    // split_inclusive שומר את ה-\n (וגם שורה אחרונה בלעדיו) - מה שנשאר זהה למקור
+    for line in contents.split_inclusive(|&b| b == b'\n') {
+        let text = line.strip_suffix(b"\n").unwrap_or(line);
+        match bookmark_uri(text).and_then(decode_file_uri) {
+            Some(path) if !exists(&path) => removed.push(path),
+            _ => kept.extend_from_slice(line),
+        }
+    }
+    (kept, removed)
+}
+
+// Dolphin שומר תצוגה לכל תיקייה מקומית ב-view_properties/local/<הנתיב המלא>.
+// התיקיות העליונות ביותר שהנתיב שהן מייצגות (תחת root) כבר לא קיים - בלי הילדים שלהן
+pub fn dead_view_properties(local: &Path, root: &Path) -> Vec<PathBuf> {
+    let mut dead = Vec::new();
+    let mut walker = walkdir::WalkDir::new(local).min_depth(1).follow_links(false).into_iter();
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(local) else { continue };
+        if !root.join(relative).exists() {
+            dead.push(entry.into_path());
+            walker.skip_current_dir();
+        }
+    }
+    dead
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_percent_escapes() {
+        assert_eq!(decode_file_uri("file:///home/u/My%20Docs"), Some(PathBuf::from("/home/u/My Docs")));
+        assert_eq!(decode_file_uri("file:///home/u/%D7%A9%D7%9C%D7%95%D7%9D"), Some(PathBuf::from("/home/u/שלום")));
+        assert_eq!(decode_file_uri("file:///home/u/100%"), Some(PathBuf::from("/home/u/100%")));
+        assert_eq!(decode_file_uri("sftp://host/srv"), None);
+        assert_eq!(decode_file_uri("file://host/share"), None);
+    }
+
+    #[test]
+    fn prune_keeps_live_lines_byte_for_byte() {
+        let contents = b"file:///live/a A label\nfile:///dead/b\nsmb://nas/share NAS\n\nfile:///live/c%20d";
+        let (kept, removed) = prune_bookmarks(contents, |p| p.starts_with("/live"));
+        assert_eq!(kept, b"file:///live/a A label\nsmb://nas/share NAS\n\nfile:///live/c%20d".to_vec());
+        assert_eq!(removed, vec![PathBuf::from("/dead/b")]);
+    }
+
+    #[test]
+    fn prune_without_dead_lines_is_identity() {
+        let contents = b"file:///x%2Fy label with  two spaces\r\n";
+        let (kept, removed) = prune_bookmarks(contents, |_| true);
+        assert_eq!(kept, contents.to_vec());
+        assert!(removed.is_empty());
+    }
+}
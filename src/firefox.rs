@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// === פרופילים של Firefox, ומה שנשאר בהם מעבר ל-cache2 ===
+
+pub struct Leftover {
+    // תיקייה בתוך הפרופיל
+    pub dir: &'static str,
+    // מפתח התרגום של הקבוצה בעץ התצוגה המקדימה
+    pub group: &'static str,
+    // מסומן מראש - גיבויי session לא, כי בלעדיהם אין "שחזר את החלונות הקודמים" אחרי קריסה
+    pub selected: bool,
+}
+
+pub const LEFTOVERS: &[Leftover] = &[
+    Leftover { dir: "crashes", group: "preview.firefox_crashes", selected: true },
+    Leftover { dir: "minidumps", group: "preview.firefox_crashes", selected: true },
+    Leftover { dir: "datareporting", group: "preview.firefox_telemetry", selected: true },
+    Leftover { dir: "saved-telemetry-pings", group: "preview.firefox_telemetry", selected: true },
+    Leftover { dir: "sessionstore-backups", group: "preview.firefox_session", selected: false },
+];
+
+// קבצי ה-session שדפדפן פתוח כותב אליהם עכשיו
+const LIVE_SESSION_FILES: &[&str] = &["sessionstore.jsonlz4", "recovery.jsonlz4", "recovery.baklz4"];
+
+pub fn is_live_session(path: &Path) -> bool {
+    path.file_name().and_then(|n| n.to_str()).is_some_and(|name| LIVE_SESSION_FILES.contains(&name))
+}
+
+// הפרופילים שב-<root>/profiles.ini, רק אלה שקיימים בדיסק
+pub fn profiles(root: &Path) -> Vec<PathBuf> {
+    let Ok(contents) = fs::read_to_string(root.join("profiles.ini")) else { return Vec::new() };
+    parse_profiles_ini(&contents, root).into_iter().filter(|p| p.is_dir()).collect()
+}
+
+// רק סעיפי [ProfileN]; [Install...] מצביעים על אותם פרופילים. IsRelative חסר = יחסי
+pub fn parse_profiles_ini(contents: &str, root: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = Vec::new();
+    let mut section: Option<(Option<String>, bool)> = None;
+    let finish = |section: Option<(Option<String>, bool)>, found: &mut Vec<PathBuf>| {
+        if let Some((Some(path), relative)) = section {
+            let path = if relative { root.join(path) } else { PathBuf::from(path) };
+            if !found.contains(&path) {
+                found.push(path);
+            }
+        }
+    };
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            finish(section.take(), &mut found);
+            if line.starts_with("[Profile") {
+                section = Some((None, true));
+            }
+            continue;
+        }
+        let Some((path, relative)) = section.as_mut() else { continue };
+        match line.split_once('=') {
+            Some(("Path", value)) => *path = Some(value.trim().to_string()),
+            Some(("IsRelative", value)) => *relative = value.trim() != "0",
+            _ => {}
+        }
+    }
+    finish(section, &mut found);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_relative_and_absolute_profiles() {
+        let ini = "[Install4F96D1932A9F858E]\nDefault=abcd.default-release\n\n\
+                   [Profile1]\nName=work\nIsRelative=0\nPath=/data/ff/work\n\n\
+                   [Profile0]\nName=default-release\nIsRelative=1\nPath=abcd.default-release\nDefault=1\n\n\
+                   [General]\nStartWithLastProfile=1\nVersion=2\n";
+        let root = Path::new("/home/u/.mozilla/firefox");
+        assert_eq!(parse_profiles_ini(ini, root), vec![PathBuf::from("/data/ff/work"), root.join("abcd.default-release")]);
+    }
+
+    #[test]
+    fn live_session_files_are_recognised() {
+        assert!(is_live_session(Path::new("/p/sessionstore-backups/recovery.jsonlz4")));
+        assert!(!is_live_session(Path::new("/p/sessionstore-backups/previous.jsonlz4")));
+    }
+}
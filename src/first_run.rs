@@ -0,0 +1,106 @@
+use crate::config::{SelectionPreset, Settings};
+use crate::i18n::Language;
+use crate::registry;
+
+// === אשף ההפעלה הראשונה (אין עדיין config.toml) ===
+// אוסף בחירות ומחיל אותן על Settings. את הקובץ כותב CleanerApp כשהאשף נגמר או מדולג;
+// שום דבר אחר בדיסק לא נסרק ולא נמחק מכאן. נפתח שוב מההגדרות
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Language,
+    Preset,
+    Startup,
+    // הסבר על תצוגה מקדימה לפני ניקוי - הכפתורים בסרגל מודגשים
+    Preview,
+}
+
+impl Step {
+    pub const ALL: [Step; 4] = [Step::Language, Step::Preset, Step::Startup, Step::Preview];
+
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|s| *s == self).unwrap_or(0)
+    }
+
+    pub fn next(self) -> Option<Step> {
+        Self::ALL.get(self.index() + 1).copied()
+    }
+
+    pub fn previous(self) -> Option<Step> {
+        self.index().checked_sub(1).map(|i| Self::ALL[i])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FirstRun {
+    pub step: Step,
+    pub language: Language,
+    pub preset: SelectionPreset,
+    pub scan_on_startup: bool,
+    pub notify_on_finish: bool,
+    // registry::detected_desktop, ושמות הפריטים שמסומנים מראש בגללו
+    pub desktop: Option<&'static str>,
+    pub desktop_items: Vec<String>,
+}
+
+impl FirstRun {
+    // מתחיל מההגדרות הנוכחיות - כשנפתח שוב מההגדרות, "הבא, הבא, סיום" לא משנה כלום
+    pub fn new(settings: &Settings) -> Self {
+        let desktop = registry::detected_desktop();
+        let desktop_items = registry::builtin_cleaners()
+            .into_iter()
+            .filter(|e| e.desktop.is_some() && e.desktop == desktop && e.is_applicable())
+            .map(|e| e.name)
+            .collect();
+        FirstRun {
+            step: Step::Language,
+            language: settings.appearance.language,
+            preset: settings.general.preset,
+            scan_on_startup: settings.general.scan_on_startup,
+            notify_on_finish: settings.general.notify_on_finish,
+            desktop,
+            desktop_items,
+        }
+    }
+
+    pub fn apply(&self, settings: &mut Settings) {
+        settings.appearance.language = self.language;
+        settings.general.preset = self.preset;
+        settings.general.scan_on_startup = self.scan_on_startup;
+        settings.general.notify_on_finish = self.notify_on_finish;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_walk_forward_and_back() {
+        assert_eq!(Step::Language.previous(), None);
+        assert_eq!(Step::Language.next(), Some(Step::Preset));
+        assert_eq!(Step::Preview.previous(), Some(Step::Startup));
+        assert_eq!(Step::Preview.next(), None);
+    }
+
+    #[test]
+    fn apply_changes_only_the_wizard_choices() {
+        let mut settings = Settings::default();
+        settings.safety.exclusions.push("~/keep".to_string());
+        let mut wizard = FirstRun::new(&settings);
+        wizard.language = Language::Hebrew;
+        wizard.preset = SelectionPreset::Safe;
+        wizard.scan_on_startup = true;
+        wizard.notify_on_finish = false;
+        wizard.apply(&mut settings);
+
+        assert_eq!(settings.appearance.language, Language::Hebrew);
+        assert_eq!(settings.general.preset, SelectionPreset::Safe);
+        assert!(settings.general.scan_on_startup && !settings.general.notify_on_finish);
+        assert_eq!(settings.safety.exclusions, vec!["~/keep".to_string()]);
+
+        // נפתח שוב מההגדרות: מתחיל ממה שנבחר
+        let again = FirstRun::new(&settings);
+        assert_eq!((again.language, again.preset, again.scan_on_startup), (Language::Hebrew, SelectionPreset::Safe, true));
+    }
+}
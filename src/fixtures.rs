@@ -0,0 +1,135 @@
+// לא כל בדיקה משתמשת בכל העזרים - הקובץ משותף לבדיקות היחידה, ל-tests/engine_integration.rs ול---self-test
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use walkdir::WalkDir;
+
+// === עצי תיקיות מזויפים לבדיקות: בית של משתמש בתוך TempDir, ולצדו תיקייה "מבחוץ" ===
+// <tmp>/home    - מועבר ל-SystemCleaner::with_home (או ל-HOME של הבינארי)
+// <tmp>/outside - יעד ל-symlinks; אסור שמשהו ממנו ייעלם
+
+pub struct Fixture {
+    dir: TempDir,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::create_dir_all(dir.path().join("home")).unwrap();
+        fs::create_dir_all(dir.path().join("outside")).unwrap();
+        Fixture { dir }
+    }
+
+    pub fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn home(&self) -> PathBuf {
+        self.dir.path().join("home")
+    }
+
+    pub fn outside(&self) -> PathBuf {
+        self.dir.path().join("outside")
+    }
+
+    // קובץ יחסית לבית, עם התיקיות שמעליו
+    pub fn file(&self, relative: &str, contents: &[u8]) -> PathBuf {
+        let path = self.home().join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    // קובץ יחסית לשורש המזויף (tmp/..., var/cache/...) - לבדיקות עם SystemPaths::new
+    pub fn system_file(&self, relative: &str, contents: &[u8]) -> PathBuf {
+        let path = self.root().join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    // פרופיל Firefox כמו שהדפדפן יוצר: profiles.ini, cache2 עם entries, ונתוני משתמש שלא נוגעים בהם
+    pub fn firefox_profile(&self, name: &str) -> PathBuf {
+        let profile = format!(".mozilla/firefox/{}.default-release", name);
+        self.file(
+            ".mozilla/firefox/profiles.ini",
+            format!("[Profile0]\nName=default-release\nIsRelative=1\nPath={}.default-release\nDefault=1\n", name).as_bytes(),
+        );
+        self.file(&format!("{}/places.sqlite", profile), &[1; 8192]);
+        self.file(&format!("{}/prefs.js", profile), b"user_pref(\"browser.startup.page\", 3);\n");
+        for i in 0..3 {
+            self.file(&format!("{}/cache2/entries/{:040X}", profile, i), &[0xAB; 5000]);
+        }
+        self.file(&format!("{}/cache2/index", profile), &[0; 512]);
+        self.home().join(profile)
+    }
+
+    // זוג files/<name> + info/<name>.trashinfo, כמו בפח של freedesktop
+    pub fn trash_item(&self, name: &str, contents: &[u8]) -> PathBuf {
+        self.file(
+            &format!(".local/share/Trash/info/{}.trashinfo", name),
+            format!("[Trash Info]\nPath=/home/user/{}\nDeletionDate=2024-01-01T12:00:00\n", name).as_bytes(),
+        );
+        self.file(&format!(".local/share/Trash/files/{}", name), contents)
+    }
+
+    // פרויקט פייתון עם __pycache__ מקוננים; קבצי המקור נשארים
+    pub fn python_project(&self, name: &str) -> PathBuf {
+        for module in ["app", "app/core", "app/core/utils"] {
+            let stem = module.rsplit('/').next().unwrap();
+            self.file(&format!("{}/{}/{}.py", name, module, stem), b"def main():\n    pass\n");
+            self.file(&format!("{}/{}/__pycache__/{}.cpython-311.pyc", name, module, stem), &[0x42; 3000]);
+        }
+        self.home().join(name)
+    }
+
+    // לוג פעיל ועותקים מסובבים (.1, .2.gz) תחת ~/.local/state
+    pub fn rotated_logs(&self, app: &str) -> PathBuf {
+        let dir = format!(".local/state/{}", app);
+        self.file(&format!("{}/{}.log", dir, app), &[b'x'; 4096]);
+        self.file(&format!("{}/{}.log.1", dir, app), &[b'y'; 4096]);
+        self.file(&format!("{}/{}.log.2.gz", dir, app), &[0x1f; 1024]);
+        self.home().join(dir)
+    }
+
+    // symlink בתוך הבית שמצביע לקובץ מחוצה לו
+    #[cfg(unix)]
+    pub fn symlink_outside(&self, relative: &str, target_name: &str) -> (PathBuf, PathBuf) {
+        let target = self.outside().join(target_name);
+        fs::write(&target, [0x55; 10_000]).unwrap();
+        let link = self.home().join(relative);
+        fs::create_dir_all(link.parent().unwrap()).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        (link, target)
+    }
+
+    // כל הקבצים וה-symlinks (בלי לעקוב אחריהם) תחת שורש ה-fixture, עם המקום שהם תופסים בדיסק
+    pub fn snapshot(&self) -> BTreeMap<PathBuf, u64> {
+        WalkDir::new(self.root())
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| !e.file_type().is_dir())
+            .filter_map(|e| e.metadata().ok().map(|m| (e.into_path(), allocated(&m))))
+            .collect()
+    }
+}
+
+// מה שנעלם בין שתי תמונות מצב
+pub fn removed(before: &BTreeMap<PathBuf, u64>, after: &BTreeMap<PathBuf, u64>) -> BTreeMap<PathBuf, u64> {
+    before.iter().filter(|(path, _)| !after.contains_key(*path)).map(|(p, s)| (p.clone(), *s)).collect()
+}
+
+// אותה ספירה כמו במנוע: blocks, לא האורך
+#[cfg(unix)]
+pub fn allocated(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.blocks() * 512
+}
+
+#[cfg(not(unix))]
+pub fn allocated(metadata: &fs::Metadata) -> u64 {
+    metadata.len()
+}
@@ -0,0 +1,106 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+// === קבצים בתוך git work tree: *~ או .pyc שם יכולים להיות במעקב, או שמורים בכוונה ===
+// נמחקים רק מה ש-git check-ignore מאשר שהוא ignored. קובץ במעקב אף פעם לא ignored
+
+// ה-work tree הקרוב ביותר מעל הקובץ, עד top (כולל - גם ~ יכול להיות repo של dotfiles).
+// .git יכול להיות גם קובץ (worktree, submodule). cache לפי תיקייה, כי הרבה קבצים חולקים הורים
+pub fn work_tree_of(file: &Path, top: &Path, cache: &mut HashMap<PathBuf, Option<PathBuf>>) -> Option<PathBuf> {
+    let mut visited = Vec::new();
+    let mut found = None;
+    for dir in file.ancestors().skip(1) {
+        if let Some(known) = cache.get(dir) {
+            found = known.clone();
+            break;
+        }
+        visited.push(dir.to_path_buf());
+        if dir.join(".git").exists() {
+            found = Some(dir.to_path_buf());
+            break;
+        }
+        if dir == top || !dir.starts_with(top) {
+            break;
+        }
+    }
+    for dir in visited {
+        cache.insert(dir, found.clone());
+    }
+    found
+}
+
+// אילו מהקבצים git מחשיב ignored ב-repo. None = אין git או שהפקודה נכשלה - אז לא מוחקים כלום שם
+pub fn ignored(repo: &Path, files: &[PathBuf]) -> Option<HashSet<PathBuf>> {
+    let git = which::which("git").ok()?;
+    let mut child = Command::new(git)
+        .arg("-C")
+        .arg(repo)
+        .args(["check-ignore", "-z", "--stdin"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let mut stdin = child.stdin.take()?;
+    // כתיבה מ-thread נפרד: עם אלפי קבצים ה-pipe מתמלא משני הצדדים
+    let output = thread::scope(|scope| {
+        scope.spawn(move || {
+            for file in files {
+                let mut line = file.as_os_str().as_encoded_bytes().to_vec();
+                line.push(0);
+                if stdin.write_all(&line).is_err() {
+                    break;
+                }
+            }
+        });
+        child.wait_with_output()
+    })
+    .ok()?;
+    // 0 = חלק ignored, 1 = אף אחד. כל השאר (128) - שגיאה
+    if !matches!(output.status.code(), Some(0 | 1)) {
+        return None;
+    }
+    Some(match_listed(files, &output.stdout))
+}
+
+// check-ignore מחזיר את הנתיבים בדיוק כמו שקיבל, מופרדים ב-NUL
+fn match_listed(files: &[PathBuf], listed: &[u8]) -> HashSet<PathBuf> {
+    let listed: HashSet<&[u8]> = listed.split(|&b| b == 0).filter(|p| !p.is_empty()).collect();
+    files.iter().filter(|f| listed.contains(f.as_os_str().as_encoded_bytes())).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn nearest_work_tree_wins_and_is_cached() {
+        let home = tempfile::tempdir().unwrap();
+        let repo = home.path().join("src/app");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(repo.join("vendor/lib")).unwrap();
+        fs::write(repo.join("vendor/lib/.git"), "gitdir: ../../.git/modules/lib\n").unwrap();
+        fs::create_dir_all(home.path().join("notes")).unwrap();
+
+        let mut cache = HashMap::new();
+        assert_eq!(work_tree_of(&repo.join("main.py~"), home.path(), &mut cache), Some(repo.clone()));
+        assert_eq!(work_tree_of(&repo.join("vendor/lib/x.bak"), home.path(), &mut cache), Some(repo.join("vendor/lib")));
+        assert_eq!(work_tree_of(&home.path().join("notes/todo.txt~"), home.path(), &mut cache), None);
+        assert_eq!(work_tree_of(&repo.join("vendor/README~"), home.path(), &mut cache), Some(repo.clone()));
+        assert_eq!(cache.get(&repo.join("vendor")), Some(&Some(repo.clone())));
+    }
+
+    #[test]
+    fn only_listed_paths_count_as_ignored() {
+        let files = vec![PathBuf::from("/r/a.pyc"), PathBuf::from("/r/b.bak"), PathBuf::from("/r/c~")];
+        let ignored = match_listed(&files, b"/r/a.pyc\0/r/c~\0");
+        assert!(ignored.contains(Path::new("/r/a.pyc")));
+        assert!(ignored.contains(Path::new("/r/c~")));
+        assert!(!ignored.contains(Path::new("/r/b.bak")));
+        assert!(match_listed(&files, b"").is_empty());
+    }
+}
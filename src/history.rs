@@ -0,0 +1,97 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::engine::app_data_dir;
+use crate::largest::LargeDeletion;
+use crate::persist;
+use crate::run_id;
+
+// === היסטוריית ניקויים: שורת JSON לכל ריצה אמיתית (תצוגה מקדימה לא נרשמת) ===
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    // run_id::new_run_id. שורות ישנות בלי השדה מקבלות מזהה מזמן הסיום ב-load
+    #[serde(default)]
+    pub run_id: String,
+    pub finished: DateTime<Local>,
+    pub items: Vec<String>,
+    pub files: u64,
+    pub bytes: u64,
+    // רשימת המחיקות של הריצה, אם ההגדרה הייתה דלוקה
+    #[serde(default)]
+    pub manifest: Option<PathBuf>,
+    // המחיקות הגדולות ביותר של הריצה (AdvancedSettings::largest_files)
+    #[serde(default)]
+    pub largest: Vec<LargeDeletion>,
+}
+
+pub const HISTORY_FILE: &str = "history.jsonl";
+// "Super Cleaner data" מקצר את ההיסטוריה לריצות האחרונות האלה
+pub const DEFAULT_MAX_RUNS: usize = 500;
+
+pub fn history_path() -> PathBuf {
+    app_data_dir().join(HISTORY_FILE)
+}
+
+pub fn append(record: &RunRecord) -> io::Result<()> {
+    let path = history_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(record).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+// מתי כל פריט נוקה בהצלחה בפעם האחרונה - קובץ נפרד, כדי שלא ילך לאיבוד כשההיסטוריה מתקצרת
+pub fn last_cleaned_path() -> PathBuf {
+    app_data_dir().join("last-cleaned.json")
+}
+
+// קובץ חסר או פגום = אף פריט לא נוקה
+pub fn load_last_cleaned() -> HashMap<String, DateTime<Local>> {
+    fs::read_to_string(last_cleaned_path()).ok().and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default()
+}
+
+// רק אחרי ריצה אמיתית, ורק לפריטים שהצליחו
+pub fn mark_cleaned(items: &[String], when: DateTime<Local>) -> io::Result<()> {
+    if items.is_empty() {
+        return Ok(());
+    }
+    let mut last = load_last_cleaned();
+    for item in items {
+        last.insert(item.clone(), when);
+    }
+    let path = last_cleaned_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(&last).map_err(io::Error::other)?;
+    persist::write_atomically(&path, json.as_bytes())
+}
+
+// רק max_runs השורות האחרונות (החדשות). None = אין מה לקצר, או 0 = בלי הגבלה
+pub fn trim_to_last(content: &str, max_runs: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).collect();
+    if max_runs == 0 || lines.len() <= max_runs {
+        return None;
+    }
+    let mut kept = lines[lines.len() - max_runs..].join("\n");
+    kept.push('\n');
+    Some(kept)
+}
+
+// החדשות קודם; שורות פגומות (כתיבה שנקטעה) מדולגות
+pub fn load() -> Vec<RunRecord> {
+    let Ok(content) = fs::read_to_string(history_path()) else { return Vec::new() };
+    let mut records: Vec<RunRecord> = content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect();
+    for record in records.iter_mut().filter(|r| r.run_id.is_empty()) {
+        record.run_id = run_id::legacy_run_id(record.finished);
+    }
+    records.reverse();
+    records
+}
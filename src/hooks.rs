@@ -0,0 +1,167 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+
+// === hooks: קבצי הרצה ב-~/.config/system-cleaner-pro/hooks.d/, כל אחד פריט בקטגוריית "Hooks" ===
+//
+// hook scan   - מדפיס "גודל<TAB>נתיב" לכל מה שהיה מוחק, ולא נוגע בכלום
+// hook clean  - מוחק בעצמו, ובסוף מדפיס כמה בייטים פינה
+//
+// קוד חיצוני שרץ עם ההרשאות שלנו: קובץ או תיקייה שאחרים יכולים לכתוב אליהם - לא מריצים
+
+pub fn hooks_dir() -> PathBuf {
+    config::config_dir().join("hooks.d")
+}
+
+// hooks שנמצאו (ממוינים לפי שם, כדי שהסדר בסרגל יהיה יציב), ושגיאות ללוג ההפעלה
+#[derive(Debug, Default)]
+pub struct Discovery {
+    pub hooks: Vec<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+pub fn discover() -> Discovery {
+    discover_in(&hooks_dir())
+}
+
+fn discover_in(dir: &Path) -> Discovery {
+    let mut found = Discovery::default();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return found,
+    };
+    if let Err(e) = check_writable_by_others(dir) {
+        found.errors.push(format!("{}: {}", dir.display(), e));
+        return found;
+    }
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        // קבצי גיבוי של עורכים, README וכו' - לא hooks
+        if name.starts_with('.') || name.ends_with('~') || !is_executable_file(&path) {
+            continue;
+        }
+        match check_writable_by_others(&path) {
+            Ok(()) => found.hooks.push(path),
+            Err(e) => found.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    found
+}
+
+// נבדק שוב לפני כל הרצה: ההרשאות יכולות להשתנות מאז ההפעלה
+pub fn check(hook: &Path) -> Result<(), String> {
+    if !is_executable_file(hook) {
+        return Err("not an executable file".to_string());
+    }
+    if let Some(dir) = hook.parent() {
+        check_writable_by_others(dir)?;
+    }
+    check_writable_by_others(hook)
+}
+
+#[cfg(unix)]
+fn is_executable_file(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable_file(_path: &Path) -> bool {
+    false
+}
+
+// symlink נבדק לפי היעד - זה מה שירוץ
+#[cfg(unix)]
+fn check_writable_by_others(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+    if metadata.mode() & 0o022 != 0 {
+        return Err("group- or world-writable, refusing to run it".to_string());
+    }
+    let uid = metadata.uid();
+    if uid != 0 && uid != nix::unistd::geteuid().as_raw() {
+        return Err("owned by another user, refusing to run it".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_writable_by_others(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+// פלט של scan: שורות "גודל<TAB>נתיב". שורות שלא בפורמט חוזרות בנפרד, ללוג
+pub fn parse_scan(lines: &[String]) -> (Vec<(u64, PathBuf)>, Vec<String>) {
+    let mut entries = Vec::new();
+    let mut bad = Vec::new();
+    for line in lines.iter().filter(|l| !l.trim().is_empty()) {
+        match line.split_once('\t') {
+            Some((size, path)) if !path.is_empty() => match size.trim().parse() {
+                Ok(size) => entries.push((size, PathBuf::from(path))),
+                Err(_) => bad.push(line.clone()),
+            },
+            _ => bad.push(line.clone()),
+        }
+    }
+    (entries, bad)
+}
+
+// פלט של clean: השורה האחרונה שאינה ריקה היא מספר הבייטים. מה שלפניה - סתם לוג
+pub fn parse_clean(lines: &[String]) -> Option<u64> {
+    lines.iter().rev().find(|l| !l.trim().is_empty())?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn scan_lines_are_size_tab_path() {
+        let (entries, bad) = parse_scan(&lines("4096\t/home/u/.cache/app/a\n\n12\t/home/u/with space/b\nnot a line\nx\t/c\n"));
+        assert_eq!(entries, vec![(4096, PathBuf::from("/home/u/.cache/app/a")), (12, PathBuf::from("/home/u/with space/b"))]);
+        assert_eq!(bad, vec!["not a line".to_string(), "x\t/c".to_string()]);
+    }
+
+    #[test]
+    fn clean_reports_the_last_line() {
+        assert_eq!(parse_clean(&lines("removing a\nremoving b\n 2048 \n\n")), Some(2048));
+        assert_eq!(parse_clean(&lines("done\n")), None);
+        assert_eq!(parse_clean(&[]), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn writable_hooks_are_refused() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        fs::set_permissions(dir.path(), fs::Permissions::from_mode(0o755)).unwrap();
+        let write = |name: &str, mode: u32| {
+            let path = dir.path().join(name);
+            fs::write(&path, "#!/bin/sh\n").unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+            path
+        };
+        let good = write("docker-prune", 0o755);
+        let shared = write("shared", 0o775);
+        write("README", 0o644);
+        write("old~", 0o755);
+
+        let found = discover_in(dir.path());
+        assert_eq!(found.hooks, vec![good.clone()]);
+        assert_eq!(found.errors.len(), 1);
+        assert!(found.errors[0].starts_with(&shared.display().to_string()));
+        assert!(check(&good).is_ok());
+        assert!(check(&shared).is_err());
+
+        fs::set_permissions(dir.path(), fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(discover_in(dir.path()).hooks.is_empty());
+        assert!(check(&good).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+use unicode_bidi::BidiInfo;
+
+// === תרגום: מפות JSON מוטמעות, מפתח -> טקסט ===
+
+const EN_JSON: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/i18n/en.json"));
+const HE_JSON: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/i18n/he.json"));
+
+// תווי בידוד של יוניקוד (FSI/PDI) - עוטפים קטעים דינמיים כמו נתיבים ומספרים
+const FSI: char = '\u{2068}';
+const PDI: char = '\u{2069}';
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Hebrew,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Hebrew];
+
+    // השם בשפה עצמה, לבורר בהגדרות
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Hebrew => "עברית",
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Language::Hebrew,
+            _ => Language::English,
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+static CATALOGS: OnceLock<[HashMap<String, String>; 2]> = OnceLock::new();
+
+fn catalogs() -> &'static [HashMap<String, String>; 2] {
+    CATALOGS.get_or_init(|| {
+        // JSON שבור הוא באג בבנייה, לא מצב ריצה - עדיף מפה ריקה מאשר קריסה
+        let parse = |json: &str| serde_json::from_str(json).unwrap_or_default();
+        [parse(EN_JSON), parse(HE_JSON)]
+    })
+}
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn current_language() -> Language {
+    Language::from_u8(CURRENT.load(Ordering::Relaxed))
+}
+
+// מחפש בשפה הנוכחית, אחר כך באנגלית, ולבסוף ה-fallback
+fn lookup(key: &str) -> Option<&'static str> {
+    let catalogs = catalogs();
+    catalogs[current_language() as usize]
+        .get(key)
+        .or_else(|| catalogs[Language::English as usize].get(key))
+        .map(|s| s.as_str())
+}
+
+pub fn tr(key: &str) -> String {
+    visual(lookup(key).unwrap_or(key))
+}
+
+// למחרוזות שהמקור האנגלי שלהן נמצא בקוד (למשל שמות פריטים מה-registry)
+pub fn tr_or(key: &str, fallback: &str) -> String {
+    visual(lookup(key).unwrap_or(fallback))
+}
+
+// "Deleted: {0} ({1})" - כל ארגומנט מבודד כדי שנתיב באנגלית לא יתהפך בתוך שורה בעברית
+pub fn tr_args(key: &str, args: &[&str]) -> String {
+    visual(&tr_plain(key, args))
+}
+
+// בסדר לוגי, עם תווי הבידוד - לטקסט שיוצא מ-egui (התראות, טרמינל) ושם ה-bidi כבר נעשה
+pub fn tr_plain(key: &str, args: &[&str]) -> String {
+    let mut text = lookup(key).unwrap_or(key).to_string();
+    for (i, arg) in args.iter().enumerate() {
+        text = text.replace(&format!("{{{}}}", i), &format!("{}{}{}", FSI, arg, PDI));
+    }
+    text
+}
+
+fn has_rtl(text: &str) -> bool {
+    text.chars().any(|c| matches!(c, '\u{0590}'..='\u{08FF}' | '\u{FB1D}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFF}'))
+}
+
+// egui לא מממש bidi - מסדרים את השורה בסדר התצוגה ומסירים את תווי הבידוד שאין להם גליף
+pub fn visual(text: &str) -> String {
+    if !has_rtl(text) {
+        return text.chars().filter(|c| !matches!(c, '\u{2066}'..='\u{2069}')).collect();
+    }
+    let info = BidiInfo::new(text, None);
+    let mut out = String::with_capacity(text.len());
+    for para in &info.paragraphs {
+        out.push_str(&info.reorder_line(para, para.range.clone()));
+    }
+    out.retain(|c| !matches!(c, '\u{2066}'..='\u{2069}'));
+    out
+}
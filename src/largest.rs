@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::engine::{escape_name, SystemCleaner};
+
+// === "מה באמת תפס את המקום": N הקבצים הגדולים ביותר שנמחקו בריצה (בתצוגה מקדימה - שהיו נמחקים) ===
+// נקרא מ-report_deleted לכל קובץ, גם מכמה threads. רוב הקבצים קטנים מהסף ולא נוגעים במנעול בכלל
+
+pub const DEFAULT_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct LargeDeletion {
+    // ראשון: הסדר של ה-heap לפי גודל, ובשוויון לפי נתיב
+    pub bytes: u64,
+    pub path: PathBuf,
+    pub item: String,
+}
+
+#[derive(Debug)]
+pub struct LargestFiles {
+    limit: usize,
+    // min-heap: השורש הוא הקטן מבין N הגדולים, הראשון שיוצא
+    heap: Mutex<BinaryHeap<Reverse<LargeDeletion>>>,
+    // הגודל של השורש כשה-heap מלא (אחרת 0) - בדיקה בלי מנעול
+    floor: AtomicU64,
+}
+
+impl Default for LargestFiles {
+    fn default() -> Self {
+        LargestFiles::new(DEFAULT_LIMIT)
+    }
+}
+
+impl LargestFiles {
+    pub fn new(limit: usize) -> Self {
+        LargestFiles { limit, heap: Mutex::new(BinaryHeap::with_capacity(limit + 1)), floor: AtomicU64::new(0) }
+    }
+
+    pub fn record(&self, bytes: u64, path: &std::path::Path, item: &str) {
+        if self.limit == 0 || bytes == 0 || bytes <= self.floor.load(Ordering::Relaxed) {
+            return;
+        }
+        let Ok(mut heap) = self.heap.lock() else { return };
+        heap.push(Reverse(LargeDeletion { bytes, path: path.to_path_buf(), item: item.to_string() }));
+        if heap.len() > self.limit {
+            heap.pop();
+        }
+        if heap.len() == self.limit {
+            if let Some(Reverse(smallest)) = heap.peek() {
+                self.floor.store(smallest.bytes, Ordering::Relaxed);
+            }
+        }
+    }
+
+    // מהגדול לקטן
+    pub fn top(&self) -> Vec<LargeDeletion> {
+        let mut top: Vec<LargeDeletion> = self.heap.lock().map(|heap| heap.iter().map(|Reverse(d)| d.clone()).collect()).unwrap_or_default();
+        top.sort_by(|a, b| b.cmp(a));
+        top
+    }
+}
+
+// שורות הטבלה לטרמינל: גודל, פריט, נתיב מלא
+pub fn table_lines(largest: &[LargeDeletion]) -> Vec<String> {
+    largest.iter()
+        .map(|d| format!("  {:>10}  {:<18}  {}", SystemCleaner::format_bytes(d.bytes), d.item, escape_name(d.path.as_os_str())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_the_largest_in_descending_order() {
+        let largest = LargestFiles::new(3);
+        for (bytes, name) in [(10, "a"), (500, "b"), (40, "c"), (0, "empty"), (2_400_000_000, "docker.img"), (40, "d"), (7, "e")] {
+            largest.record(bytes, std::path::Path::new(name), "var_cache");
+        }
+        let top: Vec<(u64, String)> = largest.top().into_iter().map(|d| (d.bytes, d.path.display().to_string())).collect();
+        // בשוויון לסף - מי שהגיע ראשון נשאר
+        assert_eq!(top, vec![(2_400_000_000, "docker.img".to_string()), (500, "b".to_string()), (40, "c".to_string())]);
+
+        let off = LargestFiles::new(0);
+        off.record(100, std::path::Path::new("x"), "tmp");
+        assert!(off.top().is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(unix)]
+use nix::errno::Errno;
+#[cfg(unix)]
+use nix::fcntl::{Flock, FlockArg};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+#[cfg(unix)]
+use std::io::Read;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+// === נעילה בין מופעים: רק ניקוי/תצוגה מקדימה אחד בכל רגע (GUI או CLI) ===
+
+pub fn lock_path() -> PathBuf {
+    dirs::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("system-cleaner-pro.lock")
+}
+
+#[derive(Debug)]
+pub enum LockError {
+    // מופע אחר מחזיק בנעילה (ה-PID שכתוב בקובץ, אם הצלחנו לקרוא אותו)
+    Held(Option<u32>),
+    Io(io::Error),
+}
+
+impl fmt::Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockError::Held(Some(pid)) => write!(f, "Another cleaning operation is in progress (PID {})", pid),
+            LockError::Held(None) => write!(f, "Another cleaning operation is in progress"),
+            LockError::Io(e) => write!(f, "Could not create lock file: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LockError {}
+
+impl From<io::Error> for LockError {
+    fn from(e: io::Error) -> Self {
+        LockError::Io(e)
+    }
+}
+
+// RAII: הנעילה משתחררת ב-drop - בסיום רגיל, בעצירה, ובפאניקה (unwind).
+// אם התהליך קורס לגמרי, הקרנל משחרר את ה-flock בעצמו.
+pub struct InstanceLock {
+    #[cfg(unix)]
+    file: Flock<File>,
+    #[cfg(not(unix))]
+    file: File,
+}
+
+impl InstanceLock {
+    pub fn acquire() -> Result<Self, LockError> {
+        Self::acquire_at(&lock_path())
+    }
+
+    // ה-flock הוא הנעילה, לא ה-PID שבקובץ: PID של תהליך שמת רק נדרס כשלוקחים את ה-flock.
+    // קובץ שה-flock שלו תפוס לא נמחק אף פעם - מחיקה הייתה נותנת למופע הבא inode חדש ונעילה משלו
+    pub fn acquire_at(path: &Path) -> Result<Self, LockError> {
+        Self::try_lock(path)?.map_err(LockError::Held)
+    }
+
+    // Ok(Err(pid)) = תפוס
+    #[cfg(unix)]
+    fn try_lock(path: &Path) -> io::Result<Result<Self, Option<u32>>> {
+        let file = open_lock_file(path)?;
+        match Flock::lock(file, FlockArg::LockExclusiveNonblock) {
+            Ok(mut file) => {
+                file.set_len(0)?;
+                file.seek(SeekFrom::Start(0))?;
+                write!(file, "{}", std::process::id())?;
+                file.flush()?;
+                Ok(Ok(InstanceLock { file }))
+            }
+            Err((mut file, Errno::EWOULDBLOCK)) => {
+                let mut content = String::new();
+                let _ = file.read_to_string(&mut content);
+                Ok(Err(content.trim().parse().ok()))
+            }
+            Err((_, errno)) => Err(io::Error::from(errno)),
+        }
+    }
+
+    // LockFileEx: נעילה בלעדית חוסמת גם קריאה, אז אין PID להציג
+    #[cfg(not(unix))]
+    fn try_lock(path: &Path) -> io::Result<Result<Self, Option<u32>>> {
+        let mut file = open_lock_file(path)?;
+        match file.try_lock() {
+            Ok(()) => {
+                file.set_len(0)?;
+                file.seek(SeekFrom::Start(0))?;
+                write!(file, "{}", std::process::id())?;
+                file.flush()?;
+                Ok(Ok(InstanceLock { file }))
+            }
+            Err(std::fs::TryLockError::WouldBlock) => Ok(Err(None)),
+            Err(std::fs::TryLockError::Error(e)) => Err(e),
+        }
+    }
+}
+
+fn open_lock_file(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        // לא מוחקים את הקובץ - מופע אחר אולי כבר פתח אותו; רק מרוקנים את ה-PID
+        let _ = self.file.set_len(0);
+    }
+}
+
+// kill(pid, 0): EPERM = קיים אבל של משתמש אחר. בלי /proc, כך שעובד גם ב-macOS
+#[cfg(unix)]
+pub fn pid_alive(pid: u32) -> bool {
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+    let Ok(pid) = i32::try_from(pid) else { return false };
+    !matches!(kill(Pid::from_raw(pid), None), Err(Errno::ESRCH))
+}
+
+// ב-Windows אין בדיקה זולה - נחשב חי, כך ששום lock של תהליך אחר לא נמחק בטעות
+#[cfg(not(unix))]
+pub fn pid_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    // מעל pid_max של לינוקס (2^22) - אף תהליך לא יכול להחזיק אותו
+    const DEAD_PID: u32 = 999_999_999;
+
+    #[test]
+    fn liveness_comes_from_kill_not_proc() {
+        assert!(pid_alive(std::process::id()));
+        assert!(!pid_alive(DEAD_PID));
+    }
+
+    #[test]
+    fn held_lock_is_not_taken_over_even_with_a_dead_pid() {
+        let path = std::env::temp_dir().join(format!("sysclean-lock-test-{}.lock", std::process::id()));
+        let held = InstanceLock::acquire_at(&path).unwrap();
+        fs::write(&path, DEAD_PID.to_string()).unwrap();
+
+        assert!(matches!(InstanceLock::acquire_at(&path), Err(LockError::Held(Some(DEAD_PID)))));
+        assert!(path.exists());
+
+        drop(held);
+        let next = InstanceLock::acquire_at(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), std::process::id().to_string());
+        drop(next);
+        fs::remove_file(&path).unwrap();
+    }
+}
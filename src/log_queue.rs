@@ -0,0 +1,86 @@
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::engine::LogCallback;
+
+// === הלוג של ה-GUI: המנוע שולח שורות לתור, הפריים מרוקן אותו בקצב שלו ===
+// callback שמבקש repaint לכל קובץ שנמחק = 200k בקשות ציור וליבה תפוסה. כאן ה-callback רק
+// שולח לערוץ (בלי נעילה של הלוג), וה-update מרוקן עד LINES_PER_FRAME שורות בכל פריים.
+
+pub const LINES_PER_FRAME: usize = 2000;
+// קצב הרענון בזמן ריצה - במקום repaint מכל שורה
+pub const REPAINT_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct LogQueue {
+    sender: Sender<String>,
+    receiver: Receiver<String>,
+}
+
+impl Default for LogQueue {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        LogQueue { sender, receiver }
+    }
+}
+
+impl LogQueue {
+    // ל-threads שכותבים ללוג ישירות (סיכום הריצה) - באותו תור, כדי שהסדר יישמר
+    pub fn sender(&self) -> Sender<String> {
+        self.sender.clone()
+    }
+
+    // ל-SystemCleaner::with_callback
+    pub fn callback(&self) -> LogCallback {
+        let sender = self.sender.clone();
+        Arc::new(Mutex::new(Box::new(move |line: String| {
+            let _ = sender.send(line);
+        }) as Box<dyn Fn(String) + Send + Sync>))
+    }
+
+    // עד max שורות לסוף logs. true = נשארו עוד בתור
+    pub fn drain_into(&self, logs: &mut Vec<String>, max: usize) -> bool {
+        for _ in 0..max {
+            match self.receiver.try_recv() {
+                Ok(line) => logs.push(line),
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => return false,
+            }
+        }
+        // בדיוק max שורות בתור - הפריים הבא יגלה שהוא ריק
+        true
+    }
+
+    // ריצה חדשה מתחילה עם לוג נקי - גם בלי שורות שעוד חיכו מהקודמת
+    pub fn clear(&self) {
+        while self.receiver.try_recv().is_ok() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drains_in_order_up_to_the_limit() {
+        let queue = LogQueue::default();
+        let callback = queue.callback();
+        for i in 0..5 {
+            (callback.lock().unwrap())(i.to_string());
+        }
+        let mut logs = Vec::new();
+        assert!(queue.drain_into(&mut logs, 3));
+        assert_eq!(logs, ["0", "1", "2"]);
+        assert!(!queue.drain_into(&mut logs, 3));
+        assert_eq!(logs, ["0", "1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn sender_lines_follow_callback_lines() {
+        let queue = LogQueue::default();
+        (queue.callback().lock().unwrap())("file".to_string());
+        queue.sender().send("summary".to_string()).unwrap();
+        let mut logs = Vec::new();
+        assert!(!queue.drain_into(&mut logs, LINES_PER_FRAME));
+        assert_eq!(logs, ["file", "summary"]);
+    }
+}
@@ -0,0 +1,75 @@
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::engine::LogCallback;
+use crate::i18n::tr;
+
+// === --verbose: שורות הלוג של המנוע גם ל-stdout, עם שעה ===
+// עובד גם ב-GUI (מריצים מטרמינל בשביל דיבאג). צבעים רק כש-stdout הוא טרמינל ו-NO_COLOR לא מוגדר.
+
+static MIRROR: AtomicBool = AtomicBool::new(false);
+
+pub fn set_mirror_stdout(enabled: bool) {
+    MIRROR.store(enabled, Ordering::Relaxed);
+}
+
+pub fn mirror_stdout() -> bool {
+    MIRROR.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    Success,
+    Info,
+}
+
+// אותו סיווג לצבע בחלון הלוג ובטרמינל
+pub fn level_of(line: &str) -> LogLevel {
+    if line.contains("Error") || line.contains(&tr("log.error")) {
+        LogLevel::Error
+    } else if line.contains("Cleaned") || line.contains("Deleted") || line.contains(&tr("log.deleted_marker")) {
+        LogLevel::Success
+    } else {
+        LogLevel::Info
+    }
+}
+
+pub fn format_line(line: &str, time: &str, color: bool) -> String {
+    if !color {
+        return format!("{} {}", time, line);
+    }
+    let code = match level_of(line) {
+        LogLevel::Error => "31",
+        LogLevel::Success => "32",
+        LogLevel::Info => "0",
+    };
+    format!("\x1b[2m{}\x1b[0m \x1b[{}m{}\x1b[0m", time, code, line)
+}
+
+// מנוי נוסף ל-SystemCleaner::with_callback. מזהה הריצה בכל שורה - כשה-stdout נשמר לקובץ או מודבק בדיווח באג
+pub fn stdout_sink(run_id: &str) -> LogCallback {
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let run_id = run_id.to_string();
+    Arc::new(Mutex::new(Box::new(move |line: String| {
+        let time = format!("{} [{}]", chrono::Local::now().format("%H:%M:%S%.3f"), run_id);
+        let _ = writeln!(std::io::stdout().lock(), "{}", format_line(&line, &time, color));
+    }) as Box<dyn Fn(String) + Send + Sync>))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_output_has_no_escape_codes() {
+        assert_eq!(format_line("❌ Error: boom", "12:00:01.250", false), "12:00:01.250 ❌ Error: boom");
+    }
+
+    #[test]
+    fn colored_output_follows_level() {
+        assert_eq!(format_line("❌ Error: boom", "t", true), "\x1b[2mt\x1b[0m \x1b[31m❌ Error: boom\x1b[0m");
+        assert_eq!(format_line("Deleted a (1 B)", "t", true), "\x1b[2mt\x1b[0m \x1b[32mDeleted a (1 B)\x1b[0m");
+    }
+}
@@ -0,0 +1,2839 @@
+use clap::Parser;
+use eframe::egui;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering}; // <--- חשוב לייבוא הזה
+use std::thread;
+use std::fs;
+
+mod autostart;
+mod chromium;
+mod cli;
+mod completions;
+mod config;
+mod custom;
+#[cfg(target_os = "linux")]
+mod dbus;
+mod dir_sizes;
+mod duplicates;
+mod engine;
+mod events;
+mod exclusions;
+mod file_manager;
+mod firefox;
+mod first_run;
+mod git;
+// גם ל---self-test, לא רק לבדיקות
+mod fixtures;
+mod history;
+mod hooks;
+mod i18n;
+mod largest;
+mod lock;
+mod log_queue;
+mod log_tail;
+mod manifest;
+mod mounts;
+mod preview;
+mod priority;
+mod progress;
+mod notification;
+mod parallel_walk;
+mod pause;
+mod palette;
+mod paths;
+mod persist;
+mod quarantine;
+mod registry;
+mod run_id;
+mod run_state;
+mod saved_preview;
+mod self_test;
+mod size_cache;
+#[cfg(not(windows))]
+mod trash;
+mod units;
+mod unmeasured;
+mod users;
+#[cfg(windows)]
+mod win;
+mod wine;
+use config::{SelectionPreset, Settings, ThemeChoice};
+use first_run::{FirstRun, Step};
+use palette::Palette;
+use custom::CustomCleaner;
+use dir_sizes::{DirSize, DirSizer};
+use duplicates::{DuplicateGroup, KeepPolicy};
+use engine::{SystemCleaner, DeletionStrategy, ItemOutcome, RetryReport, RiskLevel, Skipped};
+use exclusions::{LoadError, Rule};
+use i18n::{tr, tr_args, tr_plain, Language};
+use lock::{InstanceLock, LockError};
+use log_queue::LogQueue;
+use manifest::DeletionManifest;
+use tokio_util::sync::CancellationToken;
+use units::ByteUnits;
+use users::UserAccount;
+use mounts::FreeSpaceSnapshot;
+use pause::PauseGate;
+use preview::PreviewStore;
+use progress::{format_ago, format_duration, truncate_middle, CurrentPath, EtaEstimator, RateTracker, ScanProgress};
+use quarantine::{QuarantineRun, QuarantinedRun};
+use run_state::RunTracker;
+
+const APP_ID: &str = "system-cleaner-pro";
+
+// === קיצורי מקלדת ===
+// COMMAND = Ctrl בלינוקס. egui מתעלם מ-Shift עודף בהתאמה, אז את הגרסאות עם Shift בודקים קודם
+const SHORTCUT_PREVIEW: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::P);
+const SHORTCUT_CLEAN: egui::KeyboardShortcut =
+egui::KeyboardShortcut::new(egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT), egui::Key::C);
+const SHORTCUT_LOG_SEARCH: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::L);
+const SHORTCUT_SELECT_ALL: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::A);
+const SHORTCUT_DESELECT_ALL: egui::KeyboardShortcut =
+egui::KeyboardShortcut::new(egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT), egui::Key::A);
+const SHORTCUT_QUIT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Q);
+const SHORTCUT_ABORT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::Escape);
+
+// הנתיב הנוכחי בסרגל המצב - ארוך מזה נחתך באמצע
+const ACTIVITY_PATH_CHARS: usize = 60;
+
+fn main() -> Result<(), eframe::Error> {
+    let cli = cli::Cli::parse();
+    if cli.is_headless() {
+        std::process::exit(cli::run(cli));
+    }
+    log_tail::set_mirror_stdout(cli.verbose);
+
+    install_icon();
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+        .with_inner_size([1100.0, 700.0])
+        .with_min_inner_size([800.0, 600.0])
+        .with_title("System Cleaner Pro")
+        .with_app_id(APP_ID)
+        .with_icon(load_icon()),
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "System Cleaner Pro",
+        options,
+        Box::new(|cc| {
+            // קודם טוענים פונטים
+            setup_custom_fonts(&cc.egui_ctx);
+            // העדפת המערכת, לפני שהעיצוב שלנו דורס אותה.
+            // העיצוב עצמו (כהה/בהיר) מוחל בפריים הראשון לפי ההגדרות.
+            let system_dark = cc.egui_ctx.style().visuals.dark_mode;
+
+            // לפני הטעינה: קובץ שבור הוא לא הפעלה ראשונה
+            let first_run = !config::config_path().exists();
+            let (settings, config_recovery) = Settings::load_or_default();
+            // לפני יצירת האפליקציה - שמות הקטגוריות מתורגמים ב-init_categories
+            i18n::set_language(settings.appearance.language);
+            units::set_byte_units(settings.appearance.byte_units);
+            cc.egui_ctx.set_zoom_factor(settings.appearance.zoom);
+            // ריצות הסגר ישנות/גדולות מדי נמחקות בהפעלה
+            let purged = quarantine::purge_expired(
+                settings.advanced.quarantine_max_age_days,
+                settings.advanced.quarantine_max_size_mb * 1024 * 1024,
+            );
+            if purged > 0 {
+                println!("🛡️ Purged {} expired quarantine run(s)", purged);
+            }
+            let mut app = CleanerApp::with_settings(settings, system_dark);
+            app.config_recovery = config_recovery;
+            if first_run {
+                app.first_run = Some(FirstRun::new(&app.settings));
+            }
+            Ok(Box::new(app))
+        }),
+    )
+}
+
+// הפונט והאייקון מוטמעים בבינארי - עובד מכל תיקייה שמריצים ממנה
+const EMBEDDED_FONT: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/DejaVuSans-Bold.ttf"));
+const ICON_PNG: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/icon.png"));
+// אם הקובץ הזה קיים הוא דורס את הפונט המוטמע
+const FONT_OVERRIDE_PATH: &str = "assets/qqw.ttf";
+
+// === טעינת הפונט: מוטמע, עם אפשרות לדרוס מתיקיית assets ===
+fn setup_custom_fonts(ctx: &egui::Context) {
+    let mut fonts = egui::FontDefinitions::default();
+
+    // מנסה לטעון את הפונט מהתיקייה
+    // הנתיב הוא יחסי למיקום שממנו מריצים את התוכנה
+    let font_data = match fs::read(FONT_OVERRIDE_PATH) {
+        Ok(data) => {
+            println!("✅ Custom font loaded from {}", FONT_OVERRIDE_PATH);
+            data
+        }
+        // אין override - זה המצב הרגיל, בלי אזהרות
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => EMBEDDED_FONT.to_vec(),
+        Err(e) => {
+            println!("⚠️ Could not load custom font {}: {}", FONT_OVERRIDE_PATH, e);
+            println!("Using the embedded font.");
+            EMBEDDED_FONT.to_vec()
+        }
+    };
+
+    // טעינת הפונט לזיכרון בשם "my_bold_font"
+    fonts.font_data.insert(
+        "my_bold_font".to_owned(),
+                           Arc::new(egui::FontData::from_owned(font_data)),
+    );
+
+    // הגדרה: הפונט הזה יהיה העדיפות הראשונה גם לטקסט רגיל וגם למונוספייס
+    // (שים לב: זה יהפוך את כל התוכנה לפונט הזה)
+
+    // 1. Proportional (טקסט רגיל)
+    fonts.families.entry(egui::FontFamily::Proportional)
+    .or_default()
+    .insert(0, "my_bold_font".to_owned());
+
+    // 2. Monospace (לוגים)
+    fonts.families.entry(egui::FontFamily::Monospace)
+    .or_default()
+    .insert(0, "my_bold_font".to_owned());
+
+    // הגדלת הפונטים טיפה כדי שיראו את העובי
+    // זה משפיע על כל הטקסטים באפליקציה
+    /* כאן אנחנו דורסים את הגדרות הגודל.
+     *      אם הפונט שלך עבה מדי, תקטין את המספרים כאן.
+     */
+    /*
+     *   fonts.font_data.iter_mut().for_each(|(_, data)| {
+     *       data.tweak.scale = 1.2; // אופציונלי: הגדלת סקאלת הפונט ב-20%
+});
+*/
+
+    ctx.set_fonts(fonts);
+}
+
+
+
+
+
+
+
+
+
+
+
+
+// === עיצוב בסגנון BleachBit ===
+fn setup_bleachbit_style(ctx: &egui::Context, palette: &Palette) {
+    let mut visuals = if palette.dark { egui::Visuals::dark() } else { egui::Visuals::light() };
+
+    // רקע כהה אבל קריא - הצבעים עצמם ב-palette.rs
+    visuals.panel_fill = palette.panel_fill;
+    visuals.window_fill = palette.window_fill;
+
+    // צבעי הדגשה
+    visuals.selection.bg_fill = palette.selection;
+
+    // טקסטים. טקסט חלש (תיאורים, גדלים) - בניגודיות גבוהה צבע מלא במקום שקיפות
+    visuals.widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, palette.text);
+    visuals.weak_text_color = palette.weak_text;
+
+    ctx.set_visuals(visuals);
+
+    let mut style = (*ctx.style()).clone();
+    style.spacing.item_spacing = egui::vec2(8.0, 6.0);
+    style.spacing.scroll.bar_width = 12.0;
+    ctx.set_style(style);
+}
+
+fn load_icon() -> egui::IconData {
+    match image::load_from_memory(ICON_PNG) {
+        Ok(img) => {
+            let rgba = img.into_rgba8();
+            let (width, height) = rgba.dimensions();
+            egui::IconData { rgba: rgba.into_raw(), width, height }
+        }
+        // לא אמור לקרות - ה-PNG מוטמע
+        Err(_) => egui::IconData::default(),
+    }
+}
+
+// ב-Wayland האייקון נמצא לפי ה-app_id מתוך ערכת האייקונים, לא מהחלון
+fn install_icon() {
+    let icon_path = match dirs::data_dir() {
+        Some(dir) => dir.join("icons/hicolor/64x64/apps").join(format!("{}.png", APP_ID)),
+        None => return,
+    };
+    if icon_path.exists() {
+        return;
+    }
+    if let Some(parent) = icon_path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(&icon_path, ICON_PNG);
+}
+
+// מיקום ההתאמה (בבתים) בלי תלות באותיות גדולות/קטנות
+fn find_case_insensitive(haystack: &str, needle_lower: &str) -> Option<(usize, usize)> {
+    if needle_lower.is_empty() {
+        return None;
+    }
+    for (start, _) in haystack.char_indices() {
+        let mut lowered = String::new();
+        for (offset, ch) in haystack[start..].char_indices() {
+            lowered.extend(ch.to_lowercase());
+            if lowered.len() >= needle_lower.len() {
+                if lowered == needle_lower {
+                    return Some((start, start + offset + ch.len_utf8()));
+                }
+                break;
+            }
+        }
+    }
+    None
+}
+
+// שם הפריט עם הדגשה של החלק שתואם לחיפוש
+fn highlighted_label(ui: &egui::Ui, text: &str, query: &str) -> egui::text::LayoutJob {
+    let normal = egui::TextFormat {
+        font_id: egui::TextStyle::Body.resolve(ui.style()),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let mut job = egui::text::LayoutJob::default();
+    match find_case_insensitive(text, query) {
+        Some((start, end)) => {
+            let highlight = egui::TextFormat {
+                color: egui::Color32::BLACK,
+                background: egui::Color32::from_rgb(255, 215, 0),
+                ..normal.clone()
+            };
+            job.append(&text[..start], 0.0, normal.clone());
+            job.append(&text[start..end], 0.0, highlight);
+            job.append(&text[end..], 0.0, normal);
+        }
+        None => job.append(text, 0.0, normal),
+    }
+    job
+}
+
+// ✔ / – / ✖ ליד כל פריט אחרי ריצה, עם הסבר ב-tooltip
+fn show_outcome_glyph(ui: &mut egui::Ui, outcome: &ItemOutcome, palette: &Palette) {
+    match outcome {
+        ItemOutcome::Ok { files, bytes } => {
+            ui.colored_label(palette.success, "✔")
+            .on_hover_text(format!("{} files, {}", files, SystemCleaner::format_bytes(*bytes)));
+        }
+        ItemOutcome::Skipped(reason) => {
+            ui.colored_label(palette.warning, "–").on_hover_text(reason);
+        }
+        ItemOutcome::Failed(error) => {
+            ui.colored_label(palette.error, "✖").on_hover_text(error);
+        }
+    }
+}
+
+// עורך לרשימת נתיבים (החרגות, תיקיות לסריקה): ✕ להסרה, שדה + Add להוספה
+// row_extra: עוד מידע בסוף כל שורה (גודל וספירת פגיעות בעורך ההחרגות)
+fn path_list_editor(ui: &mut egui::Ui, paths: &mut Vec<String>, new_path: &mut String, hint: &str, row_extra: &mut dyn FnMut(&mut egui::Ui, &str)) {
+    let mut remove: Option<usize> = None;
+    for (i, path) in paths.iter().enumerate() {
+        ui.horizontal(|ui| {
+            if ui.small_button("✕").clicked() {
+                remove = Some(i);
+            }
+            ui.monospace(path);
+            row_extra(ui, path);
+        });
+    }
+    if let Some(i) = remove {
+        paths.remove(i);
+    }
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(new_path).hint_text(hint));
+        if ui.button(tr("settings.add")).clicked() && !new_path.trim().is_empty() {
+            paths.push(new_path.trim().to_string());
+            new_path.clear();
+        }
+    });
+}
+
+// === מבנה הנתונים ===
+
+#[derive(Clone)]
+struct Category {
+    id: String,
+    name: String,
+    icon: String,
+    items: Vec<CleanItem>,
+}
+
+// תת-פריט מה-registry (registry::SubEntry) - תיבת סימון מוזחת מתחת לפריט
+#[derive(Clone)]
+struct SubItem {
+    id: &'static str,
+    name: String,
+    risk: RiskLevel,
+    enabled: bool,
+}
+
+#[derive(Clone)]
+struct CleanItem {
+    id: String,
+    name: String,
+    description: String,
+    enabled: bool,
+    size_info: String,
+    risk: RiskLevel,
+    skip_in_use: bool,
+    keep_newest: Option<usize>,
+    // מופיע ב-"keep files newer than" של לשונית הבטיחות
+    keeps_recent: bool,
+    session_protected: &'static [&'static str],
+    sub_items: Vec<SubItem>,
+    // רץ לכל בית בנפרד כשמנקים כ-root
+    per_user: bool,
+    // הנתיבים/פקודות בפועל, ל-tooltip
+    targets: Vec<String>,
+    // תיקייה שנגררה לחלון: אפשר להסיר או לשמור כפריט קבוע
+    temporary: bool,
+    // "(not installed)": אפור, לא נבחר ב-select all ולא רץ
+    applicable: bool,
+    // "רץ לפני..." / "רץ אחרי..." - ל-tooltip
+    run_order: Vec<String>,
+}
+
+// שורה בגרף השחרור: (קטגוריה, סה"כ בייטים, סה"כ קבצים, [(שם, קבצים, בייטים, זמן)])
+type ChartRow<'a> = (&'a Category, u64, u64, Vec<(&'a str, u64, u64, Option<std::time::Duration>)>);
+
+// מה שה-thread של הניקוי צריך מכל CleaningItem שנבחר:
+// (id, שם, סיכון, skip_in_use, keep_newest, keep_recent, session_protected, תתי-פריטים, per_user)
+type SelectedItem = (String, String, RiskLevel, bool, Option<usize>, Option<std::time::Duration>, &'static [&'static str], Vec<String>, bool);
+
+struct CleanerApp {
+    categories: Vec<Category>,
+    // פריטים מ-custom-cleaners.toml, נטענים פעם אחת בהפעלה
+    custom: Arc<Vec<CustomCleaner>>,
+    // גודל התיקיות שנגררו, מחושב ברקע (לפי id)
+    dropped_sizes: Arc<Mutex<HashMap<String, u64>>>,
+    // כשרצים כ-root: המשתמשים שהבתים שלהם ינוקו. None בבחירה = כולם
+    users: Vec<UserAccount>,
+    selected_user: Option<String>,
+    cleaner: Option<Arc<SystemCleaner>>,
+
+    // Idle/Running/Aborting/Finished, לפי מזהה ריצה - ה-worker מעדכן, הפריים קורא
+    run: RunTracker,
+    progress: f32,
+    // תחילת הריצה הנוכחית; בסיום הזמן הכולל נשמר ב-run_elapsed
+    run_started: Option<std::time::Instant>,
+    run_elapsed: Option<std::time::Duration>,
+    eta: EtaEstimator,
+    // קבצים לשנייה בסרגל המצב, מהמונה של המנוע
+    files_rate: RateTracker,
+    logs: Arc<Mutex<Vec<String>>>,
+    // שורות מה-worker שעוד לא הגיעו ל-logs; update מרוקן אותן
+    log_queue: LogQueue,
+
+    // נוצר מחדש בכל ריצה; Abort מבטל אותו
+    cancel: Option<CancellationToken>,
+    // Pause/Resume; גם הוא נוצר מחדש בכל ריצה
+    pause: PauseGate,
+    // מתעדכן בכל פריים; ה-worker בודק אותו כדי להחליט אם לשלוח התראה
+    window_focused: Arc<AtomicBool>,
+
+    status_text: String,
+    // טקסט החיפוש בסרגל הצד
+    filter: String,
+    // סינון שורות ביומן (Ctrl+L)
+    log_filter: String,
+    // "Reported freed ... Free space increased by ..." אחרי ניקוי אמיתי
+    run_summary: Arc<Mutex<Option<String>>>,
+    // תוצאה לכל פריט בריצה האחרונה (נמחק כשמתחילה ריצה חדשה)
+    item_results: Arc<Mutex<HashMap<String, ItemOutcome>>>,
+    // זמן נטו לכל פריט בריצה האחרונה, לגרף הסיכום
+    item_times: Arc<Mutex<HashMap<String, std::time::Duration>>>,
+    // כמה אפשר לפנות בכל פריט, בבייטים - מהתצוגה המקדימה האחרונה. פריט שאינו במפה = לא ידוע
+    item_sizes: Arc<Mutex<HashMap<String, u64>>>,
+
+    // הגדרות גלובליות (נשמרות ל-config.toml)
+    settings: Settings,
+    show_settings: bool,
+    settings_tab: SettingsTab,
+    new_exclusion: String,
+    // גדלים של ההחרגות בעורך - נמדדים ברקע, ה-spinner מרענן עד שמגיעים
+    dir_sizes: DirSizer,
+    new_keep_site: String,
+    // "החרג קובץ זה" מהתפריט בעץ: רק לריצות של ההפעלה הנוכחית
+    session_exclusions: Vec<std::path::PathBuf>,
+    // "ייבוא החרגות": רשימה משותפת מקובץ, גם היא רק להפעלה הנוכחית
+    exclusion_file: String,
+    imported_exclusions: Option<(std::path::PathBuf, Vec<Rule>)>,
+    exclusion_import_errors: Vec<String>,
+    // העדפת המערכת בהפעלה, והעיצוב שכרגע מוחל
+    system_dark: bool,
+    applied_palette: Option<Palette>,
+    // חלון אישור לפני ניקוי אמיתי
+    confirm_clean_open: bool,
+    // האישור הפתוח הוא לפריט בודד (▶ בסרגל הצד) ולא לבחירה
+    confirm_only: Option<String>,
+
+    // תוצאות סריקת הכפולים - נשמרות בין תצוגה מקדימה לניקוי, הבחירה נעשית בעץ
+    duplicate_groups: Arc<Mutex<Vec<DuplicateGroup>>>,
+    duplicate_progress: Arc<ScanProgress>,
+    // בייטים שנכתבו ב-Wipe Free Space
+    wipe_progress: Arc<ScanProgress>,
+    // הקובץ שהמנוע מטפל בו עכשיו (מתעדכן עד 5 פעמים בשנייה)
+    current_path: Arc<CurrentPath>,
+    // סקירת דיסקים (וגם בורר ה-mount של Wipe Free Space). מתרענן בהפעלה, אחרי ריצה ובכפתור - לא בכל פריים
+    disks: Vec<(mounts::MountInfo, mounts::DiskUsage)>,
+    // לחיצה על דיסק מסננת את עץ התצוגה המקדימה לקבצים שעליו
+    disk_filter: Option<std::path::PathBuf>,
+    // "הדיסק כמעט מלא" - נבדק בהפעלה ואחרי כל ריצה; סגירה מסתירה עד סוף ההפעלה
+    low_space: Option<(std::path::PathBuf, f32)>,
+    low_space_dismissed: bool,
+    // config.toml לא נקרא בהפעלה - באנר עד שסוגרים אותו
+    config_recovery: Option<config::ConfigRecovery>,
+    // אשף ההפעלה הראשונה - פתוח כשאין config.toml, או כשפותחים אותו מההגדרות
+    first_run: Option<FirstRun>,
+    // GeneralSettings::scan_on_startup - תצוגה מקדימה בפריים הראשון (אחרי האשף, אם הוא פתוח)
+    startup_scan_pending: bool,
+    // התצוגה המקדימה של הפעלה קודמת - מוצעת בבאנר עד שטוענים, סורקים או סוגרים
+    saved_preview: Option<saved_preview::SavedPreview>,
+    // מתי נעשתה התצוגה המקדימה שנטענה; None = ההערכות מהפעלה זו
+    stale_preview: Option<chrono::DateTime<chrono::Local>>,
+    new_duplicate_root: String,
+    new_large_root: String,
+    // בחירה לכל קובץ בפריטים שמוחקים נתוני משתמש
+    preview: PreviewStore,
+
+    show_quarantine: bool,
+    quarantine_runs: Vec<QuarantinedRun>,
+    // ה-thread של שחזור/מחיקה מסמן כאן שצריך לרענן את הרשימה
+    quarantine_refresh: Arc<AtomicBool>,
+    show_history: bool,
+    // ריצות קודמות מ-history.jsonl, מתרעננות עם ההסגר בסוף כל ריצה
+    history: Vec<history::RunRecord>,
+    // לתווית "נוקה לפני..." בסרגל הצד; נטען מחדש יחד עם ההיסטוריה
+    last_cleaned: HashMap<String, chrono::DateTime<chrono::Local>>,
+    // השם ב-session bus, כשההגדרה דלוקה
+    #[cfg(target_os = "linux")]
+    dbus_service: Option<dbus::ServiceHandle>,
+}
+
+impl Default for CleanerApp {
+    fn default() -> Self {
+        Self {
+            categories: Self::init_categories(&[]),
+            custom: Arc::new(Vec::new()),
+            dropped_sizes: Arc::new(Mutex::new(HashMap::new())),
+            users: if users::is_root() { users::human_users() } else { Vec::new() },
+            selected_user: None,
+            cleaner: None,
+            run: RunTracker::default(),
+            progress: 0.0,
+            run_started: None,
+            run_elapsed: None,
+            eta: EtaEstimator::default(),
+            files_rate: RateTracker::default(),
+            logs: Arc::new(Mutex::new(Vec::new())),
+            log_queue: LogQueue::default(),
+            window_focused: Arc::new(AtomicBool::new(true)),
+            cancel: None,
+            pause: PauseGate::default(),
+            status_text: tr("app.ready"),
+            filter: String::new(),
+            log_filter: String::new(),
+            run_summary: Arc::new(Mutex::new(None)),
+            item_results: Arc::new(Mutex::new(HashMap::new())),
+            item_sizes: Arc::new(Mutex::new(HashMap::new())),
+            item_times: Arc::new(Mutex::new(HashMap::new())),
+            settings: Settings::default(),
+            show_settings: false,
+            settings_tab: SettingsTab::General,
+            new_exclusion: String::new(),
+            dir_sizes: DirSizer::spawn(|| {}),
+            new_keep_site: String::new(),
+            session_exclusions: Vec::new(),
+            exclusion_file: String::new(),
+            imported_exclusions: None,
+            exclusion_import_errors: Vec::new(),
+            system_dark: true,
+            applied_palette: None,
+            confirm_clean_open: false,
+            confirm_only: None,
+            duplicate_groups: Arc::new(Mutex::new(Vec::new())),
+            duplicate_progress: Arc::new(ScanProgress::default()),
+            wipe_progress: Arc::new(ScanProgress::default()),
+            current_path: Arc::new(CurrentPath::default()),
+            disks: Vec::new(),
+            disk_filter: None,
+            low_space: None,
+            low_space_dismissed: false,
+            config_recovery: None,
+            first_run: None,
+            startup_scan_pending: false,
+            saved_preview: saved_preview::load(),
+            stale_preview: None,
+            new_duplicate_root: String::new(),
+            new_large_root: String::new(),
+            preview: PreviewStore::default(),
+            show_quarantine: false,
+            quarantine_runs: quarantine::list_runs(),
+            quarantine_refresh: Arc::new(AtomicBool::new(false)),
+            show_history: false,
+            history: history::load(),
+            last_cleaned: history::load_last_cleaned(),
+            #[cfg(target_os = "linux")]
+            dbus_service: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingsTab {
+    General,
+    Safety,
+    Appearance,
+    Advanced,
+}
+
+// תפריט הקליק הימני על שורה בעץ התצוגה המקדימה
+#[derive(Debug, Clone)]
+enum PreviewAction {
+    OpenFolder(std::path::PathBuf),
+    CopyPath(std::path::PathBuf),
+    ExcludeFile(std::path::PathBuf),
+    ExcludeDir(std::path::PathBuf),
+}
+
+impl CleanerApp {
+    fn with_settings(settings: Settings, system_dark: bool) -> Self {
+        let mut app = Self { settings, system_dark, ..Self::default() };
+        let custom = custom::load();
+        // שגיאות בקובץ לא מפילות את האפליקציה - מופיעות כשורת לוג בהפעלה
+        if let Ok(mut logs) = app.logs.lock() {
+            for error in &custom.errors {
+                logs.push(tr_args("log.custom_invalid", &[error]));
+            }
+        }
+        app.categories = Self::init_categories(&custom.cleaners);
+        app.custom = Arc::new(custom.cleaners);
+        app.select_preset(app.settings.general.preset);
+        app.startup_scan_pending = app.settings.general.scan_on_startup;
+        app.refresh_disks();
+        app.sync_dbus_service();
+        app
+    }
+
+    // הבעלות על השם ב-session bus לפי ההגדרה: נלקחת כשמדליקים, משתחררת כשמכבים
+    #[cfg(target_os = "linux")]
+    fn sync_dbus_service(&mut self) {
+        match (self.settings.advanced.dbus_service, self.dbus_service.is_some()) {
+            (true, false) => self.dbus_service = Some(dbus::spawn()),
+            (false, true) => self.dbus_service = None,
+            _ => {}
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn sync_dbus_service(&mut self) {}
+
+    fn refresh_disks(&mut self) {
+        self.disks = mounts::real_mounts()
+        .into_iter()
+        .filter_map(|m| mounts::disk_usage(&m.mount_point).map(|usage| (m, usage)))
+        .filter(|(_, usage)| usage.total > 0)
+        .collect();
+        self.low_space = self.settings.low_space_disk(&self.disks)
+        .map(|(mount, usage)| (mount.mount_point.clone(), usage.used_fraction()));
+    }
+
+    // "Standard" = הבחירה שה-registry מסמן כברירת מחדל
+    fn select_standard(&mut self) {
+        let defaults: std::collections::HashSet<String> = registry::builtin_cleaners()
+        .into_iter()
+        .filter(|e| e.enabled_by_default)
+        .map(|e| e.id)
+        .collect();
+        for item in self.categories.iter_mut().flat_map(|c| c.items.iter_mut()) {
+            item.enabled = item.applicable && defaults.contains(&item.id);
+        }
+    }
+
+    fn select_preset(&mut self, preset: SelectionPreset) {
+        self.select_standard();
+        if preset == SelectionPreset::Safe {
+            for item in self.categories.iter_mut().flat_map(|c| c.items.iter_mut()) {
+                item.enabled &= item.risk == RiskLevel::Safe;
+            }
+        }
+    }
+
+    fn show_config_recovery_banner(&mut self, ctx: &egui::Context) {
+        let Some(recovery) = &self.config_recovery else { return };
+        let message = recovery.message(tr_args);
+        let warning = self.palette().warning;
+        egui::TopBottomPanel::top("config_recovery_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(warning, message);
+                if ui.small_button("✕").on_hover_text(tr("banner.close")).clicked() {
+                    self.config_recovery = None;
+                }
+            });
+        });
+    }
+
+    fn show_saved_preview_banner(&mut self, ctx: &egui::Context) {
+        let saved_at = match &self.saved_preview {
+            Some(saved) if !self.run.is_running() => saved.saved_at,
+            _ => return,
+        };
+        egui::TopBottomPanel::top("saved_preview_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr_args("banner.saved_preview", &[&format_ago(chrono::Local::now() - saved_at)]))
+                .on_hover_text(saved_at.format("%Y-%m-%d %H:%M").to_string());
+                if ui.button(tr("banner.load_preview")).clicked() {
+                    self.load_saved_preview();
+                }
+                if ui.button(tr("banner.rescan")).clicked() {
+                    self.saved_preview = None;
+                    self.run_process(ctx, true, None);
+                }
+                if ui.small_button("✕").on_hover_text(tr("banner.dismiss")).clicked() {
+                    self.saved_preview = None;
+                }
+            });
+        });
+    }
+
+    // ההערכות והרשימות נכנסות כמו אחרי תצוגה מקדימה, מסומנות כישנות
+    fn load_saved_preview(&mut self) {
+        let Some(saved) = self.saved_preview.take() else { return };
+        let mut logs = self.logs.lock().unwrap();
+        logs.push(tr_args("log.preview_loaded", &[&saved.saved_at.format("%Y-%m-%d %H:%M").to_string(), &saved.sizes.len().to_string()]));
+        if !saved.trimmed.is_empty() {
+            logs.push(tr_args("log.preview_trimmed", &[&saved.trimmed.len().to_string()]));
+        }
+        self.item_sizes.lock().unwrap().extend(saved.sizes);
+        self.preview.load_stale(saved.entries);
+        self.stale_preview = Some(saved.saved_at);
+    }
+
+    fn show_low_space_banner(&mut self, ctx: &egui::Context) {
+        let (mount, fraction) = match &self.low_space {
+            Some(low) if self.settings.general.low_space_warning && !self.low_space_dismissed && !self.run.is_running() => low.clone(),
+            _ => return,
+        };
+        let warning = self.palette().warning;
+        egui::TopBottomPanel::top("low_space_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let percent = format!("{:.0}", fraction * 100.0);
+                ui.colored_label(warning, tr_args("banner.low_space", &[&mount.display().to_string(), &percent]));
+                if ui.button(tr("banner.preview_standard")).clicked() {
+                    self.select_standard();
+                    self.run_process(ctx, true, None);
+                }
+                if ui.small_button("✕").on_hover_text(tr("banner.dismiss")).clicked() {
+                    self.low_space_dismissed = true;
+                }
+            });
+        });
+    }
+
+    fn is_dark(&self) -> bool {
+        match self.settings.appearance.theme {
+            ThemeChoice::System => self.system_dark,
+            ThemeChoice::Dark => true,
+            ThemeChoice::Light => false,
+        }
+    }
+
+    fn palette(&self) -> Palette {
+        Palette::new(self.is_dark(), &self.settings.appearance)
+    }
+
+    // === כאן השינוי הגדול: הוספת הקטגוריות החדשות ===
+    fn init_categories(custom: &[CustomCleaner]) -> Vec<Category> {
+        let mut categories = vec![
+            Category {
+                id: "system".to_string(),
+                name: tr("category.system"),
+                icon: "💻".to_string(),
+                items: Vec::new(),
+            },
+            Category {
+                id: "browsers".to_string(),
+                name: tr("category.browsers"),
+                icon: "🌐".to_string(),
+                items: Vec::new(),
+            },
+            Category {
+                id: "dev".to_string(),
+                name: tr("category.dev"),
+                icon: "🛠️".to_string(),
+                items: Vec::new(),
+            },
+            Category {
+                id: "privacy".to_string(),
+                name: tr("category.privacy"),
+                icon: "🕵️".to_string(),
+                items: Vec::new(),
+            },
+            Category {
+                id: "packages".to_string(),
+                name: tr("category.packages"),
+                icon: "📦".to_string(),
+                items: Vec::new(),
+            },
+            Category {
+                id: "analysis".to_string(),
+                name: tr("category.analysis"),
+                icon: "🔎".to_string(),
+                items: Vec::new(),
+            },
+            Category {
+                id: "custom".to_string(),
+                name: tr("category.custom"),
+                icon: "🧩".to_string(),
+                items: Vec::new(),
+            },
+            // קבצי הרצה מ-hooks.d (hooks.rs)
+            Category {
+                id: "hooks".to_string(),
+                name: tr("category.hooks"),
+                icon: "🪝".to_string(),
+                items: Vec::new(),
+            },
+        ];
+        // APT/DNF/Flatpak - אין מה להציג מחוץ ללינוקס
+        if !cfg!(unix) {
+            categories.retain(|c| c.id != "packages");
+        }
+
+        // הפריטים מגיעים מה-registry, והנתיבים מחושבים מאותו מקור שהמנוע מריץ
+        let probe = SystemCleaner::new(false, true).with_custom(Arc::new(custom.to_vec()));
+        let entries = registry::all_cleaners(custom);
+        for entry in &entries {
+            debug_assert!(
+                SystemCleaner::has_handler(&entry.id) || custom.iter().any(|c| c.id == entry.id),
+                "registry item '{}' has no engine handler", entry.id
+            );
+            if let Some(cat) = categories.iter_mut().find(|c| c.id == entry.category) {
+                let applicable = entry.is_applicable();
+                cat.items.push(CleanItem {
+                    targets: entry.targets(&probe).iter().map(|t| t.to_string()).collect(),
+                    id: entry.id.clone(),
+                    name: i18n::tr_or(&format!("item.{}.name", entry.id), &entry.name),
+                    description: i18n::tr_or(&format!("item.{}.description", entry.id), &entry.description),
+                    enabled: entry.enabled_by_default && applicable,
+                    size_info: "".to_string(),
+                    risk: entry.risk,
+                    skip_in_use: entry.skip_in_use,
+                    keep_newest: entry.keep_newest,
+                    keeps_recent: entry.keeps_recent,
+                    session_protected: entry.session_protected,
+                    sub_items: entry.sub_entries.iter().map(|sub| SubItem {
+                        id: sub.id,
+                        name: i18n::tr_or(&format!("item.{}.{}.name", entry.id, sub.id), sub.name),
+                        risk: sub.risk,
+                        enabled: sub.enabled_by_default,
+                    }).collect(),
+                    per_user: entry.per_user,
+                    temporary: custom.iter().any(|c| c.id == entry.id && c.temporary),
+                    applicable,
+                    run_order: Vec::new(),
+                });
+            }
+        }
+        // ב-tooltip: למה הפריט רץ לפני/אחרי אחרים (registry::run_order)
+        let name_of = |id: &str| i18n::tr_or(&format!("item.{}.name", id), entries.iter().find(|e| e.id == id).map_or(id, |e| e.name.as_str()));
+        for item in categories.iter_mut().flat_map(|c| c.items.iter_mut()) {
+            let Some(entry) = entries.iter().find(|e| e.id == item.id) else { continue };
+            if !entry.runs_before.is_empty() {
+                let names: Vec<String> = entry.runs_before.iter().map(|id| name_of(id)).collect();
+                item.run_order.push(tr_args("sidebar.runs_before", &[&names.join(", ")]));
+            }
+            let after: Vec<String> = entries.iter().filter(|e| e.runs_before.contains(&entry.id.as_str())).map(|e| name_of(&e.id)).collect();
+            if !after.is_empty() {
+                item.run_order.push(tr_args("sidebar.runs_after", &[&after.join(", ")]));
+            }
+        }
+        categories
+    }
+
+    // אותה לוגיקה לכפתור ולקיצור - עם אישור אם הוגדר
+    fn request_clean(&mut self, ctx: &egui::Context) {
+        if self.settings.general.confirm_before_clean {
+            self.confirm_only = None;
+            self.confirm_clean_open = true;
+        } else {
+            self.run_process(ctx, false, None);
+        }
+    }
+
+    // ▶ בסרגל הצד: רק הפריט הזה, בלי לגעת בתיבות הסימון. מסוכן - דרך האישור גם אם כובה
+    fn request_clean_item(&mut self, ctx: &egui::Context, id: String, risk: RiskLevel) {
+        if self.settings.general.confirm_before_clean || risk == RiskLevel::Dangerous {
+            self.confirm_only = Some(id);
+            self.confirm_clean_open = true;
+        } else {
+            self.run_process(ctx, false, Some(&id));
+        }
+    }
+
+    fn abort(&mut self) {
+        if let Some(cancel) = &self.cancel {
+            cancel.cancel();
+        }
+        // מעיר את ה-worker אם הוא מחכה בהשהיה
+        self.pause.resume();
+        self.run.abort();
+        self.status_text = tr("app.aborted");
+    }
+
+    // בסרגל המצב בזמן ריצה: הקובץ הנוכחי (האמצע נחתך) וקבצים לשנייה. בין תיקיות ובהשהיה - ריק
+    fn show_activity(&mut self, ui: &mut egui::Ui, files_deleted: u64, paused: bool) {
+        if paused {
+            return;
+        }
+        self.files_rate.sample(files_deleted);
+        if let Some(rate) = self.files_rate.per_second().filter(|&rate| rate >= 1.0) {
+            ui.separator();
+            ui.label(tr_args("status.rate", &[&format!("{:.0}", rate)]));
+        }
+        if let Some(path) = self.current_path.get() {
+            ui.separator();
+            let full = path.display().to_string();
+            ui.add(egui::Label::new(egui::RichText::new(truncate_middle(&full, ACTIVITY_PATH_CHARS)).monospace().weak()).truncate())
+            .on_hover_text(full);
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        if self.pause.is_paused() {
+            self.pause.resume();
+            // הקצב שלפני ההשהיה כבר לא מייצג
+            self.eta.reset();
+            self.files_rate.reset();
+        } else {
+            self.pause.pause();
+        }
+    }
+
+    fn set_all_enabled(&mut self, enabled: bool) {
+        for item in self.categories.iter_mut().flat_map(|c| c.items.iter_mut()) {
+            item.enabled = enabled && item.applicable;
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // כשיש פוקוס בשדה טקסט, Ctrl+A / Esc / Ctrl+Shift+C שייכים לשדה
+        let typing = ctx.wants_keyboard_input();
+        let pressed = |shortcut: &egui::KeyboardShortcut| ctx.input_mut(|i| i.consume_shortcut(shortcut));
+
+        if pressed(&SHORTCUT_QUIT) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+        if pressed(&SHORTCUT_LOG_SEARCH) {
+            ctx.memory_mut(|m| m.request_focus(egui::Id::new("log_search")));
+        }
+        if !self.run.is_busy() && pressed(&SHORTCUT_PREVIEW) {
+            self.run_process(ctx, true, None);
+        }
+        if typing {
+            return;
+        }
+        if !self.run.is_busy() && pressed(&SHORTCUT_CLEAN) {
+            self.request_clean(ctx);
+        }
+        if self.run.is_running() && pressed(&SHORTCUT_ABORT) {
+            self.abort();
+        }
+        if pressed(&SHORTCUT_DESELECT_ALL) {
+            self.set_all_enabled(false);
+        } else if pressed(&SHORTCUT_SELECT_ALL) {
+            self.set_all_enabled(true);
+        }
+    }
+
+    // אחרי החלפת שפה או שינוי בפריטים המותאמים: בונים מחדש ושומרים על הבחירות של המשתמש
+    fn rebuild_categories(&mut self) {
+        let mut categories = Self::init_categories(&self.custom);
+        for item in categories.iter_mut().flat_map(|c| c.items.iter_mut()) {
+            if let Some(old) = self.categories.iter().flat_map(|c| c.items.iter()).find(|i| i.id == item.id) {
+                item.enabled = old.enabled;
+                item.size_info = old.size_info.clone();
+            }
+        }
+        self.categories = categories;
+    }
+
+    fn retranslate(&mut self) {
+        self.rebuild_categories();
+        if !self.run.is_running() {
+            self.status_text = tr("app.ready");
+        }
+    }
+
+    // תיקייה שנגררה מסייר הקבצים -> פריט זמני בקטגוריה Custom, מסומן מיד
+    fn add_dropped_dir(&mut self, ctx: &egui::Context, path: std::path::PathBuf) {
+        let dir = match fs::canonicalize(&path) {
+            Ok(dir) if dir.is_dir() => dir,
+            _ => {
+                self.logs.lock().unwrap().push(tr_args("log.drop_not_dir", &[&path.display().to_string()]));
+                return;
+            }
+        };
+        if engine::is_protected_path(&dir) {
+            self.logs.lock().unwrap().push(tr_args("log.drop_protected", &[&dir.display().to_string()]));
+            return;
+        }
+        let dir_text = dir.display().to_string();
+        if self.custom.iter().any(|c| c.directories.iter().any(|d| config::expand_path(d) == dir)) {
+            return;
+        }
+        let taken: std::collections::HashSet<String> = self.categories.iter().flat_map(|c| c.items.iter()).map(|i| i.id.clone()).collect();
+        let cleaner = CustomCleaner::from_dropped_dir(&dir, &taken);
+        let id = cleaner.id.clone();
+        let mut custom = (*self.custom).clone();
+        custom.push(cleaner);
+        self.custom = Arc::new(custom);
+        self.rebuild_categories();
+        if let Some(item) = self.categories.iter_mut().flat_map(|c| c.items.iter_mut()).find(|i| i.id == id) {
+            item.enabled = true;
+        }
+        self.logs.lock().unwrap().push(tr_args("log.drop_added", &[&dir_text]));
+
+        let sizes = self.dropped_sizes.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let size: u64 = walkdir::WalkDir::new(&dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| e.metadata().ok())
+            .map(|m| m.len())
+            .sum();
+            sizes.lock().unwrap().insert(id, size);
+            ctx.request_repaint();
+        });
+    }
+
+    fn remove_dropped(&mut self, id: &str) {
+        let custom: Vec<CustomCleaner> = self.custom.iter().filter(|c| c.id != id).cloned().collect();
+        self.custom = Arc::new(custom);
+        self.dropped_sizes.lock().unwrap().remove(id);
+        self.rebuild_categories();
+    }
+
+    fn save_dropped(&mut self, id: &str) {
+        let mut custom = (*self.custom).clone();
+        let Some(cleaner) = custom.iter_mut().find(|c| c.id == id) else { return };
+        match custom::append(cleaner) {
+            Ok(()) => {
+                cleaner.temporary = false;
+                self.logs.lock().unwrap().push(tr_args("log.drop_saved", &[&cleaner.name, &custom::custom_cleaners_path().display().to_string()]));
+                self.custom = Arc::new(custom);
+                self.rebuild_categories();
+            }
+            Err(e) => self.logs.lock().unwrap().push(format!("{}: {}", tr("log.error"), e)),
+        }
+    }
+
+    // שורה שגויה מבטלת את כל הייבוא, עם מספרי השורות - כמו --exclude-from
+    fn import_exclusions(&mut self) {
+        let source = config::expand_path(&self.exclusion_file);
+        self.exclusion_import_errors.clear();
+        match exclusions::load_list(&source) {
+            Ok(rules) => {
+                let count = rules.len().to_string();
+                self.logs.lock().unwrap().push(tr_args("log.exclusions_imported", &[&count, &source.display().to_string()]));
+                self.imported_exclusions = Some((source, rules));
+            }
+            Err(LoadError::Io(e)) => {
+                self.exclusion_import_errors.push(tr_args("cli.exclusions_unreadable", &[&source.display().to_string(), &e.to_string()]));
+            }
+            Err(LoadError::Lines(errors)) => {
+                self.exclusion_import_errors.extend(errors.iter().map(|error| {
+                    tr_args("settings.exclusion_line", &[&error.line.to_string(), &tr(error.reason), &error.text])
+                }));
+            }
+        }
+    }
+
+    fn exclusion_paths(&self) -> Vec<std::path::PathBuf> {
+        let mut paths = self.settings.exclusion_paths();
+        paths.extend(self.session_exclusions.iter().cloned());
+        paths
+    }
+
+    fn apply_preview_action(&mut self, ctx: &egui::Context, action: PreviewAction) {
+        match action {
+            PreviewAction::OpenFolder(path) => {
+                let dir = path.parent().map(|p| p.to_path_buf()).unwrap_or(path);
+                self.open_external(&dir);
+            }
+            PreviewAction::CopyPath(path) => ctx.copy_text(path.display().to_string()),
+            PreviewAction::ExcludeFile(path) => {
+                self.deselect_preview_under(&path);
+                self.logs.lock().unwrap().push(tr_args("log.excluded_file", &[&path.display().to_string()]));
+                self.session_exclusions.push(path);
+            }
+            PreviewAction::ExcludeDir(path) => {
+                let Some(dir) = path.parent().map(|p| p.to_path_buf()) else { return };
+                self.deselect_preview_under(&dir);
+                let text = dir.display().to_string();
+                if !self.settings.exclusion_paths().contains(&dir) {
+                    self.settings.safety.exclusions.push(text.clone());
+                }
+                self.logs.lock().unwrap().push(tr_args("log.excluded_dir", &[&text]));
+                if let Err(e) = self.settings.save() {
+                    self.status_text = tr_args("app.save_failed", &[&e.to_string()]);
+                }
+            }
+        }
+    }
+
+    // מה שהוחרג לא יימחק גם אם עדיין מסומן בעץ
+    fn deselect_preview_under(&self, path: &std::path::Path) {
+        for entry in self.preview.lock().values_mut().flatten() {
+            if entry.path.starts_with(path) {
+                entry.selected = false;
+            }
+        }
+    }
+
+    // only = פריט בודד במקום הפריטים המסומנים
+    fn run_process(&mut self, ctx: &egui::Context, is_preview: bool, only: Option<&str>) {
+        // מופע אחר (עוד חלון, או ה-CLI מ-cron) כבר עובד על אותן תיקיות
+        let instance_lock = match InstanceLock::acquire() {
+            Ok(lock) => lock,
+            Err(LockError::Held(_)) => {
+                self.status_text = tr("app.locked");
+                return;
+            }
+            Err(e) => {
+                self.status_text = format!("{}: {}", tr("log.error"), e);
+                return;
+            }
+        };
+
+        let run_token = self.run.start();
+        // המספרים בסרגל המצב שייכים לריצה הזו - גם אם היא תיעצר לפני שתתחיל (הסגר שנכשל וכו')
+        self.cleaner = None;
+        self.progress = 0.0;
+        self.run_started = Some(std::time::Instant::now());
+        self.run_elapsed = None;
+        self.eta.reset();
+        self.logs.lock().unwrap().clear();
+        self.log_queue.clear();
+        *self.run_summary.lock().unwrap() = None;
+        // ריצה של פריט בודד לא מוחקת את התוצאות של האחרים מהריצה הקודמת
+        match only {
+            Some(id) => {
+                self.item_results.lock().unwrap().remove(id);
+                self.item_times.lock().unwrap().remove(id);
+            }
+            None => {
+                self.item_results.lock().unwrap().clear();
+                self.item_times.lock().unwrap().clear();
+            }
+        }
+        self.duplicate_progress.reset();
+        self.wipe_progress.reset();
+        self.current_path.clear();
+        self.files_rate.reset();
+        // תצוגה מקדימה חדשה = בחירה חדשה; ניקוי משתמש בבחירה הקיימת
+        if is_preview {
+            self.preview.clear();
+            self.stale_preview = None;
+        }
+        // הבאנר מציע את מה שהריצה הזו עומדת להחליף
+        self.saved_preview = None;
+
+        let only_name = only.and_then(|id| self.categories.iter().flat_map(|c| c.items.iter()).find(|item| item.id == id)).map(|item| item.name.clone());
+        self.status_text = match only_name {
+            Some(name) => tr_args(if is_preview { "app.previewing_item" } else { "app.cleaning_item" }, &[&name]),
+            None => tr(if is_preview { "app.previewing" } else { "app.cleaning" }),
+        };
+
+        // אותו מזהה להסגר, לרשימת המחיקות, להיסטוריה, להתראה ולסיכום
+        let run_id = run_id::new_run_id();
+        let mut cleaner_instance = SystemCleaner::new(true, is_preview).with_run_id(run_id.clone());
+        // בלי repaint מכל שורה - update מרענן כל REPAINT_INTERVAL כל עוד יש ריצה
+        let callback = self.log_queue.callback();
+        if log_tail::mirror_stdout() {
+            cleaner_instance = cleaner_instance.with_callback(log_tail::stdout_sink(&run_id));
+        }
+
+        let cancel = CancellationToken::new();
+        self.cancel = Some(cancel.clone());
+        self.pause = PauseGate::default();
+        cleaner_instance = cleaner_instance
+        .with_settings(&self.settings)
+        .with_exclusions(self.exclusion_paths())
+        .with_callback(callback)
+        .with_cancel(cancel)
+        .with_pause(self.pause.clone())
+        .with_preview(self.preview.clone())
+        .with_custom(self.custom.clone());
+        if let Some((source, rules)) = &self.imported_exclusions {
+            cleaner_instance = cleaner_instance.with_imported_exclusions(source.clone(), rules.clone());
+        }
+        // תוצאות הכפולים וההתקדמות משותפות עם העץ וסרגל המצב
+        cleaner_instance.duplicates.groups = self.duplicate_groups.clone();
+        cleaner_instance.duplicates.progress = self.duplicate_progress.clone();
+        cleaner_instance.wipe.progress = self.wipe_progress.clone();
+        cleaner_instance.current_path = self.current_path.clone();
+
+        if self.settings.safety.quarantine_enabled && !is_preview {
+            match QuarantineRun::create(&run_id) {
+                Ok(run) => {
+                    cleaner_instance = cleaner_instance.with_strategy(DeletionStrategy::Quarantine(Arc::new(run)));
+                }
+                Err(e) => {
+                    // בלי הסגר תקין לא מוחקים בכלל - המשתמש ביקש רשת ביטחון
+                    self.run.cancel_start(run_token);
+                    self.status_text = tr_args("app.quarantine_failed", &[&e.to_string()]);
+                    return;
+                }
+            }
+        }
+
+        if self.settings.safety.deletion_manifest && !is_preview {
+            match DeletionManifest::create(&run_id) {
+                Ok(manifest) => {
+                    cleaner_instance = cleaner_instance.with_manifest(Arc::new(manifest));
+                }
+                Err(e) => {
+                    // הרישום נדרש לביקורת - בלעדיו לא מוחקים
+                    self.run.cancel_start(run_token);
+                    self.status_text = tr_args("app.manifest_failed", &[&e.to_string()]);
+                    return;
+                }
+            }
+        }
+
+        let cleaner = Arc::new(cleaner_instance);
+        self.cleaner = Some(cleaner.clone());
+
+        let selected_items: Vec<SelectedItem> = self.categories.iter()
+        .flat_map(|cat| cat.items.iter())
+        .filter(|item| item.applicable && only.map_or(item.enabled, |id| item.id == id))
+        .map(|item| (item.id.clone(), item.name.clone(), item.risk, item.skip_in_use, item.keep_newest, self.settings.keep_recent(&item.id), item.session_protected, item.sub_items.iter().filter(|s| s.enabled).map(|s| s.id.to_string()).collect(), item.per_user))
+        .collect();
+        // מנהלי חבילות קודם - פריטי התיקיות רואים את המצב אחרי apt-get clean וכו'
+        let selected_items = registry::sort_for_run(&registry::builtin_cleaners(), selected_items, |(id, ..)| id.as_str());
+        // בלי root הרשימה ריקה - הכל רץ פעם אחת על הבית הנוכחי
+        let history_items: Vec<String> = selected_items.iter().map(|(id, ..)| id.clone()).collect();
+        let run_users: Vec<UserAccount> = self.users.iter()
+        .filter(|u| self.selected_user.as_ref().is_none_or(|name| &u.name == name))
+        .cloned()
+        .collect();
+        let secure_delete = self.settings.safety.secure_delete_privacy;
+        let skip_in_use_files = self.settings.safety.skip_in_use_files;
+
+        let ctx = ctx.clone();
+        let cleaner_thread = cleaner.clone();
+        let run_state = self.run.clone();
+        let quarantine_refresh = self.quarantine_refresh.clone();
+        let item_results = self.item_results.clone();
+        let item_sizes = self.item_sizes.clone();
+        let preview_store = self.preview.clone();
+        let item_times = self.item_times.clone();
+        let run_summary = self.run_summary.clone();
+        let summary_logs = self.log_queue.sender();
+        let window_focused = self.window_focused.clone();
+        let notify_on_finish = self.settings.general.notify_on_finish;
+        let low_priority = self.settings.advanced.low_priority;
+        let estimate_unreadable = self.settings.advanced.estimate_unreadable_as_root;
+
+        thread::spawn(move || {
+            // גם אם משהו כאן קורס, ה-UI לא נשאר תקוע ב"מנקה..."
+            let finished = run_state.finish_on_drop(run_token);
+            let started = std::time::Instant::now();
+            // לפני יצירת ה-runtime, כדי שה-threads שלו יירשו את העדיפות
+            if low_priority {
+                match priority::lower_current_thread() {
+                    Ok(()) => cleaner_thread.log_sync(&tr("log.low_priority")),
+                    Err(e) => cleaner_thread.log_sync(&tr_args("log.low_priority_failed", &[&e.to_string()])),
+                }
+            }
+            // בתצוגה מקדימה אין מה לאמת - שום דבר לא נמחק
+            let roots = cleaner_thread.candidate_roots();
+            let before = if is_preview { None } else { Some(FreeSpaceSnapshot::capture(&roots)) };
+
+            // כמה זמן לקח כל פריט - כדי לזהות מנקים איטיים במיוחד
+            let mut item_durations: Vec<(String, std::time::Duration)> = Vec::new();
+            // פריטים שהניקוי שלהם הצליח - ל"נוקה לאחרונה"
+            let mut cleaned_items: Vec<String> = Vec::new();
+            // כמה התפנה בבית של כל משתמש (רק בריצה כ-root)
+            let mut per_user_bytes: Vec<(String, u64)> = run_users.iter().map(|u| (u.name.clone(), 0)).collect();
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async {
+                for (item, name, risk, skip_in_use, keep_newest, keep_recent, session_protected, sub_entries, per_user) in selected_items {
+                    if cleaner_thread.checkpoint_async().await {
+                        break;
+                    }
+                    let item_users: Vec<Option<UserAccount>> = if per_user && !run_users.is_empty() {
+                        run_users.iter().cloned().map(Some).collect()
+                    } else {
+                        vec![None]
+                    };
+                    let before_item = cleaner_thread.get_stats_sync();
+                    let item_started = std::time::Instant::now();
+                    let paused_before = cleaner_thread.pause.paused_for();
+                    let mut failure: Option<ItemOutcome> = None;
+                    for user in item_users {
+                        // דריסה רק לפריטי פרטיות - לדרוס cache של דפדפן זה סתם איטי
+                        // בדיקת קבצים פתוחים רק איפה שזה שווה את סריקת /proc
+                        let user_name = user.as_ref().map(|u| u.name.clone());
+                        let cleaner_item = (*cleaner_thread).clone()
+                        .with_secure_delete(secure_delete && risk == RiskLevel::Privacy)
+                        .with_skip_in_use(skip_in_use_files && skip_in_use)
+                        .with_keep_newest(keep_newest)
+                        .with_keep_recent(keep_recent)
+                        .with_session_protected(session_protected)
+                        .with_sub_entries(sub_entries.clone())
+                        .with_user(user);
+                        let before_user = cleaner_item.get_stats_sync().bytes_freed;
+                        let result = cleaner_item.run_item(&item).await;
+                        if let Some(entry) = user_name.and_then(|name| per_user_bytes.iter_mut().find(|(n, _)| *n == name)) {
+                            entry.1 += cleaner_item.get_stats_sync().bytes_freed - before_user;
+                        }
+                        // כישלון גובר על דילוג; דילוג של משתמש אחד לא מסתיר הצלחה של אחר
+                        if let Err(e) = result {
+                            match e.downcast_ref::<Skipped>() {
+                                Some(Skipped(reason)) => {
+                                    if failure.is_none() {
+                                        failure = Some(ItemOutcome::Skipped(reason.clone()));
+                                    }
+                                }
+                                None => {
+                                    cleaner_item.record_error();
+                                    failure = Some(ItemOutcome::Failed(e.to_string()));
+                                }
+                            }
+                        }
+                    }
+                    let paused_during = cleaner_thread.pause.paused_for().saturating_sub(paused_before);
+                    let item_duration = item_started.elapsed().saturating_sub(paused_during);
+                    item_times.lock().unwrap().insert(item.clone(), item_duration);
+                    item_durations.push((name, item_duration));
+
+                    let after_item = cleaner_thread.get_stats_sync();
+                    let freed = ItemOutcome::Ok {
+                        files: after_item.files_deleted - before_item.files_deleted,
+                        bytes: after_item.bytes_freed - before_item.bytes_freed,
+                    };
+                    let outcome = match failure {
+                        Some(ItemOutcome::Skipped(_)) if after_item.files_deleted > before_item.files_deleted => freed,
+                        Some(failure) => failure,
+                        None => freed,
+                    };
+                    // תצוגה מקדימה = ההערכה; ניקוי מוצלח = לא נשאר מה לפנות; כישלון = לא ידוע
+                    match (&outcome, is_preview) {
+                        (ItemOutcome::Ok { bytes, .. }, true) => item_sizes.lock().unwrap().insert(item.clone(), *bytes),
+                        (ItemOutcome::Ok { .. }, false) => item_sizes.lock().unwrap().insert(item.clone(), 0),
+                        _ => item_sizes.lock().unwrap().remove(&item),
+                    };
+                    if let (ItemOutcome::Ok { .. }, false) = (&outcome, is_preview) {
+                        cleaned_items.push(item.clone());
+                    }
+                    item_results.lock().unwrap().insert(item.clone(), outcome);
+                    thread::sleep(std::time::Duration::from_millis(50));
+                }
+            });
+            // קבצים שנכשלו באמצע (תפוסים, NFS) - ניסיון נוסף אחרי שכל הפריטים סיימו
+            let retry = if is_preview { RetryReport::default() } else { runtime.block_on(cleaner_thread.retry_failed()) };
+
+            let total_duration = started.elapsed().saturating_sub(cleaner_thread.pause.paused_for());
+            let _ = summary_logs.send(tr_args("log.timing", &[&format_duration(total_duration)]));
+            item_durations.sort_by_key(|(_, duration)| std::cmp::Reverse(*duration));
+            for (name, duration) in &item_durations {
+                let _ = summary_logs.send(format!("  {:>8}  {}", format_duration(*duration), name));
+            }
+
+            if let Some(before) = before {
+                let after = FreeSpaceSnapshot::capture(&roots);
+                let freed = cleaner_thread.get_stats_sync();
+                let reported = (freed.disk_bytes_freed, freed.ram_bytes_freed);
+                let mut summary = before.describe_delta(&after, &cleaner_thread.touched_roots(), reported);
+                // ליד ההשוואה למקום הפנוי - זה מה שמסביר פער בה
+                for note in cleaner_thread.snapshot_notes() {
+                    summary.push_str(&note);
+                }
+                let skipped_in_use = cleaner_thread.get_stats_sync().skipped_in_use;
+                if skipped_in_use > 0 {
+                    summary.push_str(&tr_args("summary.in_use", &[&skipped_in_use.to_string()]));
+                }
+                let skipped_needs_root = cleaner_thread.get_stats_sync().skipped_needs_root;
+                if skipped_needs_root > 0 {
+                    summary.push_str(&tr_args("summary.needs_root", &[&skipped_needs_root.to_string()]));
+                }
+                let skipped_protected = cleaner_thread.get_stats_sync().skipped_protected;
+                if skipped_protected > 0 {
+                    summary.push_str(&tr_args("summary.protected", &[&skipped_protected.to_string()]));
+                }
+                let skipped_immutable = cleaner_thread.get_stats_sync().skipped_immutable;
+                if skipped_immutable > 0 {
+                    summary.push_str(&tr_args("summary.immutable", &[&skipped_immutable.to_string()]));
+                }
+                let git = cleaner_thread.get_stats_sync();
+                if git.git_verified + git.git_skipped > 0 {
+                    summary.push_str(&tr_args("summary.git", &[&git.git_verified.to_string(), &git.git_skipped.to_string()]));
+                }
+                let organized = cleaner_thread.get_stats_sync();
+                if organized.files_organized > 0 {
+                    summary.push_str(&tr_args("summary.organized", &[&SystemCleaner::format_bytes(organized.bytes_organized), &organized.files_organized.to_string()]));
+                }
+                if retry.recovered > 0 {
+                    summary.push_str(&tr_args("summary.retried", &[&retry.recovered.to_string()]));
+                }
+                if !retry.failures.is_empty() {
+                    summary.push_str(&tr_args("summary.failed_files", &[&retry.failures.len().to_string()]));
+                    // הרשימה המלאה בלוג, מעל שורת הסיכום
+                    let _ = summary_logs.send(tr_args("log.failed_files", &[&retry.failures.len().to_string()]));
+                    for line in retry.failure_lines() {
+                        let _ = summary_logs.send(line);
+                    }
+                }
+                for note in cleaner_thread.summary_notes().into_iter().chain(cleaner_thread.exclusion_notes()) {
+                    summary.push_str(&note);
+                }
+                summary.push_str(&tr_args("summary.duration", &[&format_duration(total_duration)]));
+                for (user, bytes) in &per_user_bytes {
+                    summary.push_str(&tr_args("summary.user", &[user, &SystemCleaner::format_bytes(*bytes)]));
+                }
+                if low_priority {
+                    summary.push_str(&tr("summary.low_priority"));
+                }
+                if let Some(manifest) = &cleaner_thread.manifest {
+                    summary.push_str(&tr_args("summary.manifest", &[&manifest.path().display().to_string()]));
+                }
+                summary.push_str(&tr_args("summary.run_id", &[&cleaner_thread.run_id]));
+                let _ = summary_logs.send(summary.clone());
+                *run_summary.lock().unwrap() = Some(summary);
+            }
+
+            if notify_on_finish && started.elapsed() >= notification::NOTIFY_AFTER && !window_focused.load(Ordering::Relaxed) {
+                let stats = cleaner_thread.get_stats_sync();
+                let bytes = SystemCleaner::format_bytes(stats.bytes_freed);
+                let body = if is_preview {
+                    tr_plain("notify.preview", &[&bytes])
+                } else {
+                    if stats.ram_bytes_freed > 0 {
+                        let disk = SystemCleaner::format_bytes(stats.disk_bytes_freed);
+                        let ram = SystemCleaner::format_bytes(stats.ram_bytes_freed);
+                        tr_plain("notify.freed_split", &[&disk, &ram, &notification::format_count(stats.files_deleted)])
+                    } else {
+                        tr_plain("notify.freed", &[&bytes, &notification::format_count(stats.files_deleted)])
+                    }
+                };
+                // דיווח באג מההתראה מגיע עם המזהה שמוביל להיסטוריה ולרשימת המחיקות
+                let body = format!("{}\n{}", body, tr_plain("notify.run_id", &[&cleaner_thread.run_id]));
+                if let Err(e) = notification::send(&body) {
+                    let _ = summary_logs.send(tr_args("notify.failed", &[&e]));
+                }
+            }
+
+            // pkexec רק אחרי שהסריקה נגמרה, ורק אם באמת נחסם משהו
+            if is_preview && estimate_unreadable && !cleaner_thread.cancel.is_cancelled() && !cleaner_thread.unmeasured.denied_paths().is_empty() {
+                if let Err(e) = cleaner_thread.unmeasured.estimate_as_root() {
+                    cleaner_thread.log_sync(&tr_args("log.unmeasured_estimate_failed", &[&e.to_string()]));
+                }
+            }
+
+            // ריצה שבוטלה באמצע לא נשמרת - ההערכות שלה חלקיות
+            if is_preview && !cleaner_thread.cancel.is_cancelled() {
+                let sizes = item_sizes.lock().unwrap().clone();
+                let entries = preview_store.lock().clone();
+                if let Err(e) = saved_preview::save(&saved_preview::SavedPreview::new(chrono::Local::now(), sizes, entries)) {
+                    cleaner_thread.log_sync(&tr_args("log.preview_save_failed", &[&e.to_string()]));
+                }
+            } else if !is_preview {
+                saved_preview::discard();
+            }
+
+            if !is_preview {
+                let stats = cleaner_thread.get_stats_sync();
+                let record = history::RunRecord {
+                    run_id,
+                    finished: chrono::Local::now(),
+                    items: history_items,
+                    files: stats.files_deleted,
+                    bytes: stats.bytes_freed,
+                    manifest: cleaner_thread.manifest.as_ref().map(|m| {
+                        m.flush();
+                        m.path().to_path_buf()
+                    }),
+                    largest: stats.largest.clone(),
+                };
+                if let Err(e) = history::append(&record).and_then(|()| history::mark_cleaned(&cleaned_items, record.finished)) {
+                    cleaner_thread.log_sync(&tr_args("log.history_failed", &[&e.to_string()]));
+                }
+            }
+
+            // משחררים לפני הסימון, כדי שריצה חדשה מה-UI לא תיתקל בנעילה של זו
+            drop(instance_lock);
+            quarantine_refresh.store(true, Ordering::Relaxed);
+            drop(finished);
+            ctx.request_repaint();
+        });
+    }
+
+    // שחזור/מחיקה של ריצה מההסגר ב-thread נפרד
+    fn quarantine_action(&mut self, ctx: &egui::Context, run: QuarantinedRun, restore: bool) {
+        let logs = self.logs.clone();
+        let refresh = self.quarantine_refresh.clone();
+        let ctx = ctx.clone();
+        self.status_text = if restore {
+            tr_args("app.restoring", &[&run.run_id])
+        } else {
+            tr_args("app.purging", &[&run.run_id])
+        };
+
+        thread::spawn(move || {
+            let message = if restore {
+                let report = quarantine::restore_run(&run);
+                tr_args(
+                    "log.restored",
+                    &[&report.restored.to_string(), &run.run_id, &report.conflicts.to_string(), &report.failed.to_string()],
+                )
+            } else {
+                match quarantine::purge_run(&run) {
+                    Ok(()) => tr_args("log.purged", &[&run.run_id, &SystemCleaner::format_bytes(run.bytes)]),
+                    Err(e) => tr_args("log.purge_failed", &[&run.run_id, &e.to_string()]),
+                }
+            };
+            if let Ok(mut logs) = logs.lock() {
+                logs.push(message);
+            }
+            refresh.store(true, Ordering::Relaxed);
+            ctx.request_repaint();
+        });
+    }
+
+    fn show_settings_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_settings;
+        let before = self.settings.clone();
+        let mut import_clicked = false;
+        let mut rerun_wizard = false;
+
+        egui::Window::new(tr("settings.title"))
+        .open(&mut open)
+        .default_width(460.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.settings_tab, SettingsTab::General, tr("settings.general"));
+                ui.selectable_value(&mut self.settings_tab, SettingsTab::Safety, tr("settings.safety"));
+                ui.selectable_value(&mut self.settings_tab, SettingsTab::Appearance, tr("settings.appearance"));
+                ui.selectable_value(&mut self.settings_tab, SettingsTab::Advanced, tr("settings.advanced"));
+            });
+            ui.separator();
+
+            match self.settings_tab {
+                SettingsTab::General => {
+                    let general = &mut self.settings.general;
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.min_age"));
+                        ui.add(egui::DragValue::new(&mut general.min_file_age_days).range(0..=3650).suffix(tr("settings.days")));
+                    }).response.on_hover_text(tr("settings.min_age_hint"));
+                    ui.checkbox(&mut general.confirm_before_clean, tr("settings.confirm"));
+                    ui.checkbox(&mut general.notify_on_finish, tr("settings.notify"));
+                    ui.checkbox(&mut general.low_space_warning, tr("settings.low_space"));
+                    ui.add_enabled_ui(general.low_space_warning, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("settings.low_space_below"));
+                            ui.add(egui::DragValue::new(&mut general.low_space_percent).range(1..=50).suffix("%"));
+                            ui.label(tr("settings.low_space_or"));
+                            ui.add(egui::DragValue::new(&mut general.low_space_gb).range(0..=1000).suffix(" GB"));
+                        });
+                    });
+                    ui.checkbox(&mut general.scan_on_startup, tr("first_run.scan_on_startup"))
+                    .on_hover_text(tr("first_run.scan_on_startup_hint"));
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("settings.restore_defaults")).clicked() {
+                            *general = Default::default();
+                        }
+                        if ui.button(tr("settings.first_run")).clicked() {
+                            rerun_wizard = true;
+                        }
+                    });
+                }
+                SettingsTab::Safety => {
+                    let safety = &mut self.settings.safety;
+                    ui.checkbox(&mut safety.quarantine_enabled, tr("settings.quarantine"));
+                    ui.checkbox(&mut safety.deletion_manifest, tr("settings.deletion_manifest"))
+                    .on_hover_text(tr("settings.deletion_manifest_hint"));
+                    ui.checkbox(&mut safety.secure_delete_privacy, tr("settings.secure_delete"));
+                    ui.checkbox(&mut safety.skip_in_use_files, tr("settings.skip_in_use"));
+                    ui.checkbox(&mut safety.same_filesystem, tr("settings.same_filesystem"))
+                    .on_hover_text(tr("settings.same_filesystem_hint"));
+                    ui.checkbox(&mut safety.fix_permissions, tr("settings.fix_permissions"))
+                    .on_hover_text(tr("settings.fix_permissions_hint"));
+                    ui.checkbox(&mut safety.git_aware, tr("settings.git_aware"))
+                    .on_hover_text(tr("settings.git_aware_hint"));
+                    ui.checkbox(&mut safety.ignore_session_protection, tr("settings.ignore_session_protection"))
+                    .on_hover_text(tr("settings.ignore_session_protection_hint"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.shred_max"));
+                        ui.add(egui::DragValue::new(&mut safety.shred_max_mb).range(1..=100_000).suffix(" MB"));
+                    });
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.exclusions"));
+                        ui.checkbox(&mut safety.show_exclusion_sizes, tr("settings.show_exclusion_sizes"))
+                        .on_hover_text(tr("settings.show_exclusion_sizes_hint"));
+                    });
+                    let show_sizes = safety.show_exclusion_sizes;
+                    let dir_sizes = &self.dir_sizes;
+                    // הפגיעות של הריצה האחרונה (תצוגה מקדימה או ניקוי), לפי הנתיב המורחב - כמו ב-exclusion_paths
+                    let last_run = self.cleaner.as_ref().filter(|_| !self.run.is_running()).map(|c| c.exclusions.clone());
+                    path_list_editor(ui, &mut safety.exclusions, &mut self.new_exclusion, "~/Projects/keep-me", &mut |ui, text| {
+                        let path = config::expand_path(text);
+                        if show_sizes {
+                            match dir_sizes.get(&path) {
+                                DirSize::Pending => {
+                                    ui.spinner();
+                                }
+                                DirSize::Ready { files, bytes } => {
+                                    ui.label(egui::RichText::new(SystemCleaner::format_bytes(bytes)).weak())
+                                    .on_hover_text(tr_args("settings.exclusion_size_hint", &[&notification::format_count(files)]));
+                                    if ui.small_button("⟳").on_hover_text(tr("settings.exclusion_size_refresh")).clicked() {
+                                        dir_sizes.refresh(&path);
+                                    }
+                                }
+                                DirSize::Missing => {
+                                    ui.label(egui::RichText::new(tr("settings.exclusion_missing")).weak());
+                                }
+                            }
+                        }
+                        if let Some(hits) = last_run.as_ref().and_then(|exclusions| exclusions.hits_for(&path)) {
+                            ui.label(egui::RichText::new(tr_args("settings.exclusion_hits", &[&notification::format_count(hits)])).small().weak());
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut self.exclusion_file).hint_text("~/team-exclusions.txt"));
+                        import_clicked = ui.button(tr("settings.import_exclusions")).on_hover_text(tr("settings.import_exclusions_hint")).clicked();
+                    });
+                    if let Some((source, rules)) = &self.imported_exclusions {
+                        let mut clear = false;
+                        ui.horizontal(|ui| {
+                            clear = ui.small_button("✕").clicked();
+                            ui.label(tr_args("settings.imported_exclusions", &[&rules.len().to_string(), &source.display().to_string()]));
+                        });
+                        if clear {
+                            self.imported_exclusions = None;
+                        }
+                    }
+                    for error in &self.exclusion_import_errors {
+                        ui.colored_label(egui::Color32::RED, error);
+                    }
+                    ui.label(tr("settings.keep_sites"));
+                    path_list_editor(ui, &mut safety.keep_sites, &mut self.new_keep_site, "example.com", &mut |_, _| {});
+                    ui.separator();
+                    ui.label(tr("settings.keep_recent")).on_hover_text(tr("settings.keep_recent_hint"));
+                    for item in self.categories.iter().flat_map(|c| c.items.iter()).filter(|i| i.keeps_recent) {
+                        let mut days = safety.keep_recent_days.get(&item.id).copied().unwrap_or(0);
+                        ui.horizontal(|ui| {
+                            ui.label(&item.name);
+                            if ui.add(egui::DragValue::new(&mut days).range(0..=365).suffix(tr("settings.days"))).changed() {
+                                if days == 0 {
+                                    safety.keep_recent_days.remove(&item.id);
+                                } else {
+                                    safety.keep_recent_days.insert(item.id.clone(), days);
+                                }
+                            }
+                        });
+                    }
+                    ui.separator();
+                    if ui.button(tr("settings.restore_defaults")).clicked() {
+                        *safety = Default::default();
+                    }
+                }
+                SettingsTab::Appearance => {
+                    let appearance = &mut self.settings.appearance;
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.language"));
+                        egui::ComboBox::from_id_salt("language")
+                        .selected_text(i18n::visual(appearance.language.native_name()))
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                ui.selectable_value(&mut appearance.language, language, i18n::visual(language.native_name()));
+                            }
+                        });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.theme"));
+                        ui.radio_value(&mut appearance.theme, ThemeChoice::System, tr("settings.theme_system"));
+                        ui.radio_value(&mut appearance.theme, ThemeChoice::Dark, tr("settings.theme_dark"));
+                        ui.radio_value(&mut appearance.theme, ThemeChoice::Light, tr("settings.theme_light"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.byte_units"));
+                        for (units, key) in ByteUnits::ALL.into_iter().zip(["settings.units_iec", "settings.units_si", "settings.units_bytes"]) {
+                            ui.radio_value(&mut appearance.byte_units, units, tr(key));
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.zoom"));
+                        ui.add(egui::Slider::new(&mut appearance.zoom, 0.75..=2.0));
+                    });
+                    ui.checkbox(&mut appearance.show_descriptions, tr("settings.show_descriptions"));
+                    ui.checkbox(&mut appearance.show_inapplicable, tr("settings.show_inapplicable"));
+                    ui.separator();
+                    ui.strong(tr("settings.accessibility"));
+                    ui.checkbox(&mut appearance.high_contrast, tr("settings.high_contrast"))
+                    .on_hover_text(tr("settings.high_contrast_hint"));
+                    ui.checkbox(&mut appearance.color_blind_palette, tr("settings.color_blind_palette"))
+                    .on_hover_text(tr("settings.color_blind_palette_hint"));
+                    ui.separator();
+                    if ui.button(tr("settings.restore_defaults")).clicked() {
+                        *appearance = Default::default();
+                    }
+                }
+                SettingsTab::Advanced => {
+                    let advanced = &mut self.settings.advanced;
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.quarantine_max_age"));
+                        ui.add(egui::DragValue::new(&mut advanced.quarantine_max_age_days).range(1..=365).suffix(tr("settings.days")));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.quarantine_max_size"));
+                        ui.add(egui::DragValue::new(&mut advanced.quarantine_max_size_mb).range(10..=1_000_000).suffix(" MB"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.history_max_runs"));
+                        ui.add(egui::DragValue::new(&mut advanced.history_max_runs).range(0..=100_000));
+                    })
+                    .response
+                    .on_hover_text(tr("settings.history_max_runs_hint"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.manifest_max_age"));
+                        ui.add(egui::DragValue::new(&mut advanced.manifest_max_age_days).range(0..=3650).suffix(tr("settings.days")));
+                    })
+                    .response
+                    .on_hover_text(tr("settings.manifest_max_age_hint"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.command_timeout"));
+                        ui.add(egui::DragValue::new(&mut advanced.command_timeout_mins).range(1..=240).suffix(tr("settings.minutes")));
+                    });
+                    ui.checkbox(&mut advanced.empty_dirs_include_hidden, tr("settings.empty_dirs_hidden"));
+                    ui.checkbox(&mut advanced.broken_links_include_removable, tr("settings.broken_links_removable"));
+                    ui.checkbox(&mut advanced.low_priority, tr("settings.low_priority"))
+                    .on_hover_text(tr("settings.low_priority_hint"));
+                    ui.checkbox(&mut advanced.size_cache, tr("settings.size_cache"))
+                    .on_hover_text(tr("settings.size_cache_hint"));
+                    ui.add_enabled_ui(advanced.size_cache, |ui| {
+                        ui.checkbox(&mut advanced.force_rescan, tr("settings.force_rescan"));
+                    });
+                    if cfg!(target_os = "linux") {
+                        ui.checkbox(&mut advanced.dbus_service, tr("settings.dbus_service"))
+                        .on_hover_text(tr("settings.dbus_service_hint"));
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.wipe_mount"));
+                        let default_label = tr("settings.wipe_mount_home");
+                        let selected = if advanced.wipe_mount.is_empty() { default_label.clone() } else { advanced.wipe_mount.clone() };
+                        egui::ComboBox::from_id_salt("wipe_mount")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut advanced.wipe_mount, String::new(), default_label);
+                            for (mount, _) in &self.disks {
+                                let path = mount.mount_point.display().to_string();
+                                let label = format!("{}  ({}, {})", path, mount.device, mount.fs_type);
+                                ui.selectable_value(&mut advanced.wipe_mount, path, label);
+                            }
+                        });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.wipe_reserve"));
+                        ui.add(egui::DragValue::new(&mut advanced.wipe_reserve_mb).range(256..=1_000_000).suffix(" MB"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.android_snapshot_age"));
+                        ui.add(egui::DragValue::new(&mut advanced.android_snapshot_max_age_days).range(0..=3650).suffix(tr("settings.days")));
+                    })
+                    .response
+                    .on_hover_text(tr("settings.android_snapshot_age_hint"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.screen_media_age"));
+                        ui.add(egui::DragValue::new(&mut advanced.screen_media_min_age_days).range(1..=3650).suffix(tr("settings.days")));
+                    })
+                    .response
+                    .on_hover_text(tr("settings.screen_media_age_hint"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.delete_retries"));
+                        ui.add(egui::DragValue::new(&mut advanced.delete_retries).range(0..=10));
+                        ui.label(tr("settings.delete_retry_backoff"));
+                        ui.add(egui::DragValue::new(&mut advanced.delete_retry_backoff_ms).range(0..=60_000).suffix(" ms"));
+                    })
+                    .response
+                    .on_hover_text(tr("settings.delete_retries_hint"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.largest_files"));
+                        ui.add(egui::DragValue::new(&mut advanced.largest_files).range(0..=500));
+                    })
+                    .response
+                    .on_hover_text(tr("settings.largest_files_hint"));
+                    ui.checkbox(&mut advanced.estimate_unreadable_as_root, tr("settings.estimate_unreadable"))
+                        .on_hover_text(tr("settings.estimate_unreadable_hint"));
+                    ui.add_enabled_ui(advanced.low_priority, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("settings.low_priority_pause_every"));
+                            ui.add(egui::DragValue::new(&mut advanced.low_priority_pause_every).range(0..=100_000));
+                        });
+                    });
+                    ui.add_enabled_ui(!advanced.low_priority, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("settings.walk_threads"));
+                            ui.add(egui::DragValue::new(&mut advanced.walk_threads).range(0..=64));
+                        })
+                        .response
+                        .on_hover_text(tr("settings.walk_threads_hint"));
+                    });
+                    ui.separator();
+                    if ui.button(tr("settings.restore_defaults")).clicked() {
+                        *advanced = Default::default();
+                    }
+
+                    ui.add_space(8.0);
+                    ui.strong(tr("settings.duplicates"));
+                    let dups = &mut self.settings.duplicates;
+                    ui.label(tr("settings.duplicate_roots"));
+                    path_list_editor(ui, &mut dups.roots, &mut self.new_duplicate_root, "~/Music", &mut |_, _| {});
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.duplicate_max_size"));
+                        ui.add(egui::DragValue::new(&mut dups.max_file_mb).range(1..=1_000_000).suffix(" MB"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("duplicates.keep"));
+                        ui.radio_value(&mut dups.keep, KeepPolicy::Oldest, tr("duplicates.keep_oldest"));
+                        ui.radio_value(&mut dups.keep, KeepPolicy::Newest, tr("duplicates.keep_newest"));
+                    });
+                    if ui.button(tr("settings.restore_defaults")).clicked() {
+                        *dups = Default::default();
+                    }
+
+                    ui.add_space(8.0);
+                    ui.strong(tr("settings.large_files"));
+                    let large = &mut self.settings.large_files;
+                    ui.label(tr("settings.large_roots"));
+                    path_list_editor(ui, &mut large.roots, &mut self.new_large_root, "~/Videos", &mut |_, _| {});
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.large_min_size"));
+                        ui.add(egui::DragValue::new(&mut large.min_size_mb).range(1..=1_000_000).suffix(" MB"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("settings.large_min_age"));
+                        ui.add(egui::DragValue::new(&mut large.min_age_days).range(1..=3650).suffix(tr("settings.days")));
+                    });
+                    ui.checkbox(&mut large.archive, tr("settings.large_archive"))
+                    .on_hover_text(tr("settings.large_archive_hint"));
+                    ui.add_enabled_ui(large.archive, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("settings.large_archive_dir"));
+                            ui.text_edit_singleline(&mut large.archive_dir);
+                        });
+                    });
+                    if ui.button(tr("settings.restore_defaults")).clicked() {
+                        *large = Default::default();
+                    }
+                }
+            }
+        });
+
+        if import_clicked {
+            self.import_exclusions();
+        }
+        if rerun_wizard {
+            open = false;
+            self.first_run = Some(FirstRun::new(&self.settings));
+        }
+        self.show_settings = open;
+        if self.settings != before {
+            // מה שאפשר מוחל מיד, השאר בריצה הבאה
+            ctx.set_zoom_factor(self.settings.appearance.zoom);
+            if self.settings.appearance.language != before.appearance.language {
+                i18n::set_language(self.settings.appearance.language);
+                self.retranslate();
+            }
+            units::set_byte_units(self.settings.appearance.byte_units);
+            self.sync_dbus_service();
+            if let Err(e) = self.settings.save() {
+                self.status_text = tr_args("app.save_failed", &[&e.to_string()]);
+            }
+        }
+    }
+
+    fn show_confirm_clean(&mut self, ctx: &egui::Context) {
+        let mut confirmed = false;
+        let mut cancelled = false;
+        let only_name = self.confirm_only.as_ref()
+        .and_then(|id| self.categories.iter().flat_map(|c| c.items.iter()).find(|item| &item.id == id))
+        .map(|item| item.name.clone());
+        let action = if self.settings.safety.quarantine_enabled {
+            tr("confirm.quarantine")
+        } else {
+            tr("confirm.delete")
+        };
+
+        egui::Window::new(tr("confirm.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            if let Some(name) = &only_name {
+                ui.strong(tr_args("confirm.item", &[name]));
+            }
+            ui.label(action);
+            ui.horizontal(|ui| {
+                if ui.button(tr("toolbar.clean")).clicked() {
+                    confirmed = true;
+                }
+                if ui.button(tr("confirm.cancel")).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+        if cancelled {
+            self.confirm_only = None;
+        }
+        if confirmed || cancelled {
+            self.confirm_clean_open = false;
+        }
+        if confirmed {
+            let only = self.confirm_only.take();
+            self.run_process(ctx, false, only.as_deref());
+        }
+    }
+
+    // עץ התוצאות של סריקת הכפולים: קבוצה לכל תוכן, עם תיבת סימון לכל עותק
+    fn show_duplicates_tree(&mut self, ui: &mut egui::Ui) {
+        let mut groups = self.duplicate_groups.lock().unwrap();
+        if groups.is_empty() {
+            return;
+        }
+        let reclaimable: u64 = groups.iter().map(|g| g.reclaimable()).sum();
+        let title = tr_args("duplicates.title", &[&groups.len().to_string(), &SystemCleaner::format_bytes(reclaimable)]);
+
+        egui::CollapsingHeader::new(title).default_open(true).show(ui, |ui| {
+            let keep_before = self.settings.duplicates.keep;
+            ui.horizontal(|ui| {
+                ui.label(tr("duplicates.keep"));
+                ui.radio_value(&mut self.settings.duplicates.keep, KeepPolicy::Oldest, tr("duplicates.keep_oldest"));
+                ui.radio_value(&mut self.settings.duplicates.keep, KeepPolicy::Newest, tr("duplicates.keep_newest"));
+            });
+            if self.settings.duplicates.keep != keep_before {
+                for group in groups.iter_mut() {
+                    group.apply_policy(self.settings.duplicates.keep);
+                }
+                if let Err(e) = self.settings.save() {
+                    self.status_text = tr_args("app.save_failed", &[&e.to_string()]);
+                }
+            }
+
+            egui::ScrollArea::vertical().id_salt("duplicates").max_height(260.0).show(ui, |ui| {
+                for group in groups.iter_mut() {
+                    let header = format!("{} × {}  ({}…)", group.files.len(), SystemCleaner::format_bytes(group.size), &group.hash[..12]);
+                    egui::CollapsingHeader::new(header).id_salt(&group.hash).show(ui, |ui| {
+                        for file in group.files.iter_mut() {
+                            let modified = chrono::DateTime::<chrono::Local>::from(file.modified).format("%Y-%m-%d %H:%M");
+                            ui.checkbox(&mut file.selected, format!("{}  {}", modified, engine::escape_name(file.path.as_os_str())))
+                            .on_hover_text(file.path.display().to_string());
+                        }
+                        if group.files.iter().all(|f| f.selected) {
+                            ui.colored_label(egui::Color32::YELLOW, tr("duplicates.all_selected"));
+                        }
+                    });
+                }
+            });
+        });
+        ui.separator();
+    }
+
+    // פריטים עם בחירה לכל קובץ (קבצים גדולים וכו'): רק מה שנשאר מסומן יימחק בניקוי
+    fn show_disks(&mut self, ui: &mut egui::Ui, dark: bool) {
+        egui::CollapsingHeader::new(tr("disks.title")).id_salt("disks").default_open(true).show(ui, |ui| {
+            let mut clicked = None;
+            for (mount, usage) in &self.disks {
+                ui.horizontal(|ui| {
+                    let fraction = usage.used_fraction();
+                    let color = if fraction >= 0.9 {
+                        egui::Color32::from_rgb(220, 80, 80)
+                    } else if fraction >= 0.75 {
+                        egui::Color32::from_rgb(230, 160, 60)
+                    } else if dark {
+                        egui::Color32::from_rgb(90, 170, 110)
+                    } else {
+                        egui::Color32::from_rgb(60, 150, 90)
+                    };
+                    let path = mount.mount_point.display().to_string();
+                    let active = self.disk_filter.as_ref() == Some(&mount.mount_point);
+                    if ui.selectable_label(active, &path).on_hover_text(tr("disks.filter_hint")).clicked() {
+                        clicked = Some(mount.mount_point.clone());
+                    }
+                    let text = tr_args("disks.usage", &[
+                        &SystemCleaner::format_bytes(usage.used),
+                        &SystemCleaner::format_bytes(usage.total),
+                        &SystemCleaner::format_bytes(usage.available),
+                    ]);
+                    ui.add(egui::ProgressBar::new(fraction).fill(color).text(text).desired_width(320.0));
+                });
+            }
+            if let Some(mount) = clicked {
+                self.disk_filter = if self.disk_filter.as_ref() == Some(&mount) { None } else { Some(mount) };
+            }
+            if ui.small_button(tr("disks.refresh")).clicked() {
+                self.refresh_disks();
+            }
+        });
+        ui.separator();
+    }
+
+    // "Selected: 3.2 GB reclaimable across 9 items" ומתחת הסך לכל הפריטים. פריט בלי הערכה = "+ ?"
+    fn show_savings_header(&self, ui: &mut egui::Ui) {
+        let sizes = self.item_sizes.lock().unwrap();
+        let dropped = self.dropped_sizes.lock().unwrap();
+        let total = |items: &mut dyn Iterator<Item = &CleanItem>| {
+            let (mut bytes, mut count, mut unknown) = (0u64, 0usize, false);
+            for item in items {
+                count += 1;
+                match sizes.get(&item.id).or_else(|| dropped.get(&item.id)) {
+                    Some(size) => bytes += size,
+                    None => unknown = true,
+                }
+            }
+            let size = SystemCleaner::format_bytes(bytes);
+            (if unknown { format!("{} {}", size, tr("sidebar.unknown_size")) } else { size }, count)
+        };
+        let items = || self.categories.iter().flat_map(|c| c.items.iter()).filter(|i| i.applicable);
+        let (selected, selected_count) = total(&mut items().filter(|i| i.enabled));
+        let (all, _) = total(&mut items());
+        ui.label(tr_args("sidebar.savings_selected", &[&selected, &selected_count.to_string()]));
+        ui.label(egui::RichText::new(tr_args("sidebar.savings_all", &[&all])).small().weak());
+        if let Some(cleaner) = self.cleaner.as_ref().filter(|c| c.dry_run && !self.run.is_running()) {
+            if let Some(line) = cleaner.unmeasured.summary_line(tr_args, SystemCleaner::format_bytes) {
+                ui.label(egui::RichText::new(line).small().weak()).on_hover_text(tr("sidebar.unmeasured_hint"));
+            }
+        }
+        if let Some(saved_at) = self.stale_preview {
+            ui.colored_label(self.palette().warning, tr_args("sidebar.stale_estimates", &[&format_ago(chrono::Local::now() - saved_at)]))
+            .on_hover_text(tr("sidebar.stale_estimates_hint"));
+        }
+    }
+
+    // המחיקות הגדולות ביותר של הריצה האחרונה - בתצוגה מקדימה, מה שהיה נמחק (הפתעות לפני שמנקים)
+    fn show_largest_files(&self, ui: &mut egui::Ui) {
+        if self.run.is_running() {
+            return;
+        }
+        let Some(cleaner) = &self.cleaner else { return };
+        let largest = cleaner.largest.top();
+        if largest.is_empty() {
+            return;
+        }
+        let key = if cleaner.dry_run { "largest.title_preview" } else { "largest.title" };
+        egui::CollapsingHeader::new(tr_args(key, &[&largest.len().to_string()])).id_salt("largest_files").default_open(true).show(ui, |ui| {
+            egui::Grid::new("largest_table").striped(true).show(ui, |ui| {
+                for deletion in &largest {
+                    let item = self.categories.iter().flat_map(|c| c.items.iter()).find(|i| i.id == deletion.item);
+                    let full = engine::escape_name(deletion.path.as_os_str());
+                    ui.label(SystemCleaner::format_bytes(deletion.bytes));
+                    ui.label(item.map_or(deletion.item.as_str(), |i| i.name.as_str()));
+                    ui.label(egui::RichText::new(truncate_middle(&full, ACTIVITY_PATH_CHARS)).monospace()).on_hover_text(&full);
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    // סיכום אחרי ריצה: כמה התפנה בכל קטגוריה ובכל פריט, מהגדול לקטן
+    fn show_freed_chart(&self, ui: &mut egui::Ui, palette: &Palette) {
+        if self.run.is_running() {
+            return;
+        }
+        let results = self.item_results.lock().unwrap().clone();
+        let times = self.item_times.lock().unwrap().clone();
+        let mut rows: Vec<ChartRow> = self.categories.iter()
+        .map(|cat| {
+            let mut items: Vec<_> = cat.items.iter()
+            .filter_map(|item| match results.get(&item.id) {
+                Some(ItemOutcome::Ok { files, bytes }) => Some((item.name.as_str(), *files, *bytes, times.get(&item.id).copied())),
+                _ => None,
+            })
+            .collect();
+            items.sort_by_key(|item| std::cmp::Reverse(item.2));
+            let bytes = items.iter().map(|i| i.2).sum();
+            let files = items.iter().map(|i| i.1).sum();
+            (cat, bytes, files, items)
+        })
+        .filter(|(_, _, _, items)| !items.is_empty())
+        .collect();
+        if rows.is_empty() {
+            return;
+        }
+        rows.sort_by_key(|row| std::cmp::Reverse(row.1));
+        let total: u64 = rows.iter().map(|r| r.1).sum();
+
+        egui::CollapsingHeader::new(tr("chart.title")).id_salt("freed_chart").default_open(true).show(ui, |ui| {
+            // הכל אפס - גרף ריק לא אומר כלום, טבלה כן
+            if total == 0 {
+                egui::Grid::new("freed_table").striped(true).show(ui, |ui| {
+                    for (cat, _, _, items) in &rows {
+                        for (name, files, _, duration) in items {
+                            ui.label(&cat.name);
+                            ui.label(*name);
+                            ui.label(tr_args("status.files", &[&files.to_string()]));
+                            ui.label(duration.map(format_duration).unwrap_or_default());
+                            ui.end_row();
+                        }
+                    }
+                });
+                return;
+            }
+            let bar = |ui: &mut egui::Ui, name: &str, bytes: u64, color: egui::Color32, width: f32| {
+                let fraction = bytes as f32 / total as f32;
+                let text = tr_args("chart.bar", &[name, &SystemCleaner::format_bytes(bytes), &format!("{:.0}", fraction * 100.0)]);
+                ui.add(egui::ProgressBar::new(fraction).fill(color).text(text).desired_width(width))
+            };
+            for (cat, bytes, files, items) in &rows {
+                let color = palette.category_fill(&cat.id);
+                bar(ui, &format!("{} {}", cat.icon, cat.name), *bytes, color, 420.0)
+                .on_hover_text(tr_args("status.files", &[&files.to_string()]));
+                let item_color = if palette.dark { color.gamma_multiply(0.6) } else { color.gamma_multiply(0.75) };
+                ui.indent(("freed_items", &cat.id), |ui| {
+                    for (name, files, bytes, duration) in items {
+                        let mut hover = tr_args("status.files", &[&files.to_string()]);
+                        if let Some(duration) = duration {
+                            hover.push_str(&format!(" · {}", format_duration(*duration)));
+                        }
+                        bar(ui, name, *bytes, item_color, 400.0).on_hover_text(hover);
+                    }
+                });
+            }
+        });
+        ui.separator();
+    }
+
+    fn show_preview_tree(&mut self, ui: &mut egui::Ui) {
+        let mut action: Option<PreviewAction> = None;
+        self.show_preview_entries(ui, &mut action);
+        if let Some(action) = action {
+            self.apply_preview_action(ui.ctx(), action);
+        }
+    }
+
+    fn show_preview_entries(&self, ui: &mut egui::Ui, action: &mut Option<PreviewAction>) {
+        let mut store = self.preview.lock();
+        if store.is_empty() {
+            return;
+        }
+        // סינון לפי דיסק - בדיקת prefix מול הרשימה שכבר בזיכרון, בלי syscalls
+        let mounts: Vec<mounts::MountInfo> = self.disks.iter().map(|(m, _)| m.clone()).collect();
+        let filter = self.disk_filter.clone();
+        let visible = |entry: &preview::PreviewEntry| match &filter {
+            Some(mount) => mounts::mount_for(&entry.path, &mounts).map(|m| &m.mount_point) == Some(mount),
+            None => true,
+        };
+        let now = std::time::SystemTime::now();
+        for item in self.categories.iter().flat_map(|c| c.items.iter()) {
+            let entries = match store.get_mut(&item.id) {
+                Some(entries) if !entries.is_empty() => entries,
+                _ => continue,
+            };
+            let selected_bytes: u64 = entries.iter().filter(|e| e.selected).map(|e| e.size).sum();
+            let mut title = tr_args("preview.title", &[&item.name, &entries.len().to_string(), &SystemCleaner::format_bytes(selected_bytes)]);
+            if self.preview.is_stale(&item.id) {
+                title.push_str(&tr("preview.stale"));
+            }
+            egui::CollapsingHeader::new(title).id_salt(&item.id).default_open(true).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.small_button(tr("preview.select_all")).clicked() {
+                        entries.iter_mut().filter(|e| visible(e)).for_each(|e| e.selected = true);
+                    }
+                    if ui.small_button(tr("preview.select_none")).clicked() {
+                        entries.iter_mut().filter(|e| visible(e)).for_each(|e| e.selected = false);
+                    }
+                });
+                // קבוצות לפי סדר ההופעה הראשונה (למשל סל המחזור של כל דיסק)
+                let mut groups: Vec<String> = Vec::new();
+                for entry in entries.iter() {
+                    if !groups.contains(&entry.group) {
+                        groups.push(entry.group.clone());
+                    }
+                }
+                egui::ScrollArea::vertical().id_salt(("preview", &item.id)).max_height(220.0).show(ui, |ui| {
+                    for group in &groups {
+                        if !group.is_empty() {
+                            ui.label(egui::RichText::new(group).strong());
+                        }
+                        for entry in entries.iter_mut().filter(|e| e.group == *group && visible(e)) {
+                            Self::show_preview_entry(ui, entry, now, action);
+                        }
+                    }
+                });
+            });
+        }
+        ui.separator();
+    }
+
+    fn show_preview_entry(ui: &mut egui::Ui, entry: &mut preview::PreviewEntry, now: std::time::SystemTime, action: &mut Option<PreviewAction>) {
+        let mut label = if entry.title.is_empty() {
+            let mut label = format!("{:>9}  {}", SystemCleaner::format_bytes(entry.size), engine::escape_name(entry.path.as_os_str()));
+            let age_days = entry.modified
+            .and_then(|m| now.duration_since(m).ok())
+            .map(|d| d.as_secs() / (24 * 60 * 60));
+            if let Some(days) = age_days {
+                label.push_str(&format!("  ({})", tr_args("preview.age_days", &[&days.to_string()])));
+            }
+            label
+        } else {
+            entry.title.clone()
+        };
+        if !entry.detail.is_empty() {
+            label.push_str(&format!("  → {}", entry.detail));
+        }
+        ui.horizontal(|ui| {
+            if !entry.warning.is_empty() {
+                ui.colored_label(egui::Color32::from_rgb(230, 160, 60), "⚠").on_hover_text(&entry.warning);
+            }
+            ui.checkbox(&mut entry.selected, label).context_menu(|ui| {
+                if ui.button(tr("preview.open_folder")).clicked() {
+                    *action = Some(PreviewAction::OpenFolder(entry.path.clone()));
+                    ui.close();
+                }
+                if ui.button(tr("preview.copy_path")).clicked() {
+                    *action = Some(PreviewAction::CopyPath(entry.path.clone()));
+                    ui.close();
+                }
+                ui.separator();
+                if ui.button(tr("preview.exclude_file")).clicked() {
+                    *action = Some(PreviewAction::ExcludeFile(entry.path.clone()));
+                    ui.close();
+                }
+                if ui.button(tr("preview.exclude_dir")).clicked() {
+                    *action = Some(PreviewAction::ExcludeDir(entry.path.clone()));
+                    ui.close();
+                }
+            });
+        });
+    }
+
+    // בתוכנה שמוגדרת במערכת (מנהל קבצים לתיקייה, עורך/גיליון ל-CSV)
+    fn open_external(&self, path: &std::path::Path) {
+        let opener = if cfg!(windows) { "explorer" } else { "xdg-open" };
+        if let Err(e) = std::process::Command::new(opener).arg(path).spawn() {
+            self.logs.lock().unwrap().push(format!("{}: {}: {}", tr("log.error"), opener, e));
+        }
+    }
+
+    fn show_history_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_history;
+        let mut to_open: Option<std::path::PathBuf> = None;
+
+        egui::Window::new(tr("history.title"))
+        .open(&mut open)
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            if self.history.is_empty() {
+                ui.label(tr("history.empty"));
+            }
+            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for record in &self.history {
+                    ui.horizontal(|ui| {
+                        let finished = record.finished.format("%Y-%m-%d %H:%M").to_string();
+                        ui.label(tr_args("history.run", &[&finished, &record.files.to_string(), &SystemCleaner::format_bytes(record.bytes)]))
+                        .on_hover_text(format!("{}\n{}", record.run_id, record.items.join(", ")));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("📋").on_hover_text(tr_args("status.copy_run_id", &[&record.run_id])).clicked() {
+                                ui.ctx().copy_text(record.run_id.clone());
+                            }
+                            // רשימה שנמחקה ידנית - אין מה לפתוח
+                            if let Some(manifest) = record.manifest.as_ref().filter(|m| m.exists()) {
+                                if ui.button(tr("history.open")).on_hover_text(manifest.display().to_string()).clicked() {
+                                    to_open = Some(manifest.clone());
+                                }
+                            }
+                        });
+                    });
+                }
+            });
+        });
+
+        self.show_history = open;
+        if let Some(path) = to_open {
+            self.open_external(&path);
+        }
+    }
+
+    fn show_quarantine_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_quarantine;
+        let mut action: Option<(QuarantinedRun, bool)> = None;
+
+        egui::Window::new(tr("quarantine.title"))
+        .open(&mut open)
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            if self.quarantine_runs.is_empty() {
+                ui.label(tr("quarantine.empty"));
+            }
+            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for run in &self.quarantine_runs {
+                    ui.horizontal(|ui| {
+                        let created = run.created.map(|c| c.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default();
+                        ui.label(tr_args("quarantine.run", &[&created, &run.files.to_string(), &SystemCleaner::format_bytes(run.bytes)]))
+                        .on_hover_text(&run.run_id);
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.add_enabled(!self.run.is_busy(), egui::Button::new(tr("quarantine.purge"))).clicked() {
+                                action = Some((run.clone(), false));
+                            }
+                            if ui.add_enabled(!self.run.is_busy(), egui::Button::new(tr("quarantine.restore"))).clicked() {
+                                action = Some((run.clone(), true));
+                            }
+                        });
+                    });
+                }
+            });
+        });
+
+        self.show_quarantine = open;
+        if let Some((run, restore)) = action {
+            self.quarantine_action(ctx, run, restore);
+        }
+    }
+
+    // אשף ההפעלה הראשונה. כותב רק את config.toml - בסיום, או בדילוג כדי שלא ייפתח שוב
+    fn show_first_run_window(&mut self, ctx: &egui::Context) {
+        let Some(mut wizard) = self.first_run.take() else { return };
+        let mut skipped = false;
+        let mut finished = false;
+        let language_before = wizard.language;
+
+        egui::Window::new(tr("first_run.title"))
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            ui.label(egui::RichText::new(tr_args("first_run.step", &[&(wizard.step.index() + 1).to_string(), &Step::ALL.len().to_string()])).weak());
+            ui.add_space(4.0);
+            match wizard.step {
+                Step::Language => {
+                    ui.label(tr("first_run.language"));
+                    egui::ComboBox::from_id_salt("first_run_language")
+                    .selected_text(i18n::visual(wizard.language.native_name()))
+                    .show_ui(ui, |ui| {
+                        for language in Language::ALL {
+                            ui.selectable_value(&mut wizard.language, language, i18n::visual(language.native_name()));
+                        }
+                    });
+                }
+                Step::Preset => {
+                    ui.label(tr("first_run.preset"));
+                    ui.radio_value(&mut wizard.preset, SelectionPreset::Standard, tr("first_run.standard"));
+                    ui.radio_value(&mut wizard.preset, SelectionPreset::Safe, tr("first_run.safe"));
+                    if let Some(desktop) = wizard.desktop.filter(|_| !wizard.desktop_items.is_empty()) {
+                        ui.add_space(4.0);
+                        ui.label(egui::RichText::new(tr_args("first_run.desktop", &[desktop, &wizard.desktop_items.join(", ")])).weak());
+                    }
+                }
+                Step::Startup => {
+                    ui.checkbox(&mut wizard.scan_on_startup, tr("first_run.scan_on_startup"))
+                    .on_hover_text(tr("first_run.scan_on_startup_hint"));
+                    ui.checkbox(&mut wizard.notify_on_finish, tr("settings.notify"));
+                }
+                Step::Preview => {
+                    ui.label(tr("first_run.preview_explained"));
+                }
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(tr("first_run.skip")).clicked() {
+                    skipped = true;
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if let Some(next) = wizard.step.next() {
+                        if ui.button(tr("first_run.next")).clicked() {
+                            wizard.step = next;
+                        }
+                    } else if ui.button(tr("first_run.finish")).clicked() {
+                        finished = true;
+                    }
+                    if let Some(previous) = wizard.step.previous() {
+                        if ui.button(tr("first_run.back")).clicked() {
+                            wizard.step = previous;
+                        }
+                    }
+                });
+            });
+        });
+
+        // השפה מתחלפת מיד, כדי ששאר האשף כבר יוצג בה
+        if wizard.language != language_before {
+            i18n::set_language(wizard.language);
+            self.retranslate();
+        }
+        if skipped {
+            // ההגדרות נשארות כמו שהיו - גם השפה
+            if wizard.language != self.settings.appearance.language {
+                i18n::set_language(self.settings.appearance.language);
+                self.retranslate();
+            }
+        } else if finished {
+            wizard.apply(&mut self.settings);
+            self.select_preset(self.settings.general.preset);
+            self.startup_scan_pending = self.settings.general.scan_on_startup;
+        } else {
+            self.first_run = Some(wizard);
+            return;
+        }
+        if let Err(e) = self.settings.save() {
+            self.status_text = tr_args("app.save_failed", &[&e.to_string()]);
+        }
+    }
+}
+
+
+
+
+
+
+
+
+
+
+
+impl eframe::App for CleanerApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let focused = ctx.input(|i| i.viewport().focused.unwrap_or(true));
+        self.window_focused.store(focused, Ordering::Relaxed);
+
+        // החלפת עיצוב בזמן ריצה, בלי restart
+        let dark = self.is_dark();
+        let palette = self.palette();
+        if self.applied_palette.as_ref() != Some(&palette) {
+            setup_bleachbit_style(ctx, &palette);
+            self.applied_palette = Some(palette.clone());
+        }
+
+        // שורות הלוג מהריצה - עד LINES_PER_FRAME בכל פריים, והשאר בפריים הבא
+        let backlog = self.log_queue.drain_into(&mut self.logs.lock().unwrap(), log_queue::LINES_PER_FRAME);
+        if backlog || self.run.is_busy() {
+            ctx.request_repaint_after(log_queue::REPAINT_INTERVAL);
+        }
+
+        self.handle_shortcuts(ctx);
+        let dropped: Vec<std::path::PathBuf> = ctx.input(|i| i.raw.dropped_files.iter().filter_map(|f| f.path.clone()).collect());
+        if !self.run.is_running() {
+            for path in dropped {
+                self.add_dropped_dir(ctx, path);
+            }
+        }
+        let hint = |key: &str, shortcut: &egui::KeyboardShortcut| format!("{} ({})", tr(key), ctx.format_shortcut(shortcut));
+
+        if self.quarantine_refresh.swap(false, Ordering::Relaxed) {
+            self.quarantine_runs = quarantine::list_runs();
+            self.history = history::load();
+            self.last_cleaned = history::load_last_cleaned();
+        }
+        if self.show_quarantine {
+            self.show_quarantine_window(ctx);
+        }
+        if self.show_history {
+            self.show_history_window(ctx);
+        }
+        if self.show_settings {
+            self.show_settings_window(ctx);
+        }
+        if self.confirm_clean_open {
+            self.show_confirm_clean(ctx);
+        }
+        if self.first_run.is_some() {
+            self.show_first_run_window(ctx);
+        } else if self.startup_scan_pending && !self.run.is_busy() {
+            // תצוגה מקדימה בלבד - ניקוי תמיד דורש לחיצה
+            self.startup_scan_pending = false;
+            self.run_process(ctx, true, None);
+        }
+
+        // --- Top Toolbar ---
+        egui::TopBottomPanel::top("toolbar").show(ctx, |ui| {
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                // השלב האחרון באשף מצביע על שני הכפתורים
+                let tour = self.first_run.as_ref().is_some_and(|w| w.step == Step::Preview);
+                let preview_btn = egui::Button::new(tr("toolbar.preview")).selected(tour);
+                if ui.add_enabled(!self.run.is_busy(), preview_btn)
+                .on_hover_text(hint("toolbar.preview_hint", &SHORTCUT_PREVIEW))
+                .on_disabled_hover_text(hint("toolbar.preview_hint", &SHORTCUT_PREVIEW))
+                .clicked() {
+                    self.run_process(ctx, true, None);
+                }
+
+                ui.add_space(10.0);
+
+                let clean_btn = egui::Button::new(egui::RichText::new(tr("toolbar.clean")).color(egui::Color32::WHITE))
+                .fill(egui::Color32::from_rgb(180, 0, 0))
+                .selected(tour);
+
+                if ui.add_enabled(!self.run.is_busy(), clean_btn)
+                .on_hover_text(hint("toolbar.clean_hint", &SHORTCUT_CLEAN))
+                .on_disabled_hover_text(hint("toolbar.clean_hint", &SHORTCUT_CLEAN))
+                .clicked() {
+                    self.request_clean(ctx);
+                }
+
+                ui.add_space(10.0);
+                let can_undo = !self.run.is_busy() && !self.quarantine_runs.is_empty();
+                if ui.add_enabled(can_undo, egui::Button::new(tr("toolbar.undo"))).on_hover_text(tr("toolbar.undo_hint")).clicked() {
+                    if let Some(run) = self.quarantine_runs.first().cloned() {
+                        self.quarantine_action(ctx, run, true);
+                    }
+                }
+                if ui.button(tr("toolbar.quarantine")).clicked() {
+                    self.show_quarantine = !self.show_quarantine;
+                }
+                if ui.button(tr("toolbar.history")).clicked() {
+                    self.show_history = !self.show_history;
+                }
+                let theme_icon = if dark { "☀" } else { "🌙" };
+                if ui.button(theme_icon).on_hover_text(tr("toolbar.theme_hint")).clicked() {
+                    self.settings.appearance.theme = if dark { ThemeChoice::Light } else { ThemeChoice::Dark };
+                    if let Err(e) = self.settings.save() {
+                        self.status_text = tr_args("app.save_failed", &[&e.to_string()]);
+                    }
+                }
+                if ui.button("⚙").on_hover_text(tr("toolbar.settings_hint")).clicked() {
+                    self.show_settings = !self.show_settings;
+                }
+
+                if !self.users.is_empty() {
+                    ui.add_space(10.0);
+                    let all_users = tr("toolbar.all_users");
+                    egui::ComboBox::from_id_salt("run_user")
+                    .selected_text(self.selected_user.clone().unwrap_or_else(|| all_users.clone()))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.selected_user, None, all_users);
+                        for user in &self.users {
+                            let label = format!("{}  ({})", user.name, user.home.display());
+                            ui.selectable_value(&mut self.selected_user, Some(user.name.clone()), label);
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr("toolbar.user_hint"));
+                }
+
+                ui.add_space(10.0);
+                if self.run.is_running() {
+                    let pause_label = tr(if self.pause.is_paused() { "toolbar.resume" } else { "toolbar.pause" });
+                    if ui.button(pause_label).clicked() {
+                        self.toggle_pause();
+                    }
+                    if ui.button(tr("toolbar.abort")).on_hover_text(ctx.format_shortcut(&SHORTCUT_ABORT)).clicked() {
+                        self.abort();
+                    }
+                }
+            });
+            ui.add_space(5.0);
+        });
+        self.show_config_recovery_banner(ctx);
+        self.show_saved_preview_banner(ctx);
+        self.show_low_space_banner(ctx);
+
+        // --- Bottom Status Bar ---
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.add_space(2.0);
+            ui.horizontal(|ui| {
+                let paused = self.run.is_running() && self.pause.is_paused();
+                if paused {
+                    ui.label(tr("app.paused"));
+                } else {
+                    ui.label(&self.status_text);
+                }
+
+                // בדיקה: האם העבודה הסתיימה?
+                // סיום של ריצה שבוטלה לא מגיע לכאן - RunTracker מתעלם ממזהים ישנים
+                if self.run.take_finished().is_some() {
+                    self.progress = 1.0;
+                    self.status_text = tr("app.completed");
+                    self.refresh_disks();
+                    self.run_elapsed = self.run_started.map(|started| started.elapsed().saturating_sub(self.pause.paused_for()));
+                }
+
+                // השעון מתקדם גם בלי אירועים - מבקשים ציור מחדש כל שנייה
+                if self.run.is_running() {
+                    if let Some(started) = self.run_started {
+                        ui.separator();
+                        // בזמן השהיה השעון קפוא - זמן ההשהיה לא נספר
+                        let elapsed = started.elapsed().saturating_sub(self.pause.paused_for());
+                        ui.label(tr_args("status.elapsed", &[&format_duration(elapsed)]));
+                        if let Some(remaining) = self.eta.remaining().filter(|_| !paused) {
+                            ui.label(tr_args("status.eta", &[&format_duration(remaining)]));
+                        }
+                        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+                    }
+                } else if let Some(elapsed) = self.run_elapsed {
+                    ui.separator();
+                    ui.label(tr_args("status.elapsed", &[&format_duration(elapsed)]));
+                }
+
+                if let Some(cleaner) = &self.cleaner {
+                    let stats = cleaner.get_stats_sync();
+
+                    if self.run.is_running() {
+                        // סריקת כפולים ומחיקת מקום פנוי יודעות כמה נשאר; לשאר הפריטים - אנימציה
+                        if let Some(fraction) = self.wipe_progress.fraction() {
+                            ui.separator();
+                            ui.label(tr_args("status.wiped", &[
+                                &SystemCleaner::format_bytes(self.wipe_progress.done.load(Ordering::Relaxed)),
+                                &SystemCleaner::format_bytes(self.wipe_progress.total.load(Ordering::Relaxed)),
+                            ]));
+                            self.progress = fraction;
+                            if !paused {
+                                self.eta.sample(fraction);
+                            }
+                        } else if let Some(fraction) = self.duplicate_progress.fraction() {
+                            self.progress = fraction;
+                            if !paused {
+                                self.eta.sample(fraction);
+                            }
+                        } else {
+                            self.progress += 0.005;
+                            if self.progress > 1.0 { self.progress = 0.0; }
+                        }
+                        // המונים האטומיים מתעדכנים תוך כדי - אותה תמונה שהסיכום ייבנה ממנה בסוף
+                        if stats.files_deleted > 0 {
+                            ui.separator();
+                            let key = if cleaner.dry_run { "status.would_free_so_far" } else { "status.freed_so_far" };
+                            ui.label(tr_args(key, &[&SystemCleaner::format_bytes(stats.bytes_freed)]));
+                            ui.label(tr_args("status.files", &[&stats.files_deleted.to_string()]));
+                        }
+                        self.show_activity(ui, stats.files_deleted, paused);
+                    } else if let Some(summary) = self.run_summary.lock().unwrap().as_ref() {
+                        ui.separator();
+                        ui.label(summary);
+                        if ui.small_button("📋").on_hover_text(tr_args("status.copy_run_id", &[&cleaner.run_id])).clicked() {
+                            ui.ctx().copy_text(cleaner.run_id.clone());
+                        }
+                        ui.label(tr_args("status.files", &[&stats.files_deleted.to_string()]));
+                    } else if stats.files_deleted > 0 || stats.bytes_freed > 0 {
+                        ui.separator();
+                        if cleaner.dry_run {
+                            ui.label(tr_args("status.would_free", &[&SystemCleaner::format_bytes(stats.bytes_freed)]));
+                        } else if stats.ram_bytes_freed > 0 {
+                            let disk = SystemCleaner::format_bytes(stats.disk_bytes_freed);
+                            let ram = SystemCleaner::format_bytes(stats.ram_bytes_freed);
+                            ui.label(tr_args("status.freed_split", &[&disk, &ram]));
+                        } else {
+                            ui.label(tr_args("status.freed", &[&SystemCleaner::format_bytes(stats.bytes_freed)]));
+                        }
+                        ui.label(tr_args("status.files", &[&stats.files_deleted.to_string()]));
+                    }
+                    if !self.run.is_running() && stats.skipped_in_use > 0 && self.run_summary.lock().unwrap().is_none() {
+                        ui.separator();
+                        ui.label(tr_args("status.in_use", &[&stats.skipped_in_use.to_string()]));
+                    }
+                    if !self.run.is_running() && stats.errors > 0 {
+                        ui.separator();
+                        ui.colored_label(palette.error, tr_args("status.errors", &[&stats.errors.to_string()]));
+                    }
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let progress_bar = egui::ProgressBar::new(self.progress)
+                    .show_percentage()
+                    .animate(self.run.is_running());
+                    ui.add(progress_bar);
+                });
+            });
+            ui.add_space(2.0);
+        });
+
+        // --- Left Sidebar ---
+        egui::SidePanel::left("sidebar")
+        .resizable(true)
+        .default_width(280.0)
+        .width_range(200.0..=400.0)
+        .show(ctx, |ui| {
+            ui.add_space(5.0);
+            ui.heading(tr("sidebar.categories"));
+            self.show_savings_header(ui);
+
+            // חיפוש - משפיע רק על התצוגה, run_process עדיין אוסף את כל הפריטים המסומנים
+            ui.horizontal(|ui| {
+                let search = ui.add(egui::TextEdit::singleline(&mut self.filter).hint_text(tr("sidebar.search_hint")));
+                if search.has_focus() && ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    self.filter.clear();
+                }
+                if !self.filter.is_empty() && ui.small_button("✕").on_hover_text(tr("sidebar.clear_filter")).clicked() {
+                    self.filter.clear();
+                }
+            });
+            ui.separator();
+
+            // השמות מוצגים בסדר חזותי (i18n::visual), אז גם החיפוש משווה בסדר הזה
+            let query = i18n::visual(&self.filter.trim().to_lowercase());
+            let show_descriptions = self.settings.appearance.show_descriptions;
+            let show_inapplicable = self.settings.appearance.show_inapplicable;
+            let results = self.item_results.lock().unwrap().clone();
+            let dropped_sizes = self.dropped_sizes.lock().unwrap().clone();
+            let last_cleaned = &self.last_cleaned;
+            let now = chrono::Local::now();
+            let mut dropped_action: Option<(String, bool)> = None;
+            let mut clean_now: Option<(String, RiskLevel)> = None;
+            let busy = self.run.is_busy();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.add_space(5.0);
+                for cat in &mut self.categories {
+                    let matches_query = |item: &CleanItem| {
+                        (item.applicable || show_inapplicable)
+                        && (query.is_empty()
+                        || item.name.to_lowercase().contains(&query)
+                        || item.description.to_lowercase().contains(&query))
+                    };
+                    if !cat.items.iter().any(matches_query) {
+                        continue;
+                    }
+
+                    let header_text = egui::RichText::new(format!("{} {}", cat.icon, cat.name))
+                    .color(palette.category_heading(&cat.id))
+                    .strong();
+
+                    // כשיש חיפוש פותחים את הקטגוריות שיש בהן התאמה
+                    egui::CollapsingHeader::new(header_text)
+                    .default_open(true)
+                    .open(if query.is_empty() { None } else { Some(true) })
+                    .show(ui, |ui| {
+                        for item in &mut cat.items {
+                            if !matches_query(item) {
+                                continue;
+                            }
+                            ui.horizontal(|ui| {
+                                let response = if item.applicable {
+                                    let label = highlighted_label(ui, &item.name, &query);
+                                    ui.checkbox(&mut item.enabled, label)
+                                } else {
+                                    let label = egui::RichText::new(format!("{} {}", item.name, tr("sidebar.not_installed"))).weak();
+                                    ui.add_enabled(false, egui::Checkbox::new(&mut item.enabled, label))
+                                }
+                                .on_hover_text(item.targets.iter().chain(&item.run_order).cloned().collect::<Vec<_>>().join("\n"));
+                                // קליק ימני - פרטים מלאים
+                                response.context_menu(|ui| {
+                                    ui.strong(&item.name);
+                                    ui.label(&item.description);
+                                    ui.separator();
+                                    for target in &item.targets {
+                                        ui.monospace(target);
+                                    }
+                                });
+                                if let Some(outcome) = results.get(&item.id) {
+                                    show_outcome_glyph(ui, outcome, &palette);
+                                }
+                                // מופיע רק כשהעכבר על השורה
+                                if item.applicable && ui.ui_contains_pointer() {
+                                    let button = egui::Button::new("▶").small();
+                                    let hint = tr_args("sidebar.clean_now", &[&item.name]);
+                                    if ui.add_enabled(!busy, button).on_hover_text(&hint).on_disabled_hover_text(&hint).clicked() {
+                                        clean_now = Some((item.id.clone(), item.risk));
+                                    }
+                                }
+                                if item.temporary {
+                                    if item.size_info.is_empty() {
+                                        if let Some(size) = dropped_sizes.get(&item.id) {
+                                            item.size_info = SystemCleaner::format_bytes(*size);
+                                        }
+                                    }
+                                    ui.label(egui::RichText::new(&item.size_info).small().weak());
+                                    if ui.small_button("💾").on_hover_text(tr("sidebar.save_custom")).clicked() {
+                                        dropped_action = Some((item.id.clone(), true));
+                                    }
+                                    if ui.small_button("✕").on_hover_text(tr("sidebar.remove_custom")).clicked() {
+                                        dropped_action = Some((item.id.clone(), false));
+                                    }
+                                }
+                            });
+                            if item.applicable && !item.sub_items.is_empty() {
+                                ui.indent(("sub", &item.id), |ui| {
+                                    ui.add_enabled_ui(item.enabled, |ui| {
+                                        for sub in &mut item.sub_items {
+                                            let mut label = egui::RichText::new(&sub.name);
+                                            if sub.risk == RiskLevel::Dangerous {
+                                                label = label.color(palette.warning);
+                                            }
+                                            ui.checkbox(&mut sub.enabled, label);
+                                        }
+                                    });
+                                });
+                            }
+                            // פריט שמעולם לא נוקה - בלי שורה
+                            let cleaned = last_cleaned.get(&item.id);
+                            if show_descriptions || cleaned.is_some() {
+                                ui.indent("desc", |ui| {
+                                    if show_descriptions {
+                                        ui.label(egui::RichText::new(&item.description).small().weak());
+                                    }
+                                    if let Some(when) = cleaned {
+                                        ui.label(egui::RichText::new(tr_args("sidebar.last_cleaned", &[&format_ago(now - *when)])).small().weak())
+                                        .on_hover_text(when.format("%Y-%m-%d %H:%M").to_string());
+                                    }
+                                });
+                            }
+                            ui.add_space(2.0);
+                        }
+                    });
+                    ui.separator();
+                }
+            });
+            match dropped_action {
+                Some((id, true)) => self.save_dropped(&id),
+                Some((id, false)) => self.remove_dropped(&id),
+                None => {}
+            }
+            if let Some((id, risk)) = clean_now {
+                self.request_clean_item(ctx, id, risk);
+            }
+        });
+
+        // --- Central Panel ---
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(tr("log.title"));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.log_filter)
+                        .id(egui::Id::new("log_search"))
+                        .hint_text(hint("log.search_hint", &SHORTCUT_LOG_SEARCH))
+                        .desired_width(220.0),
+                    );
+                });
+            });
+            ui.separator();
+
+            self.show_disks(ui, dark);
+            self.show_duplicates_tree(ui);
+            self.show_preview_tree(ui);
+            self.show_freed_chart(ui, &palette);
+            self.show_largest_files(ui);
+
+            let log_query = i18n::visual(&self.log_filter.trim().to_lowercase());
+
+            egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                if let Ok(logs) = self.logs.lock() {
+                    for log in logs.iter().filter(|l| log_query.is_empty() || l.to_lowercase().contains(&log_query)) {
+                        let (log, color) = palette.log_line(log);
+                        let text = egui::RichText::new(log).color(color);
+
+                        ui.label(text.family(egui::FontFamily::Monospace));
+                    }
+                }
+            });
+        });
+    }
+}
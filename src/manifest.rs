@@ -0,0 +1,77 @@
+use chrono::{DateTime, Local};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::engine::app_data_dir;
+
+// === רשימת מחיקות לכל ריצה (CSV), לביקורת ולכלי שחזור חיצוניים ===
+// בניגוד להסגר - רק רישום, הקבצים עצמם נמחקים. התיקייה בתוך app_data_dir, שהסריקות לא נכנסות אליו.
+
+const HEADER: &str = "timestamp,item,path,size,mtime";
+pub const MANIFESTS_DIR: &str = "manifests";
+// "Super Cleaner data" מוחק רשימות ישנות מזה
+pub const DEFAULT_MAX_AGE_DAYS: u64 = 30;
+
+pub fn manifests_dir() -> PathBuf {
+    app_data_dir().join(MANIFESTS_DIR)
+}
+
+pub struct DeletionManifest {
+    path: PathBuf,
+    // BufWriter: בלי כתיבה לדיסק על כל קובץ. נשטף בסוף כל אצווה, בסיום ובעצירה (drop)
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl DeletionManifest {
+    pub fn create(run_id: &str) -> io::Result<Self> {
+        let dir = manifests_dir();
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.csv", run_id));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writeln!(writer, "{}", HEADER)?;
+        Ok(DeletionManifest { path, writer: Mutex::new(writer) })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, item: &str, path: &Path, size: u64, modified: Option<SystemTime>) {
+        let mtime = modified.map(|m| DateTime::<Local>::from(m).to_rfc3339()).unwrap_or_default();
+        let line = format!(
+            "{},{},{},{},{}",
+            Local::now().to_rfc3339(),
+            csv_field(item),
+            csv_field(&path.to_string_lossy()),
+            size,
+            mtime
+        );
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writeln!(writer, "{}", line);
+        }
+    }
+
+    pub fn flush(&self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+impl Drop for DeletionManifest {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+// שמות קבצים יכולים להכיל פסיקים, מירכאות ושורות חדשות
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,319 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::engine::SystemCleaner;
+
+// === נקודות עיגון ומקום פנוי ===
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MountInfo {
+    pub device: String,
+    pub mount_point: PathBuf,
+    pub fs_type: String,
+}
+
+impl MountInfo {
+    // מחיקה ב-tmpfs משחררת זיכרון, לא דיסק
+    pub fn is_ram_backed(&self) -> bool {
+        matches!(self.fs_type.as_str(), "tmpfs" | "ramfs")
+    }
+
+    // btrfs/ZFS: קובץ שנמחק אבל שמור גם ב-snapshot לא מפנה מקום עד שה-snapshot נמחק
+    pub fn has_snapshots_support(&self) -> bool {
+        matches!(self.fs_type.as_str(), "btrfs" | "zfs")
+    }
+
+    // proc, sysfs, cgroup וכו' - וגם squashfs של snap, שהוא לקריאה בלבד
+    pub fn is_pseudo(&self) -> bool {
+        const PSEUDO: &[&str] = &[
+            "proc", "sysfs", "devtmpfs", "devpts", "tmpfs", "ramfs", "cgroup", "cgroup2", "securityfs", "pstore",
+            "bpf", "debugfs", "tracefs", "configfs", "fusectl", "mqueue", "hugetlbfs", "autofs", "binfmt_misc",
+            "nsfs", "efivarfs", "rpc_pipefs", "squashfs", "overlay", "fuse.portal", "fuse.gvfsd-fuse",
+        ];
+        PSEUDO.contains(&self.fs_type.as_str())
+    }
+}
+
+// /proc/mounts מקודד רווחים ותווים מיוחדים כ-\040 וכו'
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() && bytes[i + 1..i + 4].iter().all(|b| (b'0'..=b'7').contains(b)) {
+            let code = (bytes[i + 1] - b'0') as u32 * 64 + (bytes[i + 2] - b'0') as u32 * 8 + (bytes[i + 3] - b'0') as u32;
+            out.push(code as u8);
+            i += 4;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+pub fn read_mounts() -> Vec<MountInfo> {
+    let content = fs::read_to_string("/proc/mounts").unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let device = parts.next()?;
+            let mount_point = parts.next()?;
+            let fs_type = parts.next()?;
+            Some(MountInfo {
+                device: unescape_mount_field(device),
+                mount_point: PathBuf::from(unescape_mount_field(mount_point)),
+                fs_type: fs_type.to_string(),
+            })
+        })
+        .collect()
+}
+
+// מערכות קבצים אמיתיות, כל אחת פעם אחת: bind mount מופיע שוב עם אותו st_dev - נשארת
+// נקודת העיגון הקצרה. (לפי st_dev ולא לפי שם ההתקן - subvolumes של btrfs חולקים התקן)
+pub fn real_mounts() -> Vec<MountInfo> {
+    let mut mounts: Vec<MountInfo> = read_mounts().into_iter().filter(|m| !m.is_pseudo()).collect();
+    mounts.sort_by_key(|m| m.mount_point.components().count());
+    let mut seen = HashSet::new();
+    mounts.retain(|m| match fs::metadata(&m.mount_point) {
+        Ok(metadata) => seen.insert(device_id(&metadata)),
+        Err(_) => false,
+    });
+    mounts.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    mounts
+}
+
+#[cfg(unix)]
+pub fn device_id(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.dev()
+}
+
+// אין /proc/self/mounts ב-Windows, כך שהרשימה ממילא ריקה
+#[cfg(not(unix))]
+pub fn device_id(_metadata: &fs::Metadata) -> u64 {
+    0
+}
+
+// דיסק מסתובב (HDD): סריקה מקבילית רק מקפיצה את הראש בין תיקיות ומאטה.
+// /sys/dev/block/<major>:<minor> של מחיצה - ה-queue שלה נמצא אצל הדיסק שמעליה
+#[cfg(target_os = "linux")]
+pub fn is_rotational(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    let Ok(metadata) = fs::metadata(path) else { return false };
+    let dev = metadata.dev();
+    let Ok(block) = fs::canonicalize(format!("/sys/dev/block/{}:{}", libc::major(dev), libc::minor(dev))) else { return false };
+    let disk = block.parent().map(|p| p.join("queue/rotational"));
+    std::iter::once(block.join("queue/rotational"))
+        .chain(disk)
+        .find_map(|flag| fs::read_to_string(flag).ok())
+        .is_some_and(|flag| flag.trim() == "1")
+}
+
+// לא יודעים - מניחים SSD
+#[cfg(not(target_os = "linux"))]
+pub fn is_rotational(_path: &Path) -> bool {
+    false
+}
+
+// ה-mount הכי עמוק שמכיל את הנתיב (במקרה של עיגון כפול - האחרון מנצח)
+pub fn mount_for<'a>(path: &Path, mounts: &'a [MountInfo]) -> Option<&'a MountInfo> {
+    mounts
+        .iter()
+        .filter(|m| path.starts_with(&m.mount_point))
+        .max_by_key(|m| m.mount_point.components().count())
+}
+
+#[cfg(unix)]
+pub fn free_space(path: &Path) -> Option<u64> {
+    let stat = nix::sys::statvfs::statvfs(path).ok()?;
+    Some(stat.blocks_available() as u64 * stat.fragment_size() as u64)
+}
+
+// כמו df: used = total - free, והאחוז מחושב מול used + available (בלי הבלוקים השמורים ל-root)
+#[derive(Debug, Clone, Copy)]
+pub struct DiskUsage {
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+}
+
+impl DiskUsage {
+    pub fn used_fraction(&self) -> f32 {
+        let usable = self.used + self.available;
+        if usable == 0 {
+            return 0.0;
+        }
+        self.used as f32 / usable as f32
+    }
+}
+
+#[cfg(windows)]
+pub fn free_space(path: &Path) -> Option<u64> {
+    disk_usage(path).map(|usage| usage.available)
+}
+
+#[cfg(unix)]
+pub fn disk_usage(path: &Path) -> Option<DiskUsage> {
+    let stat = nix::sys::statvfs::statvfs(path).ok()?;
+    let block = stat.fragment_size() as u64;
+    let total = stat.blocks() as u64 * block;
+    Some(DiskUsage {
+        total,
+        used: total.saturating_sub(stat.blocks_free() as u64 * block),
+        available: stat.blocks_available() as u64 * block,
+    })
+}
+
+#[cfg(windows)]
+pub fn disk_usage(path: &Path) -> Option<DiskUsage> {
+    crate::win::disk_usage(path)
+}
+
+// "מעט מקום": פחות מאחוז מסוים פנוי, או פחות מכמות קבועה - מה שמגיע קודם
+pub fn is_low_on_space(usage: &DiskUsage, min_free_percent: f32, min_free_bytes: u64) -> bool {
+    (1.0 - usage.used_fraction()) * 100.0 < min_free_percent || usage.available < min_free_bytes
+}
+
+// הדיסקים שהניקוי משפיע עליהם: של תיקיית הבית ושל /
+pub fn watched_mount_points(mounts: &[MountInfo]) -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+    let mut points: Vec<PathBuf> = [home.as_path(), Path::new("/")]
+        .iter()
+        .filter_map(|p| mount_for(p, mounts).map(|m| m.mount_point.clone()))
+        .collect();
+    points.dedup();
+    points
+}
+
+// צילום של המקום הפנוי לפני/אחרי ריצה, כדי להשוות למה שהמנוע דיווח
+pub struct FreeSpaceSnapshot {
+    mounts: Vec<MountInfo>,
+    entries: Vec<(MountInfo, u64)>,
+}
+
+impl FreeSpaceSnapshot {
+    pub fn capture(roots: &[PathBuf]) -> Self {
+        let mounts = read_mounts();
+        let mut entries: Vec<(MountInfo, u64)> = Vec::new();
+        for root in roots {
+            if let Some(mount) = mount_for(root, &mounts) {
+                if entries.iter().any(|(m, _)| m.mount_point == mount.mount_point) {
+                    continue;
+                }
+                if let Some(free) = free_space(&mount.mount_point) {
+                    entries.push((mount.clone(), free));
+                }
+            }
+        }
+        FreeSpaceSnapshot { mounts, entries }
+    }
+
+    fn free_on(&self, mount_point: &Path) -> Option<u64> {
+        self.entries.iter().find(|(m, _)| m.mount_point == mount_point).map(|(_, free)| *free)
+    }
+
+    fn format_delta(delta: i64) -> String {
+        if delta >= 0 {
+            SystemCleaner::format_bytes(delta as u64)
+        } else {
+            format!("-{}", SystemCleaner::format_bytes(delta.unsigned_abs()))
+        }
+    }
+
+    // "Reported freed: 2.3 GB — Free space increased by 2.1 GB on /home, 0.2 GB on /"
+    // reported = (דיסק, tmpfs) כפי שהמנוע ספר
+    pub fn describe_delta(&self, after: &FreeSpaceSnapshot, touched: &[PathBuf], reported: (u64, u64)) -> String {
+        let mut disk_parts = Vec::new();
+        let mut ram_parts = Vec::new();
+
+        for (mount, before_free) in &self.entries {
+            let was_touched = touched
+                .iter()
+                .any(|p| mount_for(p, &self.mounts).map(|m| &m.mount_point) == Some(&mount.mount_point));
+            if !was_touched {
+                continue;
+            }
+            let after_free = match after.free_on(&mount.mount_point) {
+                Some(free) => free,
+                None => continue,
+            };
+            let delta = after_free as i64 - *before_free as i64;
+            let part = format!("{} on {}", Self::format_delta(delta), mount.mount_point.display());
+            if mount.is_ram_backed() {
+                ram_parts.push(part);
+            } else {
+                disk_parts.push(part);
+            }
+        }
+
+        let (disk, ram) = reported;
+        let mut summary = if ram > 0 {
+            format!("Reported freed: {} disk + {} RAM-backed tmp", SystemCleaner::format_bytes(disk), SystemCleaner::format_bytes(ram))
+        } else {
+            format!("Reported freed: {}", SystemCleaner::format_bytes(disk))
+        };
+        if !disk_parts.is_empty() {
+            summary.push_str(&format!(" — Free space increased by {}", disk_parts.join(", ")));
+        }
+        if !ram_parts.is_empty() {
+            summary.push_str(&format!(" — RAM freed (tmpfs): {}", ram_parts.join(", ")));
+        }
+        summary
+    }
+}
+
+// כמה snapshots יש על העיגון. None = לא הצלחנו לבדוק (אין את הכלי, או שהוא דורש root)
+pub fn snapshot_count(mount: &MountInfo) -> Option<usize> {
+    match mount.fs_type.as_str() {
+        "btrfs" => {
+            let listed = list_with("btrfs", &["subvolume", "list", "-s"], &mount.mount_point.to_string_lossy());
+            // בלי root הפקודה נכשלת; snapper שומר את שלו ב-.snapshots/<מספר>, שמותר לקרוא
+            listed.or_else(|| {
+                let entries = fs::read_dir(mount.mount_point.join(".snapshots")).ok()?;
+                Some(entries.filter_map(|e| e.ok()).filter(|e| e.file_name().to_string_lossy().parse::<u64>().is_ok()).count())
+            })
+        }
+        // ב-ZFS ה-device הוא שם ה-dataset (pool/home); -d 1 = רק שלו, לא של ה-datasets שמתחתיו
+        "zfs" => list_with("zfs", &["list", "-H", "-t", "snapshot", "-o", "name", "-d", "1"], &mount.device),
+        _ => None,
+    }
+}
+
+fn list_with(tool: &str, args: &[&str], target: &str) -> Option<usize> {
+    let tool = which::which(tool).ok()?;
+    let output = Command::new(tool).args(args).arg(target).output().ok()?;
+    output.status.success().then(|| count_listed(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// שורה לכל snapshot, גם ב-btrfs וגם ב-zfs -H
+fn count_listed(output: &str) -> usize {
+    output.lines().filter(|line| !line.trim().is_empty()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_listings_are_counted_per_line() {
+        let btrfs = "ID 259 gen 1402 cgen 1399 top level 5 otime 2026-10-01 10:00:00 path .snapshots/1/snapshot\n\
+                     ID 260 gen 1450 cgen 1448 top level 5 otime 2026-10-08 10:00:00 path .snapshots/2/snapshot\n";
+        assert_eq!(count_listed(btrfs), 2);
+        assert_eq!(count_listed("tank/home@daily-1\ntank/home@daily-2\ntank/home@weekly-1\n\n"), 3);
+        assert_eq!(count_listed(""), 0);
+    }
+
+    #[test]
+    fn only_copy_on_write_filesystems_keep_snapshots() {
+        let mount = |fs_type: &str| MountInfo { device: "tank/home".into(), mount_point: PathBuf::from("/home"), fs_type: fs_type.into() };
+        assert!(mount("btrfs").has_snapshots_support());
+        assert!(mount("zfs").has_snapshots_support());
+        assert!(!mount("ext4").has_snapshots_support());
+        assert_eq!(snapshot_count(&mount("ext4")), None);
+    }
+}
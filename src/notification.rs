@@ -0,0 +1,38 @@
+use notify_rust::Notification;
+use std::time::Duration;
+
+// === התראות שולחן עבודה (freedesktop) בסוף ריצה ארוכה ===
+
+// ריצות קצרות מזה לא מצדיקות התראה - המשתמש כנראה עדיין מסתכל
+pub const NOTIFY_AFTER: Duration = Duration::from_secs(10);
+
+// 14302 -> "14,302"
+pub fn format_count(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// נקרא מה-thread של הניקוי - שגיאה (אין daemon, אין D-Bus) חוזרת כטקסט ולא מפילה אותו
+pub fn send(body: &str) -> Result<(), String> {
+    let result = std::panic::catch_unwind(|| {
+        Notification::new()
+        .summary("System Cleaner Pro")
+        .body(body)
+        .icon(crate::APP_ID)
+        .appname(crate::APP_ID)
+        .show()
+        .map(|_| ())
+    });
+    match result {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err("notification backend panicked".to_string()),
+    }
+}
@@ -0,0 +1,187 @@
+use egui::Color32;
+
+use crate::config::AppearanceSettings;
+use crate::log_tail::LogLevel;
+
+// === הצבעים של החלון במקום אחד: רקעים, טקסט, חומרת שורות הלוג וכותרות הקטגוריות ===
+// setup_bleachbit_style והלוג קוראים מכאן. שלוש פלטות בסיס (רגילה, ניגודיות גבוהה, ידידותית לעיוורון צבעים)
+// ניגודיות גבוהה משנה רקע/טקסט; הפלטה לעיוורון צבעים משנה גוונים (Okabe-Ito) - אפשר לשלב ביניהן
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    pub dark: bool,
+    pub panel_fill: Color32,
+    pub window_fill: Color32,
+    pub selection: Color32,
+    pub text: Color32,
+    // None = ברירת המחדל של egui (טקסט חלש ושקוף למחצה)
+    pub weak_text: Option<Color32>,
+    pub error: Color32,
+    pub success: Color32,
+    pub warning: Color32,
+    pub info: Color32,
+    // ✖ / ✔ לפני שורות שגיאה/מחיקה בלוג - כדי שהצבע לא יהיה ההבדל היחיד
+    pub glyphs: bool,
+    categories: &'static [(&'static str, Color32)],
+    high_contrast: bool,
+}
+
+// נבחרו לרקע כהה - ברקע בהיר מכהים אותם (category_heading)
+const STANDARD_CATEGORIES: &[(&str, Color32)] = &[
+    ("system", Color32::from_rgb(144, 238, 144)),
+    ("browsers", Color32::from_rgb(100, 149, 237)),
+    ("dev", Color32::from_rgb(255, 215, 0)),
+    ("privacy", Color32::from_rgb(205, 92, 92)),
+    ("packages", Color32::from_rgb(135, 206, 250)),
+    ("analysis", Color32::from_rgb(221, 160, 221)),
+    ("custom", Color32::from_rgb(240, 200, 120)),
+    ("hooks", Color32::from_rgb(144, 238, 144)),
+];
+
+// Okabe-Ito: גוונים שנשארים שונים זה מזה גם בלי הבחנה בין אדום לירוק
+const COLOR_BLIND_CATEGORIES: &[(&str, Color32)] = &[
+    ("system", Color32::from_rgb(86, 180, 233)),
+    ("browsers", Color32::from_rgb(0, 158, 115)),
+    ("dev", Color32::from_rgb(240, 228, 66)),
+    ("privacy", Color32::from_rgb(213, 94, 0)),
+    ("packages", Color32::from_rgb(0, 114, 178)),
+    ("analysis", Color32::from_rgb(204, 121, 167)),
+    ("custom", Color32::from_rgb(230, 159, 0)),
+    ("hooks", Color32::from_rgb(86, 180, 233)),
+];
+
+impl Palette {
+    pub fn new(dark: bool, appearance: &AppearanceSettings) -> Self {
+        let (high_contrast, color_blind) = (appearance.high_contrast, appearance.color_blind_palette);
+        let (panel_fill, window_fill, text) = match (dark, high_contrast) {
+            (true, false) => (Color32::from_rgb(32, 33, 36), Color32::from_rgb(32, 33, 36), Color32::from_rgb(232, 234, 237)),
+            (false, false) => (Color32::from_rgb(248, 249, 250), Color32::WHITE, Color32::from_rgb(32, 33, 36)),
+            (true, true) => (Color32::BLACK, Color32::BLACK, Color32::WHITE),
+            (false, true) => (Color32::WHITE, Color32::WHITE, Color32::BLACK),
+        };
+        let weak_text = match (dark, high_contrast) {
+            (_, false) => None,
+            (true, true) => Some(Color32::from_rgb(205, 205, 205)),
+            (false, true) => Some(Color32::from_rgb(60, 60, 60)),
+        };
+        // (שגיאה, הצלחה, אזהרה). בפלטה לעיוורון צבעים: כתום-אדמדם מול כחול, לא אדום מול ירוק
+        let (error, success, warning) = match (dark, color_blind, high_contrast) {
+            (true, false, false) => (Color32::RED, Color32::GREEN, Color32::from_rgb(230, 160, 60)),
+            (false, false, false) => (Color32::from_rgb(180, 0, 0), Color32::from_rgb(0, 120, 0), Color32::from_rgb(170, 100, 0)),
+            (true, false, true) => (Color32::from_rgb(255, 110, 110), Color32::from_rgb(110, 255, 110), Color32::from_rgb(255, 200, 80)),
+            (false, false, true) => (Color32::from_rgb(150, 0, 0), Color32::from_rgb(0, 90, 0), Color32::from_rgb(130, 70, 0)),
+            (true, true, false) => (Color32::from_rgb(240, 120, 40), Color32::from_rgb(86, 180, 233), Color32::from_rgb(240, 228, 66)),
+            (false, true, false) => (Color32::from_rgb(170, 60, 0), Color32::from_rgb(0, 100, 160), Color32::from_rgb(150, 100, 0)),
+            (true, true, true) => (Color32::from_rgb(255, 150, 70), Color32::from_rgb(120, 200, 255), Color32::from_rgb(255, 240, 100)),
+            (false, true, true) => (Color32::from_rgb(140, 45, 0), Color32::from_rgb(0, 70, 130), Color32::from_rgb(110, 75, 0)),
+        };
+        let info = match (dark, high_contrast) {
+            (true, false) => Color32::LIGHT_GRAY,
+            (false, false) => Color32::DARK_GRAY,
+            (_, true) => text,
+        };
+        Palette {
+            dark,
+            panel_fill,
+            window_fill,
+            selection: if high_contrast && !dark { Color32::from_rgb(0, 70, 170) } else { Color32::from_rgb(66, 133, 244) },
+            text,
+            weak_text,
+            error,
+            success,
+            warning,
+            info,
+            glyphs: high_contrast || color_blind,
+            categories: if color_blind { COLOR_BLIND_CATEGORIES } else { STANDARD_CATEGORIES },
+            high_contrast,
+        }
+    }
+
+    pub fn log_color(&self, level: LogLevel) -> Color32 {
+        match level {
+            LogLevel::Error => self.error,
+            LogLevel::Success => self.success,
+            LogLevel::Info => self.info,
+        }
+    }
+
+    // שורת לוג כפי שמוצגת: צבע לפי החומרה, ו-glyph בהתחלה אם הופעל
+    pub fn log_line(&self, line: &str) -> (String, Color32) {
+        let level = crate::log_tail::level_of(line);
+        let text = match (self.glyphs, level) {
+            (true, LogLevel::Error) => format!("✖ {}", line),
+            (true, LogLevel::Success) => format!("✔ {}", line),
+            _ => line.to_string(),
+        };
+        (text, self.log_color(level))
+    }
+
+    // מילוי הפסים בגרף - כמו שהוא, על כל רקע
+    pub fn category_fill(&self, id: &str) -> Color32 {
+        self.categories.iter().find(|(category, _)| *category == id).map_or(self.info, |(_, color)| *color)
+    }
+
+    // טקסט הכותרת בסרגל: מוכהה ברקע בהיר, ובניגודיות גבוהה קרוב יותר ללבן/שחור
+    pub fn category_heading(&self, id: &str) -> Color32 {
+        let color = self.category_fill(id);
+        let scale = |factor: f32| Color32::from_rgb((color.r() as f32 * factor) as u8, (color.g() as f32 * factor) as u8, (color.b() as f32 * factor) as u8);
+        match (self.dark, self.high_contrast) {
+            (true, false) => color,
+            (false, false) => scale(0.55),
+            (true, true) => color.lerp_to_gamma(Color32::WHITE, 0.4),
+            (false, true) => scale(0.4),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ניגודיות לפי WCAG 2.x
+    fn contrast(a: Color32, b: Color32) -> f32 {
+        let luminance = |c: Color32| {
+            let channel = |v: u8| {
+                let v = v as f32 / 255.0;
+                if v <= 0.03928 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+            };
+            0.2126 * channel(c.r()) + 0.7152 * channel(c.g()) + 0.0722 * channel(c.b())
+        };
+        let (la, lb) = (luminance(a), luminance(b));
+        (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+    }
+
+    fn appearance(high_contrast: bool, color_blind_palette: bool) -> AppearanceSettings {
+        AppearanceSettings { high_contrast, color_blind_palette, ..Default::default() }
+    }
+
+    #[test]
+    fn high_contrast_keeps_log_and_weak_text_readable() {
+        for dark in [true, false] {
+            for color_blind in [false, true] {
+                let palette = Palette::new(dark, &appearance(true, color_blind));
+                for color in [palette.error, palette.success, palette.warning, palette.info, palette.weak_text.unwrap()] {
+                    assert!(contrast(color, palette.panel_fill) >= 4.5, "dark={} color_blind={} {:?}", dark, color_blind, color);
+                }
+                for (id, _) in palette.categories {
+                    assert!(contrast(palette.category_heading(id), palette.panel_fill) >= 4.5, "{}", id);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn accessible_palettes_mark_severity_with_a_glyph() {
+        let error = "Error: permission denied".to_string();
+        let standard = Palette::new(true, &appearance(false, false));
+        assert_eq!(standard.log_line(&error).0, error);
+
+        let color_blind = Palette::new(true, &appearance(false, true));
+        let (text, color) = color_blind.log_line(&error);
+        assert!(text.starts_with("✖ "));
+        assert_eq!(color, color_blind.error);
+        assert!(color_blind.log_line("Deleted: /tmp/a").0.starts_with("✔ "));
+        assert_eq!(color_blind.log_line("Scanning...").0, "Scanning...");
+        assert_eq!(color_blind.category_fill("not-a-category"), color_blind.info);
+    }
+}
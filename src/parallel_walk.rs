@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+// === סריקה מקבילית לעצים ענקיים (~/.cache עם מאות אלפי קבצים) ===
+// לכל thread תור תיקיות משלו, שממנו הוא לוקח מהסוף (לעומק, קרוב למה שכבר נקרא). thread שנגמרה
+// לו העבודה גונב מההתחלה של תור אחר - תיקיות קרובות לשורש, שמתחתיהן יש הכי הרבה עבודה.
+
+// thread בלי עבודה מחכה רגע - אולי תיקייה שאחר קורא עכשיו תוסיף עוד
+const IDLE: Duration = Duration::from_micros(200);
+
+pub trait Visitor: Sync {
+    // false = מדלגים על הרשומה, ואם היא תיקייה - גם על כל מה שמתחתיה
+    fn enter(&self, path: &Path, metadata: &fs::Metadata) -> bool;
+    // כל מה שאינו תיקייה: קבצים, וגם symlinks (בלי לעקוב אחריהם)
+    fn visit(&self, path: PathBuf, metadata: fs::Metadata);
+    fn error(&self, path: &Path, error: &io::Error);
+    // ביטול: כל ה-threads עוצרים אחרי הרשומה שהם באמצעה. מותר לחסום (השהיה)
+    fn stop(&self) -> bool;
+}
+
+struct Walk<'a, V> {
+    queues: Vec<Mutex<VecDeque<PathBuf>>>,
+    // תיקיות שנכנסו לתור ועוד לא נקראו עד הסוף. 0 = אין ולא תהיה עוד עבודה
+    pending: AtomicUsize,
+    stopped: AtomicBool,
+    visitor: &'a V,
+}
+
+// התוכן של root, בלי root עצמו. threads <= 1 = הכל ב-thread הנוכחי
+pub fn walk<V: Visitor>(root: &Path, threads: usize, visitor: &V) {
+    let threads = threads.max(1);
+    let walk = Walk {
+        queues: (0..threads).map(|_| Mutex::new(VecDeque::new())).collect(),
+        pending: AtomicUsize::new(1),
+        stopped: AtomicBool::new(false),
+        visitor,
+    };
+    walk.queues[0].lock().unwrap().push_back(root.to_path_buf());
+    if threads == 1 {
+        walk.work(0);
+        return;
+    }
+    thread::scope(|scope| {
+        for index in 0..threads {
+            let walk = &walk;
+            scope.spawn(move || walk.work(index));
+        }
+    });
+}
+
+impl<V: Visitor> Walk<'_, V> {
+    fn work(&self, index: usize) {
+        while self.pending.load(Ordering::Acquire) > 0 && !self.stopped.load(Ordering::Relaxed) {
+            match self.next(index) {
+                Some(dir) => {
+                    self.read_dir(index, &dir);
+                    self.pending.fetch_sub(1, Ordering::AcqRel);
+                }
+                None => thread::sleep(IDLE),
+            }
+        }
+    }
+
+    fn next(&self, index: usize) -> Option<PathBuf> {
+        if let Some(dir) = self.queues[index].lock().unwrap().pop_back() {
+            return Some(dir);
+        }
+        let count = self.queues.len();
+        (1..count).find_map(|offset| self.queues[(index + offset) % count].lock().unwrap().pop_front())
+    }
+
+    fn read_dir(&self, index: usize, dir: &Path) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.visitor.error(dir, &e);
+                return;
+            }
+        };
+        for entry in entries {
+            if self.stopped.load(Ordering::Relaxed) || self.visitor.stop() {
+                self.stopped.store(true, Ordering::Relaxed);
+                return;
+            }
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => {
+                    self.visitor.error(dir, &e);
+                    continue;
+                }
+            };
+            let metadata = match fs::symlink_metadata(&path) {
+                Ok(m) => m,
+                Err(e) => {
+                    self.visitor.error(&path, &e);
+                    continue;
+                }
+            };
+            if !self.visitor.enter(&path, &metadata) {
+                continue;
+            }
+            if metadata.is_dir() {
+                // לפני שהתיקייה הנוכחית יוצאת מ-pending, כדי שהספירה לא תגיע ל-0 באמצע
+                self.pending.fetch_add(1, Ordering::AcqRel);
+                self.queues[index].lock().unwrap().push_back(path);
+            } else {
+                self.visitor.visit(path, metadata);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU64;
+
+    struct Collect {
+        files: Mutex<Vec<PathBuf>>,
+        skip: &'static str,
+        stop_after: u64,
+        seen: AtomicU64,
+    }
+
+    impl Visitor for Collect {
+        fn enter(&self, path: &Path, _metadata: &fs::Metadata) -> bool {
+            path.file_name().is_some_and(|name| name != self.skip)
+        }
+        fn visit(&self, path: PathBuf, _metadata: fs::Metadata) {
+            self.seen.fetch_add(1, Ordering::Relaxed);
+            self.files.lock().unwrap().push(path);
+        }
+        fn error(&self, _path: &Path, _error: &io::Error) {}
+        fn stop(&self) -> bool {
+            self.seen.load(Ordering::Relaxed) >= self.stop_after
+        }
+    }
+
+    fn tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for a in 0..8 {
+            for b in 0..8 {
+                let sub = dir.path().join(format!("{}/{}", a, b));
+                fs::create_dir_all(&sub).unwrap();
+                for c in 0..10 {
+                    fs::write(sub.join(format!("{}.bin", c)), [c as u8]).unwrap();
+                }
+            }
+        }
+        fs::create_dir_all(dir.path().join("3/skip/deep")).unwrap();
+        fs::write(dir.path().join("3/skip/deep/hidden.bin"), b"x").unwrap();
+        dir
+    }
+
+    fn collect(skip: &'static str, stop_after: u64) -> Collect {
+        Collect { files: Mutex::new(Vec::new()), skip, stop_after, seen: AtomicU64::new(0) }
+    }
+
+    #[test]
+    fn every_file_is_visited_once_whatever_the_thread_count() {
+        let dir = tree();
+        for threads in [1, 2, 8] {
+            let visitor = collect("skip", u64::MAX);
+            walk(dir.path(), threads, &visitor);
+            let mut files = visitor.files.into_inner().unwrap();
+            files.sort();
+            files.dedup();
+            assert_eq!(files.len(), 640, "threads = {}", threads);
+            assert!(files.iter().all(|f| !f.to_string_lossy().contains("skip")));
+        }
+    }
+
+    #[test]
+    fn stopping_drains_all_threads() {
+        let dir = tree();
+        let visitor = collect("", 50);
+        walk(dir.path(), 8, &visitor);
+        // כל thread עוצר אחרי הרשומה שהוא באמצעה
+        assert!(visitor.seen.load(Ordering::Relaxed) < 50 + 8);
+    }
+}
@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::{XdgDir, XdgEnv};
+
+// === איפה הדברים נמצאים: הבית, תיקיות ה-XDG שלו ושורשי המערכת (/tmp, /var/cache, /var/log) ===
+// ברירת המחדל היא המיקומים האמיתיים. בקונטיינר או ב-chroot מזיזים את שורשי המערכת עם SYSCLEAN_ROOT
+// ואת הבית עם SYSCLEAN_HOME; בבדיקות הכל מצביע לעץ מזויף (SystemPaths::new).
+
+pub const ROOT_ENV: &str = "SYSCLEAN_ROOT";
+pub const HOME_ENV: &str = "SYSCLEAN_HOME";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemPaths {
+    // "/" - או השורש של ה-chroot
+    pub root: PathBuf,
+    pub home: PathBuf,
+    pub cache: PathBuf,
+    pub config: PathBuf,
+    pub data: PathBuf,
+    pub state: PathBuf,
+    // /tmp (ב-Windows: %TEMP%) ו-/var/tmp
+    pub tmp: PathBuf,
+    pub var_tmp: PathBuf,
+    pub system_cache: PathBuf,
+    pub system_log: PathBuf,
+    // /usr/share - ערכות האייקונים של המערכת
+    pub system_share: PathBuf,
+    // XDG_*_HOME שנקראו מהסביבה פעם אחת; cache/config/data/state נגזרים מהם
+    pub xdg: XdgEnv,
+}
+
+impl Default for SystemPaths {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl SystemPaths {
+    // כל שורשי המערכת תחת root, והבית (עם תיקיות ה-XDG שלו) ב-home
+    pub fn new(root: &Path, home: PathBuf) -> Self {
+        let mut paths = SystemPaths {
+            root: root.to_path_buf(),
+            home: PathBuf::new(),
+            cache: PathBuf::new(),
+            config: PathBuf::new(),
+            data: PathBuf::new(),
+            state: PathBuf::new(),
+            tmp: root.join("tmp"),
+            var_tmp: root.join("var/tmp"),
+            system_cache: root.join("var/cache"),
+            system_log: root.join("var/log"),
+            system_share: root.join("usr/share"),
+            xdg: XdgEnv::default(),
+        };
+        paths.set_home(home);
+        paths
+    }
+
+    // המיקומים האמיתיים, אחרי הדריסות מהסביבה
+    pub fn from_env() -> Self {
+        let root = std::env::var_os(ROOT_ENV).map(PathBuf::from);
+        let home = std::env::var_os(HOME_ENV)
+            .map(PathBuf::from)
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("/tmp"));
+        let mut paths = Self::new(root.as_deref().unwrap_or(Path::new("/")), home).with_xdg_env(XdgEnv::from_env());
+        if root.is_none() && !cfg!(unix) {
+            paths.tmp = std::env::temp_dir();
+        }
+        paths
+    }
+
+    pub fn with_xdg_env(mut self, xdg: XdgEnv) -> Self {
+        self.xdg = xdg;
+        let home = std::mem::take(&mut self.home);
+        self.set_home(home);
+        self
+    }
+
+    // בדיקות: כאילו XDG_<dir>_HOME=path בסביבה של בעל הבית הנוכחי
+    #[cfg(test)]
+    pub fn with_xdg(self, dir: XdgDir, path: PathBuf) -> Self {
+        let mut xdg = self.xdg.clone();
+        xdg.owner = Some(self.home.clone());
+        xdg.dirs.retain(|(d, _)| *d != dir);
+        xdg.dirs.push((dir, path));
+        self.with_xdg_env(xdg)
+    }
+
+    // אותם שורשי מערכת, בית אחר (ריצה כ-root עבור משתמש, --home)
+    pub fn with_home(mut self, home: PathBuf) -> Self {
+        self.set_home(home);
+        self
+    }
+
+    // משתני XDG שייכים למי שהריץ - רק לבית שלו; לכל בית אחר ברירות המחדל
+    fn set_home(&mut self, home: PathBuf) {
+        self.cache = self.xdg.resolve(XdgDir::Cache, &home);
+        self.config = self.xdg.resolve(XdgDir::Config, &home);
+        self.data = self.xdg.resolve(XdgDir::Data, &home);
+        self.state = self.xdg.resolve(XdgDir::State, &home);
+        self.home = home;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // המשתנים של מי שהריץ לא חלים על הבית של משתמש אחר
+    #[test]
+    fn xdg_homes_stay_with_their_owner() {
+        let paths = SystemPaths::new(Path::new("/fake"), PathBuf::from("/fake/home/me")).with_xdg(XdgDir::Data, PathBuf::from("/scratch/data"));
+        assert_eq!(paths.data, Path::new("/scratch/data"));
+        assert_eq!(paths.cache, Path::new("/fake/home/me/.cache"));
+        assert_eq!(paths.with_home(PathBuf::from("/fake/home/other")).data, Path::new("/fake/home/other/.local/share"));
+    }
+}
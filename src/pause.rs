@@ -0,0 +1,69 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+// === השהיה/המשך של ריצה ===
+// ה-worker בודק את השער בין קבצים ונעצר שם עד Resume. פקודות חיצוניות שכבר רצות לא נעצרות,
+// אבל פקודה חדשה לא מתחילה בזמן השהיה. Abort משחרר את ההמתנה מיד.
+
+// כל כמה זמן ההמתנה בודקת גם את ה-CancellationToken
+const POLL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Default)]
+struct PauseState {
+    paused_since: Option<Instant>,
+    // סך ההשהיות שהסתיימו - לחישוב זמן ריצה נטו
+    paused_total: Duration,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PauseGate {
+    inner: Arc<(Mutex<PauseState>, Condvar)>,
+}
+
+impl PauseGate {
+    pub fn pause(&self) {
+        let mut state = self.inner.0.lock().unwrap();
+        if state.paused_since.is_none() {
+            state.paused_since = Some(Instant::now());
+        }
+    }
+
+    pub fn resume(&self) {
+        let (lock, condvar) = &*self.inner;
+        let mut state = lock.lock().unwrap();
+        if let Some(since) = state.paused_since.take() {
+            state.paused_total += since.elapsed();
+        }
+        condvar.notify_all();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.inner.0.lock().unwrap().paused_since.is_some()
+    }
+
+    // כולל ההשהיה הנוכחית, אם יש
+    pub fn paused_for(&self) -> Duration {
+        let state = self.inner.0.lock().unwrap();
+        state.paused_total + state.paused_since.map(|since| since.elapsed()).unwrap_or_default()
+    }
+
+    // חוסם עד Resume או ביטול - רק מ-thread חוסם (spawn_blocking)
+    pub fn wait(&self, cancel: &CancellationToken) {
+        let (lock, condvar) = &*self.inner;
+        let mut state = lock.lock().unwrap();
+        while state.paused_since.is_some() && !cancel.is_cancelled() {
+            state = condvar.wait_timeout(state, POLL).unwrap().0;
+        }
+    }
+
+    // אותו דבר בלי לתפוס thread של ה-runtime
+    pub async fn wait_async(&self, cancel: &CancellationToken) {
+        while self.is_paused() && !cancel.is_cancelled() {
+            tokio::select! {
+                _ = tokio::time::sleep(POLL) => {}
+                _ = cancel.cancelled() => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+
+// === קבצים שהתוכנה עצמה שומרת (הגדרות, מטמון גדלים, "נוקה לאחרונה") ===
+// קריסה או הפסקת חשמל באמצע כתיבה לא משאירות קובץ חצי כתוב, וקובץ שבכל זאת לא נקרא
+// נשמר בצד לבדיקה במקום שייכתב מעליו.
+
+// קובץ זמני באותה תיקייה, fsync ואז rename - קורא אף פעם לא רואה קובץ חצי כתוב
+pub fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut temp_name = path.file_name().ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?.to_os_string();
+    temp_name.push(".sysclean-tmp");
+    let temp = path.with_file_name(temp_name);
+    let result = (|| {
+        let mut file = fs::File::create(&temp)?;
+        file.write_all(contents)?;
+        if let Ok(metadata) = fs::metadata(path) {
+            file.set_permissions(metadata.permissions())?;
+        }
+        file.sync_all()?;
+        fs::rename(&temp, path)?;
+        sync_parent(path);
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+// ה-rename עצמו נשמר בדיסק רק כשהתיקייה מסונכרנת. כישלון כאן לא מבטל את הכתיבה
+#[cfg(unix)]
+fn sync_parent(path: &Path) {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        let _ = fs::File::open(parent).and_then(|dir| dir.sync_all());
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) {}
+
+// config.toml -> config.toml.broken-20241102-091500, ליד המקור
+pub fn broken_path(path: &Path, when: DateTime<Local>) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".broken-{}", when.format("%Y%m%d-%H%M%S")));
+    path.with_file_name(name)
+}
+
+// מזיז קובץ שלא נקרא הצידה, כדי שהשמירה הבאה לא תמחק את מה שאפשר עוד להציל ממנו
+pub fn set_aside(path: &Path) -> io::Result<PathBuf> {
+    let destination = broken_path(path, Local::now());
+    fs::rename(path, &destination)?;
+    Ok(destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atomic_write_replaces_without_leftovers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "old").unwrap();
+        write_atomically(&path, b"new").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn broken_files_are_set_aside_with_a_timestamp() {
+        let when = DateTime::parse_from_rfc3339("2024-11-02T09:15:00+00:00").unwrap().with_timezone(&Local);
+        let expected = format!("config.toml.broken-{}", when.format("%Y%m%d-%H%M%S"));
+        assert_eq!(broken_path(Path::new("/x/config.toml"), when), Path::new("/x").join(expected));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[general").unwrap();
+        let moved = set_aside(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(moved).unwrap(), "[general");
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+// === תוצאות תצוגה מקדימה עם בחירה לכל קובץ ===
+// פריטים שמוחקים נתוני משתמש (קבצים גדולים, תיקיות ריקות...) ממלאים את הרשימה בתצוגה מקדימה,
+// המשתמש מסמן/מבטל בעץ, והניקוי מוחק רק את מה שנשאר מסומן.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    // מידע נוסף לתצוגה (למשל היעד של symlink)
+    pub detail: String,
+    // כשלא ריק - מוצג במקום "גודל  נתיב" (למשל המיקום המקורי של קובץ בפח)
+    pub title: String,
+    // כותרת משנה בעץ; רשומות עם אותה קבוצה מוצגות יחד
+    pub group: String,
+    // תג אזהרה ליד הרשומה
+    pub warning: String,
+    pub selected: bool,
+}
+
+impl PreviewEntry {
+    pub fn new(path: PathBuf, size: u64, modified: Option<SystemTime>) -> Self {
+        PreviewEntry {
+            path,
+            size,
+            modified,
+            detail: String::new(),
+            title: String::new(),
+            group: String::new(),
+            warning: String::new(),
+            selected: true,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: String) -> Self {
+        self.detail = detail;
+        self
+    }
+
+    pub fn with_title(mut self, title: String) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn with_group(mut self, group: String) -> Self {
+        self.group = group;
+        self
+    }
+
+    // כרגע רק הפח של freedesktop, שאין ב-Windows
+    #[cfg(not(windows))]
+    pub fn with_warning(mut self, warning: String) -> Self {
+        self.warning = warning;
+        self
+    }
+
+    pub fn with_selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+}
+
+// לפי id של פריט. משותף בין ה-GUI (תיבות סימון) וה-worker
+#[derive(Debug, Clone, Default)]
+pub struct PreviewStore {
+    entries: Arc<Mutex<HashMap<String, Vec<PreviewEntry>>>>,
+    // פריטים שהרשימה שלהם נטענה מתצוגה מקדימה של הפעלה קודמת (saved_preview.rs)
+    stale: Arc<Mutex<HashSet<String>>>,
+}
+
+impl PreviewStore {
+    pub fn lock(&self) -> MutexGuard<'_, HashMap<String, Vec<PreviewEntry>>> {
+        self.entries.lock().unwrap()
+    }
+
+    pub fn set(&self, id: &str, entries: Vec<PreviewEntry>) {
+        self.lock().insert(id.to_string(), entries);
+        self.stale.lock().unwrap().remove(id);
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+        self.stale.lock().unwrap().clear();
+    }
+
+    // רשימות שמורות: מוצגות ואפשר לסמן בהן, אבל הניקוי סורק מחדש לפני שהוא מוחק משהו
+    pub fn load_stale(&self, saved: HashMap<String, Vec<PreviewEntry>>) {
+        let mut store = self.lock();
+        let mut stale = self.stale.lock().unwrap();
+        for (id, entries) in saved.into_iter().filter(|(_, entries)| !entries.is_empty()) {
+            stale.insert(id.clone());
+            store.insert(id, entries);
+        }
+    }
+
+    pub fn is_stale(&self, id: &str) -> bool {
+        self.stale.lock().unwrap().contains(id)
+    }
+
+    // None = לא הייתה תצוגה מקדימה לפריט הזה
+    pub fn selected(&self, id: &str) -> Option<Vec<PreviewEntry>> {
+        self.lock().get(id).map(|entries| entries.iter().filter(|e| e.selected).cloned().collect())
+    }
+
+    // אחרי ניקוי: מה שנמחק כבר לא מוצג
+    pub fn remove_paths(&self, id: &str, removed: &HashSet<PathBuf>) {
+        let mut store = self.lock();
+        if let Some(entries) = store.get_mut(id) {
+            entries.retain(|e| !removed.contains(&e.path));
+            if entries.is_empty() {
+                store.remove(id);
+                self.stale.lock().unwrap().remove(id);
+            }
+        }
+    }
+}
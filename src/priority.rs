@@ -0,0 +1,43 @@
+use std::io;
+use std::time::Duration;
+
+// === מצב עדיפות נמוכה: I/O במחלקת idle ו-nice 19, כדי שניקוי ברקע לא יחנוק את הדיסק ===
+
+// linux/ioprio.h
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+#[cfg(unix)]
+const LOWEST_NICE: libc::c_int = 19;
+
+// גיבוי למקרים ש-ioprio לא משפיע (scheduler בלי תמיכה, NFS): הפסקה קצרה כל N מחיקות
+pub const DEFAULT_PAUSE_EVERY: u64 = 200;
+pub const THROTTLE_PAUSE: Duration = Duration::from_millis(20);
+
+// בלינוקס גם ioprio וגם nice הם לכל thread. מפעילים מה-thread של ה-worker לפני שיוצרים את
+// ה-runtime של tokio - threads חדשים ותהליכי apt/dnf יורשים את העדיפות ממנו.
+#[cfg(unix)]
+pub fn lower_current_thread() -> io::Result<()> {
+    // ioprio קיים רק בלינוקס; ב-macOS נשאר nice בלבד
+    #[cfg(target_os = "linux")]
+    {
+        // who = 0 -> ה-thread הנוכחי
+        let ioprio = IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT;
+        if unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, 0, ioprio) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, LOWEST_NICE) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn lower_current_thread() -> io::Result<()> {
+    crate::win::lower_current_thread()
+}
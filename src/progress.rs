@@ -0,0 +1,234 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::i18n::{tr, tr_args};
+
+// === התקדמות, זמן ריצה והערכת זמן שנותר ===
+
+// התקדמות בבייטים (hashing של כפולים, מחיקת מקום פנוי), לקריאה מה-UI
+#[derive(Debug, Default)]
+pub struct ScanProgress {
+    pub done: AtomicU64,
+    pub total: AtomicU64,
+}
+
+impl ScanProgress {
+    pub fn fraction(&self) -> Option<f32> {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            return None;
+        }
+        Some((self.done.load(Ordering::Relaxed) as f32 / total as f32).min(1.0))
+    }
+
+    pub fn reset(&self) {
+        self.done.store(0, Ordering::Relaxed);
+        self.total.store(0, Ordering::Relaxed);
+    }
+}
+
+// הנתיב שהמנוע עובד עליו עכשיו, לסרגל המצב. נקרא מכל קובץ - רק עדכון אחד לכל CURRENT_PATH_EVERY
+// נוגע ב-Mutex, השאר יוצאים אחרי קריאה אטומית אחת
+const CURRENT_PATH_EVERY: Duration = Duration::from_millis(200);
+
+#[derive(Debug)]
+pub struct CurrentPath {
+    path: Mutex<Option<PathBuf>>,
+    started: Instant,
+    // מילישניות מ-started של העדכון האחרון
+    last_set: AtomicU64,
+}
+
+impl Default for CurrentPath {
+    fn default() -> Self {
+        CurrentPath { path: Mutex::new(None), started: Instant::now(), last_set: AtomicU64::new(0) }
+    }
+}
+
+impl CurrentPath {
+    pub fn set(&self, path: &Path) {
+        let now = self.started.elapsed().as_millis() as u64;
+        let last = self.last_set.load(Ordering::Relaxed);
+        if now.saturating_sub(last) < CURRENT_PATH_EVERY.as_millis() as u64 && last != 0 {
+            return;
+        }
+        // thread אחר עדכן בינתיים - הוא מספיק
+        if self.last_set.compare_exchange(last, now.max(1), Ordering::Relaxed, Ordering::Relaxed).is_err() {
+            return;
+        }
+        if let Ok(mut current) = self.path.lock() {
+            *current = Some(path.to_path_buf());
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut current) = self.path.lock() {
+            *current = None;
+        }
+        self.last_set.store(0, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> Option<PathBuf> {
+        self.path.lock().ok().and_then(|current| current.clone())
+    }
+}
+
+// קבצים לשנייה על החלון האחרון - ממוצע מתחילת הריצה היה מסתיר שהסריקה נתקעה עכשיו
+const RATE_WINDOW: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Default)]
+pub struct RateTracker {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl RateTracker {
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    // count = המונה המצטבר (קבצים שנמחקו עד עכשיו)
+    pub fn sample(&mut self, count: u64) {
+        self.sample_at(Instant::now(), count);
+    }
+
+    fn sample_at(&mut self, now: Instant, count: u64) {
+        if self.samples.back().is_some_and(|&(_, last)| count < last) {
+            self.samples.clear();
+        }
+        self.samples.push_back((now, count));
+        while self.samples.front().is_some_and(|&(at, _)| now.duration_since(at) > RATE_WINDOW) {
+            self.samples.pop_front();
+        }
+    }
+
+    // None עד שיש חצי שנייה של נתונים
+    pub fn per_second(&self) -> Option<f64> {
+        let &(first_at, first) = self.samples.front()?;
+        let &(last_at, last) = self.samples.back()?;
+        let elapsed = last_at.duration_since(first_at).as_secs_f64();
+        (elapsed >= 0.5).then(|| (last - first) as f64 / elapsed)
+    }
+}
+
+// "/home/u/.cache/…/entries/3F2A" - ההתחלה והשם נשארים, האמצע נחתך
+pub fn truncate_middle(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    let keep = max_chars.saturating_sub(1);
+    let head = keep / 2;
+    let tail = keep - head;
+    let start: String = text.chars().take(head).collect();
+    let end: String = text.chars().skip(count - tail).collect();
+    format!("{}…{}", start, end)
+}
+
+// הקצב מחושב רק על החלון האחרון, כדי שקפיצות (קובץ ענק אחד) לא יזיזו את ההערכה בפראות
+const ETA_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Default)]
+pub struct EtaEstimator {
+    samples: VecDeque<(Instant, f32)>,
+}
+
+impl EtaEstimator {
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    // fraction = 0.0..=1.0 מההתקדמות האמיתית (לא מהאנימציה)
+    pub fn sample(&mut self, fraction: f32) {
+        let now = Instant::now();
+        // התקדמות שירדה = סריקה חדשה התחילה, ההיסטוריה כבר לא רלוונטית
+        if self.samples.back().is_some_and(|&(_, last)| fraction < last) {
+            self.samples.clear();
+        }
+        self.samples.push_back((now, fraction));
+        while self.samples.front().is_some_and(|&(at, _)| now.duration_since(at) > ETA_WINDOW) {
+            self.samples.pop_front();
+        }
+    }
+
+    // None עד שיש מספיק נתונים או כשאין התקדמות בחלון
+    pub fn remaining(&self) -> Option<Duration> {
+        let &(first_at, first) = self.samples.front()?;
+        let &(last_at, last) = self.samples.back()?;
+        let elapsed = last_at.duration_since(first_at).as_secs_f32();
+        let rate = (last - first) / elapsed;
+        if elapsed < 1.0 || rate <= 0.0 {
+            return None;
+        }
+        Some(Duration::from_secs_f32((1.0 - last).max(0.0) / rate))
+    }
+}
+
+// "3 days ago" - לתווית "נוקה לאחרונה". שעון שזז אחורה = "just now"
+pub fn format_ago(elapsed: chrono::Duration) -> String {
+    let minutes = elapsed.num_minutes();
+    let (hours, days) = (elapsed.num_hours(), elapsed.num_days());
+    if minutes < 1 {
+        tr("time.just_now")
+    } else if hours < 1 {
+        tr_args("time.minutes_ago", &[&minutes.to_string()])
+    } else if days < 1 {
+        tr_args("time.hours_ago", &[&hours.to_string()])
+    } else if days < 60 {
+        tr_args("time.days_ago", &[&days.to_string()])
+    } else {
+        tr_args("time.months_ago", &[&(days / 30).to_string()])
+    }
+}
+
+// 0:42, 12:05, 1:02:03
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_paths_lose_their_middle() {
+        assert_eq!(truncate_middle("/tmp/a", 10), "/tmp/a");
+        let cut = truncate_middle("/home/user/.cache/mozilla/firefox/entries/3F2A", 20);
+        assert_eq!(cut.chars().count(), 20);
+        assert!(cut.starts_with("/home/use") && cut.ends_with("3F2A") && cut.contains('…'));
+    }
+
+    #[test]
+    fn rate_uses_only_the_recent_window() {
+        let mut rate = RateTracker::default();
+        let start = Instant::now();
+        rate.sample_at(start, 0);
+        assert_eq!(rate.per_second(), None);
+        rate.sample_at(start + Duration::from_secs(1), 5000);
+        assert_eq!(rate.per_second(), Some(5000.0));
+        // עשר שניות בלי התקדמות - הקצב יורד לאפס, לא נשאר על הממוצע
+        rate.sample_at(start + Duration::from_secs(11), 5000);
+        rate.sample_at(start + Duration::from_secs(12), 5000);
+        assert_eq!(rate.per_second(), Some(0.0));
+    }
+
+    #[test]
+    fn current_path_updates_are_rate_limited() {
+        let current = CurrentPath::default();
+        current.set(Path::new("/a"));
+        current.set(Path::new("/b"));
+        assert_eq!(current.get(), Some(PathBuf::from("/a")));
+        current.clear();
+        assert_eq!(current.get(), None);
+        current.set(Path::new("/c"));
+        assert_eq!(current.get(), Some(PathBuf::from("/c")));
+    }
+}
@@ -0,0 +1,235 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::engine::app_data_dir;
+
+// === הסגר: במקום מחיקה, הקבצים עוברים לתיקייה שאפשר לשחזר ממנה ===
+
+const MANIFEST_FILE: &str = "manifest.jsonl";
+pub const QUARANTINE_DIR: &str = "quarantine";
+
+// ברירות מחדל לניקוי אוטומטי של ריצות ישנות
+pub const DEFAULT_MAX_AGE_DAYS: i64 = 7;
+pub const DEFAULT_MAX_TOTAL_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub original: PathBuf,
+    pub stored: PathBuf,
+    pub size: u64,
+}
+
+pub fn quarantine_root() -> PathBuf {
+    app_data_dir().join(QUARANTINE_DIR)
+}
+
+// rename כשאפשר, copy+delete כשעוברים בין מערכות קבצים
+pub fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            fs::copy(from, to)?;
+            fs::remove_file(from)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// ריצה פעילה שכותבת להסגר
+pub struct QuarantineRun {
+    pub run_id: String,
+    pub dir: PathBuf,
+    manifest: Mutex<BufWriter<File>>,
+}
+
+impl QuarantineRun {
+    pub fn create(run_id: &str) -> io::Result<Self> {
+        Self::create_in(&quarantine_root(), run_id)
+    }
+
+    // תיקייה שכבר קיימת (מזהה ישן ברזולוציה של שנייה, או מזהה שהגיע מבחוץ) לא משותפת -
+    // הריצה מקבלת "-2", "-3" וכו'. create_dir ולא create_dir_all, כדי שהבדיקה והיצירה יהיו פעולה אחת
+    pub fn create_in(root: &Path, run_id: &str) -> io::Result<Self> {
+        fs::create_dir_all(root)?;
+        let mut attempt = 1;
+        let (run_id, dir) = loop {
+            let id = if attempt == 1 { run_id.to_string() } else { format!("{}-{}", run_id, attempt) };
+            let dir = root.join(&id);
+            match fs::create_dir(&dir) {
+                Ok(()) => break (id, dir),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+                Err(e) => return Err(e),
+            }
+        };
+        fs::create_dir(dir.join("files"))?;
+        let file = OpenOptions::new().create(true).append(true).open(dir.join(MANIFEST_FILE))?;
+        Ok(QuarantineRun {
+            run_id,
+            dir,
+            manifest: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    // מעביר את הקובץ להסגר תוך שמירה על הנתיב היחסי שלו
+    pub fn stash(&self, path: &Path, size: u64) -> io::Result<()> {
+        let relative = path.strip_prefix("/").unwrap_or(path);
+        let stored = self.dir.join("files").join(relative);
+        move_file(path, &stored)?;
+
+        let entry = ManifestEntry { original: path.to_path_buf(), stored, size };
+        let line = serde_json::to_string(&entry).map_err(io::Error::other)?;
+        if let Ok(mut manifest) = self.manifest.lock() {
+            writeln!(manifest, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&self) {
+        if let Ok(mut manifest) = self.manifest.lock() {
+            let _ = manifest.flush();
+        }
+    }
+}
+
+impl Drop for QuarantineRun {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+// ריצה שכבר נמצאת בדיסק, לתצוגה ושחזור
+#[derive(Debug, Clone)]
+pub struct QuarantinedRun {
+    pub run_id: String,
+    pub dir: PathBuf,
+    pub created: Option<DateTime<Local>>,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct RestoreReport {
+    pub restored: usize,
+    pub conflicts: usize,
+    pub failed: usize,
+}
+
+pub fn read_manifest(dir: &Path) -> Vec<ManifestEntry> {
+    let file = match File::open(dir.join(MANIFEST_FILE)) {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+// החדשה ביותר ראשונה
+pub fn list_runs() -> Vec<QuarantinedRun> {
+    list_runs_in(&quarantine_root())
+}
+
+pub fn list_runs_in(root: &Path) -> Vec<QuarantinedRun> {
+    let mut runs: Vec<QuarantinedRun> = fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| {
+            let dir = e.path();
+            let entries = read_manifest(&dir);
+            let created = e.metadata().and_then(|m| m.modified()).ok().map(DateTime::<Local>::from);
+            QuarantinedRun {
+                run_id: e.file_name().to_string_lossy().into_owned(),
+                files: entries.len(),
+                bytes: entries.iter().map(|entry| entry.size).sum(),
+                created,
+                dir,
+            }
+        })
+        .collect();
+    runs.sort_by(|a, b| b.run_id.cmp(&a.run_id));
+    runs
+}
+
+// מחזיר הכל למקום. קבצים שבינתיים נוצר במקומם קובץ חדש לא נדרסים.
+pub fn restore_run(run: &QuarantinedRun) -> RestoreReport {
+    let mut report = RestoreReport::default();
+    for entry in read_manifest(&run.dir) {
+        if entry.original.exists() {
+            report.conflicts += 1;
+            continue;
+        }
+        match move_file(&entry.stored, &entry.original) {
+            Ok(()) => report.restored += 1,
+            Err(_) => report.failed += 1,
+        }
+    }
+    if report.conflicts == 0 && report.failed == 0 {
+        let _ = fs::remove_dir_all(&run.dir);
+    }
+    report
+}
+
+pub fn purge_run(run: &QuarantinedRun) -> io::Result<()> {
+    fs::remove_dir_all(&run.dir)
+}
+
+// מוחק ריצות ישנות מ-max_age_days, ואז את הישנות ביותר עד שהסך יורד מתחת ל-max_total_bytes
+pub fn purge_expired(max_age_days: i64, max_total_bytes: u64) -> usize {
+    expired_runs(list_runs(), max_age_days, max_total_bytes, Local::now()).iter().filter(|run| purge_run(run).is_ok()).count()
+}
+
+// אילו ריצות purge_expired היה מוחק - בלי לגעת בדיסק. runs ממוין מהחדש לישן, כמו ב-list_runs
+pub fn expired_runs(runs: Vec<QuarantinedRun>, max_age_days: i64, max_total_bytes: u64, now: DateTime<Local>) -> Vec<QuarantinedRun> {
+    let (mut expired, mut kept): (Vec<_>, Vec<_>) =
+        runs.into_iter().partition(|run| run.created.is_some_and(|c| (now - c).num_days() > max_age_days));
+    let mut total: u64 = kept.iter().map(|r| r.bytes).sum();
+    // מוחקים מהסוף - הישנות ביותר
+    while total > max_total_bytes {
+        let Some(run) = kept.pop() else { break };
+        total -= run.bytes;
+        expired.push(run);
+    }
+    expired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn run(id: &str, age_days: i64, bytes: u64, now: DateTime<Local>) -> QuarantinedRun {
+        QuarantinedRun { run_id: id.to_string(), dir: PathBuf::from(id), created: Some(now - Duration::days(age_days)), files: 1, bytes }
+    }
+
+    #[test]
+    fn old_runs_expire_then_the_oldest_until_under_the_size_limit() {
+        let now = Local::now();
+        let runs = vec![run("4", 0, 600, now), run("3", 1, 600, now), run("2", 2, 600, now), run("1", 30, 100, now)];
+        let ids: Vec<String> = expired_runs(runs, 7, 1000, now).into_iter().map(|r| r.run_id).collect();
+        assert_eq!(ids, ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn runs_with_the_same_id_get_their_own_folder() {
+        let root = tempfile::tempdir().unwrap();
+        let first = QuarantineRun::create_in(root.path(), "20261016-143012").unwrap();
+        let second = QuarantineRun::create_in(root.path(), "20261016-143012").unwrap();
+        let third = QuarantineRun::create_in(root.path(), "20261016-143012").unwrap();
+        assert_eq!(first.run_id, "20261016-143012");
+        assert_eq!(second.run_id, "20261016-143012-2");
+        assert_eq!(third.run_id, "20261016-143012-3");
+        assert_ne!(first.dir, second.dir);
+        assert!(second.dir.join("files").is_dir());
+    }
+}
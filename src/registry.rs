@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use which::which;
+
+use crate::config::expand_path;
+use crate::custom::CustomCleaner;
+use crate::engine::{RiskLevel, SystemCleaner, Target};
+
+// === רישום הפריטים: מקור אחד לסרגל הצד ולמנוע ===
+
+#[derive(Debug, Clone)]
+pub struct CleanerEntry {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub enabled_by_default: bool,
+    pub risk: RiskLevel,
+    // לדלג על קבצים שפתוחים בתהליך אחר (דפדפנים/Electron מחזיקים את ה-cache פתוח)
+    pub skip_in_use: bool,
+    // מה צריך להיות במערכת כדי שלפריט יהיה מה לעשות (APT על Fedora - אין)
+    pub requires: Vec<Requirement>,
+    // רץ פעם אחת לכל בית כשמנקים כ-root עבור כמה משתמשים (false = נתיבי מערכת בלבד)
+    pub per_user: bool,
+    // ריקון תיקייה משאיר את N הקבצים החדשים ביותר
+    pub keep_newest: Option<usize>,
+    // מציע "keep files newer than" משלו בהגדרות (SafetySettings::keep_recent_days), כבוי כברירת מחדל
+    pub keeps_recent: bool,
+    // תיקיות (globs) ב-~/.cache שלא נוגעים בהן בזמן session גרפי (SystemCleaner::session_protected)
+    pub session_protected: &'static [&'static str],
+    // חלקים עם סיכון משלהם, כתיבות סימון מוזחות מתחת לפריט במקום פריטים נפרדים בסרגל
+    pub sub_entries: Vec<SubEntry>,
+    // שולחן העבודה שהפריט שייך לו ("GNOME", "KDE") - מסומן מראש רק בו, ואשף ההפעלה הראשונה מציג אותו
+    pub desktop: Option<&'static str>,
+    // פריטים שצריכים לראות את המצב אחרי הפריט הזה: apt-get clean מרוקן את /var/cache/apt
+    // לפני ש-System Cache סופר אותה, אחרת היא נספרת פעמיים (או "0 קבצים", לפי הסדר)
+    pub runs_before: Vec<&'static str>,
+}
+
+// חלק של פריט: למשל cache בטוח מול מדיה של המשתמש שנבדקת קובץ-קובץ. ה-id מגיע למנוע
+// (SystemCleaner::sub_entries) כשהחלק מסומן
+#[derive(Debug, Clone)]
+pub struct SubEntry {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub risk: RiskLevel,
+    pub enabled_by_default: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Requirement {
+    // פקודה ב-PATH
+    Binary(&'static str),
+    // לפחות אחד מהנתיבים קיים (~ מורחב)
+    AnyPath(&'static [&'static str]),
+    // נשען על נתיבים/כלים של לינוקס (/var/log, xclip, apt...)
+    Unix,
+}
+
+impl Requirement {
+    fn is_met(&self) -> bool {
+        match self {
+            Requirement::Binary(name) => which(name).is_ok(),
+            Requirement::AnyPath(paths) => paths.iter().any(|p| expand_path(p).exists()),
+            Requirement::Unix => cfg!(unix),
+        }
+    }
+}
+
+// נבדק פעם אחת לכל פריט - הסרגל נבנה מחדש בכל החלפת שפה
+static APPLICABLE: OnceLock<Mutex<HashMap<String, bool>>> = OnceLock::new();
+
+impl CleanerEntry {
+    fn new(id: &str, name: &str, description: &str, category: &str, enabled_by_default: bool, risk: RiskLevel) -> Self {
+        CleanerEntry {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            category: category.to_string(),
+            enabled_by_default,
+            risk,
+            skip_in_use: false,
+            requires: Vec::new(),
+            per_user: true,
+            keep_newest: None,
+            keeps_recent: false,
+            session_protected: &[],
+            sub_entries: Vec::new(),
+            desktop: None,
+            runs_before: Vec::new(),
+        }
+    }
+
+    fn system_wide(mut self) -> Self {
+        self.per_user = false;
+        self
+    }
+
+    fn needs_binary(mut self, name: &'static str) -> Self {
+        self.requires.push(Requirement::Binary(name));
+        self
+    }
+
+    fn needs_path(mut self, paths: &'static [&'static str]) -> Self {
+        self.requires.push(Requirement::AnyPath(paths));
+        self
+    }
+
+    fn unix_only(mut self) -> Self {
+        self.requires.push(Requirement::Unix);
+        self
+    }
+
+    pub fn is_applicable(&self) -> bool {
+        if self.requires.is_empty() {
+            return true;
+        }
+        let cache = APPLICABLE.get_or_init(Default::default);
+        if let Some(applicable) = cache.lock().unwrap().get(&self.id) {
+            return *applicable;
+        }
+        let applicable = self.requires.iter().all(Requirement::is_met);
+        cache.lock().unwrap().insert(self.id.clone(), applicable);
+        applicable
+    }
+
+    fn skip_in_use(mut self) -> Self {
+        self.skip_in_use = true;
+        self
+    }
+
+    fn keep_newest(mut self, count: usize) -> Self {
+        self.keep_newest = Some(count);
+        self
+    }
+
+    fn keeps_recent(mut self) -> Self {
+        self.keeps_recent = true;
+        self
+    }
+
+    fn protects_session(mut self, names: &'static [&'static str]) -> Self {
+        self.session_protected = names;
+        self
+    }
+
+    fn for_desktop(mut self, name: &'static str) -> Self {
+        self.desktop = Some(name);
+        self.enabled_by_default = desktop_is(name);
+        self
+    }
+
+    fn sub_entry(mut self, id: &'static str, name: &'static str, risk: RiskLevel, enabled_by_default: bool) -> Self {
+        self.sub_entries.push(SubEntry { id, name, risk, enabled_by_default });
+        self
+    }
+
+    // מה שרץ כשאף אחד לא בחר (CLI, D-Bus)
+    pub fn default_sub_entries(&self) -> Vec<String> {
+        self.sub_entries.iter().filter(|s| s.enabled_by_default).map(|s| s.id.to_string()).collect()
+    }
+
+    fn runs_before(mut self, ids: &[&'static str]) -> Self {
+        self.runs_before.extend_from_slice(ids);
+        self
+    }
+
+    // הנתיבים כבר מורחבים (~ של מי שהריץ) - לא משכפלים אותם לבתים אחרים
+    fn from_custom(custom: &CustomCleaner) -> Self {
+        CleanerEntry::new(&custom.id, &custom.name, &custom.description, &custom.category, false, custom.risk).system_wide()
+    }
+
+    // הנתיבים והפקודות בפועל, כפי שהמנוע יריץ אותם
+    pub fn targets(&self, cleaner: &SystemCleaner) -> Vec<Target> {
+        cleaner.targets(&self.id)
+    }
+}
+
+pub fn builtin_cleaners() -> Vec<CleanerEntry> {
+    use RiskLevel::*;
+    let mut entries = vec![
+        // System
+        CleanerEntry::new("tmp", "Temporary Files", "/tmp, /var/tmp cleaning", "system", true, Safe).system_wide(),
+        CleanerEntry::new("trash", "Trash", "Empty recycle bin", "system", true, Moderate),
+        CleanerEntry::new("logs", "System Logs", "Old log files & rotated logs", "system", false, Moderate).unix_only().keep_newest(2),
+        // מה ש-GNOME/KDE מחזיקים פתוח: מחיקה באמצע session = תקלות תצוגה או ניתוק
+        CleanerEntry::new("var_cache", "System Cache", "General system cache", "system", true, Safe).unix_only()
+            .protects_session(&["gdm", "keyring-*", "dconf", "gnome-shell", "mutter", "ibus", "kwin", "plasmashell", "plasma*", "ksycoca*", "icon-cache.kcache"]),
+        CleanerEntry::new("thumbnails", "Thumbnails", "Cached image thumbnails", "system", true, Safe),
+        CleanerEntry::new("clipboard", "Clipboard", "Clear current clipboard", "system", false, Privacy).unix_only().needs_binary("xclip").system_wide(),
+        CleanerEntry::new("empty_dirs", "Empty Folders", "Folders with nothing inside, under your home", "system", false, Moderate),
+        CleanerEntry::new("broken_symlinks", "Broken Symlinks", "Links pointing to files that no longer exist", "system", false, Moderate),
+        CleanerEntry::new("cachedir_sweep", "Tagged Cache Folders", "Any folder under your home marked with CACHEDIR.TAG (cargo, pip, ccache...)", "system", false, Moderate),
+        CleanerEntry::new("broken_desktop", "Broken Shortcuts", "Invalid .desktop files", "system", false, Moderate).unix_only(),
+        // ההגדרות עצמן לא כאן - רק מה שהתוכנה צוברת עם הזמן
+        CleanerEntry::new("app_data", "Super Cleaner data", "Old history, deletion lists and expired quarantine", "system", false, Safe),
+        CleanerEntry::new("stale_autostart", "Stale Autostart Entries", "Autostart entries and user systemd units of programs that are gone", "system", false, Moderate)
+            .unix_only()
+            .needs_path(&["~/.config/autostart", "~/.config/systemd/user"]),
+        CleanerEntry::new("fonts", "Font & Icon Caches", "Stale fontconfig, icon theme and MIME caches, rebuilt afterwards", "system", false, Safe).unix_only(),
+        // אינדקס שנמחק נבנה מחדש ברקע - מסומן מראש רק בשולחן העבודה שלו
+        CleanerEntry::new("tracker", "GNOME Tracker Index", "Desktop search index (rebuilt automatically)", "system", false, Moderate)
+            .for_desktop("GNOME").unix_only().needs_path(&["~/.cache/tracker3", "~/.local/share/tracker"]),
+        CleanerEntry::new("baloo", "KDE Baloo Index", "Desktop search index (rebuilt automatically)", "system", false, Moderate)
+            .for_desktop("KDE").unix_only().needs_path(&["~/.local/share/baloo"]),
+        CleanerEntry::new("wine", "Wine / Proton", "Temp and INetCache folders inside Wine and Proton prefixes", "system", false, Safe)
+            .unix_only().needs_path(&["~/.wine", "~/Games", "~/.steam/steam/steamapps/compatdata", "~/.local/share/Steam/steamapps/compatdata"]),
+        // Browsers
+        CleanerEntry::new("chrome_cache", "Google Chrome Cache", "Cache files", "browsers", true, Safe).skip_in_use().keeps_recent().needs_path(&["~/.config/google-chrome", "~/AppData/Local/Google/Chrome", "~/Library/Caches/Google/Chrome"]),
+        CleanerEntry::new("firefox_cache", "Firefox Cache", "Cache files", "browsers", true, Safe).skip_in_use().keeps_recent().needs_path(&["~/.mozilla/firefox", "~/AppData/Local/Mozilla/Firefox", "~/Library/Caches/Firefox"]),
+        CleanerEntry::new("site_storage", "Site Storage (Service Workers)", "Offline data of websites and web apps in Chromium browsers, per site", "browsers", false, Privacy)
+            .unix_only()
+            .needs_path(&["~/.config/google-chrome", "~/.config/chromium", "~/.config/BraveSoftware/Brave-Browser", "~/.config/microsoft-edge", "~/.config/vivaldi"]),
+        // נפרד מה-cache: מי שרוצה לשמור את גיבויי ה-session מנקה רק את המטמון
+        CleanerEntry::new("firefox_leftovers", "Firefox Leftovers", "Crash reports, telemetry and old session backups", "browsers", true, Privacy)
+            .needs_path(&["~/.mozilla/firefox", "~/snap/firefox", "~/.var/app/org.mozilla.firefox", "~/AppData/Roaming/Mozilla/Firefox", "~/Library/Application Support/Firefox"]),
+        CleanerEntry::new("brave_cache", "Brave Cache", "Cache files", "browsers", true, Safe).skip_in_use().keeps_recent().needs_path(&["~/.config/BraveSoftware", "~/AppData/Local/BraveSoftware", "~/Library/Caches/BraveSoftware"]),
+        // Developer
+        CleanerEntry::new("pycache", "Python Cache", "*.pyc, __pycache__", "dev", true, Safe),
+        CleanerEntry::new("pip_cache", "pip HTTP Cache", "Downloaded wheels, keeping the 50 newest", "dev", false, Safe)
+            .keep_newest(50)
+            .needs_path(&["~/.cache/pip", "~/Library/Caches/pip"]),
+        CleanerEntry::new("vim", "Vim Swap", "*.swp files", "dev", true, Safe),
+        CleanerEntry::new("android", "Android SDK", "Android caches, build cache, Gradle daemon and adb logs", "dev", false, Safe)
+            .needs_path(&["~/.android"]),
+        CleanerEntry::new("devops", "DevOps Tool Caches", "Helm, kubectl, Terraform, pre-commit caches and expired AWS tokens", "dev", false, Safe)
+            .needs_path(&["~/.cache/helm", "~/.kube", "~/.aws", "~/.terraform.d/plugin-cache", "~/.cache/pre-commit"]),
+        CleanerEntry::new("lang_caches", "Other Language Caches", "RubyGems, Bundler, Composer and Haskell Stack caches", "dev", false, Safe)
+            .needs_path(&["~/.gem", "~/.bundle/cache", "~/.cache/composer", "~/.stack"]),
+        CleanerEntry::new("backup_files", "Backup Files", "*~, *.bak files", "dev", true, Safe),
+        CleanerEntry::new("doc_temp", "Document Temp Files", "LibreOffice autorecovery, LaTeX build files next to .tex sources, editor autosaves", "dev", false, Safe),
+        CleanerEntry::new("xcode", "Xcode DerivedData", "Build products and indexes, rebuilt on the next build", "dev", false, Safe)
+            .needs_path(&["~/Library/Developer/Xcode/DerivedData"]),
+        // Privacy
+        // ה-cache של כלי הצילום נמחק כרגיל; הקלטות וצילומים שהמשתמש רואה - רק ישנים, קובץ-קובץ, וכבוי כברירת מחדל
+        CleanerEntry::new("screen_leftovers", "Screenshots & Screencasts", "Screenshot tool caches, and optionally old screencasts and screenshots", "privacy", false, Safe)
+            .unix_only()
+            .needs_path(&["~/.cache/gnome-screenshot", "~/.cache/spectacle", "~/Videos/Screencasts", "~/Pictures/Screenshots"])
+            .sub_entry("cache", "Screenshot tool caches", Safe, true)
+            .sub_entry("media", "Old screencasts & screenshots (review each file)", Dangerous, false),
+        CleanerEntry::new("wipe_free_space", "Wipe Free Space", "Overwrite free disk space so deleted files can't be recovered", "privacy", false, Dangerous).unix_only().system_wide(),
+        CleanerEntry::new("recent_docs", "Recent Documents", "Clear recently used files list", "privacy", true, Privacy).unix_only(),
+        CleanerEntry::new("fm_leftovers", "File Manager Leftovers", "Bookmarks and Dolphin view settings of deleted folders, gvfs metadata logs", "privacy", false, Privacy)
+            .unix_only().needs_path(&["~/.config/gtk-3.0/bookmarks", "~/.local/share/gvfs-metadata", "~/.local/share/dolphin/view_properties"]),
+        // Package Managers - פקודות חיצוניות, רצות לפני הפריטים שסופרים את אותן תיקיות
+        CleanerEntry::new("apt", "APT (Debian/Ubuntu)", "Autoremove & Clean", "packages", true, Moderate).unix_only().needs_binary("apt-get").system_wide().runs_before(&["var_cache"]),
+        CleanerEntry::new("dnf", "DNF (Fedora)", "Autoremove & Clean", "packages", true, Moderate).unix_only().needs_binary("dnf").system_wide().runs_before(&["var_cache"]),
+        CleanerEntry::new("flatpak", "Flatpak", "Unused runtimes & cache", "packages", true, Moderate).unix_only().needs_binary("flatpak").runs_before(&["var_cache"]),
+        CleanerEntry::new("brew", "Homebrew", "Old versions & download cache (brew cleanup)", "packages", true, Moderate).unix_only().needs_binary("brew").system_wide().runs_before(&["var_cache"]),
+        // Analysis
+        // מוחק נתוני משתמש - אף פעם לא מסומן מראש
+        CleanerEntry::new("large_downloads", "Large Old Downloads", "Big files in Downloads untouched for months", "analysis", false, Dangerous),
+        CleanerEntry::new("duplicates", "Duplicate Files", "Identical copies in Downloads, Pictures, Documents", "analysis", false, Moderate),
+    ];
+    // ב-Windows פריטים של לינוקס לא מופיעים בכלל, גם לא כ"לא מותקן"
+    entries.retain(|e| cfg!(unix) || !e.requires.iter().any(|r| matches!(r, Requirement::Unix)));
+    entries
+}
+
+// המובנים ואחריהם אלה מ-custom-cleaners.toml
+// XDG_CURRENT_DESKTOP יכול להיות רשימה, למשל "ubuntu:GNOME"
+fn desktop_is(name: &str) -> bool {
+    std::env::var("XDG_CURRENT_DESKTOP")
+        .map(|desktops| desktops.split(':').any(|d| d.eq_ignore_ascii_case(name)))
+        .unwrap_or(false)
+}
+
+// שולחנות העבודה שיש להם פריטים משלהם (for_desktop)
+pub fn detected_desktop() -> Option<&'static str> {
+    ["GNOME", "KDE"].into_iter().find(|name| desktop_is(name))
+}
+
+pub fn all_cleaners(custom: &[CustomCleaner]) -> Vec<CleanerEntry> {
+    let mut entries = builtin_cleaners();
+    entries.extend(custom.iter().map(CleanerEntry::from_custom));
+    entries
+}
+
+// סדר הריצה (אינדקסים ל-ids): כל פריט אחרי מי שהכריז עליו runs_before, וחוץ מזה הסדר המקורי -
+// בכל צעד הראשון ברשימה שאין מי שמחכה לרוץ לפניו. מעגל לא נתקע: השאר רצים בסדר המקורי
+pub fn run_order(entries: &[CleanerEntry], ids: &[&str]) -> Vec<usize> {
+    let runs_before = |id: &str| entries.iter().find(|e| e.id == id).map_or(&[][..], |e| e.runs_before.as_slice());
+    let mut done = vec![false; ids.len()];
+    let mut order = Vec::with_capacity(ids.len());
+    while order.len() < ids.len() {
+        let pending = || (0..ids.len()).filter(|&i| !done[i]);
+        let ready = pending().find(|&i| !pending().any(|j| j != i && runs_before(ids[j]).contains(&ids[i])));
+        let Some(next) = ready.or_else(|| pending().next()) else { break };
+        done[next] = true;
+        order.push(next);
+    }
+    order
+}
+
+pub fn sort_for_run<T>(entries: &[CleanerEntry], items: Vec<T>, id_of: impl Fn(&T) -> &str) -> Vec<T> {
+    let order = run_order(entries, &items.iter().map(&id_of).collect::<Vec<_>>());
+    let mut slots: Vec<Option<T>> = items.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, runs_before: &[&'static str]) -> CleanerEntry {
+        CleanerEntry::new(id, id, "", "system", true, RiskLevel::Safe).runs_before(runs_before)
+    }
+
+    #[test]
+    fn declared_edges_win_and_the_rest_keeps_its_order() {
+        let entries = [entry("var_cache", &[]), entry("tmp", &[]), entry("apt", &["var_cache"]), entry("thumbnails", &[]), entry("dnf", &["var_cache"])];
+        let ids = ["var_cache", "tmp", "apt", "thumbnails", "dnf"];
+        let ordered: Vec<&str> = run_order(&entries, &ids).into_iter().map(|i| ids[i]).collect();
+        assert_eq!(ordered, ["tmp", "apt", "thumbnails", "dnf", "var_cache"]);
+        // בלי קשתות בין הנבחרים - הסדר לא זז
+        assert_eq!(run_order(&entries, &["thumbnails", "tmp", "var_cache"]), [0, 1, 2]);
+    }
+
+    #[test]
+    fn a_cycle_still_runs_everything_once() {
+        let entries = [entry("a", &["b"]), entry("b", &["a"]), entry("c", &[])];
+        let mut order = run_order(&entries, &["a", "b", "c"]);
+        assert_eq!(order.remove(0), 2);
+        order.sort();
+        assert_eq!(order, [0, 1]);
+    }
+
+    #[test]
+    fn package_managers_run_before_system_cache() {
+        let entries = builtin_cleaners();
+        let items = vec!["var_cache".to_string(), "apt".to_string(), "trash".to_string()];
+        let sorted = sort_for_run(&entries, items, |id| id.as_str());
+        if entries.iter().any(|e| e.id == "apt") {
+            assert_eq!(sorted, ["apt", "var_cache", "trash"]);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Local};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::SystemTime;
+
+// === מזהה ריצה: אותה מחרוזת בהסגר, ברשימת המחיקות, בהיסטוריה, ב-D-Bus, בהתראה ובלוג ===
+// "20261016-143012-7f3a9c": זמן ההתחלה (ממוין כמו מחרוזת) + סיומת אקראית, כדי ששתי ריצות
+// באותה שנייה (GUI ו-cron, או שתי בקשות D-Bus) לא יכתבו לאותה תיקיית הסגר
+
+const TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+pub fn new_run_id() -> String {
+    new_run_id_at(Local::now())
+}
+
+pub fn new_run_id_at(started: DateTime<Local>) -> String {
+    // RandomState מקבל מפתח אקראי מהמערכת בכל יצירה - מספיק לסיומת, בלי תלות ב-rand
+    let salt = RandomState::new().hash_one((SystemTime::now(), std::process::id()));
+    format!("{}-{:06x}", started.format(TIME_FORMAT), salt & 0xff_ffff)
+}
+
+// שורות היסטוריה מלפני שהיה מזהה: נגזר מזמן הסיום, באותו פורמט שהגרסאות הישנות נתנו לתיקיות ההסגר
+pub fn legacy_run_id(finished: DateTime<Local>) -> String {
+    finished.format(TIME_FORMAT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn ids_sort_by_start_time_and_do_not_collide() {
+        let earlier = Local.with_ymd_and_hms(2026, 10, 16, 14, 30, 12).unwrap();
+        let later = Local.with_ymd_and_hms(2026, 10, 16, 14, 30, 13).unwrap();
+        let first = new_run_id_at(earlier);
+        assert!(first.starts_with("20261016-143012-"), "{}", first);
+        assert_eq!(first.len(), "20261016-143012-".len() + 6);
+        assert_ne!(first, new_run_id_at(earlier));
+        assert!(first < new_run_id_at(later));
+        assert_eq!(legacy_run_id(earlier), "20261016-143012");
+    }
+}
@@ -0,0 +1,168 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+// === מצב הריצה של ה-GUI ===
+// כל ריצה מקבלת מזהה; ה-worker מדווח סיום עם המזהה שלו, וסיום של ריצה ישנה (למשל אחרי Abort)
+// לא נוגע בריצה החדשה. Idle -> Running -> Finished -> Idle, או Running -> Aborting -> Idle.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RunState {
+    #[default]
+    Idle,
+    Running { run_id: u64 },
+    // המשתמש ביטל; ה-worker עוד מסיים את הפריט הנוכחי ומשחרר את הנעילה
+    Aborting { run_id: u64 },
+    // ה-worker סיים; הפריים הבא אוסף את התוצאה וחוזר ל-Idle
+    Finished { run_id: u64 },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunTracker {
+    state: Arc<Mutex<RunState>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl RunTracker {
+    pub fn state(&self) -> RunState {
+        *self.state.lock().unwrap()
+    }
+
+    // ריצה חדשה מחליפה כל מצב קודם - גם Aborting של ריצה שה-worker שלה עוד לא יצא
+    pub fn start(&self) -> u64 {
+        let run_id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        *self.state.lock().unwrap() = RunState::Running { run_id };
+        run_id
+    }
+
+    pub fn abort(&self) {
+        let mut state = self.state.lock().unwrap();
+        if let RunState::Running { run_id } = *state {
+            *state = RunState::Aborting { run_id };
+        }
+    }
+
+    // ה-worker סיים (או שהריצה נכשלה לפני שהתחילה). מזהה שלא תואם = ריצה ישנה, מתעלמים
+    pub fn finish(&self, finished: u64) {
+        let mut state = self.state.lock().unwrap();
+        *state = match *state {
+            RunState::Running { run_id } if run_id == finished => RunState::Finished { run_id },
+            RunState::Aborting { run_id } if run_id == finished => RunState::Idle,
+            other => other,
+        };
+    }
+
+    // ל-worker: הריצה מסתיימת גם אם ה-thread מת באמצע (panic), לא רק ביציאה רגילה
+    pub fn finish_on_drop(&self, run_id: u64) -> FinishGuard {
+        FinishGuard { tracker: self.clone(), run_id }
+    }
+
+    // ריצה שלא יצאה לדרך (נעילה, הסגר) - חוזרים ל-Idle בלי "הושלם"
+    pub fn cancel_start(&self, cancelled: u64) {
+        let mut state = self.state.lock().unwrap();
+        if matches!(*state, RunState::Running { run_id } if run_id == cancelled) {
+            *state = RunState::Idle;
+        }
+    }
+
+    // פעם אחת לכל ריצה שהסתיימה
+    pub fn take_finished(&self) -> Option<u64> {
+        let mut state = self.state.lock().unwrap();
+        match *state {
+            RunState::Finished { run_id } => {
+                *state = RunState::Idle;
+                Some(run_id)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.state(), RunState::Running { .. })
+    }
+
+    // גם בזמן Aborting אי אפשר להתחיל ריצה - ה-worker הקודם עוד מחזיק את נעילת המופע
+    pub fn is_busy(&self) -> bool {
+        matches!(self.state(), RunState::Running { .. } | RunState::Aborting { .. })
+    }
+}
+
+pub struct FinishGuard {
+    tracker: RunTracker,
+    run_id: u64,
+}
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        self.tracker.finish(self.run_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finish_then_collect_once() {
+        let tracker = RunTracker::default();
+        let run = tracker.start();
+        assert!(tracker.is_running());
+        tracker.finish(run);
+        assert_eq!(tracker.state(), RunState::Finished { run_id: run });
+        assert_eq!(tracker.take_finished(), Some(run));
+        assert_eq!(tracker.take_finished(), None);
+        assert_eq!(tracker.state(), RunState::Idle);
+    }
+
+    #[test]
+    fn abort_then_worker_exit_returns_to_idle() {
+        let tracker = RunTracker::default();
+        let run = tracker.start();
+        tracker.abort();
+        assert_eq!(tracker.state(), RunState::Aborting { run_id: run });
+        assert!(!tracker.is_running());
+        assert!(tracker.is_busy());
+        tracker.finish(run);
+        assert_eq!(tracker.state(), RunState::Idle);
+        assert_eq!(tracker.take_finished(), None);
+    }
+
+    #[test]
+    fn stale_finish_after_abort_and_restart_is_ignored() {
+        let tracker = RunTracker::default();
+        let first = tracker.start();
+        tracker.abort();
+        let second = tracker.start();
+        assert_ne!(first, second);
+
+        // ה-worker של הריצה שבוטלה יוצא רק עכשיו - הריצה החדשה לא "מסתיימת" בגללו
+        tracker.finish(first);
+        assert_eq!(tracker.state(), RunState::Running { run_id: second });
+        assert_eq!(tracker.take_finished(), None);
+
+        tracker.finish(second);
+        assert_eq!(tracker.take_finished(), Some(second));
+    }
+
+    #[test]
+    fn panicking_worker_still_finishes() {
+        let tracker = RunTracker::default();
+        let run = tracker.start();
+        let worker = tracker.clone();
+        let joined = std::thread::spawn(move || {
+            let _finished = worker.finish_on_drop(run);
+            panic!("cleaner bug");
+        }).join();
+        assert!(joined.is_err());
+        assert_eq!(tracker.take_finished(), Some(run));
+    }
+
+    #[test]
+    fn cancelled_start_does_not_complete() {
+        let tracker = RunTracker::default();
+        let run = tracker.start();
+        tracker.cancel_start(run);
+        assert_eq!(tracker.state(), RunState::Idle);
+        tracker.finish(run);
+        assert_eq!(tracker.take_finished(), None);
+    }
+}
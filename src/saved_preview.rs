@@ -0,0 +1,101 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::engine::app_data_dir;
+use crate::persist;
+use crate::preview::PreviewEntry;
+
+// === התצוגה המקדימה האחרונה, להפעלה הבאה: "תצוגה מקדימה מלפני 14 שעות - לטעון או לסרוק?" ===
+// מה שנטען הוא רק הערכה. הניקוי תמיד סורק מחדש לפני שהוא מוחק (current_selection במנוע)
+
+const SAVED_PREVIEW_FILE: &str = "last-preview.json";
+
+// מעבר לזה נשמרים רק הסכומים. רשימות קטנות קודם, כדי שכמה שיותר פריטים ישמרו את שלהם
+pub const MAX_SAVED_ENTRIES: usize = 20_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedPreview {
+    pub saved_at: DateTime<Local>,
+    // בייטים לפריט, כמו item_sizes
+    pub sizes: HashMap<String, u64>,
+    #[serde(default)]
+    pub entries: HashMap<String, Vec<PreviewEntry>>,
+    // פריטים שהרשימה שלהם לא נשמרה בגלל הגודל
+    #[serde(default)]
+    pub trimmed: Vec<String>,
+}
+
+impl SavedPreview {
+    pub fn new(saved_at: DateTime<Local>, sizes: HashMap<String, u64>, entries: HashMap<String, Vec<PreviewEntry>>) -> Self {
+        let mut lists: Vec<(String, Vec<PreviewEntry>)> = entries.into_iter().filter(|(_, e)| !e.is_empty()).collect();
+        lists.sort_by(|(a_id, a), (b_id, b)| a.len().cmp(&b.len()).then_with(|| a_id.cmp(b_id)));
+        let mut kept = HashMap::new();
+        let mut trimmed = Vec::new();
+        let mut total = 0;
+        for (id, list) in lists {
+            if total + list.len() > MAX_SAVED_ENTRIES {
+                trimmed.push(id);
+                continue;
+            }
+            total += list.len();
+            kept.insert(id, list);
+        }
+        trimmed.sort();
+        SavedPreview { saved_at, sizes, entries: kept, trimmed }
+    }
+}
+
+pub fn saved_preview_path() -> PathBuf {
+    app_data_dir().join(SAVED_PREVIEW_FILE)
+}
+
+pub fn save(preview: &SavedPreview) -> io::Result<()> {
+    let path = saved_preview_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string(preview).map_err(io::Error::other)?;
+    persist::write_atomically(&path, json.as_bytes())
+}
+
+// קובץ חסר או פגום = אין מה להציע
+pub fn load() -> Option<SavedPreview> {
+    fs::read_to_string(saved_preview_path()).ok().and_then(|json| serde_json::from_str(&json).ok())
+}
+
+// אחרי ניקוי אמיתי התצוגה השמורה כבר לא מתארת את הדיסק
+pub fn discard() {
+    let _ = fs::remove_file(saved_preview_path());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(prefix: &str, count: usize) -> Vec<PreviewEntry> {
+        (0..count).map(|n| PreviewEntry::new(PathBuf::from(format!("/{}/{}", prefix, n)), 10, None)).collect()
+    }
+
+    #[test]
+    fn huge_lists_keep_only_their_totals() {
+        let sizes = HashMap::from([("trash".to_string(), 40), ("large_downloads".to_string(), 9), ("empty_dirs".to_string(), 0)]);
+        let entries = HashMap::from([
+            ("trash".to_string(), list("trash", 4)),
+            ("large_downloads".to_string(), list("big", MAX_SAVED_ENTRIES)),
+            ("empty_dirs".to_string(), Vec::new()),
+        ]);
+        let saved = SavedPreview::new(Local::now(), sizes.clone(), entries);
+        assert_eq!(saved.entries.keys().collect::<Vec<_>>(), vec!["trash"]);
+        assert_eq!(saved.trimmed, vec!["large_downloads".to_string()]);
+        assert_eq!(saved.sizes, sizes);
+
+        let json = serde_json::to_string(&saved).unwrap();
+        let loaded: SavedPreview = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.entries["trash"].len(), 4);
+        assert_eq!(loaded.saved_at, saved.saved_at);
+    }
+}
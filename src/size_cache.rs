@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::engine::app_data_dir;
+use crate::persist;
+
+// === מטמון גדלים לתצוגה מקדימה ===
+// אחרי סריקה של תבנית (*.pyc וכו') נשמר לכל תיקייה ברמה העליונה מה נמצא בה, יחד עם ה-mtime שלה
+// ומספר הילדים הישירים. בתצוגה המקדימה הבאה תיקייה שלא השתנתה לא נסרקת שוב.
+// רק לתצוגה מקדימה - ניקוי תמיד עובר על הקבצים עצמם.
+
+const CACHE_FILE: &str = "size-cache.json";
+
+pub fn cache_path() -> PathBuf {
+    app_data_dir().join(CACHE_FILE)
+}
+
+// mtime של תיקייה משתנה כשנוסף או נמחק ילד ישיר; מספר הילדים תופס גם מערכות קבצים עם mtime גס
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirFingerprint {
+    pub mtime_ns: u128,
+    pub children: u64,
+}
+
+impl DirFingerprint {
+    pub fn of(dir: &Path) -> Option<Self> {
+        let mtime = fs::metadata(dir).ok()?.modified().ok()?;
+        let mtime_ns = mtime.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+        let children = fs::read_dir(dir).ok()?.count() as u64;
+        Some(DirFingerprint { mtime_ns, children })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedSize {
+    pub fingerprint: DirFingerprint,
+    pub files: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    // "<item>|<pattern>|<dir>"
+    entries: HashMap<String, CachedSize>,
+    saved: Option<SystemTime>,
+}
+
+pub struct SizeCache {
+    path: PathBuf,
+    // force_rescan: לא קוראים מהמטמון, רק כותבים אליו את התוצאות החדשות
+    force_rescan: bool,
+    state: Mutex<(CacheFile, bool)>,
+}
+
+impl SizeCache {
+    // קובץ חסר או שבור = מטמון ריק
+    pub fn load(force_rescan: bool) -> Self {
+        Self::load_from(cache_path(), force_rescan)
+    }
+
+    pub fn load_from(path: PathBuf, force_rescan: bool) -> Self {
+        let file = fs::read_to_string(&path).ok().and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default();
+        SizeCache { path, force_rescan, state: Mutex::new((file, false)) }
+    }
+
+    fn key(item: &str, pattern: &str, dir: &Path) -> String {
+        format!("{}|{}|{}", item, pattern, dir.display())
+    }
+
+    // רק אם התיקייה לא השתנתה מאז שנשמרה
+    pub fn get(&self, item: &str, pattern: &str, dir: &Path, fingerprint: DirFingerprint) -> Option<CachedSize> {
+        if self.force_rescan {
+            return None;
+        }
+        let state = self.state.lock().unwrap();
+        state.0.entries.get(&Self::key(item, pattern, dir)).copied().filter(|c| c.fingerprint == fingerprint)
+    }
+
+    pub fn put(&self, item: &str, pattern: &str, dir: &Path, size: CachedSize) {
+        let mut state = self.state.lock().unwrap();
+        state.0.entries.insert(Self::key(item, pattern, dir), size);
+        state.1 = true;
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.1 {
+            return Ok(());
+        }
+        // תיקיות שנמחקו מאז לא נשארות במטמון לנצח
+        state.0.entries.retain(|key, _| key.splitn(3, '|').nth(2).is_some_and(|dir| Path::new(dir).exists()));
+        state.0.saved = Some(SystemTime::now());
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string(&state.0).map_err(io::Error::other)?;
+        persist::write_atomically(&self.path, json.as_bytes())?;
+        state.1 = false;
+        Ok(())
+    }
+}
+
+// כמו DeletionManifest: נשמר כשהריצה מסתיימת (או נעצרת)
+impl Drop for SizeCache {
+    fn drop(&mut self) {
+        let _ = self.save();
+    }
+}
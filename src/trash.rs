@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDateTime;
+
+use crate::file_manager::percent_decode;
+
+// === מה יש בפח: המיקום המקורי ותאריך המחיקה מ-info/<name>.trashinfo ===
+// ב-files/ השמות לפעמים משוכפלים ("report.2.pdf") ולא אומרים מאיפה הקובץ הגיע - בלי זה
+// התצוגה המקדימה של הפח היא רשימת שמות אטומה.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashInfo {
+    pub original: PathBuf,
+    pub deleted: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrashedItem {
+    // files/<name> - קובץ או תיקייה שלמה
+    pub path: PathBuf,
+    // None = אין .trashinfo, או שהוא לא קריא
+    pub info: Option<TrashInfo>,
+}
+
+// לפי המפרט: Path= מקודד באחוזים, ויחסי ל-topdir בפח של דיסק (<mount>/.Trash-<uid>)
+pub fn parse_trashinfo(contents: &str, topdir: &Path) -> Option<TrashInfo> {
+    let mut lines = contents.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#'));
+    if lines.next()? != "[Trash Info]" {
+        return None;
+    }
+    let mut original = None;
+    let mut deleted = None;
+    for line in lines {
+        if line.starts_with('[') {
+            break;
+        }
+        match line.split_once('=') {
+            Some(("Path", value)) if original.is_none() => {
+                let path = percent_decode(value.trim());
+                original = Some(if path.is_absolute() { path } else { topdir.join(path) });
+            }
+            Some(("DeletionDate", value)) if deleted.is_none() => {
+                deleted = NaiveDateTime::parse_from_str(value.trim(), "%Y-%m-%dT%H:%M:%S").ok();
+            }
+            _ => {}
+        }
+    }
+    Some(TrashInfo { original: original?, deleted })
+}
+
+// <trash>/files/<name> -> <trash>/info/<name>.trashinfo
+pub fn info_path_for(item: &Path) -> Option<PathBuf> {
+    let trash_dir = item.parent()?.parent()?;
+    let mut info_name = item.file_name()?.to_os_string();
+    info_name.push(".trashinfo");
+    Some(trash_dir.join("info").join(info_name))
+}
+
+// כל מה שב-files/, לפי שם
+pub fn list(trash_dir: &Path, topdir: &Path) -> Vec<TrashedItem> {
+    let Ok(entries) = fs::read_dir(trash_dir.join("files")) else { return Vec::new() };
+    let mut items: Vec<TrashedItem> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let info = fs::read_to_string(info_path_for(&path)?).ok().and_then(|contents| parse_trashinfo(&contents, topdir));
+            Some(TrashedItem { path, info })
+        })
+        .collect();
+    items.sort_by(|a, b| a.path.cmp(&b.path));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_escaped_path_and_date() {
+        let info = parse_trashinfo("[Trash Info]\nPath=/home/u/My%20Docs/r%C3%A9sum%C3%A9.pdf\nDeletionDate=2024-11-02T09:15:00\n", Path::new("/"))
+            .unwrap();
+        assert_eq!(info.original, PathBuf::from("/home/u/My Docs/résumé.pdf"));
+        assert_eq!(info.deleted.unwrap().format("%Y-%m-%d").to_string(), "2024-11-02");
+    }
+
+    #[test]
+    fn relative_paths_belong_to_the_volume() {
+        let info = parse_trashinfo("[Trash Info]\nPath=photos/a.jpg\n", Path::new("/media/usb")).unwrap();
+        assert_eq!(info.original, PathBuf::from("/media/usb/photos/a.jpg"));
+        assert_eq!(info.deleted, None);
+    }
+
+    #[test]
+    fn rejects_files_without_the_header_or_path() {
+        assert_eq!(parse_trashinfo("Path=/x\n", Path::new("/")), None);
+        assert_eq!(parse_trashinfo("[Trash Info]\nDeletionDate=2024-01-01T00:00:00\n", Path::new("/")), None);
+    }
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+// === תצוגת גדלים: IEC (1.4 GiB, חזקות של 1024), SI (1.5 GB, חזקות של 1000) או בייטים ===
+// כמו השפה - מצב אחד לכל התהליך, נקבע מההגדרות בהפעלה ובכל שינוי שלהן.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ByteUnits {
+    // מה ש-df -h ו-du מציגים (בלי ה-i)
+    #[default]
+    Iec,
+    // מה ש-GNOME Disks ו-Nautilus מציגים
+    Si,
+    Bytes,
+}
+
+impl ByteUnits {
+    pub const ALL: [ByteUnits; 3] = [ByteUnits::Iec, ByteUnits::Si, ByteUnits::Bytes];
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => ByteUnits::Si,
+            2 => ByteUnits::Bytes,
+            _ => ByteUnits::Iec,
+        }
+    }
+}
+
+const IEC_UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+const SI_UNITS: &[&str] = &["B", "kB", "MB", "GB", "TB", "PB"];
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_byte_units(units: ByteUnits) {
+    CURRENT.store(units as u8, Ordering::Relaxed);
+}
+
+pub fn current_byte_units() -> ByteUnits {
+    ByteUnits::from_u8(CURRENT.load(Ordering::Relaxed))
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    format_bytes_as(bytes, current_byte_units(), thousands_separator())
+}
+
+pub fn format_bytes_as(bytes: u64, units: ByteUnits, separator: &str) -> String {
+    let (base, names) = match units {
+        ByteUnits::Iec => (1024.0, IEC_UNITS),
+        ByteUnits::Si => (1000.0, SI_UNITS),
+        ByteUnits::Bytes => return format!("{} B", group_digits(bytes, separator)),
+    };
+    if bytes < base as u64 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= base && unit < names.len() - 1 {
+        size /= base;
+        unit += 1;
+    }
+    // 1023.96 KiB מעוגל ל-"1024.0 KiB" - עדיף "1.0 MiB"
+    if (size * 10.0).round() / 10.0 >= base && unit < names.len() - 1 {
+        size /= base;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, names[unit])
+}
+
+// 1234567 -> "1,234,567"
+pub fn group_digits(value: u64, separator: &str) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 * separator.len());
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+// לפי ה-locale של המערכת (LC_ALL > LC_NUMERIC > LANG), לא לפי שפת הממשק - מספרים נקראים כמו בשאר המערכת
+fn thousands_separator() -> &'static str {
+    static SEPARATOR: OnceLock<&'static str> = OnceLock::new();
+    SEPARATOR.get_or_init(|| {
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        separator_for_locale(&locale)
+    })
+}
+
+fn separator_for_locale(locale: &str) -> &'static str {
+    let language = locale.split(['_', '.', '@']).next().unwrap_or("");
+    match language {
+        _ if locale.starts_with("de_CH") => "\u{2019}",
+        "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" | "el" => ".",
+        // רווח צר שאינו שובר שורה
+        "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "uk" | "hu" => "\u{202F}",
+        _ => ",",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iec(bytes: u64) -> String {
+        format_bytes_as(bytes, ByteUnits::Iec, ",")
+    }
+
+    fn si(bytes: u64) -> String {
+        format_bytes_as(bytes, ByteUnits::Si, ",")
+    }
+
+    #[test]
+    fn iec_boundaries() {
+        assert_eq!(iec(0), "0 B");
+        assert_eq!(iec(1023), "1023 B");
+        assert_eq!(iec(1024), "1.0 KiB");
+        assert_eq!(iec(1536), "1.5 KiB");
+        assert_eq!(iec(1024 * 1024 - 1), "1.0 MiB");
+        assert_eq!(iec(1_500_000_000), "1.4 GiB");
+        assert_eq!(iec(1_000_000_000_000), "931.3 GiB");
+        assert_eq!(iec(1 << 40), "1.0 TiB");
+    }
+
+    #[test]
+    fn si_boundaries() {
+        assert_eq!(si(999), "999 B");
+        assert_eq!(si(1000), "1.0 kB");
+        assert_eq!(si(1023), "1.0 kB");
+        assert_eq!(si(1024), "1.0 kB");
+        assert_eq!(si(1_500_000_000), "1.5 GB");
+        assert_eq!(si(999_999_999_999), "1.0 TB");
+        assert_eq!(si(1_000_000_000_000), "1.0 TB");
+    }
+
+    #[test]
+    fn raw_bytes_are_grouped() {
+        assert_eq!(format_bytes_as(1023, ByteUnits::Bytes, ","), "1,023 B");
+        assert_eq!(format_bytes_as(1024, ByteUnits::Bytes, "."), "1.024 B");
+        assert_eq!(format_bytes_as(1_000_000_000_000, ByteUnits::Bytes, ","), "1,000,000,000,000 B");
+        assert_eq!(format_bytes_as(999, ByteUnits::Bytes, ","), "999 B");
+    }
+
+    #[test]
+    fn separators_follow_locale() {
+        assert_eq!(separator_for_locale("en_US.UTF-8"), ",");
+        assert_eq!(separator_for_locale("he_IL.UTF-8"), ",");
+        assert_eq!(separator_for_locale("de_DE.UTF-8"), ".");
+        assert_eq!(separator_for_locale("de_CH.UTF-8"), "\u{2019}");
+        assert_eq!(separator_for_locale("fr_FR.UTF-8"), "\u{202F}");
+        assert_eq!(separator_for_locale("C"), ",");
+    }
+}
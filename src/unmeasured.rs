@@ -0,0 +1,164 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::notification::format_count;
+use crate::users;
+
+// === מה שהתצוגה המקדימה לא הצליחה למדוד: קבצים ותיקיות שה-metadata או הסריקה שלהם נכשלו ===
+// בלי זה הסך בתצוגה המקדימה פשוט חסר את כל מה שלא נקרא (לוגים של root, קבצים של משתמש אחר).
+// תיקיות שנחסמו בהרשאות נשמרות בצד - להערכה ב-du דרך root, רק אם המשתמש ביקש (estimate_as_root)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    // EACCES / EPERM - root היה קורא
+    Permission,
+    // כל תקלה אחרת (NFS, דיסק פגום, נתיב ארוך מדי)
+    Io,
+    // SIP / הגנת הפרטיות ב-macOS: גם root לא נכנס
+    Safety,
+}
+
+impl Reason {
+    const ALL: [Reason; 3] = [Reason::Permission, Reason::Io, Reason::Safety];
+
+    fn key(self) -> &'static str {
+        match self {
+            Reason::Permission => "summary.unmeasured_permission",
+            Reason::Io => "summary.unmeasured_io",
+            Reason::Safety => "summary.unmeasured_safety",
+        }
+    }
+}
+
+pub fn classify(e: &io::Error, protected_by_os: bool) -> Reason {
+    if protected_by_os {
+        Reason::Safety
+    } else if e.kind() == io::ErrorKind::PermissionDenied {
+        Reason::Permission
+    } else {
+        Reason::Io
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Unmeasured {
+    counts: [AtomicU64; 3],
+    denied: Mutex<Vec<PathBuf>>,
+    // u64::MAX = לא הוערך
+    estimated_bytes: AtomicU64,
+}
+
+impl Unmeasured {
+    pub fn new() -> Self {
+        Unmeasured { estimated_bytes: AtomicU64::new(u64::MAX), ..Default::default() }
+    }
+
+    pub fn record(&self, reason: Reason, path: &Path) {
+        self.counts[reason as usize].fetch_add(1, Ordering::Relaxed);
+        if reason == Reason::Permission {
+            if let Ok(mut denied) = self.denied.lock() {
+                denied.push(path.to_path_buf());
+            }
+        }
+    }
+
+    pub fn count(&self, reason: Reason) -> u64 {
+        self.counts[reason as usize].load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> u64 {
+        Reason::ALL.iter().map(|r| self.count(*r)).sum()
+    }
+
+    // תיקייה שבתוך תיקייה אחרת ברשימה נספרת כבר ב-du של ההורה
+    pub fn denied_paths(&self) -> Vec<PathBuf> {
+        let mut paths = self.denied.lock().map(|d| d.clone()).unwrap_or_default();
+        paths.sort();
+        paths.dedup();
+        let mut outermost: Vec<PathBuf> = Vec::new();
+        for path in paths {
+            if !outermost.last().is_some_and(|parent| path.starts_with(parent)) {
+                outermost.push(path);
+            }
+        }
+        outermost
+    }
+
+    pub fn estimated_bytes(&self) -> Option<u64> {
+        match self.estimated_bytes.load(Ordering::Relaxed) {
+            u64::MAX => None,
+            bytes => Some(bytes),
+        }
+    }
+
+    // du דרך pkexec (או ישירות כשכבר root). נקרא מה-worker בסוף תצוגה מקדימה, אחרי שהמשתמש הסכים
+    pub fn estimate_as_root(&self) -> io::Result<u64> {
+        let paths = self.denied_paths();
+        if paths.is_empty() {
+            return Ok(0);
+        }
+        let mut command = if users::is_root() { Command::new("du") } else {
+            let mut pkexec = Command::new("pkexec");
+            pkexec.arg("du");
+            pkexec
+        };
+        let output = command.args(["-s", "-B1", "--"]).args(&paths).stdin(Stdio::null()).stderr(Stdio::null()).output()?;
+        // du יוצא עם 1 גם כשרק חלק מהנתיבים נכשלו - מה שנמדד עדיין נכון. pkexec שבוטל: 126/127
+        if matches!(output.status.code(), Some(126) | Some(127)) {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, "authorization was cancelled"));
+        }
+        let bytes = parse_du_total(&String::from_utf8_lossy(&output.stdout));
+        self.estimated_bytes.store(bytes, Ordering::Relaxed);
+        Ok(bytes)
+    }
+
+    // "Additionally ~? MB in 3,120 inaccessible files (3,100 need root, 20 I/O errors)". None = הכל נמדד
+    pub fn summary_line(&self, translate: fn(&str, &[&str]) -> String, format_bytes: fn(u64) -> String) -> Option<String> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        let size = self.estimated_bytes().map_or_else(|| "?".to_string(), format_bytes);
+        let reasons: Vec<String> = Reason::ALL
+            .iter()
+            .filter(|r| self.count(**r) > 0)
+            .map(|r| translate(r.key(), &[&format_count(self.count(*r))]))
+            .collect();
+        Some(translate("summary.unmeasured", &[&size, &format_count(total), &reasons.join(", ")]))
+    }
+}
+
+// "4096\t/var/log/private" לכל נתיב
+fn parse_du_total(output: &str) -> u64 {
+    output.lines().filter_map(|line| line.split_whitespace().next()?.parse::<u64>().ok()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_and_keeps_only_outermost_denied_paths() {
+        let unmeasured = Unmeasured::new();
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        for path in ["/var/log/private/a", "/var/log/private", "/home/other", "/var/log/private"] {
+            unmeasured.record(classify(&denied, false), Path::new(path));
+        }
+        unmeasured.record(classify(&io::Error::other("stale NFS handle"), false), Path::new("/mnt/nfs/x"));
+        unmeasured.record(classify(&denied, true), Path::new("/Library/Mail"));
+
+        assert_eq!(unmeasured.count(Reason::Permission), 4);
+        assert_eq!(unmeasured.count(Reason::Io), 1);
+        assert_eq!(unmeasured.count(Reason::Safety), 1);
+        assert_eq!(unmeasured.denied_paths(), vec![PathBuf::from("/home/other"), PathBuf::from("/var/log/private")]);
+        assert_eq!(unmeasured.estimated_bytes(), None);
+    }
+
+    #[test]
+    fn du_output_is_summed() {
+        assert_eq!(parse_du_total("4096\t/var/log/private\n1048576\t/home/other\ndu: cannot read\n"), 1_052_672);
+    }
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// === משתמשים אמיתיים במערכת - לניקוי הבתים שלהם כשרצים כ-root ===
+
+// משתמשי מערכת (daemon, nobody...) מתחת לזה או עם shell שלא מאפשר כניסה
+const FIRST_HUMAN_UID: u32 = 1000;
+const NOBODY_UID: u32 = 65534;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAccount {
+    pub name: String,
+    pub uid: u32,
+    pub gid: u32,
+    pub home: PathBuf,
+}
+
+#[cfg(unix)]
+pub fn is_root() -> bool {
+    nix::unistd::geteuid().is_root()
+}
+
+// ב-Windows אין ניקוי בשם משתמשים אחרים
+#[cfg(not(unix))]
+pub fn is_root() -> bool {
+    false
+}
+
+// name:password:uid:gid:gecos:home:shell
+pub fn human_users() -> Vec<UserAccount> {
+    let Ok(passwd) = fs::read_to_string("/etc/passwd") else { return Vec::new() };
+    passwd
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(':').collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            let uid: u32 = fields[2].parse().ok()?;
+            let gid: u32 = fields[3].parse().ok()?;
+            let shell = fields[6];
+            let login_shell = !shell.is_empty() && !shell.ends_with("nologin") && !shell.ends_with("false");
+            let home = PathBuf::from(fields[5]);
+            (uid >= FIRST_HUMAN_UID && uid != NOBODY_UID && login_shell && home.is_dir())
+                .then(|| UserAccount { name: fields[0].to_string(), uid, gid, home })
+        })
+        .collect()
+}
+
+// לבדיקת נתיבים מוגנים - כל בית הוא שורש שאסור לרוקן. נקרא פעם אחת לכל הפעלה
+pub fn human_homes() -> &'static [PathBuf] {
+    static HOMES: OnceLock<Vec<PathBuf>> = OnceLock::new();
+    HOMES.get_or_init(|| human_users().into_iter().map(|u| u.home).collect())
+}
+
+pub fn find(name: &str) -> Option<UserAccount> {
+    human_users().into_iter().find(|u| u.name == name)
+}
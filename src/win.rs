@@ -0,0 +1,59 @@
+use std::ffi::OsStr;
+use std::io;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+
+use windows_sys::Win32::Foundation::S_OK;
+use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+use windows_sys::Win32::System::Threading::{GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_BEGIN};
+use windows_sys::Win32::UI::Shell::{
+    SHEmptyRecycleBinW, SHQueryRecycleBinW, SHERB_NOCONFIRMATION, SHERB_NOPROGRESSUI, SHERB_NOSOUND, SHQUERYRBINFO,
+};
+
+use crate::mounts::DiskUsage;
+
+// === Windows: מה שבלינוקס עובר דרך הקבצים עצמם, כאן עובר דרך ה-API ===
+
+fn wide(s: &OsStr) -> Vec<u16> {
+    s.encode_wide().chain(std::iter::once(0)).collect()
+}
+
+// (קבצים, בייטים) בסל המיחזור של כל הכוננים
+pub fn recycle_bin_usage() -> io::Result<(u64, u64)> {
+    let mut info = SHQUERYRBINFO {
+        cbSize: std::mem::size_of::<SHQUERYRBINFO>() as u32,
+        i64Size: 0,
+        i64NumItems: 0,
+    };
+    // NULL = כל הכוננים
+    let hr = unsafe { SHQueryRecycleBinW(std::ptr::null(), &mut info) };
+    if hr != S_OK {
+        return Err(io::Error::from_raw_os_error(hr));
+    }
+    Ok((info.i64NumItems as u64, info.i64Size as u64))
+}
+
+// בלי חלון אישור, בלי פס התקדמות ובלי צליל - האישור כבר נעשה באפליקציה
+pub fn empty_recycle_bin() -> io::Result<()> {
+    let flags = SHERB_NOCONFIRMATION | SHERB_NOPROGRESSUI | SHERB_NOSOUND;
+    let hr = unsafe { SHEmptyRecycleBinW(std::ptr::null_mut(), std::ptr::null(), flags) };
+    // סל ריק מחזיר E_UNEXPECTED - לא שגיאה מבחינתנו
+    if hr != S_OK && recycle_bin_usage().map(|(items, _)| items > 0).unwrap_or(true) {
+        return Err(io::Error::from_raw_os_error(hr));
+    }
+    Ok(())
+}
+
+// background mode מוריד גם את עדיפות ה-I/O וגם את עדיפות ה-CPU של ה-thread
+pub fn lower_current_thread() -> io::Result<()> {
+    if unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+pub fn disk_usage(path: &Path) -> Option<DiskUsage> {
+    let (mut available, mut total, mut free) = (0u64, 0u64, 0u64);
+    let ok = unsafe { GetDiskFreeSpaceExW(wide(path.as_os_str()).as_ptr(), &mut available, &mut total, &mut free) };
+    (ok != 0).then(|| DiskUsage { total, used: total.saturating_sub(free), available })
+}
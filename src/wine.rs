@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// === Wine / Proton: prefixes ותיקיות ה-cache שבתוכם ===
+// נוגעים רק בתיקיות הזמניות וב-INetCache - כל השאר ב-prefix הוא התקנות ושמירות משחק.
+
+// Lutris שם כל משחק ב-~/Games/<game>, לפעמים עוד רמה אחת פנימה
+const LUTRIS_DEPTH: usize = 3;
+
+// data: XDG_DATA_HOME של הבית (שם Steam שם את compatdata)
+pub fn prefixes(home: &Path, data: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let default = home.join(".wine");
+    if is_prefix(&default) {
+        found.push(default);
+    }
+    for entry in walkdir::WalkDir::new(home.join("Games"))
+        .max_depth(LUTRIS_DEPTH)
+        .into_iter()
+        .filter_entry(|e| e.file_type().is_dir() && e.file_name() != "drive_c")
+        .filter_map(|e| e.ok())
+    {
+        if is_prefix(entry.path()) {
+            found.push(entry.into_path());
+        }
+    }
+    // Steam: compatdata/<appid>/pfx
+    for steam in [home.join(".steam/steam"), data.join("Steam")] {
+        let Ok(apps) = fs::read_dir(steam.join("steamapps/compatdata")) else { continue };
+        for app in apps.filter_map(|e| e.ok()) {
+            let pfx = app.path().join("pfx");
+            if is_prefix(&pfx) {
+                found.push(pfx);
+            }
+        }
+    }
+    // ~/.steam/steam הוא בדרך כלל symlink ל-~/.local/share/Steam
+    let mut seen = HashSet::new();
+    found.retain(|p| seen.insert(fs::canonicalize(p).unwrap_or_else(|_| p.clone())));
+    found
+}
+
+fn is_prefix(dir: &Path) -> bool {
+    dir.join("drive_c").is_dir() && dir.join("system.reg").is_file()
+}
+
+pub fn cache_dirs(prefix: &Path) -> Vec<PathBuf> {
+    let drive_c = prefix.join("drive_c");
+    let mut dirs = vec![drive_c.join("windows/Temp")];
+    if let Ok(users) = fs::read_dir(drive_c.join("users")) {
+        for user in users.filter_map(|e| e.ok()).map(|e| e.path()) {
+            dirs.push(user.join("Temp"));
+            dirs.push(user.join("AppData/Local/Microsoft/Windows/INetCache"));
+        }
+    }
+    dirs.retain(|d| d.is_dir());
+    dirs
+}
+
+// prefixes שיש להם wineserver רץ - לפי WINEPREFIX בסביבה של התהליך (ברירת מחדל ~/.wine)
+pub fn active_prefixes(home: &Path) -> HashSet<PathBuf> {
+    let mut active = HashSet::new();
+    let Ok(entries) = fs::read_dir("/proc") else { return active };
+    for proc_dir in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        let is_wineserver = fs::read_to_string(proc_dir.join("comm"))
+            .map(|comm| comm.trim().starts_with("wineserver"))
+            .unwrap_or(false);
+        if !is_wineserver {
+            continue;
+        }
+        let prefix = fs::read(proc_dir.join("environ"))
+            .ok()
+            .and_then(|environ| {
+                environ
+                    .split(|b| *b == 0)
+                    .find_map(|var| var.strip_prefix(b"WINEPREFIX=").map(|v| PathBuf::from(String::from_utf8_lossy(v).into_owned())))
+            })
+            .unwrap_or_else(|| home.join(".wine"));
+        active.insert(fs::canonicalize(&prefix).unwrap_or(prefix));
+    }
+    active
+}
@@ -0,0 +1,124 @@
+// הבינארי עצמו (מצב CLI) מול עץ מזויף: --home מפנה את המנוע ל-fixture, וה-HOME/XDG של התהליך
+// מפנים לשם גם את ההגדרות, הנעילה וה-app data - כך שהבדיקות לא נוגעות בבית האמיתי
+#![cfg(all(unix, not(target_os = "macos")))]
+
+#[path = "../src/fixtures.rs"]
+mod fixtures;
+
+use fixtures::Fixture;
+use serde_json::Value;
+use std::process::{Command, Output};
+
+fn sysclean(fixture: &Fixture, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sysclean"))
+        .args(args)
+        .arg("--home")
+        .arg(fixture.home())
+        .env("HOME", fixture.home())
+        .env("XDG_RUNTIME_DIR", fixture.root())
+        .env_remove("XDG_CACHE_HOME")
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .env_remove("XDG_STATE_HOME")
+        .output()
+        .expect("run sysclean")
+}
+
+// האירוע האחרון של --json-progress
+fn run_done(output: &Output) -> Value {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let last = stdout.lines().last().expect("no events");
+    let event: Value = serde_json::from_str(last).unwrap();
+    assert_eq!(event["event"], "run_done", "{}", stdout);
+    event
+}
+
+fn populate(fixture: &Fixture) {
+    fixture.trash_item("report.pdf", &[7; 20_000]);
+    fixture.trash_item("notes.txt", b"hello");
+    fixture.python_project("proj");
+    fixture.rotated_logs("myapp");
+}
+
+#[test]
+fn preview_counts_without_removing() {
+    let fixture = Fixture::new();
+    populate(&fixture);
+    let before = fixture.snapshot();
+
+    let output = sysclean(&fixture, &["--preview", "trash,pycache", "--json-progress"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let done = run_done(&output);
+
+    // ההגדרות והנעילה נוצרים תחת ה-fixture - בודקים רק שכל מה שהיה שם נשאר כמו שהיה
+    let after = fixture.snapshot();
+    assert!(before.iter().all(|(path, size)| after.get(path) == Some(size)));
+    let home = fixture.home();
+    let would_remove: u64 = before
+        .iter()
+        .filter(|(p, _)| p.starts_with(home.join(".local/share/Trash")) || p.extension().is_some_and(|e| e == "pyc"))
+        .map(|(_, size)| size)
+        .sum();
+    assert_eq!(done["dry_run"], true);
+    assert!(done["run_id"].as_str().is_some_and(|id| !id.is_empty()));
+    assert_eq!(done["files"], 4 + 3);
+    assert_eq!(done["bytes"], would_remove);
+    // הגדול ביותר מבין מה שהיה נמחק, ראשון בטבלה
+    let biggest = before
+        .iter()
+        .filter(|(p, _)| p.starts_with(home.join(".local/share/Trash/files")) || p.extension().is_some_and(|e| e == "pyc"))
+        .map(|(_, size)| *size)
+        .max();
+    assert_eq!(done["largest"][0]["bytes"].as_u64(), biggest);
+}
+
+#[test]
+fn clean_removes_targets_and_reports_disk_truth() {
+    let fixture = Fixture::new();
+    populate(&fixture);
+    let before = fixture.snapshot();
+
+    let output = sysclean(&fixture, &["--clean", "trash,pycache", "--json-progress"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let done = run_done(&output);
+    let removed = fixtures::removed(&before, &fixture.snapshot());
+
+    let home = fixture.home();
+    assert!(removed.keys().all(|p| p.starts_with(home.join(".local/share/Trash")) || p.extension().is_some_and(|e| e == "pyc")));
+    assert_eq!(removed.len(), 4 + 3);
+    assert!(home.join("proj/app/app.py").exists());
+    assert!(home.join(".local/state/myapp/myapp.log").exists());
+    assert_eq!(done["dry_run"], false);
+    assert_eq!(done["files"], removed.len() as u64);
+    assert_eq!(done["bytes"], removed.values().sum::<u64>());
+}
+
+#[test]
+fn unknown_item_is_a_usage_error() {
+    let fixture = Fixture::new();
+    let output = sysclean(&fixture, &["--preview", "no_such_item"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+// chattr +i דורש root ומערכת קבצים שתומכת בדגל (tmpfs לא) - אחרת הבדיקה לא רלוונטית
+#[test]
+fn immutable_files_are_skipped_and_counted() {
+    if !nix::unistd::geteuid().is_root() {
+        return;
+    }
+    let fixture = Fixture::new();
+    let locked = fixture.trash_item("locked.bin", &[9; 4096]);
+    fixture.trash_item("free.bin", &[8; 4096]);
+    let chattr = |flag: &str| Command::new("chattr").arg(flag).arg(&locked).status().is_ok_and(|s| s.success());
+    if !chattr("+i") {
+        return;
+    }
+
+    let output = sysclean(&fixture, &["--clean", "trash", "--json-progress"]);
+    chattr("-i");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let done = run_done(&output);
+    assert!(locked.exists());
+    assert!(!fixture.home().join(".local/share/Trash/files/free.bin").exists());
+    assert_eq!(done["skipped_immutable"], 1);
+}